@@ -3,17 +3,24 @@
 //! Contains the core workflows for parse, validate, format, and explain
 //! that are common across all binding targets. Each binding crate wraps
 //! these functions with its own type conversion layer.
+//!
+//! Parse and validate never fail on ZPL *content*: malformed or binary input
+//! yields a best-effort AST plus parser diagnostics. `Err` is reserved for
+//! configuration problems (missing or invalid tables, invalid profile JSON).
 
 use std::sync::OnceLock;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Duration;
 
+use zpl_toolchain_core::grammar::dump::to_json_value;
 use zpl_toolchain_core::{
-    Compaction, EmitConfig, Indent, ParseResult, ValidationResult, emit_zpl, parse_with_tables,
-    validate_with_profile,
+    Compaction, Indent, ValidationResult, command_histogram, emit_zpl_with_source,
+    parse_with_tables, validate_with_profile,
 };
+pub use zpl_toolchain_core::{EmitConfig, ParseResult, WithSpans};
+pub use zpl_toolchain_diagnostics::DiagnosticInfo;
 use zpl_toolchain_profile::{Profile, load_profile_from_str};
-use zpl_toolchain_spec_tables::ParserTables;
+use zpl_toolchain_spec_tables::{CommandCategory, CommandScope, ParserTables, Plane};
 
 // ── Embedded tables ─────────────────────────────────────────────────────
 
@@ -43,7 +50,8 @@ pub fn embedded_tables() -> Option<&'static ParserTables> {
 
 /// Parse ZPL input using embedded parser tables.
 ///
-/// Returns an error when tables are unavailable.
+/// Returns an error only when tables are unavailable; content problems are
+/// reported as diagnostics.
 pub fn parse_zpl(input: &str) -> Result<ParseResult, String> {
     let tables = embedded_tables().ok_or_else(|| {
         "parser tables required for parse but not embedded; provide explicit tables JSON via parse_zpl_with_tables_json"
@@ -53,18 +61,47 @@ pub fn parse_zpl(input: &str) -> Result<ParseResult, String> {
 }
 
 /// Parse ZPL input with explicitly provided tables JSON.
+///
+/// Returns an error only for invalid tables JSON or tables built for a
+/// different table format version.
 pub fn parse_zpl_with_tables_json(input: &str, tables_json: &str) -> Result<ParseResult, String> {
-    let tables: ParserTables =
-        serde_json::from_str(tables_json).map_err(|e| format!("invalid tables JSON: {}", e))?;
+    let tables: ParserTables = tables_json.parse().map_err(|e| format!("{e}"))?;
     Ok(parse_with_tables(input, Some(&tables)))
 }
 
+/// Convert a parse result to JSON, omitting every `span` field when
+/// `spans` is [`WithSpans::No`].
+pub fn parse_result_to_json(result: &ParseResult, spans: WithSpans) -> serde_json::Value {
+    to_json_value(result, spans)
+}
+
+/// Convert a parse result to JSON like [`parse_result_to_json`], adding a
+/// `stats` object when `with_stats` is set.
+///
+/// `stats.commands` maps each command code to its occurrence count across
+/// all labels (see [`command_histogram`]).
+pub fn parse_result_to_json_with_stats(
+    result: &ParseResult,
+    spans: WithSpans,
+    with_stats: bool,
+) -> serde_json::Value {
+    let mut json = to_json_value(result, spans);
+    if with_stats && let Some(obj) = json.as_object_mut() {
+        obj.insert(
+            "stats".into(),
+            serde_json::json!({ "commands": command_histogram(&result.ast) }),
+        );
+    }
+    json
+}
+
 // ── Validate ────────────────────────────────────────────────────────────
 
 /// Parse and validate ZPL input with an optional profile.
 ///
 /// Returns a `ValidationResult` with parse diagnostics merged in.
-/// Requires embedded tables; returns `Err` if not available.
+/// Requires embedded tables; returns `Err` only if they are not available or
+/// the profile JSON is invalid.
 pub fn validate_zpl(input: &str, profile_json: Option<&str>) -> Result<ValidationResult, String> {
     let tables = embedded_tables()
         .ok_or_else(|| "parser tables required for validation but not embedded".to_string())?;
@@ -81,23 +118,20 @@ pub fn validate_zpl(input: &str, profile_json: Option<&str>) -> Result<Validatio
     };
 
     let mut vr = validate_with_profile(&res.ast, tables, profile.as_ref());
-    // Prepend parse diagnostics before validation diagnostics for source-order output.
-    let mut all_issues = res.diagnostics;
-    all_issues.extend(vr.issues);
-    vr.issues = all_issues;
+    vr.merge_parse(res.diagnostics);
     Ok(vr)
 }
 
 /// Parse and validate ZPL input with explicitly provided parser tables JSON.
 ///
-/// Returns a `ValidationResult` with parse diagnostics merged in.
+/// Returns a `ValidationResult` with parse diagnostics merged in; `Err` only
+/// for invalid or incompatible tables JSON, or invalid profile JSON.
 pub fn validate_zpl_with_tables_json(
     input: &str,
     profile_json: Option<&str>,
     tables_json: &str,
 ) -> Result<ValidationResult, String> {
-    let tables: ParserTables =
-        serde_json::from_str(tables_json).map_err(|e| format!("invalid tables JSON: {}", e))?;
+    let tables: ParserTables = tables_json.parse().map_err(|e| format!("{e}"))?;
     let res = parse_with_tables(input, Some(&tables));
 
     let profile = match profile_json {
@@ -110,9 +144,7 @@ pub fn validate_zpl_with_tables_json(
     };
 
     let mut vr = validate_with_profile(&res.ast, &tables, profile.as_ref());
-    let mut all_issues = res.diagnostics;
-    all_issues.extend(vr.issues);
-    vr.issues = all_issues;
+    vr.merge_parse(res.diagnostics);
     Ok(vr)
 }
 
@@ -146,17 +178,25 @@ pub fn format_zpl_with_options(
     indent: Option<&str>,
     compaction: Option<&str>,
 ) -> Result<String, String> {
+    let config = EmitConfig {
+        indent: parse_indent(indent),
+        compaction: parse_compaction(compaction),
+        ..EmitConfig::default()
+    };
+    format_zpl_with_config(input, &config)
+}
+
+/// Format ZPL input with a fully specified [`EmitConfig`].
+///
+/// The string-option functions above delegate here; bindings that already
+/// hold structured settings can pass them directly.
+pub fn format_zpl_with_config(input: &str, config: &EmitConfig) -> Result<String, String> {
     let tables = embedded_tables().ok_or_else(|| {
         "parser tables required for format but not embedded; provide explicit tables JSON via parse_zpl_with_tables_json and format externally"
             .to_string()
     })?;
     let res = parse_with_tables(input, Some(tables));
-
-    let config = EmitConfig {
-        indent: parse_indent(indent),
-        compaction: parse_compaction(compaction),
-    };
-    Ok(emit_zpl(&res.ast, Some(tables), &config))
+    Ok(emit_zpl_with_source(&res.ast, Some(tables), config, input))
 }
 
 // ── Explain ─────────────────────────────────────────────────────────────
@@ -166,10 +206,117 @@ pub fn explain_diagnostic(id: &str) -> Option<&'static str> {
     zpl_toolchain_diagnostics::explain(id)
 }
 
+/// The full diagnostic catalog (every code with severity, category,
+/// summary, and explanation), in spec order.
+pub fn diagnostic_catalog() -> &'static [DiagnosticInfo] {
+    zpl_toolchain_diagnostics::catalog()
+}
+
+// ── Command reference ───────────────────────────────────────────────────
+
+/// Reference metadata for one command, as listed by [`list_commands`].
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandSummary {
+    /// All opcodes for this command (e.g. `["^FO"]` or `["^CC", "~CC"]`).
+    pub codes: Vec<String>,
+    /// Human-readable name (e.g. `"Field Origin"`).
+    pub name: Option<String>,
+    /// Functional category (e.g. `"field"`, `"barcode"`).
+    pub category: Option<CommandCategory>,
+    /// Plane: format, device, host, or config.
+    pub plane: Option<Plane>,
+    /// Lifecycle scope of the command's effect.
+    pub scope: Option<CommandScope>,
+    /// Maximum number of arguments.
+    pub arity: u32,
+    /// Parameter keys in signature order.
+    pub params: Vec<String>,
+    /// Whether the command is deprecated.
+    pub deprecated: bool,
+}
+
+/// List every command in the embedded parser tables, sorted by first opcode.
+pub fn list_commands() -> Result<Vec<CommandSummary>, String> {
+    let tables = embedded_tables()
+        .ok_or_else(|| "parser tables required for listCommands but not embedded".to_string())?;
+    Ok(command_summaries(tables))
+}
+
+fn command_summaries(tables: &ParserTables) -> Vec<CommandSummary> {
+    let mut commands: Vec<CommandSummary> = tables
+        .commands
+        .iter()
+        .map(|cmd| CommandSummary {
+            codes: cmd.codes.clone(),
+            name: cmd.name.clone(),
+            category: cmd.category,
+            plane: cmd.plane,
+            scope: cmd.scope,
+            arity: cmd.arity,
+            params: cmd
+                .signature
+                .as_ref()
+                .map(|sig| sig.params.clone())
+                .unwrap_or_default(),
+            deprecated: cmd.deprecated.unwrap_or(false),
+        })
+        .collect();
+    commands.sort_by(|a, b| a.codes.first().cmp(&b.codes.first()));
+    commands
+}
+
 // ── Print (non-WASM only) ────────────────────────────────────────────
 
 #[cfg(not(target_arch = "wasm32"))]
-use zpl_toolchain_print_client::{Printer, PrinterConfig, StatusQuery, TcpPrinter};
+use zpl_toolchain_print_client::{PrintError, Printer, PrinterConfig, StatusQuery, TcpPrinter};
+
+/// A failed print or query call, as reported to binding callers.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintFailure {
+    /// Human-readable description of the failure.
+    pub message: String,
+    /// Stable [`PrintError::code`] when the print client failed; `None` for
+    /// configuration or serialization problems.
+    pub code: Option<&'static str>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PrintFailure {
+    fn from_print_error(context: &str, err: &PrintError) -> Self {
+        Self {
+            message: format!("{context}: {err}"),
+            code: Some(err.code()),
+        }
+    }
+
+    /// The error object returned by the JSON-based bindings:
+    /// `{"error": message, "code": code}`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "error": self.message,
+            "code": self.code,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::fmt::Display for PrintFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<String> for PrintFailure {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            code: None,
+        }
+    }
+}
 
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug, Clone, Default, serde::Deserialize)]
@@ -277,6 +424,102 @@ fn build_printer_config(
     Ok(config)
 }
 
+/// Validate ZPL before printing.
+///
+/// Returns `Some(json)` with a `validation_failed` error object when the
+/// label has errors, or `None` when it is safe to send.
+#[cfg(not(target_arch = "wasm32"))]
+fn preflight_validation(zpl: &str, profile_json: Option<&str>) -> Result<Option<String>, String> {
+    let vr = validate_zpl(zpl, profile_json)?;
+    if vr.ok {
+        return Ok(None);
+    }
+    let issues_json =
+        serde_json::to_value(&vr.issues).map_err(|e| format!("serialize error: {e}"))?;
+    Ok(Some(
+        serde_json::json!({
+            "success": false,
+            "error": "validation_failed",
+            "issues": issues_json,
+        })
+        .to_string(),
+    ))
+}
+
+/// A persistent TCP connection to a printer, shared by the bindings.
+///
+/// One-shot helpers such as [`print_zpl`] open and drop a session per call;
+/// bindings that expose a long-lived printer object keep one around so
+/// several sends and queries reuse the same socket.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct PrinterSession {
+    printer: TcpPrinter,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PrinterSession {
+    /// Connect to a printer with optional timeout/config overrides.
+    pub fn connect(
+        printer_addr: &str,
+        timeout_ms: Option<u64>,
+        config_json: Option<&str>,
+    ) -> Result<Self, PrintFailure> {
+        let config = build_printer_config(timeout_ms, config_json)?;
+        let printer = TcpPrinter::connect(printer_addr, config)
+            .map_err(|e| PrintFailure::from_print_error("connection failed", &e))?;
+        Ok(Self { printer })
+    }
+
+    /// Send ZPL over this connection, optionally validating it first.
+    ///
+    /// Returns the same JSON shape as [`print_zpl`].
+    pub fn send_zpl(
+        &mut self,
+        zpl: &str,
+        profile_json: Option<&str>,
+        validate: bool,
+    ) -> Result<String, PrintFailure> {
+        if validate && let Some(failure) = preflight_validation(zpl, profile_json)? {
+            return Ok(failure);
+        }
+        self.send_unchecked(zpl)
+    }
+
+    /// Send ZPL without validation and return the success JSON.
+    fn send_unchecked(&mut self, zpl: &str) -> Result<String, PrintFailure> {
+        let bytes_sent = zpl.len();
+        self.printer
+            .send_zpl(zpl)
+            .map_err(|e| PrintFailure::from_print_error("send failed", &e))?;
+
+        Ok(serde_json::json!({
+            "success": true,
+            "bytes_sent": bytes_sent,
+        })
+        .to_string())
+    }
+
+    /// Query printer status via `~HS` and return the result as JSON.
+    pub fn query_status(&mut self) -> Result<String, PrintFailure> {
+        let status = self
+            .printer
+            .query_status()
+            .map_err(|e| PrintFailure::from_print_error("status query failed", &e))?;
+
+        serde_json::to_string(&status).map_err(|e| format!("serialize error: {e}").into())
+    }
+
+    /// Query printer info via `~HI` and return the result as JSON.
+    pub fn query_info(&mut self) -> Result<String, PrintFailure> {
+        let info = self
+            .printer
+            .query_info()
+            .map_err(|e| PrintFailure::from_print_error("info query failed", &e))?;
+
+        serde_json::to_string(&info).map_err(|e| format!("serialize error: {e}").into())
+    }
+}
+
 /// Send ZPL to a network printer via TCP (port 9100).
 ///
 /// If `validate` is true the ZPL is validated first (using the optional
@@ -291,7 +534,7 @@ pub fn print_zpl(
     printer_addr: &str,
     profile_json: Option<&str>,
     validate: bool,
-) -> Result<String, String> {
+) -> Result<String, PrintFailure> {
     print_zpl_with_options(zpl, printer_addr, profile_json, validate, None, None)
 }
 
@@ -304,39 +547,13 @@ pub fn print_zpl_with_options(
     validate: bool,
     timeout_ms: Option<u64>,
     config_json: Option<&str>,
-) -> Result<String, String> {
-    // 1. If validate is true, run validation first
-    if validate {
-        let vr = validate_zpl(zpl, profile_json)?;
-        if !vr.ok {
-            let issues_json =
-                serde_json::to_value(&vr.issues).map_err(|e| format!("serialize error: {e}"))?;
-            return Ok(serde_json::json!({
-                "success": false,
-                "error": "validation_failed",
-                "issues": issues_json,
-            })
-            .to_string());
-        }
+) -> Result<String, PrintFailure> {
+    // Validate before connecting so a bad label never opens a socket.
+    if validate && let Some(failure) = preflight_validation(zpl, profile_json)? {
+        return Ok(failure);
     }
 
-    // 2. Connect to printer via TcpPrinter
-    let config = build_printer_config(timeout_ms, config_json)?;
-    let mut printer =
-        TcpPrinter::connect(printer_addr, config).map_err(|e| format!("connection failed: {e}"))?;
-
-    // 3. Send ZPL
-    let bytes_sent = zpl.len();
-    printer
-        .send_zpl(zpl)
-        .map_err(|e| format!("send failed: {e}"))?;
-
-    // 4. Return JSON result
-    Ok(serde_json::json!({
-        "success": true,
-        "bytes_sent": bytes_sent,
-    })
-    .to_string())
+    PrinterSession::connect(printer_addr, timeout_ms, config_json)?.send_unchecked(zpl)
 }
 
 /// Query printer status via `~HS` and return the result as JSON.
@@ -345,7 +562,7 @@ pub fn print_zpl_with_options(
 /// into a [`HostStatus`](zpl_toolchain_print_client::HostStatus) struct,
 /// and serializes it to JSON.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn query_printer_status(printer_addr: &str) -> Result<String, String> {
+pub fn query_printer_status(printer_addr: &str) -> Result<String, PrintFailure> {
     query_printer_status_with_options(printer_addr, None, None)
 }
 
@@ -355,21 +572,13 @@ pub fn query_printer_status_with_options(
     printer_addr: &str,
     timeout_ms: Option<u64>,
     config_json: Option<&str>,
-) -> Result<String, String> {
-    let config = build_printer_config(timeout_ms, config_json)?;
-    let mut printer =
-        TcpPrinter::connect(printer_addr, config).map_err(|e| format!("connection failed: {e}"))?;
-
-    let status = printer
-        .query_status()
-        .map_err(|e| format!("status query failed: {e}"))?;
-
-    serde_json::to_string(&status).map_err(|e| format!("serialize error: {e}"))
+) -> Result<String, PrintFailure> {
+    PrinterSession::connect(printer_addr, timeout_ms, config_json)?.query_status()
 }
 
 /// Query printer info via `~HI` and return the result as JSON.
 #[cfg(not(target_arch = "wasm32"))]
-pub fn query_printer_info(printer_addr: &str) -> Result<String, String> {
+pub fn query_printer_info(printer_addr: &str) -> Result<String, PrintFailure> {
     query_printer_info_with_options(printer_addr, None, None)
 }
 
@@ -379,24 +588,92 @@ pub fn query_printer_info_with_options(
     printer_addr: &str,
     timeout_ms: Option<u64>,
     config_json: Option<&str>,
-) -> Result<String, String> {
-    let config = build_printer_config(timeout_ms, config_json)?;
-    let mut printer =
-        TcpPrinter::connect(printer_addr, config).map_err(|e| format!("connection failed: {e}"))?;
-
-    let info = printer
-        .query_info()
-        .map_err(|e| format!("info query failed: {e}"))?;
-
-    serde_json::to_string(&info).map_err(|e| format!("serialize error: {e}"))
+) -> Result<String, PrintFailure> {
+    PrinterSession::connect(printer_addr, timeout_ms, config_json)?.query_info()
 }
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-    use super::{build_printer_config, parse_compaction, parse_indent};
+    use super::{
+        EmitConfig, WithSpans, build_printer_config, command_summaries, diagnostic_catalog,
+        format_zpl_with_config, format_zpl_with_options, parse_compaction, parse_indent,
+        parse_result_to_json_with_stats, parse_zpl_with_tables_json,
+        query_printer_status_with_options,
+    };
     use std::time::Duration;
     use zpl_toolchain_core::{Compaction, Indent};
 
+    #[test]
+    fn parse_json_includes_command_stats_only_when_requested() {
+        let tables_json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../generated/parser_tables.json"
+        ))
+        .expect("generated parser tables");
+        let result =
+            parse_zpl_with_tables_json("^XA^FO1,1^FDa^FS^FO2,2^FDb^FS^XZ", &tables_json).unwrap();
+        let json = parse_result_to_json_with_stats(&result, WithSpans::No, true);
+        assert_eq!(json["stats"]["commands"]["^FO"], 2);
+        assert_eq!(json["stats"]["commands"]["^FD"], 2);
+        assert_eq!(json["stats"]["commands"]["^XA"], 1);
+        assert!(json["ast"]["labels"].is_array());
+
+        let plain = parse_result_to_json_with_stats(&result, WithSpans::Yes, false);
+        assert!(plain.get("stats").is_none());
+    }
+
+    #[test]
+    fn config_and_string_format_paths_agree() {
+        let input = "^XA^FO10,10^A0N,30,30^FDHello^FS\n^FO10,60^BCN,50^FD123^FS^XZ";
+        for (indent, compaction) in [
+            (None, None),
+            (Some("label"), None),
+            (Some("field"), Some("field")),
+            (Some("none"), Some("field")),
+        ] {
+            let config = EmitConfig {
+                indent: parse_indent(indent),
+                compaction: parse_compaction(compaction),
+                ..EmitConfig::default()
+            };
+            assert_eq!(
+                format_zpl_with_config(input, &config).unwrap(),
+                format_zpl_with_options(input, indent, compaction).unwrap(),
+                "indent={indent:?} compaction={compaction:?}"
+            );
+        }
+        let config = EmitConfig {
+            indent: Indent::Field,
+            compaction: Compaction::Field,
+            ..EmitConfig::default()
+        };
+        assert_eq!(
+            format_zpl_with_config(input, &config).unwrap(),
+            format_zpl_with_options(input, Some("field"), Some("field")).unwrap()
+        );
+    }
+
+    #[test]
+    fn command_list_and_diagnostic_catalog_cover_known_entries() {
+        let tables_json = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../generated/parser_tables.json"
+        ))
+        .expect("generated parser tables");
+        let tables: zpl_toolchain_spec_tables::ParserTables = tables_json.parse().unwrap();
+        let commands = command_summaries(&tables);
+        let fo = commands
+            .iter()
+            .find(|c| c.codes.iter().any(|code| code == "^FO"))
+            .expect("^FO listed");
+        assert_eq!(fo.name.as_deref(), Some("Field Origin"));
+        assert!(!fo.params.is_empty());
+        assert!(commands.windows(2).all(|w| w[0].codes[0] <= w[1].codes[0]));
+
+        let catalog = diagnostic_catalog();
+        assert!(catalog.iter().any(|info| info.id == "ZPL1101"));
+    }
+
     #[test]
     fn timeout_ms_applies_scaled_timeouts() {
         let cfg = build_printer_config(Some(1_000), None).expect("config");
@@ -453,6 +730,26 @@ mod tests {
         assert_eq!(cfg.timeouts.connect, Duration::from_millis(1_000));
     }
 
+    #[test]
+    fn print_failures_carry_print_error_code() {
+        let err = query_printer_status_with_options("not a valid address!!!", Some(1_000), None)
+            .expect_err("address should not resolve");
+        assert_eq!(err.code, Some("no-address-found"));
+        let json = err.to_json();
+        assert_eq!(json["code"], "no-address-found");
+        assert!(
+            json["error"]
+                .as_str()
+                .is_some_and(|m| m.starts_with("connection failed:")),
+            "{json}"
+        );
+
+        let err = query_printer_status_with_options("127.0.0.1:9100", Some(0), None)
+            .expect_err("timeout=0 should fail before I/O");
+        assert_eq!(err.code, None);
+        assert!(err.to_json()["code"].is_null());
+    }
+
     #[test]
     fn parse_indent_and_compaction_are_independent() {
         assert_eq!(parse_indent(Some("label")), Indent::Label);
@@ -0,0 +1,75 @@
+//! Content-robustness tests for the bindings parse/validate entry points.
+//!
+//! Checks the error contract described in the crate docs: content problems
+//! surface as diagnostics, never as `Err`.
+
+use std::path::Path;
+
+use zpl_toolchain_bindings_common as common;
+
+fn load_tables_json() -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../generated/parser_tables.json");
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e))
+}
+
+/// Adversarial inputs: raw bytes, control characters, unterminated
+/// commands and fields, and runs of bare prefixes.
+fn garbage_inputs() -> Vec<String> {
+    let all_bytes: Vec<u8> = (0..=255u8).cycle().take(2048).collect();
+    vec![
+        String::from_utf8_lossy(&all_bytes).into_owned(),
+        "\0\u{1}\u{7f}\u{fffd}~~~^^^^,,,".to_string(),
+        "^XA^FO10,10^FD\u{0}\u{1b}unterminated".to_string(),
+        format!("^XA{}", "^".repeat(1000)),
+        "^XA^GFA,99999999,99999999,1,:::::".to_string(),
+        "^CC\u{0}^XA^FS^XZ^XZ^XZ".to_string(),
+    ]
+}
+
+#[test]
+fn garbage_input_parses_to_diagnostics_not_errors() {
+    let tables_json = load_tables_json();
+    for input in garbage_inputs() {
+        let res = common::parse_zpl_with_tables_json(&input, &tables_json)
+            .expect("content problems must not be reported as Err");
+        assert!(
+            !res.diagnostics.is_empty(),
+            "expected diagnostics for {input:?}"
+        );
+        // The best-effort result still serializes for the bindings.
+        let json = common::parse_result_to_json(&res, common::WithSpans::Yes);
+        assert!(json["ast"]["labels"].is_array());
+    }
+}
+
+#[test]
+fn garbage_input_validates_to_diagnostics_not_errors() {
+    let tables_json = load_tables_json();
+    for input in garbage_inputs() {
+        let vr = common::validate_zpl_with_tables_json(&input, None, &tables_json)
+            .expect("content problems must not be reported as Err");
+        assert!(!vr.issues.is_empty(), "expected issues for {input:?}");
+        assert!(serde_json::to_value(&vr).is_ok());
+    }
+}
+
+#[test]
+fn embedded_entry_points_only_error_without_tables() {
+    let input = &garbage_inputs()[0];
+    let parsed = common::parse_zpl(input);
+    let validated = common::validate_zpl(input, None);
+    if common::embedded_tables().is_some() {
+        assert!(!parsed.expect("parse result").diagnostics.is_empty());
+        assert!(!validated.expect("validation result").issues.is_empty());
+    } else {
+        assert!(parsed.is_err() && validated.is_err());
+    }
+}
+
+#[test]
+fn configuration_problems_are_errors() {
+    let tables_json = load_tables_json();
+    assert!(common::parse_zpl_with_tables_json("^XA^XZ", "not json").is_err());
+    assert!(common::validate_zpl_with_tables_json("^XA^XZ", Some("{"), &tables_json).is_err());
+}
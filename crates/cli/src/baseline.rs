@@ -0,0 +1,136 @@
+//! Lint baselines: record known diagnostics and report only new ones.
+//!
+//! A diagnostic's identity is a hash of its code, its whitespace-normalized
+//! message, and its span relative to the start of its line (column, length,
+//! and covered text). Line numbers are deliberately excluded so that inserting
+//! or removing lines elsewhere in the file does not turn recorded diagnostics
+//! into "new" ones. Identical diagnostics are counted, so a baseline that
+//! recorded one occurrence still reports a second.
+
+use std::collections::HashMap;
+use std::fs;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use zpl_toolchain_diagnostics::Diagnostic;
+
+use crate::encoding::Input;
+
+/// Current on-disk baseline format version.
+const BASELINE_VERSION: u32 = 1;
+
+/// A recorded set of diagnostics, as stored in a baseline file.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Baseline {
+    /// Baseline format version.
+    pub version: u32,
+    /// Recorded diagnostics, sorted by fingerprint.
+    pub diagnostics: Vec<BaselineEntry>,
+}
+
+/// One recorded diagnostic.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct BaselineEntry {
+    /// Stable identity hash (see module docs).
+    pub fingerprint: String,
+    /// Diagnostic code, kept for readability of the baseline file.
+    pub id: String,
+    /// Diagnostic message, kept for readability of the baseline file.
+    pub message: String,
+}
+
+impl Baseline {
+    /// Build a baseline from the diagnostics produced for `source`.
+    pub(crate) fn from_diagnostics(source: &Input, diagnostics: &[Diagnostic]) -> Self {
+        let mut entries: Vec<BaselineEntry> = diagnostics
+            .iter()
+            .map(|d| BaselineEntry {
+                fingerprint: fingerprint(source, d),
+                id: d.id.to_string(),
+                message: d.message.clone(),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.fingerprint.cmp(&b.fingerprint));
+        Self {
+            version: BASELINE_VERSION,
+            diagnostics: entries,
+        }
+    }
+
+    /// Load a baseline file.
+    pub(crate) fn load(path: &str) -> Result<Self> {
+        let s = fs::read_to_string(path)
+            .with_context(|| format!("failed to read baseline '{}'", path))?;
+        let baseline: Self = serde_json::from_str(&s)
+            .with_context(|| format!("failed to parse baseline '{}'", path))?;
+        if baseline.version != BASELINE_VERSION {
+            anyhow::bail!(
+                "unsupported baseline version {} in '{}' (expected {})",
+                baseline.version,
+                path,
+                BASELINE_VERSION
+            );
+        }
+        Ok(baseline)
+    }
+
+    /// Write this baseline to `path` as pretty-printed JSON.
+    pub(crate) fn save(&self, path: &str) -> Result<()> {
+        let mut out = serde_json::to_string_pretty(self)?;
+        out.push('\n');
+        fs::write(path, out).with_context(|| format!("failed to write baseline '{}'", path))
+    }
+
+    /// Remove diagnostics recorded in this baseline from `diagnostics`.
+    ///
+    /// Returns the number of diagnostics suppressed.
+    pub(crate) fn suppress(&self, source: &Input, diagnostics: &mut Vec<Diagnostic>) -> usize {
+        let mut remaining: HashMap<&str, usize> = HashMap::new();
+        for entry in &self.diagnostics {
+            *remaining.entry(entry.fingerprint.as_str()).or_default() += 1;
+        }
+
+        let before = diagnostics.len();
+        diagnostics.retain(|d| {
+            let fp = fingerprint(source, d);
+            match remaining.get_mut(fp.as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+        before - diagnostics.len()
+    }
+}
+
+/// Stable identity hash for a diagnostic.
+fn fingerprint(source: &Input, d: &Diagnostic) -> String {
+    let mut key = String::new();
+    key.push_str(&d.id);
+    key.push('\0');
+    key.push_str(&normalize_whitespace(&d.message));
+    key.push('\0');
+    if let Some(span) = d.span {
+        let (_, col) = source.line_col(span.start);
+        let len = span.end.saturating_sub(span.start);
+        let text = source.span_text(span);
+        key.push_str(&format!("{}:{}:{}", col, len, normalize_whitespace(text)));
+    }
+    format!("{:016x}", fnv1a_64(key.as_bytes()))
+}
+
+fn normalize_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// FNV-1a 64-bit hash. Used instead of `DefaultHasher`, whose output is not
+/// guaranteed to be stable across Rust releases.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(PRIME)
+    })
+}
@@ -0,0 +1,205 @@
+//! Source encodings for `--input-encoding` and the decoded [`Input`] files
+//! they produce.
+//!
+//! Latin-1 and CP437 map every byte to exactly one character, so decoding
+//! followed by [`InputEncoding::encode`] reproduces the original bytes,
+//! including binary `^GF`/`~DG` payloads.
+
+use anyhow::{Result, bail};
+use clap::ValueEnum;
+use zpl_toolchain_core::grammar::parser::{
+    ParseResult, parse_bytes_with_decoder, parse_with_tables,
+};
+use zpl_toolchain_core::grammar::tables::ParserTables;
+use zpl_toolchain_diagnostics::{LineIndex, Span};
+
+/// Encoding of ZPL input files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum InputEncoding {
+    /// UTF-8; invalid byte sequences are an error.
+    #[default]
+    Utf8,
+    /// ISO-8859-1: each byte is the code point of the same value.
+    Latin1,
+    /// IBM code page 437, the printer's default character set (`^CI0`).
+    Cp437,
+}
+
+/// Upper half (0x80–0xFF) of code page 437; the lower half is ASCII.
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', 'É', 'æ', 'Æ',
+    'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', 'á', 'í', 'ó', 'ú', 'ñ', 'Ñ',
+    'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕',
+    '╣', '║', '╗', '╝', '╜', '╛', '┐', '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦',
+    '╠', '═', '╬', '╧', '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐',
+    '▀', 'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', '≡', '±',
+    '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+impl InputEncoding {
+    /// Decode raw file bytes into text.
+    pub(crate) fn decode(self, bytes: &[u8]) -> Result<String> {
+        match self {
+            Self::Utf8 => match std::str::from_utf8(bytes) {
+                Ok(text) => Ok(text.to_string()),
+                Err(err) => bail!(
+                    "input is not valid UTF-8 ({}) — pass --input-encoding latin1 or cp437 for legacy files",
+                    err
+                ),
+            },
+            Self::Latin1 | Self::Cp437 => Ok(bytes.iter().map(|&b| self.decode_byte(b)).collect()),
+        }
+    }
+
+    /// Character for one byte of a single-byte encoding.
+    fn decode_byte(self, b: u8) -> char {
+        match (self, b) {
+            (Self::Cp437, 0x80..=0xFF) => CP437_HIGH[usize::from(b - 0x80)],
+            _ => char::from(b),
+        }
+    }
+
+    /// Encode text back into this encoding. Characters the encoding cannot
+    /// represent become `?`.
+    pub(crate) fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Self::Utf8 => text.as_bytes().to_vec(),
+            Self::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c).unwrap_or(b'?'))
+                .collect(),
+            Self::Cp437 => text
+                .chars()
+                .map(|c| match u8::try_from(c) {
+                    Ok(b) if b.is_ascii() => b,
+                    _ => CP437_HIGH
+                        .iter()
+                        .position(|&h| h == c)
+                        .map_or(b'?', |i| 0x80 + i as u8),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A ZPL input file: its raw bytes and their decoded text.
+///
+/// Diagnostic spans from [`Input::parse`] are byte offsets into the file, so
+/// tools see the same positions whatever the encoding. Use
+/// [`Input::text_offset`] to turn them into offsets into [`Input::text`].
+#[derive(Debug, Clone)]
+pub(crate) struct Input {
+    bytes: Vec<u8>,
+    text: String,
+    encoding: InputEncoding,
+    lines: LineIndex,
+}
+
+impl Input {
+    /// Decode `bytes` read from a file.
+    pub(crate) fn decode(bytes: Vec<u8>, encoding: InputEncoding) -> Result<Self> {
+        let text = encoding.decode(&bytes)?;
+        let lines = LineIndex::new(&text);
+        Ok(Self {
+            bytes,
+            text,
+            encoding,
+            lines,
+        })
+    }
+
+    /// The raw file bytes.
+    pub(crate) fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The decoded text.
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether each character of [`Input::text`] is one byte of the file.
+    pub(crate) fn is_single_byte(&self) -> bool {
+        self.encoding != InputEncoding::Utf8
+    }
+
+    /// Parse the file; spans are byte offsets into [`Input::bytes`].
+    pub(crate) fn parse(&self, tables: Option<&ParserTables>) -> ParseResult {
+        if self.is_single_byte() {
+            parse_bytes_with_decoder(&self.bytes, tables, |b| self.encoding.decode_byte(b))
+        } else {
+            parse_with_tables(&self.text, tables)
+        }
+    }
+
+    /// Offset into [`Input::text`] of file byte `offset`, clamped to the end.
+    pub(crate) fn text_offset(&self, offset: usize) -> usize {
+        if self.is_single_byte() {
+            self.text
+                .char_indices()
+                .nth(offset)
+                .map_or(self.text.len(), |(i, _)| i)
+        } else {
+            offset.min(self.text.len())
+        }
+    }
+
+    /// File byte offset of `offset` into [`Input::text`].
+    pub(crate) fn byte_offset(&self, offset: usize) -> usize {
+        if self.is_single_byte() {
+            self.text[..offset].chars().count()
+        } else {
+            offset
+        }
+    }
+
+    /// The decoded text covered by a span of file bytes.
+    pub(crate) fn span_text(&self, span: Span) -> &str {
+        let start = self.text_offset(span.start);
+        let end = self.text_offset(span.end).max(start);
+        self.text.get(start..end).unwrap_or_default()
+    }
+
+    /// 0-indexed line and byte column of file byte `offset`.
+    pub(crate) fn line_col(&self, offset: usize) -> (usize, usize) {
+        let text_offset = self.text_offset(offset);
+        let (line, col) = self.lines.line_col(text_offset);
+        (
+            line,
+            self.byte_offset(text_offset) - self.byte_offset(text_offset - col),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_byte_encodings_round_trip_every_byte() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for encoding in [InputEncoding::Latin1, InputEncoding::Cp437] {
+            let text = encoding.decode(&bytes).unwrap();
+            assert_eq!(text.chars().count(), 256);
+            assert_eq!(encoding.encode(&text), bytes, "{encoding:?}");
+        }
+    }
+
+    #[test]
+    fn decodes_high_bytes_per_encoding() {
+        assert_eq!(InputEncoding::Latin1.decode(&[0xE9]).unwrap(), "é");
+        assert_eq!(InputEncoding::Cp437.decode(&[0x82]).unwrap(), "é");
+        assert!(InputEncoding::Utf8.decode(&[0xE9]).is_err());
+    }
+
+    #[test]
+    fn single_byte_input_positions_are_file_bytes() {
+        // 0xB0 is '░' in CP437: one byte in the file, three in the text.
+        let input = Input::decode(b"\xB0\xB0\n^XA".to_vec(), InputEncoding::Cp437).unwrap();
+        assert_eq!(input.text_offset(3), 7);
+        assert_eq!(input.byte_offset(7), 3);
+        assert_eq!(input.line_col(1), (0, 1));
+        assert_eq!(input.line_col(4), (1, 1));
+        assert_eq!(input.span_text(Span::new(3, 6)), "^XA");
+    }
+}
@@ -0,0 +1,281 @@
+//! Stable exit codes for the `zpl` binary.
+//!
+//! Scripts and CI branch on these, so the numeric values are part of the
+//! CLI contract: new variants may be added, but existing values never change.
+//! `zpl --explain-exit-codes` prints the table below.
+
+use std::fmt;
+use std::process;
+
+use zpl_toolchain_diagnostics::{Diagnostic, Severity};
+use zpl_toolchain_print_client::PrintError;
+
+/// Process exit code returned by every `zpl` command.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ExitCode {
+    /// The command completed and found nothing to fail on.
+    Success = 0,
+    /// The ZPL had errors (or `format --check` found unformatted input), or
+    /// the command failed for a reason not covered by a more specific code.
+    LintErrors = 1,
+    /// The printer could not be reached, or the connection dropped mid-job.
+    ConnectionFailed = 2,
+    /// The job was sent, but post-send status checks (`--verify`, `--wait`)
+    /// failed or reported printer faults.
+    VerifyFailed = 3,
+    /// Invalid command-line usage (unknown flags, conflicting options, a
+    /// printer address that does not fit the chosen transport, invalid
+    /// printer configuration).
+    Usage = 4,
+}
+
+impl ExitCode {
+    /// Every exit code, in numeric order.
+    pub(crate) const ALL: [ExitCode; 5] = [
+        ExitCode::Success,
+        ExitCode::LintErrors,
+        ExitCode::ConnectionFailed,
+        ExitCode::VerifyFailed,
+        ExitCode::Usage,
+    ];
+
+    /// Numeric value passed to the OS.
+    pub(crate) fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Stable kebab-case name, used in `--explain-exit-codes` JSON output.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ExitCode::Success => "success",
+            ExitCode::LintErrors => "lint-errors",
+            ExitCode::ConnectionFailed => "connection-failed",
+            ExitCode::VerifyFailed => "verify-failed",
+            ExitCode::Usage => "usage",
+        }
+    }
+
+    /// One-line description shown by `--explain-exit-codes`.
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            ExitCode::Success => "command completed; no errors found",
+            ExitCode::LintErrors => {
+                "ZPL errors found, `format --check` found unformatted input, or another failure (e.g. unreadable file)"
+            }
+            ExitCode::ConnectionFailed => {
+                "printer unreachable, or the connection failed while sending"
+            }
+            ExitCode::VerifyFailed => {
+                "job sent, but --verify/--wait status checks failed or reported printer faults"
+            }
+            ExitCode::Usage => {
+                "invalid command-line usage, printer address, or printer configuration"
+            }
+        }
+    }
+
+    /// Exit code for a finished diagnostics run: any error fails it.
+    /// Warnings and info do not cause a non-zero exit.
+    pub(crate) fn for_diagnostics(diagnostics: &[Diagnostic]) -> ExitCode {
+        if diagnostics
+            .iter()
+            .any(|d| matches!(d.severity, Severity::Error))
+        {
+            ExitCode::LintErrors
+        } else {
+            ExitCode::Success
+        }
+    }
+
+    /// Exit code for an error propagated out of a command.
+    pub(crate) fn for_error(err: &anyhow::Error) -> ExitCode {
+        if err.chain().any(|e| e.is::<UsageError>()) {
+            ExitCode::Usage
+        } else if let Some(print) = err.chain().find_map(|e| e.downcast_ref::<PrintError>()) {
+            ExitCode::for_print_error(print)
+        } else {
+            ExitCode::LintErrors
+        }
+    }
+
+    /// Exit code for a print-client failure. Bad addresses and configuration
+    /// are usage errors; printer-reported faults and completion/preflight
+    /// failures are verify failures; everything else is a connection failure.
+    fn for_print_error(err: &PrintError) -> ExitCode {
+        match err {
+            PrintError::InvalidAddress(_) | PrintError::InvalidConfig(_) => ExitCode::Usage,
+            PrintError::PrinterError(_)
+            | PrintError::CompletionTimeout { .. }
+            | PrintError::PreflightFailed => ExitCode::VerifyFailed,
+            PrintError::RetriesExhausted { last_error, .. } => {
+                ExitCode::for_print_error(last_error)
+            }
+            _ => ExitCode::ConnectionFailed,
+        }
+    }
+
+    /// Terminate the process with this code.
+    pub(crate) fn exit(self) -> ! {
+        process::exit(self.code())
+    }
+}
+
+/// A command-line usage mistake detected after argument parsing, reported
+/// with [`ExitCode::Usage`].
+#[derive(Debug)]
+pub(crate) struct UsageError(pub(crate) String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// Return early with a [`UsageError`], formatted like `anyhow::bail!`.
+macro_rules! bail_usage {
+    ($($arg:tt)*) => {
+        return Err(anyhow::Error::new($crate::exit::UsageError(format!($($arg)*))))
+    };
+}
+pub(crate) use bail_usage;
+
+/// Plain-text exit code table printed by `zpl --explain-exit-codes`.
+pub(crate) fn exit_codes_text() -> String {
+    let mut out = String::from("exit codes:\n");
+    for code in ExitCode::ALL {
+        out.push_str(&format!(
+            "  {}  {:<18} {}\n",
+            code.code(),
+            code.name(),
+            code.description()
+        ));
+    }
+    out
+}
+
+/// JSON form of the exit code table.
+pub(crate) fn exit_codes_json() -> serde_json::Value {
+    serde_json::Value::Array(
+        ExitCode::ALL
+            .iter()
+            .map(|code| {
+                serde_json::json!({
+                    "code": code.code(),
+                    "name": code.name(),
+                    "description": code.description(),
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zpl_toolchain_diagnostics::Span;
+
+    #[test]
+    fn lint_errors_and_connection_failures_get_distinct_codes() {
+        let error = Diagnostic::error("ZPL1101", "bad arg", Some(Span::new(0, 1)));
+        let warning = Diagnostic::warn("ZPL2302", "off the label", None);
+        assert_eq!(
+            ExitCode::for_diagnostics(std::slice::from_ref(&warning)),
+            ExitCode::Success
+        );
+        assert_eq!(ExitCode::for_diagnostics(&[warning, error]).code(), 1);
+
+        let refused = anyhow::Error::new(PrintError::ConnectionRefused {
+            addr: "127.0.0.1:9100".into(),
+            source: std::io::Error::from(std::io::ErrorKind::ConnectionRefused),
+        })
+        .context("failed to connect to printer");
+        assert_eq!(ExitCode::for_error(&refused), ExitCode::ConnectionFailed);
+        assert_eq!(ExitCode::for_error(&refused).code(), 2);
+    }
+
+    #[test]
+    fn print_errors_map_to_their_documented_codes() {
+        use zpl_toolchain_print_client::PrinterErrorKind;
+
+        let cases = [
+            (PrintError::ConnectionClosed, ExitCode::ConnectionFailed),
+            (
+                PrintError::WriteFailed(std::io::Error::from(std::io::ErrorKind::BrokenPipe)),
+                ExitCode::ConnectionFailed,
+            ),
+            (PrintError::ReadTimeout, ExitCode::ConnectionFailed),
+            (
+                PrintError::InvalidAddress("host:notaport".into()),
+                ExitCode::Usage,
+            ),
+            (
+                PrintError::InvalidConfig("retry.max_attempts must be > 0".into()),
+                ExitCode::Usage,
+            ),
+            (
+                PrintError::PrinterError(PrinterErrorKind::PaperOut),
+                ExitCode::VerifyFailed,
+            ),
+            (
+                PrintError::CompletionTimeout {
+                    formats_in_buffer: 1,
+                    labels_remaining: 2,
+                },
+                ExitCode::VerifyFailed,
+            ),
+            (PrintError::PreflightFailed, ExitCode::VerifyFailed),
+            (
+                PrintError::RetriesExhausted {
+                    attempts: 3,
+                    last_error: Box::new(PrintError::ReadTimeout),
+                },
+                ExitCode::ConnectionFailed,
+            ),
+            (
+                PrintError::RetriesExhausted {
+                    attempts: 3,
+                    last_error: Box::new(PrintError::CompletionTimeout {
+                        formats_in_buffer: 0,
+                        labels_remaining: 1,
+                    }),
+                },
+                ExitCode::VerifyFailed,
+            ),
+        ];
+        for (err, expected) in cases {
+            let code = err.code();
+            let err = anyhow::Error::new(err).context("print failed");
+            assert_eq!(ExitCode::for_error(&err), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn usage_errors_and_other_failures_map_to_their_codes() {
+        fn usage() -> anyhow::Result<()> {
+            bail_usage!(
+                "--serial cannot be used with USB printer address '{}'",
+                "usb"
+            );
+        }
+        let err = usage().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "--serial cannot be used with USB printer address 'usb'"
+        );
+        assert_eq!(ExitCode::for_error(&err).code(), 4);
+
+        let other = anyhow::anyhow!("no such file");
+        assert_eq!(ExitCode::for_error(&other), ExitCode::LintErrors);
+    }
+
+    #[test]
+    fn exit_code_table_lists_every_code_in_order() {
+        let codes: Vec<i32> = ExitCode::ALL.iter().map(|c| c.code()).collect();
+        assert_eq!(codes, vec![0, 1, 2, 3, 4]);
+        let text = exit_codes_text();
+        assert!(text.contains("2  connection-failed"), "{text}");
+        assert_eq!(exit_codes_json().as_array().map(Vec::len), Some(5));
+    }
+}
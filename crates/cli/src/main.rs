@@ -1,36 +1,43 @@
 //! ZPL CLI — parse, lint, format, and validate Zebra Programming Language files.
 
+mod baseline;
+mod encoding;
+mod exit;
+mod profile_detect;
 mod render;
+mod status_report;
 
 use std::fs;
-use std::io::Read;
-use std::process;
+use std::io::{Read, Write};
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use zpl_toolchain_core::grammar::{
-    dump::to_pretty_json,
-    emit::{Compaction, EmitConfig, Indent, emit_zpl},
+    ast::{Ast, select_label},
+    emit::{Compaction, EmitConfig, Indent, LineEnding, emit_zpl, emit_zpl_with_source},
+    lexer::{TokKind, tokenize},
     parser::parse_with_tables,
     tables::ParserTables,
 };
 use zpl_toolchain_core::validate;
 use zpl_toolchain_diagnostics::{self as diag, Diagnostic, Severity};
-#[cfg(feature = "tcp")]
-use zpl_toolchain_print_client::TcpPrinter;
-#[cfg(feature = "usb")]
-use zpl_toolchain_print_client::UsbPrinter;
+#[cfg(feature = "serial")]
+use zpl_toolchain_print_client::stderr_trace_sink;
 use zpl_toolchain_print_client::{
-    PrinterConfig, StatusQuery, resolve_printer_addr, wait_for_completion,
+    Printer, PrinterConfig, PrinterTarget, StatusQuery, connect_any, resolve_printer_addr,
+    wait_for_completion,
 };
 #[cfg(feature = "serial")]
 use zpl_toolchain_print_client::{
     SerialDataBits, SerialFlowControl, SerialParity, SerialPrinter, SerialSettings, SerialStopBits,
 };
 
+use crate::baseline::Baseline;
+use crate::encoding::{Input, InputEncoding};
+use crate::exit::{ExitCode, bail_usage, exit_codes_json, exit_codes_text};
 use crate::render::{
-    Format, SarifArtifactInput, emit_sarif_run, print_summary, render_diagnostics,
-    render_diagnostics_sarif_multi, sarif_result, sarif_rule,
+    Format, Report, SarifArtifactInput, SarifOutput, diagnostics_by_code_json, render_counts,
+    render_report, resolve_pretty_width, sarif_log, sarif_result, sarif_rule,
 };
 
 // ── Embedded tables (ADR 0005) ──────────────────────────────────────────
@@ -46,17 +53,34 @@ const EMBEDDED_TABLES_JSON: &str = include_str!(concat!(env!("OUT_DIR"), "/parse
 #[command(
     name = "zpl",
     version,
-    about = "ZPL toolchain — parse, lint, format, validate, and print Zebra Programming Language files"
+    about = "ZPL toolchain — parse, lint, format, validate, and print Zebra Programming Language files",
+    arg_required_else_help = true
 )]
 struct Cli {
     /// Output mode: "pretty" for coloured terminal output, "json" for
     /// machine-readable JSON, "sarif" for SARIF 2.1.0 (CI/tooling integration).
-    /// Defaults to "pretty" when stdout is a TTY, "json" otherwise.
+    /// Defaults to "pretty" when stdout is a TTY, "json" otherwise; commands
+    /// that print ZPL (`format`, `normalize`, `extract`) always default to raw ZPL.
     #[arg(long, global = true, value_parser = ["pretty", "json", "sarif"])]
     output: Option<String>,
 
+    /// Fixed width (columns) for pretty diagnostic output, e.g. for
+    /// reproducible CI logs. Falls back to `COLUMNS`, then auto-detection.
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pretty_width: Option<u16>,
+
+    /// Encoding of ZPL input files. Text produced by `format`, `normalize`,
+    /// and `print` is written back in the same encoding, so binary payload
+    /// bytes survive unchanged.
+    #[arg(long, global = true, value_enum, default_value_t = InputEncoding::Utf8)]
+    input_encoding: InputEncoding,
+
+    /// Print the exit codes `zpl` returns and what each one means, then exit.
+    #[arg(long)]
+    explain_exit_codes: bool,
+
     #[command(subcommand)]
-    cmd: Cmd,
+    cmd: Option<Cmd>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -72,6 +96,13 @@ enum Cmd {
         tables: Option<String>,
     },
 
+    /// Dump the lexer token stream of a ZPL file.
+    Tokens {
+        /// ZPL source file to tokenize.
+        #[arg(value_name = "FILE")]
+        file: String,
+    },
+
     /// Syntax-check a ZPL file (parse only, no validation).
     #[command(name = "syntax-check", visible_alias = "check")]
     SyntaxCheck {
@@ -81,6 +112,9 @@ enum Cmd {
         /// Override the embedded parser tables with a custom JSON file.
         #[arg(long, value_name = "PATH", hide = true)]
         tables: Option<String>,
+        /// Print only error/warning/info counts instead of each diagnostic.
+        #[arg(long)]
+        count: bool,
     },
 
     /// Lint: parse and validate a ZPL file against the spec and an optional
@@ -99,6 +133,19 @@ enum Cmd {
         /// Which note audiences to include in diagnostics.
         #[arg(long, value_enum, default_value_t = NoteAudienceMode::All)]
         note_audience: NoteAudienceMode,
+        /// Baseline JSON of known diagnostics; only diagnostics not in it are reported.
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<String>,
+        /// Record the current diagnostics into the --baseline file instead of reporting them.
+        #[arg(long, requires = "baseline")]
+        update_baseline: bool,
+        /// Group diagnostics under their code with a count instead of source order
+        /// (pretty and JSON output; SARIF is unaffected).
+        #[arg(long)]
+        group_by_code: bool,
+        /// Print only error/warning/info counts instead of each diagnostic.
+        #[arg(long, conflicts_with = "group_by_code")]
+        count: bool,
     },
 
     // ── File transformation ─────────────────────────────────────────
@@ -122,6 +169,65 @@ enum Cmd {
         /// Optional compaction mode.
         #[arg(long, value_enum, default_value_t = CompactionStyle::None)]
         compaction: CompactionStyle,
+        /// Keep a single blank line wherever the source groups commands with blank lines.
+        #[arg(long)]
+        preserve_blank_lines: bool,
+        /// Line ending for the formatted output.
+        #[arg(long, value_enum, default_value_t = LineEndingStyle::Lf)]
+        line_ending: LineEndingStyle,
+        /// Wrap a command fragment with no ^XA/^XZ in a single label.
+        #[arg(long)]
+        wrap_bare_commands: bool,
+        /// Re-format the output and fail if the second pass differs (formatter self-check).
+        #[arg(long, hide = true)]
+        check_idempotent: bool,
+    },
+
+    /// Print a ZPL file in canonical form for golden-file comparisons
+    /// (fixed pipeline: canonical args, upper-case hex, LF, no indentation).
+    Normalize {
+        /// ZPL source file to normalize.
+        #[arg(value_name = "FILE")]
+        file: String,
+        /// Override the embedded parser tables with a custom JSON file.
+        #[arg(long, value_name = "PATH", hide = true)]
+        tables: Option<String>,
+    },
+
+    /// Print one ^XA...^XZ label of a multi-label file.
+    Extract {
+        /// ZPL source file to extract from.
+        #[arg(value_name = "FILE")]
+        file: String,
+        /// Label to extract, 1-based; negative values count from the end (-1 is the last).
+        #[arg(long, allow_negative_numbers = true)]
+        label: isize,
+        /// Format the label instead of printing its source verbatim.
+        #[arg(long)]
+        formatted: bool,
+        /// Override the embedded parser tables with a custom JSON file.
+        #[arg(long, value_name = "PATH", hide = true)]
+        tables: Option<String>,
+    },
+
+    /// Render a label to a monochrome PNG preview (approximate).
+    #[cfg(feature = "render")]
+    Render {
+        /// ZPL source file to render.
+        #[arg(value_name = "FILE")]
+        file: String,
+        /// Output PNG path.
+        #[arg(long, short, value_name = "PATH")]
+        output_png: String,
+        /// Label to render, 1-based, for files with several ^XA...^XZ blocks.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        label: u32,
+        /// Printer profile JSON supplying resolution and page size defaults.
+        #[arg(long, value_name = "PATH")]
+        profile: Option<String>,
+        /// Override the embedded parser tables with a custom JSON file.
+        #[arg(long, value_name = "PATH", hide = true)]
+        tables: Option<String>,
     },
 
     // ── Printing ─────────────────────────────────────────────────────
@@ -134,6 +240,7 @@ enum Cmd {
         /// - TCP: `IP`, `hostname`, or `host:port`
         /// - USB: `usb` or `usb:VID:PID`
         /// - Serial/Bluetooth SPP: OS serial path (for example `/dev/cu.*`, `/dev/tty*`, `COM*`) with `--serial`
+        /// - Bluetooth RFCOMM (Linux): `bt:MAC` or `bt:MAC:CHANNEL`
         #[arg(long, short)]
         printer: String,
         /// Printer profile JSON for hardware-specific validation (see profiles/).
@@ -173,6 +280,10 @@ enum Cmd {
         /// Timeout in seconds for --wait polling (default 120s).
         #[arg(long, default_value_t = 120, requires = "wait")]
         wait_timeout: u64,
+        /// Send all files in one write instead of one write per file. A failed
+        /// write is then reported against every file.
+        #[arg(long)]
+        coalesce: bool,
         /// Use serial/Bluetooth SPP transport (printer address is a serial port path).
         #[cfg(feature = "serial")]
         #[arg(long)]
@@ -203,6 +314,65 @@ enum Cmd {
         trace_io: bool,
     },
 
+    /// Query a printer's status (~HS), info (~HI), and memory (~HM) and print a report.
+    Status {
+        /// Printer target (same forms as `print --printer`):
+        /// - TCP: `IP`, `hostname`, or `host:port`
+        /// - USB: `usb` or `usb:VID:PID`
+        /// - Serial/Bluetooth SPP: OS serial path with `--serial`
+        /// - Bluetooth RFCOMM (Linux): `bt:MAC` or `bt:MAC:CHANNEL`
+        #[arg(long, short)]
+        printer: String,
+        /// Connection timeout in seconds (scales connect/write/read proportionally).
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+        /// Use serial/Bluetooth SPP transport (printer address is a serial port path).
+        #[cfg(feature = "serial")]
+        #[arg(long)]
+        serial: bool,
+        /// Baud rate for serial connections (default: 9600).
+        #[cfg(feature = "serial")]
+        #[arg(long, default_value_t = 9600, requires = "serial")]
+        baud: u32,
+        /// Serial flow control (none/software/hardware).
+        #[cfg(feature = "serial")]
+        #[arg(long, value_enum, default_value_t = CliSerialFlowControl::Software, requires = "serial")]
+        serial_flow_control: CliSerialFlowControl,
+        /// Serial parity (none/even/odd).
+        #[cfg(feature = "serial")]
+        #[arg(long, value_enum, default_value_t = CliSerialParity::None, requires = "serial")]
+        serial_parity: CliSerialParity,
+        /// Serial stop bits (1/2).
+        #[cfg(feature = "serial")]
+        #[arg(long, value_enum, default_value_t = CliSerialStopBits::One, requires = "serial")]
+        serial_stop_bits: CliSerialStopBits,
+        /// Serial data bits (7/8).
+        #[cfg(feature = "serial")]
+        #[arg(long, value_enum, default_value_t = CliSerialDataBits::Eight, requires = "serial")]
+        serial_data_bits: CliSerialDataBits,
+    },
+
+    /// Build a starter printer profile from the printer's ~HI and ~HM replies.
+    ///
+    /// Resolution, RAM, and firmware are detected; page size, speed and
+    /// darkness ranges, features, and media are left unset.
+    #[command(name = "profile-detect")]
+    ProfileDetect {
+        /// Printer target: `IP`, `hostname`, `host:port`, `usb`,
+        /// `usb:VID:PID`, or `bt:MAC` (Linux).
+        #[arg(long, short)]
+        printer: String,
+        /// Profile id (default: `zebra-<model>` from ~HI).
+        #[arg(long)]
+        id: Option<String>,
+        /// Write the profile to this file instead of stdout.
+        #[arg(long, value_name = "PATH")]
+        save: Option<String>,
+        /// Connection timeout in seconds (scales connect/write/read proportionally).
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        timeout: Option<u64>,
+    },
+
     /// Probe a serial/Bluetooth endpoint and report bidirectional health.
     #[cfg(feature = "serial")]
     SerialProbe {
@@ -297,6 +467,13 @@ enum Cmd {
     /// Explain a diagnostic ID (e.g. ZPL1201).
     Explain { id: String },
 
+    /// Print the JSON Schema for a JSON output type.
+    Schema {
+        /// Output type to describe.
+        #[arg(value_enum)]
+        kind: SchemaKind,
+    },
+
     /// Run environment and configuration diagnostics.
     Doctor {
         /// Optional printer target to check reachability (TCP only in v1).
@@ -335,6 +512,28 @@ enum CompactionStyle {
     Field,
 }
 
+/// Line-ending style for the `format` command.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LineEndingStyle {
+    /// Unix-style LF.
+    Lf,
+    /// Windows-style CRLF.
+    Crlf,
+    /// Keep the input file's line ending (LF if mixed).
+    Preserve,
+}
+
+/// JSON output type for the `schema` command.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SchemaKind {
+    /// A single diagnostic.
+    Diagnostic,
+    /// Validation result (`ok`, `issues`, `resolved_labels`).
+    ValidationResult,
+    /// Parse result (`ast`, `diagnostics`).
+    ParseResult,
+}
+
 /// Controls which note audiences are surfaced by CLI diagnostics.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum NoteAudienceMode {
@@ -389,6 +588,18 @@ fn parse_min_success_ratio(s: &str) -> std::result::Result<f64, String> {
     }
 }
 
+impl Cmd {
+    /// Whether stdout carries ZPL rather than a report; `format --check`
+    /// and `format --write` only report a status.
+    fn prints_zpl(&self) -> bool {
+        match self {
+            Cmd::Format { write, check, .. } => !write && !check,
+            Cmd::Normalize { .. } | Cmd::Extract { .. } => true,
+            _ => false,
+        }
+    }
+}
+
 impl From<IndentStyle> for Indent {
     fn from(s: IndentStyle) -> Self {
         match s {
@@ -408,25 +619,86 @@ impl From<CompactionStyle> for Compaction {
     }
 }
 
+impl From<LineEndingStyle> for LineEnding {
+    fn from(s: LineEndingStyle) -> Self {
+        match s {
+            LineEndingStyle::Lf => LineEnding::Lf,
+            LineEndingStyle::Crlf => LineEnding::CrLf,
+            LineEndingStyle::Preserve => LineEnding::Preserve,
+        }
+    }
+}
+
 // ── Main ────────────────────────────────────────────────────────────────
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let format = Format::resolve_or_detect(cli.output.as_deref());
+    let cli = Cli::try_parse().unwrap_or_else(|err| {
+        // clap exits with 2 on usage errors; keep our own contract instead.
+        let _ = err.print();
+        if err.use_stderr() {
+            ExitCode::Usage.exit();
+        }
+        ExitCode::Success.exit();
+    });
+    let pretty_width = resolve_pretty_width(
+        cli.pretty_width.map(usize::from),
+        std::env::var("COLUMNS").ok().as_deref(),
+    );
+    let format = Format::resolve_or_detect(cli.output.as_deref(), pretty_width);
+    let encoding = cli.input_encoding;
+
+    if cli.explain_exit_codes && cli.cmd.is_some() {
+        let _ = Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--explain-exit-codes cannot be used with a subcommand",
+            )
+            .print();
+        ExitCode::Usage.exit();
+    }
+    let Some(cmd) = cli.cmd else {
+        if !cli.explain_exit_codes {
+            let _ = Cli::command().print_help();
+            ExitCode::Usage.exit();
+        }
+        let report =
+            Report::new(exit_codes_json()).pretty(|out, _| write!(out, "{}", exit_codes_text()));
+        render_report(&report, format)?;
+        return Ok(());
+    };
+    let format = if cmd.prints_zpl() {
+        Format::resolve_for_zpl(cli.output.as_deref(), pretty_width)
+    } else {
+        format
+    };
 
-    let run_result = match cli.cmd {
-        Cmd::Parse { file, tables } => cmd_parse(&file, tables.as_deref(), format),
-        Cmd::SyntaxCheck { file, tables } => cmd_syntax_check(&file, tables.as_deref(), format),
+    let run_result = match cmd {
+        Cmd::Parse { file, tables } => cmd_parse(&file, tables.as_deref(), encoding, format),
+        Cmd::Tokens { file } => cmd_tokens(&file, encoding, format),
+        Cmd::SyntaxCheck {
+            file,
+            tables,
+            count,
+        } => cmd_syntax_check(&file, tables.as_deref(), count, encoding, format),
         Cmd::Lint {
             file,
             tables,
             profile,
             note_audience,
+            baseline,
+            update_baseline,
+            group_by_code,
+            count,
         } => cmd_lint(
             &file,
             tables.as_deref(),
             profile.as_deref(),
             note_audience,
+            baseline.as_deref(),
+            update_baseline,
+            group_by_code,
+            count,
+            encoding,
             format,
         ),
         Cmd::Format {
@@ -436,6 +708,10 @@ fn main() -> Result<()> {
             check,
             indent,
             compaction,
+            preserve_blank_lines,
+            line_ending,
+            wrap_bare_commands,
+            check_idempotent,
         } => cmd_format(
             &file,
             tables.as_deref(),
@@ -443,6 +719,36 @@ fn main() -> Result<()> {
             check,
             indent,
             compaction,
+            preserve_blank_lines,
+            line_ending,
+            wrap_bare_commands,
+            check_idempotent,
+            encoding,
+            format,
+        ),
+        Cmd::Normalize { file, tables } => {
+            cmd_normalize(&file, tables.as_deref(), encoding, format)
+        }
+        Cmd::Extract {
+            file,
+            label,
+            formatted,
+            tables,
+        } => cmd_extract(&file, label, formatted, tables.as_deref(), encoding, format),
+        #[cfg(feature = "render")]
+        Cmd::Render {
+            file,
+            output_png,
+            label,
+            profile,
+            tables,
+        } => cmd_render(
+            &file,
+            &output_png,
+            label,
+            profile.as_deref(),
+            tables.as_deref(),
+            encoding,
             format,
         ),
         Cmd::Print {
@@ -460,6 +766,7 @@ fn main() -> Result<()> {
             wait,
             timeout,
             wait_timeout,
+            coalesce,
             #[cfg(feature = "serial")]
             serial,
             #[cfg(feature = "serial")]
@@ -489,6 +796,7 @@ fn main() -> Result<()> {
             wait,
             timeout,
             wait_timeout,
+            coalesce,
             #[cfg(feature = "serial")]
             serial,
             #[cfg(feature = "serial")]
@@ -503,6 +811,7 @@ fn main() -> Result<()> {
             serial_data_bits,
             #[cfg(feature = "serial")]
             trace_io,
+            encoding,
             format,
         }),
         #[cfg(feature = "serial")]
@@ -546,6 +855,48 @@ fn main() -> Result<()> {
             trace_io,
             format,
         }),
+        Cmd::Status {
+            printer,
+            timeout,
+            #[cfg(feature = "serial")]
+            serial,
+            #[cfg(feature = "serial")]
+            baud,
+            #[cfg(feature = "serial")]
+            serial_flow_control,
+            #[cfg(feature = "serial")]
+            serial_parity,
+            #[cfg(feature = "serial")]
+            serial_stop_bits,
+            #[cfg(feature = "serial")]
+            serial_data_bits,
+        } => {
+            #[cfg(feature = "serial")]
+            let serial = serial.then(|| {
+                (
+                    baud,
+                    SerialSettings {
+                        flow_control: to_print_flow_control(serial_flow_control),
+                        parity: to_print_parity(serial_parity),
+                        stop_bits: to_print_stop_bits(serial_stop_bits),
+                        data_bits: to_print_data_bits(serial_data_bits),
+                    },
+                )
+            });
+            cmd_status(
+                &printer,
+                timeout,
+                #[cfg(feature = "serial")]
+                serial,
+                format,
+            )
+        }
+        Cmd::ProfileDetect {
+            printer,
+            id,
+            save,
+            timeout,
+        } => cmd_profile_detect(&printer, id.as_deref(), save.as_deref(), timeout),
         #[cfg(feature = "tcp")]
         Cmd::BtStatus {
             printer,
@@ -559,6 +910,7 @@ fn main() -> Result<()> {
             json,
         } => cmd_coverage(&coverage, show_issues, json),
         Cmd::Explain { id } => cmd_explain(&id, format),
+        Cmd::Schema { kind } => cmd_schema(kind),
         Cmd::Doctor {
             printer,
             profile,
@@ -575,90 +927,136 @@ fn main() -> Result<()> {
 
     if let Err(err) = run_result {
         emit_cli_error(format, &err);
-        process::exit(1);
+        ExitCode::for_error(&err).exit();
     }
     Ok(())
 }
 
 // ── Commands ────────────────────────────────────────────────────────────
 
-fn cmd_parse(file: &str, tables_path: Option<&str>, format: Format) -> Result<()> {
-    let input = read_input(file)?;
-    let res = parse_with_resolved_tables(tables_path, &input)?;
+fn cmd_tokens(file: &str, encoding: InputEncoding, format: Format) -> Result<()> {
+    let input = read_input(file, encoding)?;
+    let tokens = tokenize(input.text());
 
-    match format {
-        Format::Json => {
-            // Single valid JSON object to stdout.
-            let out = serde_json::json!({
-                "ast": res.ast,
-                "diagnostics": res.diagnostics,
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        }
-        Format::Sarif => {
-            // SARIF 2.1.0 to stdout; no AST (SARIF is diagnostic-focused).
-            render_diagnostics(&input, file, &res.diagnostics, format);
-        }
-        Format::Pretty => {
-            // AST to stdout, diagnostics to stderr.
-            println!("{}", to_pretty_json(&res.ast));
-            if !res.diagnostics.is_empty() {
-                render_diagnostics(&input, file, &res.diagnostics, format);
-                print_summary(&res.diagnostics);
-            }
-        }
+    let json: Vec<_> = tokens
+        .iter()
+        .map(|t| {
+            serde_json::json!({
+                "start": input.byte_offset(t.start),
+                "end": input.byte_offset(t.end),
+                "kind": token_kind_name(&t.kind),
+            })
+        })
+        .collect();
+    let report = Report::new(serde_json::Value::Array(json)).pretty(|out, _| {
+        writeln!(out, "{:>6} {:>6}  {:<10}  TEXT", "START", "END", "KIND")?;
+        for t in &tokens {
+            writeln!(
+                out,
+                "{:>6} {:>6}  {:<10}  {:?}",
+                input.byte_offset(t.start),
+                input.byte_offset(t.end),
+                token_kind_name(&t.kind),
+                t.text
+            )?;
+        }
+        Ok(())
+    });
+    render_report(&report, format)?;
+
+    Ok(())
+}
+
+/// Stable lower-case name of a lexer token kind for `zpl tokens` output.
+fn token_kind_name(kind: &TokKind) -> &'static str {
+    match kind {
+        TokKind::Leader => "leader",
+        TokKind::Comma => "comma",
+        TokKind::Value => "value",
+        TokKind::Newline => "newline",
+        TokKind::Whitespace => "whitespace",
     }
+}
+
+fn cmd_parse(
+    file: &str,
+    tables_path: Option<&str>,
+    encoding: InputEncoding,
+    format: Format,
+) -> Result<()> {
+    let input = read_input(file, encoding)?;
+    let res = parse_with_resolved_tables(tables_path, &input)?;
+
+    format.formatter().render_parse(
+        &mut std::io::stdout().lock(),
+        &mut std::io::stderr().lock(),
+        &input,
+        file,
+        &res,
+    )?;
 
     exit_on_errors(&res.diagnostics);
     Ok(())
 }
 
-fn cmd_syntax_check(file: &str, tables_path: Option<&str>, format: Format) -> Result<()> {
-    let input = read_input(file)?;
+fn cmd_syntax_check(
+    file: &str,
+    tables_path: Option<&str>,
+    count: bool,
+    encoding: InputEncoding,
+    format: Format,
+) -> Result<()> {
+    let input = read_input(file, encoding)?;
     let res = parse_with_resolved_tables(tables_path, &input)?;
+    if count {
+        render_counts(&res.diagnostics, format)?;
+        exit_on_errors(&res.diagnostics);
+        return Ok(());
+    }
     let ok = !res
         .diagnostics
         .iter()
         .any(|d| matches!(d.severity, Severity::Error));
 
-    match format {
-        Format::Json => {
-            let out = serde_json::json!({
-                "ok": ok,
-                "diagnostics": res.diagnostics,
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        }
-        Format::Sarif => {
-            render_diagnostics(&input, file, &res.diagnostics, format);
-        }
-        Format::Pretty => {
-            render_diagnostics(&input, file, &res.diagnostics, format);
-            print_summary(&res.diagnostics);
-            if ok {
-                eprintln!("syntax ok");
-            }
+    let report = Report::new(serde_json::json!({
+        "ok": ok,
+        "diagnostics": res.diagnostics,
+    }))
+    .diagnostics(&input, file, &res.diagnostics)
+    .pretty(|_, err| {
+        if ok {
+            writeln!(err, "syntax ok")
+        } else {
+            Ok(())
         }
-    }
+    })
+    .sarif(SarifOutput::Diagnostics);
+    render_report(&report, format)?;
 
     exit_on_errors(&res.diagnostics);
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn cmd_lint(
     file: &str,
     tables_path: Option<&str>,
     profile_path: Option<&str>,
     note_audience: NoteAudienceMode,
+    baseline_path: Option<&str>,
+    update_baseline: bool,
+    group_by_code: bool,
+    count: bool,
+    encoding: InputEncoding,
     format: Format,
 ) -> Result<()> {
-    let input = read_input(file)?;
+    let input = read_input(file, encoding)?;
     let tables = resolve_tables(tables_path)?.context(
         "no parser tables available — this binary was built without embedded tables. \
          Download a release build from https://github.com/trevordcampbell/zpl-toolchain/releases, \
          reinstall via `cargo install zpl_toolchain_cli`, or pass --tables <PATH> to a tables JSON file",
     )?;
-    let res = parse_with_tables(&input, Some(&tables));
+    let res = input.parse(Some(&tables));
 
     let prof = match profile_path {
         Some(p) => {
@@ -674,31 +1072,80 @@ fn cmd_lint(
 
     let mut vr = validate::validate_with_profile(&res.ast, &tables, prof.as_ref());
     // Merge parser diagnostics into lint surface.
-    vr.issues.extend(res.diagnostics);
+    vr.merge_parse(res.diagnostics);
     filter_contextual_notes(&mut vr.issues, note_audience);
 
-    match format {
-        Format::Json => {
-            let out = serde_json::json!({
-                "ok": vr.ok,
-                // Keep both keys for compatibility; prefer diagnostics.
-                "diagnostics": vr.issues,
-                "issues": vr.issues,
-                "resolved_labels": vr.resolved_labels,
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        }
-        Format::Sarif => {
-            render_diagnostics(&input, file, &vr.issues, format);
-        }
-        Format::Pretty => {
-            render_diagnostics(&input, file, &vr.issues, format);
-            print_summary(&vr.issues);
+    if update_baseline && let Some(path) = baseline_path {
+        let baseline = Baseline::from_diagnostics(&input, &vr.issues);
+        baseline.save(path)?;
+        let report = Report::new(serde_json::json!({
+            "baseline": path,
+            "recorded": baseline.diagnostics.len(),
+        }))
+        .pretty(|_, err| {
+            writeln!(
+                err,
+                "baseline '{}' updated with {} diagnostic(s)",
+                path,
+                baseline.diagnostics.len()
+            )
+        })
+        .sarif(SarifOutput::Pretty);
+        render_report(&report, format)?;
+        return Ok(());
+    }
+
+    let mut baselined = 0;
+    if let Some(path) = baseline_path {
+        baselined = Baseline::load(path)?.suppress(&input, &mut vr.issues);
+        vr.ok = !vr
+            .issues
+            .iter()
+            .any(|d| matches!(d.severity, Severity::Error));
+    }
+
+    if count {
+        render_counts(&vr.issues, format)?;
+        exit_on_errors(&vr.issues);
+        return Ok(());
+    }
+
+    let mut json = if group_by_code {
+        serde_json::json!({
+            "ok": vr.ok,
+            "by_code": diagnostics_by_code_json(&vr.issues),
+            "resolved_labels": vr.resolved_labels,
+        })
+    } else {
+        serde_json::json!({
+            "ok": vr.ok,
+            // Keep both keys for compatibility; prefer diagnostics.
+            "diagnostics": vr.issues,
+            "issues": vr.issues,
+            "resolved_labels": vr.resolved_labels,
+        })
+    };
+    if baseline_path.is_some() {
+        json["baselined"] = serde_json::json!(baselined);
+    }
+    let report = Report::new(json)
+        .diagnostics(&input, file, &vr.issues)
+        .group_by_code(group_by_code)
+        .pretty(|_, err| {
+            if baselined > 0 {
+                writeln!(
+                    err,
+                    "{} known diagnostic(s) suppressed by baseline",
+                    baselined
+                )?;
+            }
             if vr.ok {
-                eprintln!("lint ok");
+                writeln!(err, "lint ok")?;
             }
-        }
-    }
+            Ok(())
+        })
+        .sarif(SarifOutput::Diagnostics);
+    render_report(&report, format)?;
 
     exit_on_errors(&vr.issues);
     Ok(())
@@ -729,148 +1176,292 @@ fn cmd_format(
     check: bool,
     indent: IndentStyle,
     compaction: CompactionStyle,
+    preserve_blank_lines: bool,
+    line_ending: LineEndingStyle,
+    wrap_bare_commands: bool,
+    check_idempotent: bool,
+    encoding: InputEncoding,
     format: Format,
 ) -> Result<()> {
-    let input = read_input(file)?;
+    let input = read_input(file, encoding)?;
     if file == "-" && (write || check) {
-        anyhow::bail!("--write/--check cannot be used when reading from stdin ('-')");
+        bail_usage!("--write/--check cannot be used when reading from stdin ('-')");
     }
     let tables = resolve_tables(tables_path)?.context(
         "no parser tables available for format — pass --tables <PATH> or use a build with embedded tables",
     )?;
-    let res = parse_with_tables(&input, Some(&tables));
-
-    // Surface parse diagnostics so the user knows if the input has issues.
-    if format == Format::Pretty && !res.diagnostics.is_empty() {
-        render_diagnostics(&input, file, &res.diagnostics, format);
-        print_summary(&res.diagnostics);
-    }
+    let res = input.parse(Some(&tables));
 
     let config = EmitConfig {
         indent: indent.into(),
         compaction: compaction.into(),
+        preserve_blank_lines,
+        line_ending: line_ending.into(),
+        wrap_bare_commands,
+        canonicalize: false,
     };
-    let formatted = emit_zpl(&res.ast, Some(&tables), &config);
+    let formatted = emit_zpl_with_source(&res.ast, Some(&tables), &config, input.text());
 
-    let already_formatted = formatted == input;
+    if check_idempotent {
+        let reparsed = parse_with_tables(&formatted, Some(&tables));
+        let reformatted = emit_zpl_with_source(&reparsed.ast, Some(&tables), &config, &formatted);
+        if let Some(line) = first_differing_line(&formatted, &reformatted) {
+            anyhow::bail!(
+                "formatter is not idempotent for '{}': second pass differs at line {}\n  first:  {}\n  second: {}",
+                file,
+                line + 1,
+                formatted.lines().nth(line).unwrap_or("<end of output>"),
+                reformatted.lines().nth(line).unwrap_or("<end of output>"),
+            );
+        }
+    }
 
-    if format == Format::Sarif {
-        render_diagnostics(&input, file, &res.diagnostics, format);
+    let already_formatted = formatted == input.text();
+    if write && !already_formatted {
+        fs::write(file, encoding.encode(&formatted))?;
     }
 
-    if check {
-        if format == Format::Json {
-            let out = serde_json::json!({
-                "mode": "check",
-                "file": file,
-                "already_formatted": already_formatted,
-                "status": if already_formatted { "already formatted" } else { "not formatted" },
-                "diagnostics": res.diagnostics,
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
+    // Parse diagnostics are surfaced in every mode so the user knows if the
+    // input has issues.
+    let report = if check {
+        let status = if already_formatted {
+            "already formatted"
         } else {
-            status_message(
-                format,
-                already_formatted,
-                "already formatted",
-                "not formatted",
-                file,
-            );
-        }
-        if !already_formatted {
-            process::exit(1);
-        }
+            "not formatted"
+        };
+        Report::new(serde_json::json!({
+            "mode": "check",
+            "file": file,
+            "already_formatted": already_formatted,
+            "status": status,
+            "diagnostics": res.diagnostics,
+        }))
+        .pretty(move |_, err| writeln!(err, "{}: {}", status, file))
     } else if write {
-        if !already_formatted {
-            fs::write(file, &formatted)?;
-        }
-        if format == Format::Json {
-            let out = serde_json::json!({
-                "mode": "write",
-                "file": file,
-                "changed": !already_formatted,
-                "status": if !already_formatted { "formatted" } else { "already formatted" },
-                "diagnostics": res.diagnostics,
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
+        let status = if already_formatted {
+            "already formatted"
         } else {
-            status_message(
-                format,
-                !already_formatted,
-                "formatted",
-                "already formatted",
-                file,
-            );
-        }
+            "formatted"
+        };
+        Report::new(serde_json::json!({
+            "mode": "write",
+            "file": file,
+            "changed": !already_formatted,
+            "status": status,
+            "diagnostics": res.diagnostics,
+        }))
+        .pretty(move |_, err| writeln!(err, "{}: {}", status, file))
     } else {
         // Default: print formatted output to stdout.
-        if format == Format::Json {
-            let out = serde_json::json!({
-                "mode": "stdout",
-                "file": file,
-                "formatted": formatted,
-                "diagnostics": res.diagnostics,
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        } else if format == Format::Sarif {
-            // SARIF already output above; nothing more
-        } else {
-            print!("{}", formatted);
-        }
+        Report::new(serde_json::json!({
+            "mode": "stdout",
+            "file": file,
+            "formatted": formatted,
+            "diagnostics": res.diagnostics,
+        }))
+        .pretty(|out, _| write_zpl(out, &formatted, encoding))
+    };
+    render_report(
+        &report
+            .diagnostics(&input, file, &res.diagnostics)
+            .sarif(SarifOutput::Diagnostics),
+        format,
+    )?;
+
+    if check && !already_formatted {
+        ExitCode::LintErrors.exit();
     }
+    Ok(())
+}
+
+fn cmd_normalize(
+    file: &str,
+    tables_path: Option<&str>,
+    encoding: InputEncoding,
+    format: Format,
+) -> Result<()> {
+    let input = read_input(file, encoding)?;
+    let tables = resolve_tables(tables_path)?.context(
+        "no parser tables available for normalize — pass --tables <PATH> or use a build with embedded tables",
+    )?;
+    let res = input.parse(Some(&tables));
+    let normalized = emit_zpl(&res.ast, Some(&tables), &EmitConfig::normalized());
+
+    // Diagnostics go to stderr so stdout stays a clean golden file.
+    let report = Report::new(serde_json::json!({
+        "file": file,
+        "normalized": normalized,
+        "diagnostics": res.diagnostics,
+    }))
+    .diagnostics(&input, file, &res.diagnostics)
+    .pretty(|out, _| write_zpl(out, &normalized, encoding))
+    .sarif(SarifOutput::Diagnostics);
+    render_report(&report, format)?;
 
     Ok(())
 }
 
-/// Emit a status message for --check / --write in the appropriate format.
-fn status_message(format: Format, condition: bool, if_true: &str, if_false: &str, file: &str) {
-    let msg = if condition { if_true } else { if_false };
-    match format {
-        Format::Json => {
-            let out = serde_json::json!({ "status": msg, "file": file });
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&out).expect("status JSON serialization cannot fail")
-            );
-        }
-        Format::Sarif => {
-            // Status already conveyed via exit code; SARIF output done earlier
-        }
-        Format::Pretty => {
-            eprintln!("{}: {}", msg, file);
+fn cmd_extract(
+    file: &str,
+    label: isize,
+    formatted: bool,
+    tables_path: Option<&str>,
+    encoding: InputEncoding,
+    format: Format,
+) -> Result<()> {
+    let input = read_input(file, encoding)?;
+    let tables = resolve_tables(tables_path)?.context(
+        "no parser tables available for extract — pass --tables <PATH> or use a build with embedded tables",
+    )?;
+    let res = input.parse(Some(&tables));
+    let count = res.ast.labels.len();
+    let Some(selected) = select_label(&res.ast, label) else {
+        bail_usage!(
+            "label {} not found — '{}' contains {} label(s); use 1..={} or -1 for the last",
+            label,
+            file,
+            count,
+            count
+        );
+    };
+    // 1-based position of the selected label, for reporting.
+    let position = if label > 0 {
+        label.unsigned_abs()
+    } else {
+        count + 1 - label.unsigned_abs()
+    };
+
+    let text = if formatted {
+        let single = Ast {
+            labels: vec![selected.clone()],
+            byte_decoded: res.ast.byte_decoded,
+        };
+        emit_zpl(&single, Some(&tables), &EmitConfig::default())
+    } else {
+        selected
+            .span()
+            .map_or("", |span| input.span_text(span))
+            .to_string()
+    };
+
+    let report = Report::new(serde_json::json!({
+        "file": file,
+        "label": position,
+        "labels": count,
+        "zpl": text,
+    }))
+    .diagnostics(&input, file, &res.diagnostics)
+    .pretty(|out, _| write_zpl(out, &text, encoding))
+    .sarif(SarifOutput::Diagnostics);
+    render_report(&report, format)?;
+    Ok(())
+}
+
+/// Index of the first line where `a` and `b` differ, or `None` if equal.
+fn first_differing_line(a: &str, b: &str) -> Option<usize> {
+    if a == b {
+        return None;
+    }
+    let mut a_lines = a.split_inclusive('\n');
+    let mut b_lines = b.split_inclusive('\n');
+    let mut line = 0;
+    loop {
+        match (a_lines.next(), b_lines.next()) {
+            (Some(x), Some(y)) if x == y => line += 1,
+            _ => return Some(line),
         }
     }
 }
 
+#[cfg(feature = "render")]
+fn cmd_render(
+    file: &str,
+    output_png: &str,
+    label: u32,
+    profile_path: Option<&str>,
+    tables_path: Option<&str>,
+    encoding: InputEncoding,
+    format: Format,
+) -> Result<()> {
+    let input = read_input(file, encoding)?;
+    let res = parse_with_resolved_tables(tables_path, &input)?;
+    let prof = match profile_path {
+        Some(p) => {
+            let s =
+                fs::read_to_string(p).with_context(|| format!("failed to read profile '{}'", p))?;
+            Some(
+                serde_json::from_str::<zpl_toolchain_profile::Profile>(&s)
+                    .with_context(|| format!("failed to parse profile '{}'", p))?,
+            )
+        }
+        None => None,
+    };
+    let ast_label = res.ast.labels.get(label as usize - 1).with_context(|| {
+        format!(
+            "label {} not found — '{}' contains {} label(s)",
+            label,
+            file,
+            res.ast.labels.len()
+        )
+    })?;
+
+    let bitmap = zpl_toolchain_core::render::rasterize_label(
+        ast_label,
+        zpl_toolchain_core::render::profile_label_size(prof.as_ref()),
+    );
+    let png = zpl_toolchain_core::render::encode_png(&bitmap);
+    fs::write(output_png, &png).with_context(|| format!("failed to write PNG '{}'", output_png))?;
+
+    let report = Report::new(serde_json::json!({
+        "output": output_png,
+        "label": label,
+        "width": bitmap.width(),
+        "height": bitmap.height(),
+        "bytes": png.len(),
+    }))
+    .diagnostics(&input, file, &res.diagnostics)
+    .pretty(|_, err| {
+        writeln!(
+            err,
+            "rendered label {} of {} ({}x{} dots) to {}",
+            label,
+            file,
+            bitmap.width(),
+            bitmap.height(),
+            output_png
+        )
+    })
+    .sarif(SarifOutput::Diagnostics);
+    render_report(&report, format)?;
+    Ok(())
+}
+
 fn emit_cli_error(format: Format, err: &anyhow::Error) {
     let message = format!("{err:#}");
-    match format {
-        Format::Json | Format::Sarif => {
-            let out = serde_json::json!({
-                "success": false,
-                "error": "command_failed",
-                "message": message,
-            });
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&out)
-                    .expect("error envelope JSON serialization cannot fail")
-            );
-        }
-        Format::Pretty => {
-            eprintln!("error: {message}");
-        }
-    }
+    let report = Report::new(serde_json::json!({
+        "success": false,
+        "error": "command_failed",
+        "message": message,
+    }))
+    .pretty(|_, w| writeln!(w, "error: {message}"));
+    let _ = render_report(&report, format);
 }
 
-fn read_input(file: &str) -> Result<String> {
-    if file == "-" {
-        let mut input = String::new();
-        std::io::stdin().read_to_string(&mut input)?;
-        Ok(input)
+fn read_input(file: &str, encoding: InputEncoding) -> Result<Input> {
+    let bytes = if file == "-" {
+        let mut input = Vec::new();
+        std::io::stdin().read_to_end(&mut input)?;
+        input
     } else {
-        Ok(fs::read_to_string(file)?)
-    }
+        fs::read(file)?
+    };
+    Input::decode(bytes, encoding)
+}
+
+/// Write ZPL text to `out` in the input encoding.
+fn write_zpl(out: &mut dyn Write, text: &str, encoding: InputEncoding) -> std::io::Result<()> {
+    out.write_all(&encoding.encode(text))?;
+    out.flush()
 }
 
 /// Bundled options for the `print` subcommand.
@@ -889,6 +1480,7 @@ struct PrintOpts<'a> {
     wait: bool,
     timeout: Option<u64>,
     wait_timeout: u64,
+    coalesce: bool,
     #[cfg(feature = "serial")]
     serial: bool,
     #[cfg(feature = "serial")]
@@ -903,6 +1495,7 @@ struct PrintOpts<'a> {
     serial_data_bits: CliSerialDataBits,
     #[cfg(feature = "serial")]
     trace_io: bool,
+    encoding: InputEncoding,
     format: Format,
 }
 
@@ -915,8 +1508,6 @@ struct DoctorOpts<'a> {
 }
 
 fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
-    use std::time::Duration;
-
     let PrintOpts {
         files,
         printer_addr,
@@ -932,6 +1523,7 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
         wait,
         timeout,
         wait_timeout,
+        coalesce,
         #[cfg(feature = "serial")]
         serial,
         #[cfg(feature = "serial")]
@@ -946,14 +1538,16 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
         serial_data_bits,
         #[cfg(feature = "serial")]
         trace_io,
+        encoding,
         format,
     } = opts;
 
     // ── Read all files ──────────────────────────────────────────────
-    let mut file_contents: Vec<(String, String)> = Vec::new();
+    let mut file_contents: Vec<(String, Input)> = Vec::new();
     for path in files {
+        let bytes = fs::read(path).with_context(|| format!("failed to read '{}'", path))?;
         let content =
-            fs::read_to_string(path).with_context(|| format!("failed to read '{}'", path))?;
+            Input::decode(bytes, encoding).with_context(|| format!("failed to read '{}'", path))?;
         file_contents.push((path.clone(), content));
     }
 
@@ -983,15 +1577,15 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
         let mut has_warnings = false;
 
         for (path, content) in &file_contents {
-            let res = parse_with_tables(content, Some(&tables));
+            let res = content.parse(Some(&tables));
             let mut vr = validate::validate_with_profile(&res.ast, &tables, prof.as_ref());
-            vr.issues.extend(res.diagnostics);
+            vr.merge_parse(res.diagnostics);
             filter_contextual_notes(&mut vr.issues, note_audience);
 
-            if format == Format::Pretty && !vr.issues.is_empty() {
-                render_diagnostics(content, path, &vr.issues, format);
-                print_summary(&vr.issues);
-            }
+            render_report(
+                &Report::progress().diagnostics(content, path, &vr.issues),
+                format,
+            )?;
 
             if vr
                 .issues
@@ -1012,35 +1606,28 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
             all_diagnostics.extend(vr.issues);
         }
 
-        if has_errors {
-            match format {
-                Format::Json => {
-                    let out = serde_json::json!({
-                        "error": "validation_failed",
-                        "message": "aborting print due to validation errors",
-                        "diagnostics": all_diagnostics,
-                    });
-                    println!("{}", serde_json::to_string_pretty(&out)?);
-                }
-                Format::Sarif => render_print_sarif(&file_contents, &diagnostics_by_file),
-                Format::Pretty => eprintln!("error: aborting print due to validation errors"),
-            }
-            process::exit(1);
-        }
-        if strict && has_warnings {
-            match format {
-                Format::Json => {
-                    let out = serde_json::json!({
-                        "error": "validation_warnings",
-                        "message": "aborting print due to warnings (--strict)",
-                        "diagnostics": all_diagnostics,
-                    });
-                    println!("{}", serde_json::to_string_pretty(&out)?);
-                }
-                Format::Sarif => render_print_sarif(&file_contents, &diagnostics_by_file),
-                Format::Pretty => eprintln!("error: aborting print due to warnings (--strict)"),
-            }
-            process::exit(1);
+        let abort = if has_errors {
+            Some((
+                "validation_failed",
+                "aborting print due to validation errors",
+            ))
+        } else if strict && has_warnings {
+            Some((
+                "validation_warnings",
+                "aborting print due to warnings (--strict)",
+            ))
+        } else {
+            None
+        };
+        if let Some((error, message)) = abort {
+            let report = Report::new(serde_json::json!({
+                "error": error,
+                "message": message,
+                "diagnostics": all_diagnostics,
+            }))
+            .pretty(|_, err| writeln!(err, "error: {message}"))
+            .sarif(print_sarif(&file_contents, &diagnostics_by_file));
+            exit_with_report(&report, format, ExitCode::LintErrors);
         }
 
         // Note: all_diagnostics (warnings) are included in the final result JSON below.
@@ -1059,7 +1646,7 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
         // Reject --serial with USB address (matches live-print validation).
         #[cfg(feature = "serial")]
         if is_serial && is_usb_addr {
-            anyhow::bail!(
+            bail_usage!(
                 "--serial cannot be used with USB printer address '{}'",
                 printer_addr
             );
@@ -1067,7 +1654,7 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
 
         let (transport, display_addr) = if is_serial {
             if looks_like_bluetooth_mac(printer_addr) {
-                anyhow::bail!(
+                bail_usage!(
                     "'{}' looks like a Bluetooth MAC address. With --serial, pass the OS serial port path instead \
                      (for example: /dev/cu.<name> on macOS, COM5 on Windows, /dev/rfcomm0 on Linux).",
                     printer_addr
@@ -1076,7 +1663,7 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
             ("serial", printer_addr.to_string())
         } else if is_usb_addr {
             #[cfg(not(feature = "usb"))]
-            anyhow::bail!(
+            bail_usage!(
                 "USB transport not available — this binary was compiled without USB support. \
                  Reinstall with default features: cargo install zpl_toolchain_cli"
             );
@@ -1086,24 +1673,33 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
             } else {
                 ("usb", printer_addr.to_string())
             }
+        } else if printer_addr
+            .get(..3)
+            .is_some_and(|p| p.eq_ignore_ascii_case("bt:"))
+        {
+            // Bluetooth RFCOMM: parse to validate the MAC and channel.
+            let target = PrinterTarget::parse(printer_addr)?;
+            (target.transport(), target.to_string())
         } else if looks_like_serial_port(printer_addr) {
             #[cfg(feature = "serial")]
-            anyhow::bail!(
+            bail_usage!(
                 "'{}' looks like a serial port — add --serial to use serial transport.\n  \
                  Example: zpl print <FILE> -p {} --serial",
                 printer_addr,
                 printer_addr
             );
             #[cfg(not(feature = "serial"))]
-            anyhow::bail!(
+            bail_usage!(
                 "'{}' looks like a serial port, but this binary was compiled without serial support. \
                  Reinstall with default features: cargo install zpl_toolchain_cli",
                 printer_addr
             );
         } else if looks_like_bluetooth_mac(printer_addr) {
-            anyhow::bail!(
+            bail_usage!(
                 "'{}' looks like a Bluetooth MAC address. For Bluetooth/serial printers, pass the OS serial port path \
-                 and add --serial (for example: /dev/cu.<name> on macOS, COM5 on Windows, /dev/rfcomm0 on Linux).",
+                 and add --serial (for example: /dev/cu.<name> on macOS, COM5 on Windows, /dev/rfcomm0 on Linux). \
+                 On Linux, -p bt:{} connects over RFCOMM directly.",
+                printer_addr,
                 printer_addr
             );
         } else {
@@ -1118,90 +1714,62 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
             ("tcp", resolved.to_string())
         };
 
-        match format {
-            Format::Json => {
-                let mut out = serde_json::json!({
-                    "dry_run": true,
-                    "transport": transport,
-                    "resolved_address": display_addr,
-                    "files": files,
-                    "validation": if no_lint { "skipped" } else { "passed" },
-                });
-                if !all_diagnostics.is_empty() {
-                    out["diagnostics"] = serde_json::to_value(all_diagnostics).unwrap_or_default();
-                }
-                println!("{}", serde_json::to_string_pretty(&out)?);
-            }
-            Format::Sarif => {
-                render_print_sarif(&file_contents, &diagnostics_by_file);
-            }
-            Format::Pretty => {
-                eprintln!(
+        let mut json = serde_json::json!({
+            "dry_run": true,
+            "transport": transport,
+            "resolved_address": display_addr,
+            "files": files,
+            "validation": if no_lint { "skipped" } else { "passed" },
+        });
+        if !all_diagnostics.is_empty() {
+            json["diagnostics"] = serde_json::to_value(&all_diagnostics).unwrap_or_default();
+        }
+        let report = Report::new(json)
+            .pretty(|_, err| {
+                writeln!(
+                    err,
                     "dry run: would print {} file(s) to {} ({})",
                     files.len(),
                     display_addr,
                     transport,
-                );
+                )?;
                 for (path, _) in &file_contents {
-                    eprintln!("  {}", path);
+                    writeln!(err, "  {}", path)?;
                 }
                 if no_lint {
-                    eprintln!("  validation: skipped (--no-lint)");
+                    writeln!(err, "  validation: skipped (--no-lint)")
                 } else {
-                    eprintln!("  validation: passed");
+                    writeln!(err, "  validation: passed")
                 }
-            }
-        }
+            })
+            .sarif(print_sarif(&file_contents, &diagnostics_by_file));
+        render_report(&report, format)?;
         return Ok(());
     }
 
     // ── Build printer config ────────────────────────────────────────
-    let mut config = if let Some(secs) = timeout {
-        let base = Duration::from_secs(secs);
-        let mut cfg = PrinterConfig::default();
-        cfg.timeouts.connect = base;
-        cfg.timeouts.write = base.mul_f64(6.0); // 6× connect
-        cfg.timeouts.read = base.mul_f64(2.0); // 2× connect
-        cfg
-    } else {
-        let mut cfg = PrinterConfig::default();
-        // Serial/Bluetooth links are often slower than TCP. Use a safer default
-        // timeout profile when the user explicitly selects --serial.
-        #[cfg(feature = "serial")]
-        if serial {
-            cfg.timeouts.connect = Duration::from_secs(10);
-            cfg.timeouts.write = Duration::from_secs(120);
-            cfg.timeouts.read = Duration::from_secs(30);
-        }
-        cfg
-    };
+    #[cfg(feature = "serial")]
+    let mut config = printer_config(timeout, serial);
+    #[cfg(not(feature = "serial"))]
+    let config = printer_config(timeout, false);
 
     #[cfg(feature = "serial")]
-    if serial {
-        config.trace_io = trace_io;
+    if serial && trace_io {
+        config.trace_sink = Some(stderr_trace_sink());
     }
 
     // ── Connect and run print session ─────────────────────────────
-    let connection_err = |e: zpl_toolchain_print_client::PrintError| {
-        match format {
-            Format::Json => {
-                let out = serde_json::json!({
-                    "error": "connection_failed",
-                    "message": format!("failed to connect to printer '{}': {}", printer_addr, e),
-                });
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&out).expect("JSON serialization cannot fail")
-                );
-                process::exit(1);
-            }
-            Format::Sarif => {
-                render_print_sarif(&file_contents, &diagnostics_by_file);
-                process::exit(1);
-            }
-            Format::Pretty => {}
-        }
-        anyhow::anyhow!("failed to connect to printer '{}': {}", printer_addr, e)
+    let connection_err = |e: zpl_toolchain_print_client::PrintError| -> anyhow::Error {
+        let report = Report::new(serde_json::json!({
+            "error": "connection_failed",
+            "message": format!("failed to connect to printer '{}': {}", printer_addr, e),
+        }));
+        let err = anyhow::Error::new(e)
+            .context(format!("failed to connect to printer '{}'", printer_addr));
+        let report = report
+            .pretty(|_, w| writeln!(w, "error: {err:#}"))
+            .sarif(print_sarif(&file_contents, &diagnostics_by_file));
+        exit_with_report(&report, format, ExitCode::ConnectionFailed)
     };
 
     let make_session = |transport: &'static str| SessionOpts {
@@ -1213,129 +1781,268 @@ fn cmd_print(opts: PrintOpts<'_>) -> Result<()> {
         verify,
         wait,
         wait_timeout,
+        coalesce,
         format,
         transport,
     };
 
-    // ── Serial transport ──────────────────────────────────────────
+    // ── Resolve the target, then connect through any transport ───
     #[cfg(feature = "serial")]
-    if serial && (printer_addr == "usb" || printer_addr.starts_with("usb:")) {
-        anyhow::bail!(
-            "--serial cannot be used with USB printer address '{}'",
-            printer_addr
-        );
-    }
-
-    #[cfg(feature = "serial")]
-    if serial {
-        if looks_like_bluetooth_mac(printer_addr) {
-            anyhow::bail!(
-                "'{}' looks like a Bluetooth MAC address. With --serial, pass the OS serial port path instead \
-                 (for example: /dev/cu.<name> on macOS, COM5 on Windows, /dev/rfcomm0 on Linux).",
-                printer_addr
-            );
-        }
-        let settings = SerialSettings {
-            flow_control: to_print_flow_control(serial_flow_control),
-            parity: to_print_parity(serial_parity),
-            stop_bits: to_print_stop_bits(serial_stop_bits),
-            data_bits: to_print_data_bits(serial_data_bits),
-        };
-        let mut printer = SerialPrinter::open_with_settings(printer_addr, baud, settings, config)
-            .map_err(connection_err)?;
-        if format == Format::Pretty {
-            eprintln!("connected to {} (serial, {} baud)", printer_addr, baud);
-            eprintln!(
+    let target = if serial {
+        serial_target(
+            printer_addr,
+            baud,
+            SerialSettings {
+                flow_control: to_print_flow_control(serial_flow_control),
+                parity: to_print_parity(serial_parity),
+                stop_bits: to_print_stop_bits(serial_stop_bits),
+                data_bits: to_print_data_bits(serial_data_bits),
+            },
+        )?
+    } else {
+        print_target(printer_addr)?
+    };
+    #[cfg(not(feature = "serial"))]
+    let target = print_target(printer_addr)?;
+
+    // Resolve TCP addresses up front so the session reports the connected `ip:port`.
+    let target = match target {
+        PrinterTarget::Tcp(addr) => PrinterTarget::Tcp(
+            resolve_printer_addr(&addr)
+                .map_err(connection_err)?
+                .to_string(),
+        ),
+        other => other,
+    };
+    let mut printer = connect_any(&target, config).map_err(connection_err)?;
+    let display = match &target {
+        PrinterTarget::UsbAuto => "usb".to_string(),
+        #[cfg(feature = "serial")]
+        PrinterTarget::Serial { path, .. } => path.clone(),
+        _ => target.to_string(),
+    };
+    let connected = Report::progress().pretty(|_, err| match &target {
+        PrinterTarget::UsbAuto => writeln!(err, "connected to USB Zebra printer"),
+        PrinterTarget::Usb {
+            vendor_id,
+            product_id,
+        } => writeln!(
+            err,
+            "connected to USB printer {:04X}:{:04X}",
+            vendor_id, product_id
+        ),
+        #[cfg(feature = "serial")]
+        PrinterTarget::Serial { path, baud, .. } => {
+            writeln!(err, "connected to {} (serial, {} baud)", path, baud)?;
+            writeln!(
+                err,
                 "note: serial/Bluetooth status reads require a bidirectional serial endpoint. \
 If --status/--wait times out, verify the printer serial config matches host settings \
 (baud/data/parity/stop/flow) and disable serial ACK/NAK protocol."
-            );
-            eprintln!(
+            )?;
+            writeln!(
+                err,
                 "hint: over TCP, set known-good serial defaults then persist: ^XA^SC9600,8,N,1,X,N^JUS^XZ"
-            );
+            )
         }
-        return run_print_session(&mut printer, printer_addr, &make_session("serial"));
-    }
+        _ => writeln!(err, "connected to {}", display),
+    });
+    render_report(&connected, format)?;
+    run_print_session(&mut printer, &display, &make_session(target.transport()))
+}
 
-    // ── USB transport ────────────────────────────────────────────
-    #[cfg(feature = "usb")]
-    if printer_addr == "usb" {
-        let mut printer = UsbPrinter::find_zebra(config).map_err(connection_err)?;
-        if format == Format::Pretty {
-            eprintln!("connected to USB Zebra printer");
-        }
-        return run_print_session(&mut printer, "usb", &make_session("usb"));
-    }
+/// Printer config with timeouts scaled from `--timeout`, or the transport
+/// defaults (slower for serial/Bluetooth links) when it is not given.
+fn printer_config(timeout: Option<u64>, serial: bool) -> PrinterConfig {
+    use std::time::Duration;
 
-    #[cfg(feature = "usb")]
-    if let Some(vidpid) = printer_addr.strip_prefix("usb:") {
-        let (vid, pid) = parse_usb_vidpid(vidpid)?;
-        let mut printer = UsbPrinter::find(vid, pid, config).map_err(connection_err)?;
-        if format == Format::Pretty {
-            eprintln!("connected to USB printer {:04X}:{:04X}", vid, pid);
-        }
-        return run_print_session(&mut printer, printer_addr, &make_session("usb"));
+    let mut cfg = PrinterConfig::default();
+    if let Some(secs) = timeout {
+        let base = Duration::from_secs(secs);
+        cfg.timeouts.connect = base;
+        cfg.timeouts.write = base.mul_f64(6.0); // 6× connect
+        cfg.timeouts.read = base.mul_f64(2.0); // 2× connect
+    } else if serial {
+        // Serial/Bluetooth links are often slower than TCP. Use a safer default
+        // timeout profile when the user explicitly selects --serial.
+        cfg.timeouts.connect = Duration::from_secs(10);
+        cfg.timeouts.write = Duration::from_secs(120);
+        cfg.timeouts.read = Duration::from_secs(30);
     }
+    cfg
+}
 
-    #[cfg(not(feature = "usb"))]
+/// Map a `--serial` printer address to a [`PrinterTarget::Serial`],
+/// rejecting USB addresses and Bluetooth MACs.
+#[cfg(feature = "serial")]
+fn serial_target(printer_addr: &str, baud: u32, settings: SerialSettings) -> Result<PrinterTarget> {
     if printer_addr == "usb" || printer_addr.starts_with("usb:") {
-        anyhow::bail!(
-            "USB transport not available — this binary was compiled without USB support. \
-             Reinstall with default features: cargo install zpl_toolchain_cli"
-        );
-    }
-
-    // ── Detect likely serial port paths before falling through to TCP ─
-    if looks_like_serial_port(printer_addr) {
-        #[cfg(feature = "serial")]
-        anyhow::bail!(
-            "'{}' looks like a serial port — add --serial to use serial transport.\n  \
-             Example: zpl print <FILE> -p {} --serial",
-            printer_addr,
-            printer_addr
-        );
-        #[cfg(not(feature = "serial"))]
-        anyhow::bail!(
-            "'{}' looks like a serial port, but this binary was compiled without serial support. \
-             Reinstall with default features: cargo install zpl_toolchain_cli",
+        bail_usage!(
+            "--serial cannot be used with USB printer address '{}'",
             printer_addr
         );
     }
     if looks_like_bluetooth_mac(printer_addr) {
-        anyhow::bail!(
-            "'{}' looks like a Bluetooth MAC address. For Bluetooth/serial transport, pass the OS serial port path and add --serial \
+        bail_usage!(
+            "'{}' looks like a Bluetooth MAC address. With --serial, pass the OS serial port path instead \
              (for example: /dev/cu.<name> on macOS, COM5 on Windows, /dev/rfcomm0 on Linux).",
             printer_addr
         );
     }
+    Ok(PrinterTarget::Serial {
+        path: printer_addr.to_string(),
+        baud,
+        settings,
+    })
+}
 
-    // ── TCP transport (default) ──────────────────────────────────
-    {
-        let mut printer = TcpPrinter::connect(printer_addr, config).map_err(connection_err)?;
-        let remote = printer.remote_addr();
-        if format == Format::Pretty {
-            eprintln!("connected to {}", remote);
+/// Map a non-`--serial` printer address to a [`PrinterTarget`].
+///
+/// Rejects addresses that look like serial ports or Bluetooth MACs (which
+/// need `--serial`) and transports this binary was built without.
+fn print_target(printer_addr: &str) -> Result<PrinterTarget> {
+    let is_usb_addr = printer_addr == "usb" || printer_addr.starts_with("usb:");
+    #[cfg(not(feature = "usb"))]
+    if is_usb_addr {
+        bail_usage!(
+            "USB transport not available — this binary was compiled without USB support. \
+             Reinstall with default features: cargo install zpl_toolchain_cli"
+        );
+    }
+    if !is_usb_addr {
+        if looks_like_serial_port(printer_addr) {
+            #[cfg(feature = "serial")]
+            bail_usage!(
+                "'{}' looks like a serial port — add --serial to use serial transport.\n  \
+                 Example: zpl print <FILE> -p {} --serial",
+                printer_addr,
+                printer_addr
+            );
+            #[cfg(not(feature = "serial"))]
+            bail_usage!(
+                "'{}' looks like a serial port, but this binary was compiled without serial support. \
+                 Reinstall with default features: cargo install zpl_toolchain_cli",
+                printer_addr
+            );
+        }
+        if looks_like_bluetooth_mac(printer_addr) {
+            bail_usage!(
+                "'{}' looks like a Bluetooth MAC address. For Bluetooth/serial transport, pass the OS serial port path and add --serial \
+                 (for example: /dev/cu.<name> on macOS, COM5 on Windows, /dev/rfcomm0 on Linux). \
+                 On Linux, -p bt:{} connects over RFCOMM directly.",
+                printer_addr,
+                printer_addr
+            );
         }
-        run_print_session(&mut printer, &remote.to_string(), &make_session("tcp"))
     }
+
+    Ok(PrinterTarget::parse(printer_addr)?)
+}
+
+/// Connect to a printer for status queries, returning its display name.
+fn connect_status_target(
+    printer_addr: &str,
+    timeout: Option<u64>,
+    #[cfg(feature = "serial")] serial: Option<(u32, SerialSettings)>,
+) -> Result<(String, Box<dyn StatusQuery>)> {
+    #[cfg(feature = "serial")]
+    let (target, config) = match serial {
+        Some((baud, settings)) => (
+            serial_target(printer_addr, baud, settings)?,
+            printer_config(timeout, true),
+        ),
+        None => (print_target(printer_addr)?, printer_config(timeout, false)),
+    };
+    #[cfg(not(feature = "serial"))]
+    let (target, config) = (print_target(printer_addr)?, printer_config(timeout, false));
+
+    let connection_err = |e: zpl_toolchain_print_client::PrintError| {
+        anyhow::Error::new(e).context(format!("failed to connect to printer '{}'", printer_addr))
+    };
+    let target = match target {
+        PrinterTarget::Tcp(addr) => PrinterTarget::Tcp(
+            resolve_printer_addr(&addr)
+                .map_err(connection_err)?
+                .to_string(),
+        ),
+        other => other,
+    };
+    let display = match &target {
+        PrinterTarget::UsbAuto => "usb".to_string(),
+        #[cfg(feature = "serial")]
+        PrinterTarget::Serial { path, .. } => path.clone(),
+        _ => target.to_string(),
+    };
+    let printer = connect_any(&target, config).map_err(connection_err)?;
+    Ok((display, printer))
+}
+
+fn cmd_status(
+    printer_addr: &str,
+    timeout: Option<u64>,
+    #[cfg(feature = "serial")] serial: Option<(u32, SerialSettings)>,
+    format: Format,
+) -> Result<()> {
+    let (display, mut printer) = connect_status_target(
+        printer_addr,
+        timeout,
+        #[cfg(feature = "serial")]
+        serial,
+    )?;
+    let snapshot = printer.query_snapshot().with_context(|| {
+        format!(
+            "printer '{}' did not answer any status query (~HS, ~HI, ~HM)",
+            display
+        )
+    })?;
+
+    format
+        .formatter()
+        .render_status(&mut std::io::stdout().lock(), &display, &snapshot)?;
+    Ok(())
 }
 
-/// Parse a USB VID:PID string like "0A5F:0100".
-#[cfg(feature = "usb")]
-fn parse_usb_vidpid(s: &str) -> Result<(u16, u16)> {
-    let (v, p) = s
-        .split_once(':')
-        .ok_or_else(|| anyhow::anyhow!("invalid USB address '{}': expected usb:VID:PID", s))?;
-    let vid =
-        u16::from_str_radix(v, 16).with_context(|| format!("invalid USB vendor ID '{}'", v))?;
-    let pid =
-        u16::from_str_radix(p, 16).with_context(|| format!("invalid USB product ID '{}'", p))?;
-    Ok((vid, pid))
+fn cmd_profile_detect(
+    printer_addr: &str,
+    id: Option<&str>,
+    save: Option<&str>,
+    timeout: Option<u64>,
+) -> Result<()> {
+    let (display, mut printer) = connect_status_target(
+        printer_addr,
+        timeout,
+        #[cfg(feature = "serial")]
+        None,
+    )?;
+    let no_info = || {
+        format!(
+            "printer '{}' did not answer ~HI; resolution is required for a profile",
+            display
+        )
+    };
+    let snapshot = printer.query_snapshot().with_context(no_info)?;
+    let info = snapshot.info.as_ref().with_context(no_info)?;
+    let profile = profile_detect::profile_from_printer(id, info, snapshot.memory.as_ref());
+    let json = serde_json::to_string_pretty(&profile)?;
+
+    match save {
+        Some(path) => {
+            fs::write(path, format!("{json}\n"))
+                .with_context(|| format!("failed to write profile '{}'", path))?;
+            eprintln!("wrote profile '{}' to {}", profile.id, path);
+        }
+        None => println!("{json}"),
+    }
+    eprintln!(
+        "note: {} could not be detected and are left null; fill them in for full validation",
+        profile_detect::UNDETECTED_FIELDS.join(", ")
+    );
+    Ok(())
 }
 
 /// Options passed to the transport-agnostic print session.
 struct SessionOpts<'a> {
-    file_contents: &'a [(String, String)],
+    file_contents: &'a [(String, Input)],
     all_diagnostics: &'a [Diagnostic],
     diagnostics_by_file: &'a [(String, Vec<Diagnostic>)],
     info: bool,
@@ -1343,6 +2050,7 @@ struct SessionOpts<'a> {
     verify: bool,
     wait: bool,
     wait_timeout: u64,
+    coalesce: bool,
     format: Format,
     transport: &'a str,
 }
@@ -1366,6 +2074,7 @@ fn run_print_session<P: StatusQuery>(
         verify,
         wait,
         wait_timeout,
+        coalesce,
         format,
         transport,
     } = *opts;
@@ -1381,16 +2090,15 @@ fn run_print_session<P: StatusQuery>(
     if info {
         match printer.query_info() {
             Ok(pi) => {
-                if format == Format::Pretty {
-                    eprintln!("printer info:");
-                    eprintln!("  model:    {}", pi.model);
-                    eprintln!("  firmware: {}", pi.firmware);
-                    eprintln!("  dpi:      {}", pi.dpi);
-                    eprintln!("  memory:   {} KB", pi.memory_kb);
-                }
-                if format == Format::Json {
-                    json_result["printer_info"] = serde_json::to_value(&pi).unwrap_or_default();
-                }
+                let report = Report::progress().pretty(|_, err| {
+                    writeln!(err, "printer info:")?;
+                    writeln!(err, "  model:    {}", pi.model)?;
+                    writeln!(err, "  firmware: {}", pi.firmware)?;
+                    writeln!(err, "  dpi:      {}", pi.dpi)?;
+                    writeln!(err, "  memory:   {} KB", pi.memory_kb)
+                });
+                render_report(&report, format)?;
+                json_result["printer_info"] = serde_json::to_value(&pi).unwrap_or_default();
             }
             Err(e) => {
                 eprintln!("warning: failed to query printer info: {}", e);
@@ -1400,121 +2108,86 @@ fn run_print_session<P: StatusQuery>(
 
     // ── Send each file ──────────────────────────────────────────────
     let mut files_sent: Vec<&str> = Vec::new();
-    for (path, content) in file_contents {
-        if let Err(e) = printer.send_zpl(content) {
-            match format {
-                Format::Json => {
-                    let out = serde_json::json!({
-                        "error": "send_failed",
-                        "message": format!("failed to send '{}': {}", path, e),
-                        "file": path,
-                        "files_sent": files_sent,
-                    });
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&out).expect("JSON serialization cannot fail")
-                    );
-                    process::exit(1);
-                }
-                Format::Sarif => {
-                    render_print_sarif(file_contents, diagnostics_by_file);
-                    process::exit(1);
-                }
-                Format::Pretty => {}
-            }
-            return Err(anyhow::anyhow!("failed to send '{}': {}", path, e));
-        }
-        files_sent.push(path);
-        if format == Format::Pretty {
-            eprintln!("sent: {}", path);
-        }
+    if coalesce {
+        send_coalesced(printer, file_contents, diagnostics_by_file, format)?;
+        files_sent.extend(file_contents.iter().map(|(p, _)| p.as_str()));
+    } else {
+        for (path, content) in file_contents {
+            if let Err(e) = printer.send_raw(content.bytes()) {
+                let report = Report::new(serde_json::json!({
+                    "error": "send_failed",
+                    "message": format!("failed to send '{}': {}", path, e),
+                    "file": path,
+                    "files_sent": files_sent,
+                }));
+                let err = anyhow::Error::new(e).context(format!("failed to send '{}'", path));
+                let report = report
+                    .pretty(|_, w| writeln!(w, "error: {err:#}"))
+                    .sarif(print_sarif(file_contents, diagnostics_by_file));
+                exit_with_report(&report, format, ExitCode::ConnectionFailed);
+            }
+            files_sent.push(path);
+            render_report(&Report::note(format!("sent: {}", path)), format)?;
+        }
+    }
+
+    // Push any buffered label bytes out before ~HS so the query cannot
+    // overtake them.
+    if (status || verify || wait)
+        && let Err(e) = printer.flush()
+    {
+        let report = Report::new(serde_json::json!({
+            "error": "send_failed",
+            "message": format!("failed to flush printer connection: {}", e),
+            "files_sent": files_sent,
+        }));
+        let err = anyhow::Error::new(e).context("failed to flush printer connection");
+        let report = report
+            .pretty(|_, w| writeln!(w, "error: {err:#}"))
+            .sarif(print_sarif(file_contents, diagnostics_by_file));
+        exit_with_report(&report, format, ExitCode::ConnectionFailed);
     }
 
+    let serial_hint = if transport == "serial" {
+        " Selected serial endpoint may be write-only for responses; verify the printer/adapter supports bidirectional ~HS over this port."
+    } else {
+        ""
+    };
+
     // ── Post-send: status query ─────────────────────────────────────
     let mut last_status: Option<zpl_toolchain_print_client::HostStatus> = None;
     if status || verify {
         match printer.query_status() {
             Ok(hs) => {
-                if format == Format::Pretty {
-                    use ariadne::Fmt;
-
-                    eprintln!("printer status:");
-                    eprintln!("  mode:             {:?}", hs.print_mode);
-                    eprintln!("  labels remaining: {}", hs.labels_remaining);
-                    eprintln!("  formats queued:   {}", hs.formats_in_buffer);
-                    eprintln!("  label length:     {} dots", hs.label_length_dots);
-
-                    let mut alerts: Vec<String> = Vec::new();
-                    if hs.paper_out {
-                        alerts.push(format!("{}", "paper_out".fg(ariadne::Color::Red)));
-                    }
-                    if hs.ribbon_out {
-                        alerts.push(format!("{}", "ribbon_out".fg(ariadne::Color::Red)));
-                    }
-                    if hs.head_up {
-                        alerts.push(format!("{}", "head_up".fg(ariadne::Color::Red)));
-                    }
-                    if hs.paused {
-                        alerts.push(format!("{}", "paused".fg(ariadne::Color::Yellow)));
-                    }
-                    if hs.over_temperature {
-                        alerts.push(format!("{}", "over_temp".fg(ariadne::Color::Red)));
-                    }
-                    if hs.under_temperature {
-                        alerts.push(format!("{}", "under_temp".fg(ariadne::Color::Yellow)));
-                    }
-                    if hs.corrupt_ram {
-                        alerts.push(format!("{}", "corrupt_ram".fg(ariadne::Color::Red)));
-                    }
-                    if hs.buffer_full {
-                        alerts.push(format!("{}", "buffer_full".fg(ariadne::Color::Yellow)));
-                    }
-                    if !alerts.is_empty() {
-                        eprintln!("  alerts:           {}", alerts.join(", "));
-                    }
-                }
-                if format == Format::Json {
-                    json_result["printer_status"] = serde_json::to_value(&hs).unwrap_or_default();
-                }
+                render_report(&printer_status_report(&hs), format)?;
+                json_result["printer_status"] = serde_json::to_value(&hs).unwrap_or_default();
                 last_status = Some(hs);
             }
             Err(e) => {
                 if verify {
-                    match format {
-                        Format::Json => {
-                            let serial_hint = if transport == "serial" {
-                                " Selected serial endpoint may be write-only for responses; verify the printer/adapter supports bidirectional ~HS over this port."
-                            } else {
-                                ""
-                            };
-                            json_result["success"] = serde_json::json!(false);
-                            json_result["error"] = serde_json::json!("verify_failed");
-                            json_result["message"] = serde_json::json!(format!(
-                                "post-send verification failed: could not query printer status (~HS): {}.{}",
-                                e, serial_hint
-                            ));
-                            println!(
-                                "{}",
-                                serde_json::to_string_pretty(&json_result)
-                                    .expect("JSON serialization cannot fail")
-                            );
-                        }
-                        Format::Sarif => {
-                            render_print_sarif(file_contents, diagnostics_by_file);
-                        }
-                        Format::Pretty => {
-                            eprintln!(
+                    json_result["success"] = serde_json::json!(false);
+                    json_result["error"] = serde_json::json!("verify_failed");
+                    json_result["message"] = serde_json::json!(format!(
+                        "post-send verification failed: could not query printer status (~HS): {}.{}",
+                        e, serial_hint
+                    ));
+                    let report = Report::new(json_result)
+                        .pretty(|_, err| {
+                            writeln!(
+                                err,
                                 "error: post-send verification failed: could not query printer status (~HS): {}",
                                 e
-                            );
+                            )?;
                             if transport == "serial" {
-                                eprintln!(
+                                writeln!(
+                                    err,
                                     "hint: this serial endpoint may be write-only for responses; use a bidirectional serial/SPP port for --status/--wait/--verify."
-                                );
+                                )?;
                             }
-                        }
-                    }
-                    process::exit(1);
+                            Ok(())
+                        })
+                        .sarif(print_sarif(file_contents, diagnostics_by_file));
+                    exit_with_report(&report, format, ExitCode::VerifyFailed);
                 } else {
                     eprintln!("warning: failed to query printer status: {}", e);
                     if transport == "serial" {
@@ -1534,14 +2207,10 @@ fn run_print_session<P: StatusQuery>(
     if wait {
         let poll_interval = Duration::from_millis(500);
         let wt = Duration::from_secs(wait_timeout);
-        if format == Format::Pretty {
-            eprintln!("waiting for printer to finish...");
-        }
+        render_report(&Report::note("waiting for printer to finish..."), format)?;
         match wait_for_completion(printer, poll_interval, wt) {
             Ok(()) => {
-                if format == Format::Pretty {
-                    eprintln!("printer finished");
-                }
+                render_report(&Report::note("printer finished"), format)?;
                 // Re-check status after completion when --verify is enabled.
                 // This avoids validating against stale pre-wait status.
                 if verify {
@@ -1549,34 +2218,27 @@ fn run_print_session<P: StatusQuery>(
                 }
             }
             Err(e) => {
-                match format {
-                    Format::Json => {
-                        json_result["success"] = serde_json::json!(false);
-                        json_result["error"] = serde_json::json!("wait_timeout");
-                        json_result["message"] =
-                            serde_json::json!(format!("wait for completion failed: {}", e));
-                        println!(
-                            "{}",
-                            serde_json::to_string_pretty(&json_result)
-                                .expect("JSON serialization cannot fail")
-                        );
-                    }
-                    Format::Sarif => {
-                        render_print_sarif(file_contents, diagnostics_by_file);
-                    }
-                    Format::Pretty => {
-                        eprintln!("error: wait for completion failed: {}", e);
+                json_result["success"] = serde_json::json!(false);
+                json_result["error"] = serde_json::json!("wait_timeout");
+                json_result["message"] =
+                    serde_json::json!(format!("wait for completion failed: {}", e));
+                let report = Report::new(json_result)
+                    .pretty(|_, err| {
+                        writeln!(err, "error: wait for completion failed: {}", e)?;
                         if transport == "serial" {
-                            eprintln!(
+                            writeln!(
+                                err,
                                 "hint: wait polling uses ~HS status reads. If this times out on serial/Bluetooth, check bidirectional support and serial settings."
-                            );
-                            eprintln!(
+                            )?;
+                            writeln!(
+                                err,
                                 "hint: bootstrap serial via TCP and persist: ^XA^SC9600,8,N,1,X,N^JUS^XZ"
-                            );
+                            )?;
                         }
-                    }
-                }
-                process::exit(1);
+                        Ok(())
+                    })
+                    .sarif(print_sarif(file_contents, diagnostics_by_file));
+                exit_with_report(&report, format, ExitCode::VerifyFailed);
             }
         }
     }
@@ -1589,147 +2251,178 @@ fn run_print_session<P: StatusQuery>(
             match printer.query_status() {
                 Ok(hs) => hs,
                 Err(e) => {
-                    match format {
-                        Format::Json => {
-                            let serial_hint = if transport == "serial" {
-                                " Selected serial endpoint may be write-only for responses; verify the printer/adapter supports bidirectional ~HS over this port."
-                            } else {
-                                ""
-                            };
-                            json_result["success"] = serde_json::json!(false);
-                            json_result["error"] = serde_json::json!("verify_failed");
-                            json_result["message"] = serde_json::json!(format!(
-                                "post-send verification failed: could not query printer status (~HS): {}.{}",
-                                e, serial_hint
-                            ));
-                            println!(
-                                "{}",
-                                serde_json::to_string_pretty(&json_result)
-                                    .expect("JSON serialization cannot fail")
-                            );
-                        }
-                        Format::Sarif => {
-                            render_print_sarif(file_contents, diagnostics_by_file);
-                        }
-                        Format::Pretty => {
-                            eprintln!(
+                    json_result["success"] = serde_json::json!(false);
+                    json_result["error"] = serde_json::json!("verify_failed");
+                    json_result["message"] = serde_json::json!(format!(
+                        "post-send verification failed: could not query printer status (~HS): {}.{}",
+                        e, serial_hint
+                    ));
+                    let report = Report::new(json_result)
+                        .pretty(|_, err| {
+                            writeln!(
+                                err,
                                 "error: post-send verification failed: could not query printer status (~HS): {}",
                                 e
-                            );
+                            )?;
                             if transport == "serial" {
-                                eprintln!(
+                                writeln!(
+                                    err,
                                     "hint: this serial endpoint may be write-only for responses, or serial settings/protocol may not match printer."
-                                );
-                                eprintln!(
+                                )?;
+                                writeln!(
+                                    err,
                                     "hint: bootstrap serial via TCP and persist: ^XA^SC9600,8,N,1,X,N^JUS^XZ"
-                                );
+                                )?;
                             }
-                        }
-                    }
-                    process::exit(1);
+                            Ok(())
+                        })
+                        .sarif(print_sarif(file_contents, diagnostics_by_file));
+                    exit_with_report(&report, format, ExitCode::VerifyFailed);
                 }
             }
         };
 
-        let mut hard_faults: Vec<&'static str> = Vec::new();
-        if status.paper_out {
-            hard_faults.push("paper_out");
-        }
-        if status.ribbon_out {
-            hard_faults.push("ribbon_out");
-        }
-        if status.head_up {
-            hard_faults.push("head_up");
-        }
-        if status.over_temperature {
-            hard_faults.push("over_temp");
-        }
-        if status.under_temperature {
-            hard_faults.push("under_temp");
-        }
-        if status.corrupt_ram {
-            hard_faults.push("corrupt_ram");
-        }
-        if status.buffer_full {
-            hard_faults.push("buffer_full");
-        }
-        if status.paused {
-            hard_faults.push("paused");
-        }
+        let hard_faults: Vec<&'static str> =
+            status.alerts().into_iter().map(|a| a.name()).collect();
 
         if !hard_faults.is_empty() {
-            match format {
-                Format::Json => {
-                    json_result["success"] = serde_json::json!(false);
-                    json_result["error"] = serde_json::json!("verify_failed");
-                    json_result["verify_faults"] =
-                        serde_json::to_value(&hard_faults).unwrap_or_default();
-                    json_result["message"] = serde_json::json!(format!(
-                        "post-send verification found printer fault flags: {}",
-                        hard_faults.join(", ")
-                    ));
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&json_result)
-                            .expect("JSON serialization cannot fail")
-                    );
-                }
-                Format::Sarif => {
-                    render_print_sarif(file_contents, diagnostics_by_file);
-                }
-                Format::Pretty => {
-                    eprintln!(
+            let faults = hard_faults.join(", ");
+            json_result["success"] = serde_json::json!(false);
+            json_result["error"] = serde_json::json!("verify_failed");
+            json_result["verify_faults"] = serde_json::to_value(&hard_faults).unwrap_or_default();
+            json_result["message"] = serde_json::json!(format!(
+                "post-send verification found printer fault flags: {}",
+                faults
+            ));
+            let report = Report::new(json_result)
+                .pretty(|_, err| {
+                    writeln!(
+                        err,
                         "error: post-send verification found printer fault flags: {}",
-                        hard_faults.join(", ")
-                    );
-                }
-            }
-            process::exit(1);
+                        faults
+                    )
+                })
+                .sarif(print_sarif(file_contents, diagnostics_by_file));
+            exit_with_report(&report, format, ExitCode::VerifyFailed);
         }
     }
 
     // ── Final result ────────────────────────────────────────────────
-    match format {
-        Format::Json => {
-            if !all_diagnostics.is_empty() {
-                json_result["diagnostics"] =
-                    serde_json::to_value(all_diagnostics).unwrap_or_default();
-            }
-            println!("{}", serde_json::to_string_pretty(&json_result)?);
-        }
-        Format::Sarif => {
-            render_print_sarif(file_contents, diagnostics_by_file);
-        }
-        Format::Pretty => {
-            eprintln!(
+    if !all_diagnostics.is_empty() {
+        json_result["diagnostics"] = serde_json::to_value(all_diagnostics).unwrap_or_default();
+    }
+    let report = Report::new(json_result)
+        .pretty(|_, err| {
+            writeln!(
+                err,
                 "print complete: {} file(s) sent to {}",
                 file_contents.len(),
                 printer_display
-            );
-        }
-    }
+            )
+        })
+        .sarif(print_sarif(file_contents, diagnostics_by_file));
+    render_report(&report, format)?;
     Ok(())
 }
 
-fn render_print_sarif(
-    file_contents: &[(String, String)],
+/// Pretty summary of a post-send `~HS` reply.
+fn printer_status_report(hs: &zpl_toolchain_print_client::HostStatus) -> Report<'_> {
+    Report::progress().pretty(move |_, err| {
+        use ariadne::Fmt;
+
+        writeln!(err, "printer status:")?;
+        writeln!(err, "  mode:             {:?}", hs.print_mode)?;
+        writeln!(err, "  labels remaining: {}", hs.labels_remaining)?;
+        writeln!(err, "  formats queued:   {}", hs.formats_in_buffer)?;
+        writeln!(err, "  label length:     {} dots", hs.label_length_dots)?;
+
+        let alerts: Vec<String> = hs
+            .alerts()
+            .into_iter()
+            .map(|alert| {
+                let color = match alert.severity() {
+                    zpl_toolchain_print_client::AlertSeverity::Warning => ariadne::Color::Yellow,
+                    _ => ariadne::Color::Red,
+                };
+                format!("{}", alert.name().fg(color))
+            })
+            .collect();
+        if !alerts.is_empty() {
+            writeln!(err, "  alerts:           {}", alerts.join(", "))?;
+        }
+        Ok(())
+    })
+}
+
+/// Send every file in one write, in order (`--coalesce`).
+///
+/// A failed write cannot be pinned on a single file, so every file is
+/// reported as unsent.
+fn send_coalesced<P: Printer + ?Sized>(
+    printer: &mut P,
+    file_contents: &[(String, Input)],
     diagnostics_by_file: &[(String, Vec<Diagnostic>)],
-) {
+    format: Format,
+) -> Result<()> {
+    let mut job = Vec::new();
+    for (_, content) in file_contents {
+        job.extend_from_slice(content.bytes());
+    }
+    let paths: Vec<&str> = file_contents.iter().map(|(p, _)| p.as_str()).collect();
+    if let Err(e) = printer.send_raw(&job) {
+        let what = match paths.as_slice() {
+            [path] => format!("'{}'", path),
+            _ => format!("{} files ({})", paths.len(), paths.join(", ")),
+        };
+        let report = Report::new(serde_json::json!({
+            "error": "send_failed",
+            "message": format!("failed to send {}: {}", what, e),
+            "files": paths,
+            "files_sent": [],
+        }));
+        let err = anyhow::Error::new(e).context(format!("failed to send {}", what));
+        let report = report
+            .pretty(|_, w| writeln!(w, "error: {err:#}"))
+            .sarif(print_sarif(file_contents, diagnostics_by_file));
+        exit_with_report(&report, format, ExitCode::ConnectionFailed);
+    }
+    let report = Report::progress().pretty(|_, err| {
+        for path in &paths {
+            writeln!(err, "sent: {}", path)?;
+        }
+        Ok(())
+    });
+    render_report(&report, format)?;
+    Ok(())
+}
+
+/// SARIF log of the pre-print diagnostics, one artifact per file.
+fn print_sarif<'a>(
+    file_contents: &'a [(String, Input)],
+    diagnostics_by_file: &'a [(String, Vec<Diagnostic>)],
+) -> SarifOutput<'a> {
     use std::collections::HashMap;
 
     let by_path: HashMap<&str, &[Diagnostic]> = diagnostics_by_file
         .iter()
         .map(|(path, diagnostics)| (path.as_str(), diagnostics.as_slice()))
         .collect();
-    let entries: Vec<SarifArtifactInput<'_>> = file_contents
-        .iter()
-        .map(|(path, content)| SarifArtifactInput {
-            source: content,
-            artifact_uri: path,
-            diagnostics: by_path.get(path.as_str()).copied().unwrap_or(&[]),
-        })
-        .collect();
-    render_diagnostics_sarif_multi(&entries);
+    SarifOutput::Artifacts(
+        file_contents
+            .iter()
+            .map(|(path, content)| SarifArtifactInput {
+                source: content,
+                artifact_uri: path,
+                diagnostics: by_path.get(path.as_str()).copied().unwrap_or(&[]),
+            })
+            .collect(),
+    )
+}
+
+/// Render a failure `report` in `format` and exit with `code`.
+fn exit_with_report(report: &Report<'_>, format: Format, code: ExitCode) -> ! {
+    let _ = render_report(report, format);
+    code.exit()
 }
 
 #[cfg(feature = "serial")]
@@ -1808,7 +2501,9 @@ fn cmd_serial_probe(opts: SerialProbeOpts<'_>) -> Result<()> {
     config.timeouts.connect = probe_timeout;
     config.timeouts.write = probe_timeout;
     config.timeouts.read = probe_timeout;
-    config.trace_io = trace_io;
+    if trace_io {
+        config.trace_sink = Some(stderr_trace_sink());
+    }
 
     let probe_peer_once = |peer_port: &str| -> serde_json::Value {
         let open_peer =
@@ -2011,30 +2706,28 @@ fn cmd_serial_probe(opts: SerialProbeOpts<'_>) -> Result<()> {
             }
             Err(e) => {
                 open_failures += 1;
-                if format != Format::Pretty {
-                    probe_json["success"] = serde_json::json!(false);
-                    probe_json["stage"] = serde_json::json!("connect");
-                    probe_json["message"] =
-                        serde_json::json!(format!("failed to open serial port: {}", e));
-                    probe_json["connect_timeout"] =
-                        serde_json::json!(is_timeout_error(&e.to_string()));
-                    probe_json["open_successes"] = serde_json::json!(open_successes);
-                    probe_json["open_failures"] = serde_json::json!(open_failures);
-                    match format {
-                        Format::Json => println!("{}", serde_json::to_string_pretty(&probe_json)?),
-                        Format::Sarif => render_serial_probe_sarif(&probe_json)?,
-                        Format::Pretty => {}
-                    }
-                    process::exit(1);
-                }
-                anyhow::bail!("failed to open serial port '{}': {}", port, e);
+                probe_json["success"] = serde_json::json!(false);
+                probe_json["stage"] = serde_json::json!("connect");
+                probe_json["message"] =
+                    serde_json::json!(format!("failed to open serial port: {}", e));
+                probe_json["connect_timeout"] = serde_json::json!(is_timeout_error(&e.to_string()));
+                probe_json["open_successes"] = serde_json::json!(open_successes);
+                probe_json["open_failures"] = serde_json::json!(open_failures);
+                let sarif = serial_probe_sarif(&probe_json);
+                let err =
+                    anyhow::Error::new(e).context(format!("failed to open serial port '{}'", port));
+                let report = Report::new(probe_json)
+                    .pretty(|_, w| writeln!(w, "error: {err:#}"))
+                    .sarif(SarifOutput::Log(sarif));
+                exit_with_report(&report, format, ExitCode::ConnectionFailed);
             }
         }
     };
 
     for attempt in 1..=repeat {
-        if trace_io && format == Format::Pretty {
-            eprintln!("[trace-io] serial probe attempt {attempt}/{repeat}");
+        if trace_io {
+            let note = format!("[trace-io] serial probe attempt {attempt}/{repeat}");
+            render_report(&Report::note(note), format)?;
         }
         let mut attempt_entry = serde_json::json!({
             "attempt": attempt,
@@ -2310,95 +3003,98 @@ fn cmd_serial_probe(opts: SerialProbeOpts<'_>) -> Result<()> {
         status_ok || info_ok || test_label_sent
     };
 
-    match format {
-        Format::Json | Format::Sarif => {
-            let probe_finished_ms = now_ms();
-            let mut timeout_stage_hits_json = timeout_stage_hits;
-            timeout_stage_hits_json.sort();
-            timeout_stage_hits_json.dedup();
-            probe_json["success"] = serde_json::json!(success);
-            probe_json["status_successes"] = serde_json::json!(status_successes);
-            probe_json["info_successes"] = serde_json::json!(info_successes);
-            probe_json["status_failures"] = serde_json::json!(status_failures);
-            probe_json["info_failures"] = serde_json::json!(info_failures);
-            probe_json["open_successes"] = serde_json::json!(open_successes);
-            probe_json["open_failures"] = serde_json::json!(open_failures);
-            probe_json["test_label_successes"] = serde_json::json!(test_label_successes);
-            probe_json["test_label_failures"] = serde_json::json!(test_label_failures);
-            probe_json["attempts_with_any_success"] = serde_json::json!(attempts_with_any_success);
-            probe_json["success_ratio"] = serde_json::json!(success_ratio);
-            probe_json["timeout_stages"] = serde_json::json!(timeout_stage_hits_json);
-            probe_json["diagnosis"] = serde_json::json!(diagnosis);
-            probe_json["attempts"] = serde_json::Value::Array(per_attempt);
-            probe_json["findings"] = serde_json::to_value(findings).unwrap_or_default();
-            probe_json["finished_at_ms"] = serde_json::json!(probe_finished_ms);
-            probe_json["elapsed_ms"] =
-                serde_json::json!(probe_finished_ms.saturating_sub(probe_started_ms));
-            probe_json["summary"] = serde_json::json!({
-                "attempts_total": repeat,
-                "attempts_with_status_ok": status_successes,
-                "attempts_with_info_ok": info_successes,
-                "attempts_with_any_success": attempts_with_any_success,
-                "success_ratio": success_ratio,
-                "attempts_with_open_failure": open_failures,
-                "attempts_with_status_failure": status_failures,
-                "attempts_with_info_failure": info_failures,
-                "attempts_with_test_label_success": test_label_successes,
-                "attempts_with_test_label_failure": test_label_failures
-            });
-            if compare_tty_cu && let Some(mapped) = mapped_tty_cu_peer(port) {
-                probe_json["peer_probe"] = probe_peer_once(&mapped);
-            }
-            match format {
-                Format::Json => println!("{}", serde_json::to_string_pretty(&probe_json)?),
-                Format::Sarif => render_serial_probe_sarif(&probe_json)?,
-                Format::Pretty => {}
-            }
-        }
-        Format::Pretty => {
-            eprintln!("serial probe report");
-            eprintln!("  port:      {}", port);
-            eprintln!("  baud:      {}", baud);
-            eprintln!(
+    let probe_finished_ms = now_ms();
+    let mut timeout_stage_hits_json = timeout_stage_hits;
+    timeout_stage_hits_json.sort();
+    timeout_stage_hits_json.dedup();
+    probe_json["success"] = serde_json::json!(success);
+    probe_json["status_successes"] = serde_json::json!(status_successes);
+    probe_json["info_successes"] = serde_json::json!(info_successes);
+    probe_json["status_failures"] = serde_json::json!(status_failures);
+    probe_json["info_failures"] = serde_json::json!(info_failures);
+    probe_json["open_successes"] = serde_json::json!(open_successes);
+    probe_json["open_failures"] = serde_json::json!(open_failures);
+    probe_json["test_label_successes"] = serde_json::json!(test_label_successes);
+    probe_json["test_label_failures"] = serde_json::json!(test_label_failures);
+    probe_json["attempts_with_any_success"] = serde_json::json!(attempts_with_any_success);
+    probe_json["success_ratio"] = serde_json::json!(success_ratio);
+    probe_json["timeout_stages"] = serde_json::json!(timeout_stage_hits_json);
+    probe_json["diagnosis"] = serde_json::json!(diagnosis);
+    probe_json["attempts"] = serde_json::Value::Array(per_attempt);
+    probe_json["findings"] = serde_json::to_value(&findings).unwrap_or_default();
+    probe_json["finished_at_ms"] = serde_json::json!(probe_finished_ms);
+    probe_json["elapsed_ms"] =
+        serde_json::json!(probe_finished_ms.saturating_sub(probe_started_ms));
+    probe_json["summary"] = serde_json::json!({
+        "attempts_total": repeat,
+        "attempts_with_status_ok": status_successes,
+        "attempts_with_info_ok": info_successes,
+        "attempts_with_any_success": attempts_with_any_success,
+        "success_ratio": success_ratio,
+        "attempts_with_open_failure": open_failures,
+        "attempts_with_status_failure": status_failures,
+        "attempts_with_info_failure": info_failures,
+        "attempts_with_test_label_success": test_label_successes,
+        "attempts_with_test_label_failure": test_label_failures
+    });
+    let mapped_peer = if compare_tty_cu {
+        mapped_tty_cu_peer(port)
+    } else {
+        None
+    };
+    if let Some(mapped) = &mapped_peer {
+        probe_json["peer_probe"] = probe_peer_once(mapped);
+    }
+
+    let sarif = serial_probe_sarif(&probe_json);
+    let report = Report::new(probe_json.clone())
+        .pretty(|_, err| {
+            writeln!(err, "serial probe report")?;
+            writeln!(err, "  port:      {}", port)?;
+            writeln!(err, "  baud:      {}", baud)?;
+            writeln!(
+                err,
                 "  settings:  data={:?} parity={:?} stop={:?} flow={:?}",
                 settings.data_bits, settings.parity, settings.stop_bits, settings.flow_control
-            );
-            eprintln!("  repeat:    {}", repeat);
+            )?;
+            writeln!(err, "  repeat:    {}", repeat)?;
             if reopen_each_attempt {
-                eprintln!("  reopen:    each attempt");
+                writeln!(err, "  reopen:    each attempt")?;
             }
             if reopen_on_broken_pipe {
-                eprintln!("  recovery:  reopen on broken pipe");
+                writeln!(err, "  recovery:  reopen on broken pipe")?;
             }
             if interval_ms > 0 {
-                eprintln!("  interval:  {} ms", interval_ms);
+                writeln!(err, "  interval:  {} ms", interval_ms)?;
             }
             if require_all_attempts {
-                eprintln!("  success:   require all attempts");
+                writeln!(err, "  success:   require all attempts")?;
             } else if min_success_ratio > 0.0 {
-                eprintln!("  success:   min ratio {:.2}", min_success_ratio);
+                writeln!(err, "  success:   min ratio {:.2}", min_success_ratio)?;
             }
-            if compare_tty_cu && let Some(mapped) = mapped_tty_cu_peer(port) {
-                eprintln!("  peer hint: {}", mapped);
+            if let Some(mapped) = &mapped_peer {
+                writeln!(err, "  peer hint: {}", mapped)?;
             }
-            for finding in findings {
-                eprintln!("  - {}", finding);
+            for finding in &findings {
+                writeln!(err, "  - {}", finding)?;
             }
-            eprintln!("  diagnosis: {}", diagnosis);
+            writeln!(err, "  diagnosis: {}", diagnosis)?;
             if diagnosis == "write_path_only_or_response_blocked" {
-                eprintln!(
+                writeln!(
+                    err,
                     "  hint: endpoint may allow writes but not return STX/ETX status frames."
-                );
-                eprintln!(
+                )?;
+                writeln!(
+                    err,
                     "  hint: verify BT profile/channel and printer serial config (^SC ... ^JUS)."
-                );
+                )?;
             }
-            if compare_tty_cu && let Some(mapped) = mapped_tty_cu_peer(port) {
-                let peer = probe_peer_once(&mapped);
-                eprintln!("  peer probe:");
+            if let Some(mapped) = &mapped_peer {
+                let peer = &probe_json["peer_probe"];
+                writeln!(err, "  peer probe:")?;
                 let peer_open_error = peer.get("open_error").and_then(|v| v.as_str());
-                if let Some(err) = peer_open_error {
-                    eprintln!("    {} open failed: {}", mapped, err);
+                if let Some(open_error) = peer_open_error {
+                    writeln!(err, "    {} open failed: {}", mapped, open_error)?;
                 } else {
                     let diagnosis = peer
                         .get("diagnosis")
@@ -2417,7 +3113,8 @@ fn cmd_serial_probe(opts: SerialProbeOpts<'_>) -> Result<()> {
                         .and_then(|v| v.as_u64())
                         .unwrap_or(0);
                     let repeat_total = peer.get("repeat").and_then(|v| v.as_u64()).unwrap_or(0);
-                    eprintln!(
+                    writeln!(
+                        err,
                         "    {} diagnosis={} status_ok={} info_ok={} attempt_success={}/{}",
                         mapped,
                         diagnosis,
@@ -2425,14 +3122,16 @@ fn cmd_serial_probe(opts: SerialProbeOpts<'_>) -> Result<()> {
                         info_successes,
                         attempts_with_any_success,
                         repeat_total
-                    );
+                    )?;
                 }
             }
-        }
-    }
+            Ok(())
+        })
+        .sarif(SarifOutput::Log(sarif));
+    render_report(&report, format)?;
 
     if !success {
-        process::exit(1);
+        ExitCode::ConnectionFailed.exit();
     }
 
     Ok(())
@@ -2577,44 +3276,39 @@ fn cmd_bt_status(
         }));
     }
 
-    match format {
-        Format::Json => {
-            let out = serde_json::json!({
-                "printer": addr.to_string(),
-                "timeout_secs": timeout_secs,
-                "retries": retries,
-                "retry_delay_ms": retry_delay_ms,
-                "success": !had_errors,
-                "variables": results
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        }
-        Format::Sarif => {
-            render_bt_status_sarif(&results, !had_errors)?;
-        }
-        Format::Pretty => {
-            eprintln!("bluetooth status via tcp ({})", addr);
-            for v in results {
-                let name = v
-                    .get("name")
-                    .and_then(|n| n.as_str())
-                    .unwrap_or("(unknown)");
-                let value = v.get("value").and_then(|n| n.as_str());
-                let error = v.get("error").and_then(|n| n.as_str());
-                let timed_out = v.get("timeout").and_then(|n| n.as_bool()).unwrap_or(false);
-                match (value, error) {
-                    (Some(val), _) => eprintln!("  {} = {}", name, val),
-                    (None, Some(err)) => eprintln!("  {} = (error: {})", name, err),
-                    (None, None) => eprintln!("  {} = (no response)", name),
-                }
-                if timed_out {
-                    eprintln!("    note: read timeout/would-block observed");
-                }
-            }
-        }
-    }
+    let report = Report::new(serde_json::json!({
+        "printer": addr.to_string(),
+        "timeout_secs": timeout_secs,
+        "retries": retries,
+        "retry_delay_ms": retry_delay_ms,
+        "success": !had_errors,
+        "variables": results
+    }))
+    .pretty(|_, w| {
+        writeln!(w, "bluetooth status via tcp ({})", addr)?;
+        for v in &results {
+            let name = v
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("(unknown)");
+            let value = v.get("value").and_then(|n| n.as_str());
+            let error = v.get("error").and_then(|n| n.as_str());
+            let timed_out = v.get("timeout").and_then(|n| n.as_bool()).unwrap_or(false);
+            match (value, error) {
+                (Some(val), _) => writeln!(w, "  {} = {}", name, val)?,
+                (None, Some(err)) => writeln!(w, "  {} = (error: {})", name, err)?,
+                (None, None) => writeln!(w, "  {} = (no response)", name)?,
+            }
+            if timed_out {
+                writeln!(w, "    note: read timeout/would-block observed")?;
+            }
+        }
+        Ok(())
+    })
+    .sarif(SarifOutput::Log(bt_status_sarif(&results, !had_errors)));
+    render_report(&report, format)?;
     if had_errors {
-        process::exit(1);
+        ExitCode::ConnectionFailed.exit();
     }
     Ok(())
 }
@@ -2777,29 +3471,35 @@ fn cmd_coverage(coverage_path: &str, show_issues: bool, json: bool) -> Result<()
     Ok(())
 }
 
+/// Print a JSON Schema document. The output is always JSON, regardless of
+/// `--output`.
+fn cmd_schema(kind: SchemaKind) -> Result<()> {
+    let schema = match kind {
+        SchemaKind::Diagnostic => zpl_toolchain_core::schema::diagnostic_schema(),
+        SchemaKind::ValidationResult => zpl_toolchain_core::schema::validation_result_schema(),
+        SchemaKind::ParseResult => zpl_toolchain_core::schema::parse_result_schema(),
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
 fn cmd_explain(id: &str, format: Format) -> Result<()> {
-    match format {
-        Format::Json => {
-            let text = diag::explain(id);
-            let out = serde_json::json!({
-                "id": id,
-                "explanation": text,
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        }
-        Format::Sarif => {
-            render_explain_sarif(id, diag::explain(id))?;
-        }
-        Format::Pretty => {
-            // Explanation is the expected output — write to stdout, not stderr.
-            if let Some(text) = diag::explain(id) {
-                use ariadne::Fmt;
-                println!("{}: {}", id.fg(ariadne::Color::Cyan), text);
-            } else {
-                println!("{}: (no explanation available)", id);
-            }
+    let text = diag::explain(id);
+    let report = Report::new(serde_json::json!({
+        "id": id,
+        "explanation": text,
+    }))
+    .pretty(|out, _| {
+        // Explanation is the expected output — write to stdout, not stderr.
+        if let Some(text) = text {
+            use ariadne::Fmt;
+            writeln!(out, "{}: {}", id.fg(ariadne::Color::Cyan), text)
+        } else {
+            writeln!(out, "{}: (no explanation available)", id)
         }
-    }
+    })
+    .sarif(SarifOutput::Log(explain_sarif(id, text)));
+    render_report(&report, format)?;
     Ok(())
 }
 
@@ -2926,89 +3626,85 @@ fn cmd_doctor(opts: DoctorOpts<'_>) -> Result<()> {
         }
     }
 
-    match format {
-        Format::Json => {
-            let out = serde_json::json!({
-                "success": success,
-                "tables": tables_json,
-                "profile": profile_json,
-                "printer": printer_json
-            });
-            println!("{}", serde_json::to_string_pretty(&out)?);
-        }
-        Format::Sarif => {
-            render_doctor_sarif(success, &tables_json, &profile_json, &printer_json)?;
+    let sarif = doctor_sarif(success, &tables_json, &profile_json, &printer_json);
+    let report = Report::new(serde_json::json!({
+        "success": success,
+        "tables": tables_json,
+        "profile": profile_json,
+        "printer": printer_json
+    }))
+    .pretty(|_, err| {
+        writeln!(err, "zpl doctor - environment diagnostics")?;
+        let tables_ok = tables_json
+            .get("ok")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if tables_ok {
+            let source = tables_json
+                .get("source")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            writeln!(err, "  tables: ok ({source})")?;
+        } else {
+            writeln!(err, "  tables: missing")?;
+            if let Some(message) = tables_json.get("message").and_then(|v| v.as_str()) {
+                writeln!(err, "    {message}")?;
+            }
         }
-        Format::Pretty => {
-            eprintln!("zpl doctor - environment diagnostics");
-            let tables_ok = tables_json
+        if let Some(path) = profile_path {
+            let profile_ok = profile_json
                 .get("ok")
                 .and_then(|v| v.as_bool())
                 .unwrap_or(false);
-            if tables_ok {
-                let source = tables_json
-                    .get("source")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                eprintln!("  tables: ok ({source})");
+            if profile_ok {
+                writeln!(err, "  profile: ok ({path})")?;
             } else {
-                eprintln!("  tables: missing");
-                if let Some(message) = tables_json.get("message").and_then(|v| v.as_str()) {
-                    eprintln!("    {message}");
-                }
-            }
-            if let Some(path) = profile_path {
-                let profile_ok = profile_json
-                    .get("ok")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                if profile_ok {
-                    eprintln!("  profile: ok ({path})");
-                } else {
-                    let message = profile_json
-                        .get("message")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown profile error");
-                    eprintln!("  profile: fail ({path})");
-                    eprintln!("    {message}");
-                }
+                let message = profile_json
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("unknown profile error");
+                writeln!(err, "  profile: fail ({path})")?;
+                writeln!(err, "    {message}")?;
             }
-            if printer_addr.is_some() {
-                let printer_ok = printer_json
-                    .get("ok")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false);
-                let addr = printer_json
-                    .get("addr")
+        }
+        if printer_addr.is_some() {
+            let printer_ok = printer_json
+                .get("ok")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let addr = printer_json
+                .get("addr")
+                .and_then(|v| v.as_str())
+                .unwrap_or("(unknown)");
+            if printer_ok {
+                writeln!(err, "  printer: reachable ({addr})")?;
+            } else {
+                let message = printer_json
+                    .get("message")
                     .and_then(|v| v.as_str())
-                    .unwrap_or("(unknown)");
-                if printer_ok {
-                    eprintln!("  printer: reachable ({addr})");
-                } else {
-                    let message = printer_json
-                        .get("message")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown printer error");
-                    eprintln!("  printer: unreachable ({addr})");
-                    eprintln!("    {message}");
-                }
+                    .unwrap_or("unknown printer error");
+                writeln!(err, "  printer: unreachable ({addr})")?;
+                writeln!(err, "    {message}")?;
             }
         }
-    }
+        Ok(())
+    })
+    .sarif(SarifOutput::Log(sarif));
+    render_report(&report, format)?;
 
     if !success {
-        process::exit(1);
+        ExitCode::LintErrors.exit();
     }
 
     Ok(())
 }
 
-fn render_doctor_sarif(
+fn doctor_sarif(
     success: bool,
     tables_json: &serde_json::Value,
     profile_json: &serde_json::Value,
     printer_json: &serde_json::Value,
-) -> Result<()> {
+) -> serde_json::Value {
     let mut results = Vec::new();
     let mut rules = Vec::new();
 
@@ -3058,10 +3754,10 @@ fn render_doctor_sarif(
         push_result("DOCTOR_PRINTER_UNREACHABLE", message);
     }
 
-    emit_sarif_run("zpl-toolchain-doctor", rules, results, success, None)
+    sarif_log("zpl-toolchain-doctor", rules, results, success, None)
 }
 
-fn render_explain_sarif(id: &str, explanation: Option<&'static str>) -> Result<()> {
+fn explain_sarif(id: &str, explanation: Option<&'static str>) -> serde_json::Value {
     let mut rules = Vec::new();
     let mut results = Vec::new();
     if explanation.is_none() {
@@ -3085,11 +3781,11 @@ fn render_explain_sarif(id: &str, explanation: Option<&'static str>) -> Result<(
             }
         }),
     );
-    emit_sarif_run("zpl-toolchain-explain", rules, results, true, Some(extra))
+    sarif_log("zpl-toolchain-explain", rules, results, true, Some(extra))
 }
 
 #[cfg(feature = "serial")]
-fn render_serial_probe_sarif(probe_json: &serde_json::Value) -> Result<()> {
+fn serial_probe_sarif(probe_json: &serde_json::Value) -> serde_json::Value {
     let mut results = Vec::new();
     let mut rules = Vec::new();
     if probe_json
@@ -3120,7 +3816,7 @@ fn render_serial_probe_sarif(probe_json: &serde_json::Value) -> Result<()> {
             format!("{message} (port={port}, diagnosis={diagnosis})"),
         ));
     }
-    emit_sarif_run(
+    sarif_log(
         "zpl-toolchain-serial-probe",
         rules,
         results,
@@ -3133,7 +3829,7 @@ fn render_serial_probe_sarif(probe_json: &serde_json::Value) -> Result<()> {
 }
 
 #[cfg(feature = "tcp")]
-fn render_bt_status_sarif(results: &[serde_json::Value], success: bool) -> Result<()> {
+fn bt_status_sarif(results: &[serde_json::Value], success: bool) -> serde_json::Value {
     let mut sarif_results = Vec::new();
     let mut rules = Vec::new();
     for value in results {
@@ -3153,7 +3849,7 @@ fn render_bt_status_sarif(results: &[serde_json::Value], success: bool) -> Resul
             sarif_results.push(sarif_result(&rule_id, "error", format!("{name}: {error}")));
         }
     }
-    emit_sarif_run(
+    sarif_log(
         "zpl-toolchain-bt-status",
         rules,
         sarif_results,
@@ -3164,14 +3860,12 @@ fn render_bt_status_sarif(results: &[serde_json::Value], success: bool) -> Resul
 
 // ── Helpers ─────────────────────────────────────────────────────────────
 
-/// Exit with code 1 if any diagnostic is an error.
+/// Exit with [`ExitCode::LintErrors`] if any diagnostic is an error.
 /// Warnings and info do not cause a non-zero exit.
 fn exit_on_errors(diagnostics: &[Diagnostic]) {
-    if diagnostics
-        .iter()
-        .any(|d| matches!(d.severity, Severity::Error))
-    {
-        process::exit(1);
+    let code = ExitCode::for_diagnostics(diagnostics);
+    if code != ExitCode::Success {
+        code.exit();
     }
 }
 
@@ -3185,7 +3879,8 @@ fn resolve_tables(explicit_path: Option<&str>) -> Result<Option<ParserTables>> {
     if let Some(path) = explicit_path {
         let json = fs::read_to_string(path)
             .with_context(|| format!("failed to read tables file '{}'", path))?;
-        let tables = serde_json::from_str(&json)
+        let tables = json
+            .parse::<ParserTables>()
             .with_context(|| format!("failed to parse tables file '{}'", path))?;
         return Ok(Some(tables));
     }
@@ -3208,12 +3903,12 @@ fn embedded_tables() -> Option<ParserTables> {
 /// Parse input with resolved tables.
 fn parse_with_resolved_tables(
     tables_path: Option<&str>,
-    input: &str,
+    input: &Input,
 ) -> Result<zpl_toolchain_core::grammar::parser::ParseResult> {
     let tables = resolve_tables(tables_path)?.context(
         "no parser tables available — pass --tables <PATH> or use a build with embedded tables",
     )?;
-    Ok(parse_with_tables(input, Some(&tables)))
+    Ok(input.parse(Some(&tables)))
 }
 
 /// Detect printer address strings that look like serial port paths.
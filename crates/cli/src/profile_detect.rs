@@ -0,0 +1,90 @@
+//! Best-effort printer profile built from `~HI`/`~HM` for `zpl profile-detect`.
+//!
+//! Only resolution, memory, and firmware can be read back from a printer;
+//! the remaining profile sections are left unset for the user to fill in.
+
+use zpl_toolchain_print_client::{MemoryStatus, PrinterInfo};
+use zpl_toolchain_profile::{Memory, Profile};
+
+/// Schema version written into detected profiles.
+const PROFILE_SCHEMA_VERSION: &str = "1.1.0";
+
+/// Profile sections that `~HI`/`~HM` cannot report.
+pub(crate) const UNDETECTED_FIELDS: [&str; 5] =
+    ["page", "speed_range", "darkness_range", "features", "media"];
+
+/// Build a profile from a `~HI` reply and, when available, a `~HM` reply.
+///
+/// `id` defaults to `zebra-<model>` with the model slugified.
+pub(crate) fn profile_from_printer(
+    id: Option<&str>,
+    info: &PrinterInfo,
+    memory: Option<&MemoryStatus>,
+) -> Profile {
+    let ram_kb = memory.map(|m| m.total_kb).unwrap_or(info.memory_kb).max(1);
+    Profile {
+        id: id.map_or_else(|| format!("zebra-{}", slugify(&info.model)), str::to_string),
+        schema_version: PROFILE_SCHEMA_VERSION.to_string(),
+        dpi: info.dpi,
+        page: None,
+        speed_range: None,
+        darkness_range: None,
+        features: None,
+        media: None,
+        memory: Some(Memory {
+            ram_kb: Some(ram_kb),
+            flash_kb: None,
+            firmware_version: Some(info.firmware.clone()).filter(|f| !f.is_empty()),
+        }),
+    }
+}
+
+fn slugify(model: &str) -> String {
+    let mut slug = String::new();
+    for c in model.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "printer".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zpl_toolchain_profile::load_profile_from_str;
+
+    #[test]
+    fn profile_from_fixed_replies_loads() {
+        let info = PrinterInfo::parse(&[b"ZD421-300dpi,V84.20.18Z,12,8192KB".to_vec()])
+            .expect("valid ~HI");
+        let memory = MemoryStatus::parse(&[b"1024,0780,0512".to_vec()]).expect("valid ~HM");
+
+        let profile = profile_from_printer(None, &info, Some(&memory));
+        let json = serde_json::to_string_pretty(&profile).unwrap();
+        let loaded = load_profile_from_str(&json).expect("detected profile loads");
+
+        assert_eq!(loaded.id, "zebra-zd421-300dpi");
+        assert_eq!(loaded.dpi, 300);
+        let mem = loaded.memory.expect("memory");
+        assert_eq!(mem.ram_kb, Some(1024));
+        assert_eq!(mem.firmware_version.as_deref(), Some("V84.20.18Z"));
+        assert!(loaded.page.is_none() && loaded.features.is_none());
+    }
+
+    #[test]
+    fn profile_without_hm_uses_hi_memory_and_custom_id() {
+        let info = PrinterInfo::parse(&[b"ZT411,V92.21.39Z,8,4096KB".to_vec()]).unwrap();
+        let profile = profile_from_printer(Some("line-3"), &info, None);
+        assert_eq!(profile.id, "line-3");
+        assert_eq!(profile.dpi, 203);
+        assert_eq!(profile.memory.unwrap().ram_kb, Some(4096));
+    }
+}
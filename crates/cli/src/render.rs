@@ -1,18 +1,30 @@
 //! Pretty diagnostic rendering using ariadne.
 //!
-//! Converts the toolchain's [`Diagnostic`] type into ariadne [`Report`]s for
+//! Converts the toolchain's [`Diagnostic`] type into [`ariadne::Report`]s for
 //! coloured, source-annotated terminal output. Falls back to structured JSON
 //! when the output is piped or when the user explicitly requests it.
 //! Supports SARIF 2.1.0 output for CI and tooling integration.
+//!
+//! Commands reach a format through [`Format::formatter`], which returns the
+//! [`OutputFormatter`] implementation for that format. Results without a
+//! dedicated formatter method are described as a [`Report`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
 
-use std::io::{self, IsTerminal};
+use ariadne::{Color, Config, IndexType, Label, ReportKind, Source};
+use zpl_toolchain_core::grammar::dump::{WithSpans, to_pretty_json};
+use zpl_toolchain_core::grammar::parser::ParseResult;
+use zpl_toolchain_diagnostics::{Diagnostic, DiagnosticSummary, Severity, Span};
+use zpl_toolchain_print_client::PrinterSnapshot;
 
-use ariadne::{Color, Config, Label, Report, ReportKind, Source};
-use zpl_toolchain_diagnostics::{Diagnostic, LineIndex, Severity};
+use crate::encoding::Input;
+use crate::status_report::{render_status_report, status_report_json};
 
 /// One SARIF artifact entry with its source and diagnostics.
 pub(crate) struct SarifArtifactInput<'a> {
-    pub source: &'a str,
+    pub source: &'a Input,
     pub artifact_uri: &'a str,
     pub diagnostics: &'a [Diagnostic],
 }
@@ -23,30 +35,436 @@ pub(crate) struct SarifArtifactInput<'a> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Format {
     /// Coloured, source-annotated output (ariadne).
-    Pretty,
+    Pretty(PrettyFormatter),
     /// Machine-readable JSON.
     Json,
     /// SARIF 2.1.0 for CI/tooling integration (GitHub Code Scanning, etc.).
-    Sarif,
+    Sarif(SarifFormatter),
 }
 
 impl Format {
     /// Resolve `Auto` to a concrete format based on whether stdout is a TTY.
-    pub(crate) fn resolve_or_detect(explicit: Option<&str>) -> Self {
+    /// `pretty_width` is the fixed width for pretty output, if any.
+    pub(crate) fn resolve_or_detect(explicit: Option<&str>, pretty_width: Option<usize>) -> Self {
+        let pretty = PrettyFormatter {
+            width: pretty_width,
+        };
         match explicit {
             Some("json") => Format::Json,
-            Some("pretty") => Format::Pretty,
-            Some("sarif") => Format::Sarif,
+            Some("pretty") => Format::Pretty(pretty),
+            Some("sarif") => Format::Sarif(SarifFormatter { pretty }),
             // Default: pretty for interactive terminals, JSON for pipes
             _ => {
                 if io::stdout().is_terminal() {
-                    Format::Pretty
+                    Format::Pretty(pretty)
                 } else {
                     Format::Json
                 }
             }
         }
     }
+
+    /// Resolve the format for commands whose output is ZPL (`format`,
+    /// `normalize`, `extract`). Without `--output` this is always `Pretty`, so the raw
+    /// ZPL reaches stdout even when it is redirected to a file.
+    pub(crate) fn resolve_for_zpl(explicit: Option<&str>, pretty_width: Option<usize>) -> Self {
+        match explicit {
+            None => Format::Pretty(PrettyFormatter {
+                width: pretty_width,
+            }),
+            Some(_) => Self::resolve_or_detect(explicit, pretty_width),
+        }
+    }
+
+    /// The formatter that renders command output in this format.
+    pub(crate) fn formatter(&self) -> &dyn OutputFormatter {
+        match self {
+            Format::Pretty(formatter) => formatter,
+            Format::Json => &JsonFormatter,
+            Format::Sarif(formatter) => formatter,
+        }
+    }
+}
+
+// ── Formatters ──────────────────────────────────────────────────────────
+
+/// Renders command results in one output format.
+///
+/// Primary output goes to `out` (stdout) and human-facing notes to `err`
+/// (stderr), so a command can pipe its data while still reporting problems.
+pub(crate) trait OutputFormatter {
+    /// Render the diagnostics found in `source`.
+    fn render_diagnostics(
+        &self,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+        source: &Input,
+        filename: &str,
+        diagnostics: &[Diagnostic],
+    ) -> io::Result<()>;
+
+    /// Render a parse result for `zpl parse`.
+    fn render_parse(
+        &self,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+        source: &Input,
+        filename: &str,
+        result: &ParseResult,
+    ) -> io::Result<()>;
+
+    /// Render a printer status snapshot for `zpl status`.
+    fn render_status(
+        &self,
+        out: &mut dyn Write,
+        printer: &str,
+        snapshot: &PrinterSnapshot,
+    ) -> io::Result<()>;
+
+    /// Render per-severity counts only, for `--count`.
+    fn render_counts(&self, out: &mut dyn Write, diagnostics: &[Diagnostic]) -> io::Result<()>;
+
+    /// Render any other command result.
+    fn render_report(
+        &self,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+        report: &Report<'_>,
+    ) -> io::Result<()>;
+}
+
+// ── Reports ─────────────────────────────────────────────────────────────
+
+/// Pretty rendering of a [`Report`], given `out` (stdout) and `err` (stderr).
+type PrettyFn<'a> = Box<dyn Fn(&mut dyn Write, &mut dyn Write) -> io::Result<()> + 'a>;
+
+/// What `--output sarif` prints for a [`Report`].
+pub(crate) enum SarifOutput<'a> {
+    /// Nothing; the report is human-facing progress.
+    Nothing,
+    /// The report's JSON document.
+    Json,
+    /// The pretty rendering, for results SARIF has no shape for.
+    Pretty,
+    /// A SARIF log of the report's diagnostics.
+    Diagnostics,
+    /// A SARIF log covering several files.
+    Artifacts(Vec<SarifArtifactInput<'a>>),
+    /// A prebuilt SARIF log.
+    Log(serde_json::Value),
+}
+
+/// Diagnostics found in one source file.
+struct FileDiagnostics<'a> {
+    source: &'a Input,
+    filename: &'a str,
+    diagnostics: &'a [Diagnostic],
+}
+
+/// A command result with a rendering for each output format.
+///
+/// Pretty output shows the report's diagnostics on stderr with a summary
+/// line, then runs the pretty closure. JSON prints the JSON document, and
+/// SARIF prints whatever [`SarifOutput`] the report selects.
+pub(crate) struct Report<'a> {
+    json: Option<serde_json::Value>,
+    diagnostics: Option<FileDiagnostics<'a>>,
+    group_by_code: bool,
+    pretty: Option<PrettyFn<'a>>,
+    sarif: SarifOutput<'a>,
+}
+
+impl<'a> Report<'a> {
+    /// A result whose JSON form is `json`, which SARIF output also prints
+    /// unless [`Report::sarif`] chooses otherwise.
+    pub(crate) fn new(json: serde_json::Value) -> Self {
+        Report {
+            json: Some(json),
+            diagnostics: None,
+            group_by_code: false,
+            pretty: None,
+            sarif: SarifOutput::Json,
+        }
+    }
+
+    /// Progress that only pretty output shows.
+    pub(crate) fn progress() -> Self {
+        Report {
+            json: None,
+            diagnostics: None,
+            group_by_code: false,
+            pretty: None,
+            sarif: SarifOutput::Nothing,
+        }
+    }
+
+    /// A single progress line on stderr, shown only by pretty output.
+    pub(crate) fn note(line: impl fmt::Display + 'a) -> Self {
+        Self::progress().pretty(move |_, err| writeln!(err, "{line}"))
+    }
+
+    /// Diagnostics found in `source`, shown before the pretty output.
+    pub(crate) fn diagnostics(
+        mut self,
+        source: &'a Input,
+        filename: &'a str,
+        diagnostics: &'a [Diagnostic],
+    ) -> Self {
+        self.diagnostics = Some(FileDiagnostics {
+            source,
+            filename,
+            diagnostics,
+        });
+        self
+    }
+
+    /// Show pretty diagnostics grouped by code rather than one by one.
+    pub(crate) fn group_by_code(mut self, grouped: bool) -> Self {
+        self.group_by_code = grouped;
+        self
+    }
+
+    /// Pretty rendering, run after the diagnostics.
+    pub(crate) fn pretty(
+        mut self,
+        render: impl Fn(&mut dyn Write, &mut dyn Write) -> io::Result<()> + 'a,
+    ) -> Self {
+        self.pretty = Some(Box::new(render));
+        self
+    }
+
+    /// Choose what SARIF output prints.
+    pub(crate) fn sarif(mut self, sarif: SarifOutput<'a>) -> Self {
+        self.sarif = sarif;
+        self
+    }
+}
+
+/// Coloured, source-annotated output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct PrettyFormatter {
+    /// Fixed rendering width; `None` means auto-detect.
+    pub width: Option<usize>,
+}
+
+impl OutputFormatter for PrettyFormatter {
+    fn render_diagnostics(
+        &self,
+        _out: &mut dyn Write,
+        err: &mut dyn Write,
+        source: &Input,
+        filename: &str,
+        diagnostics: &[Diagnostic],
+    ) -> io::Result<()> {
+        write_diagnostics_pretty(err, source, filename, diagnostics, self.width)
+    }
+
+    fn render_parse(
+        &self,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+        source: &Input,
+        filename: &str,
+        result: &ParseResult,
+    ) -> io::Result<()> {
+        // AST to stdout, diagnostics to stderr.
+        writeln!(out, "{}", to_pretty_json(&result.ast, WithSpans::Yes))?;
+        if !result.diagnostics.is_empty() {
+            write_diagnostics_pretty(err, source, filename, &result.diagnostics, self.width)?;
+            write_summary(err, &result.diagnostics)?;
+        }
+        Ok(())
+    }
+
+    fn render_status(
+        &self,
+        out: &mut dyn Write,
+        printer: &str,
+        snapshot: &PrinterSnapshot,
+    ) -> io::Result<()> {
+        write!(out, "{}", render_status_report(printer, snapshot))
+    }
+
+    fn render_counts(&self, out: &mut dyn Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
+        write_counts_line(out, diagnostics)
+    }
+
+    fn render_report(
+        &self,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+        report: &Report<'_>,
+    ) -> io::Result<()> {
+        // Diagnostics to stderr, so stdout stays clean for the primary output.
+        if let Some(file) = &report.diagnostics {
+            if report.group_by_code {
+                write_diagnostics_grouped(
+                    err,
+                    file.source,
+                    file.filename,
+                    file.diagnostics,
+                    self.width,
+                )?;
+            } else {
+                write_diagnostics_pretty(
+                    err,
+                    file.source,
+                    file.filename,
+                    file.diagnostics,
+                    self.width,
+                )?;
+            }
+            write_summary(err, file.diagnostics)?;
+        }
+        match &report.pretty {
+            Some(render) => render(out, err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Machine-readable JSON on stdout.
+pub(crate) struct JsonFormatter;
+
+impl OutputFormatter for JsonFormatter {
+    fn render_diagnostics(
+        &self,
+        out: &mut dyn Write,
+        _err: &mut dyn Write,
+        _source: &Input,
+        _filename: &str,
+        diagnostics: &[Diagnostic],
+    ) -> io::Result<()> {
+        write_json(out, &diagnostics)
+    }
+
+    fn render_parse(
+        &self,
+        out: &mut dyn Write,
+        _err: &mut dyn Write,
+        _source: &Input,
+        _filename: &str,
+        result: &ParseResult,
+    ) -> io::Result<()> {
+        // Single valid JSON object to stdout.
+        let json = serde_json::json!({
+            "ast": result.ast,
+            "diagnostics": result.diagnostics,
+        });
+        write_json(out, &json)
+    }
+
+    fn render_status(
+        &self,
+        out: &mut dyn Write,
+        printer: &str,
+        snapshot: &PrinterSnapshot,
+    ) -> io::Result<()> {
+        write_json(out, &status_report_json(printer, snapshot))
+    }
+
+    fn render_counts(&self, out: &mut dyn Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
+        let summary = DiagnosticSummary::from_diagnostics(diagnostics);
+        let json = serde_json::json!({
+            "ok": summary.errors == 0,
+            "counts": {
+                "errors": summary.errors,
+                "warnings": summary.warns,
+                "infos": summary.infos,
+            },
+        });
+        write_json(out, &json)
+    }
+
+    fn render_report(
+        &self,
+        out: &mut dyn Write,
+        _err: &mut dyn Write,
+        report: &Report<'_>,
+    ) -> io::Result<()> {
+        match &report.json {
+            Some(json) => write_json(out, json),
+            None => Ok(()),
+        }
+    }
+}
+
+/// SARIF 2.1.0 on stdout; outputs without diagnostics fall back to text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct SarifFormatter {
+    /// Renders the text fallbacks.
+    pub pretty: PrettyFormatter,
+}
+
+impl OutputFormatter for SarifFormatter {
+    fn render_diagnostics(
+        &self,
+        out: &mut dyn Write,
+        _err: &mut dyn Write,
+        source: &Input,
+        filename: &str,
+        diagnostics: &[Diagnostic],
+    ) -> io::Result<()> {
+        let uri = artifact_uri_for_file(filename);
+        let single = [SarifArtifactInput {
+            source,
+            artifact_uri: &uri,
+            diagnostics,
+        }];
+        write_json(out, &sarif_diagnostics_log(&single))
+    }
+
+    fn render_parse(
+        &self,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+        source: &Input,
+        filename: &str,
+        result: &ParseResult,
+    ) -> io::Result<()> {
+        // No AST; SARIF is diagnostic-focused.
+        self.render_diagnostics(out, err, source, filename, &result.diagnostics)
+    }
+
+    fn render_status(
+        &self,
+        out: &mut dyn Write,
+        printer: &str,
+        snapshot: &PrinterSnapshot,
+    ) -> io::Result<()> {
+        self.pretty.render_status(out, printer, snapshot)
+    }
+
+    fn render_counts(&self, out: &mut dyn Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
+        write_counts_line(out, diagnostics)
+    }
+
+    fn render_report(
+        &self,
+        out: &mut dyn Write,
+        err: &mut dyn Write,
+        report: &Report<'_>,
+    ) -> io::Result<()> {
+        match &report.sarif {
+            SarifOutput::Nothing => Ok(()),
+            SarifOutput::Json => JsonFormatter.render_report(out, err, report),
+            SarifOutput::Pretty => self.pretty.render_report(out, err, report),
+            SarifOutput::Diagnostics => match &report.diagnostics {
+                Some(file) => {
+                    self.render_diagnostics(out, err, file.source, file.filename, file.diagnostics)
+                }
+                None => write_json(out, &sarif_diagnostics_log(&[])),
+            },
+            SarifOutput::Artifacts(entries) => write_json(out, &sarif_diagnostics_log(entries)),
+            SarifOutput::Log(log) => write_json(out, log),
+        }
+    }
+}
+
+/// Write `value` as pretty-printed JSON followed by a newline.
+fn write_json(out: &mut dyn Write, value: &impl serde::Serialize) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    writeln!(out, "{json}")
 }
 
 // ── Severity mapping ────────────────────────────────────────────────────
@@ -69,33 +487,100 @@ fn severity_color(severity: &Severity) -> Color {
     }
 }
 
+// ── Pretty width ────────────────────────────────────────────────────────
+
+/// Columns taken by the report gutter and `Note:`/`Help:` prefix.
+const PRETTY_GUTTER: usize = 12;
+
+/// Narrowest wrap width for note and help text.
+const MIN_WRAP_WIDTH: usize = 20;
+
+/// Resolve the pretty rendering width: `--pretty-width` flag, then a positive
+/// numeric `COLUMNS` value, else `None` (auto-detect).
+pub(crate) fn resolve_pretty_width(flag: Option<usize>, columns: Option<&str>) -> Option<usize> {
+    flag.or_else(|| {
+        columns
+            .and_then(|c| c.trim().parse::<usize>().ok())
+            .filter(|&w| w > 0)
+    })
+}
+
+/// Word-wrap `text` to at most `width` columns, breaking only at whitespace.
+/// Words longer than `width` are kept whole on their own line.
+fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Wrap note/help text to `width`, if set.
+fn fit_to_width(text: &str, width: Option<usize>) -> String {
+    match width {
+        Some(width) => wrap_text(
+            text,
+            width.saturating_sub(PRETTY_GUTTER).max(MIN_WRAP_WIDTH),
+        )
+        .join("\n"),
+        None => text.to_string(),
+    }
+}
+
 // ── Pretty rendering ────────────────────────────────────────────────────
 
-/// Render a slice of diagnostics in pretty (ariadne) format to stderr.
+/// Write a slice of diagnostics in pretty (ariadne) format to `w`.
 ///
 /// Diagnostics with a [`Span`] are rendered with source context (line numbers,
 /// underlines, labels). Those without a span are rendered as standalone
 /// messages.
-pub(crate) fn render_diagnostics_pretty(source: &str, filename: &str, diagnostics: &[Diagnostic]) {
+fn write_diagnostics_pretty(
+    w: &mut dyn Write,
+    source: &Input,
+    filename: &str,
+    diagnostics: &[Diagnostic],
+    width: Option<usize>,
+) -> io::Result<()> {
     if diagnostics.is_empty() {
-        return;
+        return Ok(());
     }
 
-    let config = Config::default().with_compact(false);
+    // Spans are file byte offsets: byte indices into UTF-8 text, and
+    // character indices into text decoded one character per byte.
+    let index_type = if source.is_single_byte() {
+        IndexType::Char
+    } else {
+        IndexType::Byte
+    };
+    let config = Config::default()
+        .with_compact(false)
+        .with_index_type(index_type);
 
     // Build the Source once (O(n) line index) and reuse across all reports.
-    let mut cache = (filename, Source::from(source));
+    let mut cache = (filename, Source::from(source.text()));
 
     for diag in diagnostics {
         if let Some(span) = &diag.span {
             // Clamp span to source length to avoid panics on truncated input.
-            let start = span.start.min(source.len());
-            let end = span.end.min(source.len()).max(start);
+            let len = source.bytes().len();
+            let start = span.start.min(len);
+            let end = span.end.min(len).max(start);
 
-            let mut builder = Report::build(report_kind(&diag.severity), (filename, start..end))
-                .with_code(diag.id.as_ref())
-                .with_message(&diag.message)
-                .with_config(config);
+            let mut builder =
+                ariadne::Report::build(report_kind(&diag.severity), (filename, start..end))
+                    .with_code(diag.id.as_ref())
+                    .with_message(&diag.message)
+                    .with_config(config);
 
             // Use context for a more specific label when available,
             // otherwise fall back to the diagnostic message.
@@ -113,15 +598,15 @@ pub(crate) fn render_diagnostics_pretty(source: &str, filename: &str, diagnostic
                     .map(|(k, v)| format!("{k}={v}"))
                     .collect::<Vec<_>>()
                     .join(", ");
-                builder = builder.with_note(note);
+                builder = builder.with_note(fit_to_width(&note, width));
             }
 
             // If an explanation exists for this code, add it as help.
             if let Some(explanation) = diag.explain() {
-                builder = builder.with_help(explanation);
+                builder = builder.with_help(fit_to_width(explanation, width));
             }
 
-            builder.finish().eprint(&mut cache).ok();
+            builder.finish().write(&mut cache, &mut *w)?;
         } else {
             // No span — print a standalone message to stderr.
             let kind_str = match diag.severity {
@@ -130,7 +615,7 @@ pub(crate) fn render_diagnostics_pretty(source: &str, filename: &str, diagnostic
                 Severity::Info => "info",
                 _ => "diagnostic",
             };
-            eprintln!("{kind_str}[{}]: {}", diag.id, diag.message);
+            writeln!(w, "{kind_str}[{}]: {}", diag.id, diag.message)?;
 
             if let Some(ctx) = &diag.context {
                 let note: String = ctx
@@ -138,14 +623,28 @@ pub(crate) fn render_diagnostics_pretty(source: &str, filename: &str, diagnostic
                     .map(|(k, v)| format!("{k}={v}"))
                     .collect::<Vec<_>>()
                     .join(", ");
-                eprintln!("  = note: {note}");
+                writeln!(
+                    w,
+                    "  = note: {}",
+                    indent_continuation(&fit_to_width(&note, width))
+                )?;
             }
 
             if let Some(explanation) = diag.explain() {
-                eprintln!("  = help: {explanation}");
+                writeln!(
+                    w,
+                    "  = help: {}",
+                    indent_continuation(&fit_to_width(explanation, width))
+                )?;
             }
         }
     }
+    Ok(())
+}
+
+/// Align wrapped continuation lines under the text after `  = note: `.
+fn indent_continuation(text: &str) -> String {
+    text.replace('\n', "\n          ")
 }
 
 /// Build a concise label message from diagnostic context, avoiding duplication
@@ -167,78 +666,114 @@ fn make_label_message(diag: &Diagnostic) -> String {
     }
 }
 
-// ── JSON rendering ──────────────────────────────────────────────────────
+// ── Grouped rendering ───────────────────────────────────────────────────
 
-/// Render diagnostics as a JSON array to stdout.
-pub(crate) fn render_diagnostics_json(diagnostics: &[Diagnostic]) {
-    let json =
-        serde_json::to_string_pretty(diagnostics).expect("Diagnostic serialization cannot fail");
-    println!("{json}");
+/// Group diagnostics by code, sorted by code, keeping source order within
+/// each group.
+pub(crate) fn group_by_code(diagnostics: &[Diagnostic]) -> BTreeMap<&str, Vec<&Diagnostic>> {
+    let mut groups: BTreeMap<&str, Vec<&Diagnostic>> = BTreeMap::new();
+    for diag in diagnostics {
+        groups.entry(diag.id.as_ref()).or_default().push(diag);
+    }
+    groups
+}
+
+/// Build the grouped JSON object:
+/// `{ "ZPL1201": { "count": 5, "diagnostics": [...] }, ... }`.
+pub(crate) fn diagnostics_by_code_json(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let groups: serde_json::Map<String, serde_json::Value> = group_by_code(diagnostics)
+        .into_iter()
+        .map(|(code, diags)| {
+            (
+                code.to_string(),
+                serde_json::json!({ "count": diags.len(), "diagnostics": diags }),
+            )
+        })
+        .collect();
+    serde_json::Value::Object(groups)
+}
+
+/// Write diagnostics grouped by code to `w`: one heading per code with its
+/// count, followed by each occurrence as `file:line:col  message`.
+///
+/// With a `width`, explanations and messages wrap like pretty notes, with
+/// continuation lines indented under the occurrence list.
+fn write_diagnostics_grouped(
+    w: &mut dyn Write,
+    source: &Input,
+    filename: &str,
+    diagnostics: &[Diagnostic],
+    width: Option<usize>,
+) -> io::Result<()> {
+    use ariadne::Fmt;
+
+    let fit = |text: &str| fit_to_width(text, width).replace('\n', "\n    ");
+    for (code, diags) in group_by_code(diagnostics) {
+        let severity = &diags[0].severity;
+        let heading = format!("{severity}[{code}]").fg(severity_color(severity));
+        match zpl_toolchain_diagnostics::explain(code) {
+            Some(explanation) => writeln!(w, "{heading} ×{}: {}", diags.len(), fit(explanation))?,
+            None => writeln!(w, "{heading} ×{}", diags.len())?,
+        }
+        for diag in diags {
+            let message = fit(&diag.message);
+            match &diag.span {
+                Some(span) => {
+                    let (line, col) = source.line_col(span.start);
+                    writeln!(w, "  {filename}:{}:{}  {message}", line + 1, col + 1)?;
+                }
+                None => writeln!(w, "  {filename}  {message}")?,
+            }
+        }
+    }
+    Ok(())
 }
 
 // ── SARIF 2.1.0 rendering ───────────────────────────────────────────────
 
-/// Render diagnostics as SARIF 2.1.0 JSON to stdout.
+/// Build a SARIF 2.1.0 log for one or more artifacts.
 ///
 /// Maps toolchain diagnostics to SARIF results with rule IDs, severity levels,
 /// and physical locations (byte offsets or line/column when available).
 /// Suitable for GitHub Code Scanning, VS Code SARIF viewers, and other CI tools.
-pub(crate) fn render_diagnostics_sarif(
-    source: &str,
-    artifact_uri: &str,
-    diagnostics: &[Diagnostic],
-) {
-    let single = [SarifArtifactInput {
-        source,
-        artifact_uri,
-        diagnostics,
-    }];
-    render_diagnostics_sarif_multi(&single);
-}
-
-/// Render diagnostics for one or more artifacts as a single SARIF 2.1.0 log.
-pub(crate) fn render_diagnostics_sarif_multi(entries: &[SarifArtifactInput<'_>]) {
+fn sarif_diagnostics_log(entries: &[SarifArtifactInput<'_>]) -> serde_json::Value {
     let mut results: Vec<serde_json::Value> = Vec::new();
     let mut all_diagnostics: Vec<Diagnostic> = Vec::new();
     let mut artifacts: Vec<serde_json::Value> = Vec::new();
 
     for (index, entry) in entries.iter().enumerate() {
-        let line_index = LineIndex::new(entry.source);
         for d in entry.diagnostics {
             results.push(diagnostic_to_sarif_result(
                 d,
                 entry.artifact_uri,
                 entry.source,
-                &line_index,
                 index,
             ));
             all_diagnostics.push(d.clone());
         }
         artifacts.push(serde_json::json!({
             "location": { "uri": entry.artifact_uri },
-            "length": entry.source.len(),
+            "length": entry.source.bytes().len(),
             "sourceLanguage": "zpl",
         }));
     }
 
     let mut extra = serde_json::Map::new();
     extra.insert("artifacts".to_string(), serde_json::Value::Array(artifacts));
-    emit_sarif_run(
+    sarif_log(
         "zpl-toolchain",
         collect_unique_rules(&all_diagnostics),
         results,
         true,
         Some(extra),
     )
-    .expect("SARIF serialization cannot fail");
 }
 
 /// Convert a single diagnostic to a SARIF result object.
 fn diagnostic_to_sarif_result(
     d: &Diagnostic,
     artifact_uri: &str,
-    source: &str,
-    line_index: &LineIndex,
+    source: &Input,
     artifact_index: usize,
 ) -> serde_json::Value {
     let level = match d.severity {
@@ -258,10 +793,11 @@ fn diagnostic_to_sarif_result(
 
     // Add physical location when span is present.
     if let Some(span) = &d.span {
-        let start = span.start.min(source.len());
-        let end = span.end.min(source.len()).max(start);
-        let (start_line, start_col) = line_index.line_col(start);
-        let (end_line, end_col) = line_index.line_col(end);
+        let len = source.bytes().len();
+        let start = span.start.min(len);
+        let end = span.end.min(len).max(start);
+        let (start_line, start_col) = source.line_col(start);
+        let (end_line, end_col) = source.line_col(end);
 
         // SARIF uses 1-based line/column.
         let mut region = serde_json::json!({
@@ -274,10 +810,8 @@ fn diagnostic_to_sarif_result(
         });
 
         // Add snippet when non-empty span
-        if start < end
-            && end <= source.len()
-            && let Some(snippet) = source.get(start..end)
-        {
+        let snippet = source.span_text(Span::new(start, end));
+        if !snippet.is_empty() {
             region["snippet"] = serde_json::json!({"text": snippet});
         }
 
@@ -354,13 +888,15 @@ pub(crate) fn sarif_result(rule_id: &str, level: &str, message: String) -> serde
     })
 }
 
-pub(crate) fn emit_sarif_run(
+/// Build a single-run SARIF 2.1.0 log. Rules are sorted and deduplicated by
+/// id; `extra_run_fields` are merged into the run object.
+pub(crate) fn sarif_log(
     tool_name: &str,
     rules: Vec<serde_json::Value>,
     results: Vec<serde_json::Value>,
     execution_successful: bool,
     extra_run_fields: Option<serde_json::Map<String, serde_json::Value>>,
-) -> anyhow::Result<()> {
+) -> serde_json::Value {
     let mut rules = rules;
     rules.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
     rules.dedup_by(|a, b| a["id"] == b["id"]);
@@ -386,36 +922,30 @@ pub(crate) fn emit_sarif_run(
             run[key] = value;
         }
     }
-    let sarif = serde_json::json!({
+    serde_json::json!({
         "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
         "version": "2.1.0",
         "runs": [run]
-    });
-    println!("{}", serde_json::to_string_pretty(&sarif)?);
-    Ok(())
+    })
 }
 
-// ── Unified entry point ─────────────────────────────────────────────────
+// ── Unified entry points ────────────────────────────────────────────────
+
+/// Render a command [`Report`] in the given format to the process streams.
+pub(crate) fn render_report(report: &Report<'_>, format: Format) -> io::Result<()> {
+    format
+        .formatter()
+        .render_report(&mut io::stdout().lock(), &mut io::stderr().lock(), report)
+}
 
-/// Render diagnostics in the given format.
+/// Print only the per-severity counts to stdout, for `--count`.
 ///
-/// - `Pretty` → coloured output to stderr (source data stays on stdout).
-/// - `Json`   → JSON array to stdout.
-/// - `Sarif`  → SARIF 2.1.0 JSON to stdout.
-pub(crate) fn render_diagnostics(
-    source: &str,
-    filename: &str,
-    diagnostics: &[Diagnostic],
-    format: Format,
-) {
-    match format {
-        Format::Pretty => render_diagnostics_pretty(source, filename, diagnostics),
-        Format::Json => render_diagnostics_json(diagnostics),
-        Format::Sarif => {
-            let uri = artifact_uri_for_file(filename);
-            render_diagnostics_sarif(source, &uri, diagnostics);
-        }
-    }
+/// JSON output is `{"ok", "counts": {"errors", "warnings", "infos"}}`; the
+/// other formats print a single `errors=N warnings=M infos=K` line.
+pub(crate) fn render_counts(diagnostics: &[Diagnostic], format: Format) -> io::Result<()> {
+    format
+        .formatter()
+        .render_counts(&mut io::stdout().lock(), diagnostics)
 }
 
 /// Convert CLI file path to SARIF artifact URI.
@@ -430,10 +960,10 @@ fn artifact_uri_for_file(file: &str) -> String {
 
 // ── Summary line ────────────────────────────────────────────────────────
 
-/// Print a coloured summary line showing error/warning/info counts.
+/// Write a coloured summary line showing error/warning/info counts.
 ///
 /// Example: `2 errors, 1 warning, 0 info`
-pub(crate) fn print_summary(diagnostics: &[Diagnostic]) {
+fn write_summary(w: &mut dyn Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
     use ariadne::Fmt;
 
     let (mut errors, mut warnings, mut infos) = (0usize, 0usize, 0usize);
@@ -448,7 +978,7 @@ pub(crate) fn print_summary(diagnostics: &[Diagnostic]) {
 
     // Only print summary when there are diagnostics.
     if errors + warnings + infos == 0 {
-        return;
+        return Ok(());
     }
 
     let mut parts = Vec::new();
@@ -466,5 +996,362 @@ pub(crate) fn print_summary(diagnostics: &[Diagnostic]) {
     if infos > 0 {
         parts.push(format!("{}", format!("{infos} info").fg(Color::Blue)));
     }
-    eprintln!("{}", parts.join(", "));
+    writeln!(w, "{}", parts.join(", "))
+}
+
+/// Write the `errors=N warnings=M infos=K` line of [`render_counts`].
+fn write_counts_line(out: &mut dyn Write, diagnostics: &[Diagnostic]) -> io::Result<()> {
+    let summary = DiagnosticSummary::from_diagnostics(diagnostics);
+    writeln!(
+        out,
+        "errors={} warnings={} infos={}",
+        summary.errors, summary.warns, summary.infos
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::InputEncoding;
+    use zpl_toolchain_core::grammar::parser::parse_str;
+    use zpl_toolchain_print_client::PrinterInfo;
+
+    fn utf8(text: &str) -> Input {
+        Input::decode(text.as_bytes().to_vec(), InputEncoding::Utf8).unwrap()
+    }
+
+    /// Run `render` against in-memory streams and return `(stdout, stderr)`.
+    fn capture(
+        render: impl FnOnce(&mut dyn Write, &mut dyn Write) -> io::Result<()>,
+    ) -> (String, String) {
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        render(&mut out, &mut err).expect("render");
+        (
+            String::from_utf8(out).expect("utf-8 stdout"),
+            String::from_utf8(err).expect("utf-8 stderr"),
+        )
+    }
+
+    fn spanless_diagnostic() -> Diagnostic {
+        let mut context = BTreeMap::new();
+        context.insert("command".to_string(), "^XA".to_string());
+        Diagnostic::new("TEST0001", Severity::Warn, "something odd", None).with_context(context)
+    }
+
+    #[test]
+    fn json_formatter_matches_previous_output() {
+        let diags = [spanless_diagnostic()];
+        let (out, err) = capture(|out, err| {
+            JsonFormatter.render_diagnostics(out, err, &utf8(""), "label.zpl", &diags)
+        });
+        assert_eq!(
+            out,
+            r#"[
+  {
+    "id": "TEST0001",
+    "severity": "warn",
+    "message": "something odd",
+    "context": {
+      "command": "^XA"
+    }
+  }
+]
+"#
+        );
+        assert!(err.is_empty());
+
+        let result = parse_str("^XA^XZ");
+        let (out, err) = capture(|out, err| {
+            JsonFormatter.render_parse(out, err, &utf8(""), "label.zpl", &result)
+        });
+        assert_eq!(
+            out,
+            r#"{
+  "ast": {
+    "labels": [
+      {
+        "nodes": [
+          {
+            "args": [],
+            "code": "^XA",
+            "kind": "Command",
+            "span": {
+              "end": 3,
+              "start": 0
+            }
+          },
+          {
+            "args": [],
+            "code": "^XZ",
+            "kind": "Command",
+            "span": {
+              "end": 6,
+              "start": 3
+            }
+          }
+        ]
+      }
+    ]
+  },
+  "diagnostics": []
+}
+"#
+        );
+        assert!(err.is_empty());
+
+        let (out, _) = capture(|out, _| JsonFormatter.render_counts(out, &diags));
+        assert_eq!(
+            out,
+            r#"{
+  "counts": {
+    "errors": 0,
+    "infos": 0,
+    "warnings": 1
+  },
+  "ok": true
+}
+"#
+        );
+    }
+
+    #[test]
+    fn pretty_formatter_matches_previous_output() {
+        let diags = [spanless_diagnostic()];
+        let (out, err) = capture(|out, err| {
+            PrettyFormatter::default().render_diagnostics(out, err, &utf8(""), "label.zpl", &diags)
+        });
+        assert!(out.is_empty());
+        assert_eq!(
+            err,
+            "warning[TEST0001]: something odd\n  = note: command=^XA\n"
+        );
+
+        // AST to stdout; nothing on stderr for a clean parse.
+        let result = parse_str("^XA^XZ");
+        assert!(result.diagnostics.is_empty());
+        let (out, err) = capture(|out, err| {
+            PrettyFormatter::default().render_parse(out, err, &utf8(""), "label.zpl", &result)
+        });
+        assert_eq!(
+            out,
+            r#"{
+  "labels": [
+    {
+      "nodes": [
+        {
+          "args": [],
+          "code": "^XA",
+          "kind": "Command",
+          "span": {
+            "end": 3,
+            "start": 0
+          }
+        },
+        {
+          "args": [],
+          "code": "^XZ",
+          "kind": "Command",
+          "span": {
+            "end": 6,
+            "start": 3
+          }
+        }
+      ]
+    }
+  ]
+}
+"#
+        );
+        assert!(err.is_empty());
+
+        let (out, _) = capture(|out, _| PrettyFormatter::default().render_counts(out, &diags));
+        assert_eq!(out, "errors=0 warnings=1 infos=0\n");
+    }
+
+    #[test]
+    fn status_formatters_match_previous_output() {
+        let mut snapshot = PrinterSnapshot::default();
+        snapshot.info = Some(
+            PrinterInfo::parse(&[b"ZD421-300dpi,V84.20.18Z,12,8192KB".to_vec()])
+                .expect("valid ~HI"),
+        );
+
+        let (out, _) =
+            capture(|out, _| PrettyFormatter::default().render_status(out, "usb", &snapshot));
+        assert_eq!(
+            out,
+            "printer: usb
+status (~HS):
+  (no response)
+info (~HI):
+  model:    ZD421-300dpi
+  firmware: V84.20.18Z
+  dpi:      300
+  memory:   8192 KB
+memory (~HM):
+  (no response)
+"
+        );
+        let (sarif, _) =
+            capture(|out, _| SarifFormatter::default().render_status(out, "usb", &snapshot));
+        assert_eq!(sarif, out);
+
+        let (out, _) = capture(|out, _| JsonFormatter.render_status(out, "usb", &snapshot));
+        assert_eq!(
+            out,
+            r#"{
+  "complete": false,
+  "info": {
+    "dpi": 300,
+    "firmware": "V84.20.18Z",
+    "memory_kb": 8192,
+    "model": "ZD421-300dpi"
+  },
+  "memory": null,
+  "printer": "usb",
+  "status": null
+}
+"#
+        );
+    }
+
+    #[test]
+    fn report_renders_per_format() {
+        let diags = [spanless_diagnostic()];
+        let source = utf8("^XA");
+        let report = || {
+            Report::new(serde_json::json!({ "ok": true }))
+                .diagnostics(&source, "label.zpl", &diags)
+                .pretty(|out, err| {
+                    writeln!(out, "data")?;
+                    writeln!(err, "done")
+                })
+        };
+
+        let (out, err) = capture(|out, err| JsonFormatter.render_report(out, err, &report()));
+        assert_eq!(out, "{\n  \"ok\": true\n}\n");
+        assert!(err.is_empty());
+
+        // Diagnostics and summary first, then the pretty closure.
+        let (out, err) =
+            capture(|out, err| PrettyFormatter::default().render_report(out, err, &report()));
+        assert_eq!(out, "data\n");
+        assert!(
+            err.starts_with("warning[TEST0001]: something odd\n  = note: command=^XA\n"),
+            "{err}"
+        );
+        assert!(err.ends_with("warning\u{1b}[0m\ndone\n"), "{err:?}");
+
+        // SARIF prints the JSON document unless the report picks another view.
+        let (out, _) =
+            capture(|out, err| SarifFormatter::default().render_report(out, err, &report()));
+        assert_eq!(out, "{\n  \"ok\": true\n}\n");
+        let (out, _) = capture(|out, err| {
+            SarifFormatter::default().render_report(out, err, &report().sarif(SarifOutput::Pretty))
+        });
+        assert_eq!(out, "data\n");
+        let (out, _) = capture(|out, err| {
+            SarifFormatter::default().render_report(
+                out,
+                err,
+                &report().sarif(SarifOutput::Diagnostics),
+            )
+        });
+        let log: serde_json::Value = serde_json::from_str(&out).expect("SARIF JSON");
+        assert_eq!(log["runs"][0]["results"][0]["ruleId"], "TEST0001");
+        assert_eq!(
+            log["runs"][0]["artifacts"][0]["location"]["uri"],
+            "label.zpl"
+        );
+    }
+
+    #[test]
+    fn progress_report_is_pretty_only() {
+        let note = Report::note("sent: label.zpl");
+        for formatter in [
+            &JsonFormatter as &dyn OutputFormatter,
+            &SarifFormatter::default(),
+        ] {
+            let (out, err) = capture(|out, err| formatter.render_report(out, err, &note));
+            assert!(out.is_empty() && err.is_empty());
+        }
+        let (out, err) =
+            capture(|out, err| PrettyFormatter::default().render_report(out, err, &note));
+        assert!(out.is_empty());
+        assert_eq!(err, "sent: label.zpl\n");
+    }
+
+    #[test]
+    fn pretty_width_prefers_flag_then_columns_then_auto() {
+        assert_eq!(resolve_pretty_width(Some(60), Some("120")), Some(60));
+        assert_eq!(resolve_pretty_width(None, Some("120")), Some(120));
+        assert_eq!(resolve_pretty_width(None, Some(" 80 ")), Some(80));
+        assert_eq!(resolve_pretty_width(None, None), None);
+        // Unusable COLUMNS values fall back to auto-detect.
+        assert_eq!(resolve_pretty_width(None, Some("wide")), None);
+        assert_eq!(resolve_pretty_width(None, Some("0")), None);
+    }
+
+    #[test]
+    fn formatter_width_wraps_notes() {
+        let context = [("a", "one"), ("b", "two"), ("c", "three"), ("d", "four")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let diags = [
+            Diagnostic::new("TEST0001", Severity::Warn, "something odd", None)
+                .with_context(context),
+        ];
+        let pretty = PrettyFormatter { width: Some(32) };
+        let expected = "warning[TEST0001]: something odd\n  = note: a=one, b=two,\n          c=three, d=four\n";
+
+        let (_, err) =
+            capture(|out, err| pretty.render_diagnostics(out, err, &utf8(""), "label.zpl", &diags));
+        assert_eq!(err, expected);
+
+        let source = utf8("");
+        let report = Report::progress()
+            .diagnostics(&source, "label.zpl", &diags)
+            .sarif(SarifOutput::Pretty);
+        let sarif = SarifFormatter { pretty };
+        let (_, err) = capture(|out, err| sarif.render_report(out, err, &report));
+        assert!(err.starts_with(expected), "{err}");
+    }
+
+    #[test]
+    fn formatter_width_wraps_grouped_messages() {
+        let diags = [Diagnostic::new(
+            "TEST0001",
+            Severity::Warn,
+            "field data runs past the label edge",
+            None,
+        )];
+        let source = utf8("");
+        let report = Report::progress()
+            .diagnostics(&source, "label.zpl", &diags)
+            .group_by_code(true);
+        let pretty = PrettyFormatter { width: Some(32) };
+        let (_, err) = capture(|out, err| pretty.render_report(out, err, &report));
+        assert!(
+            err.contains("  label.zpl  field data runs past\n    the label edge\n"),
+            "{err}"
+        );
+
+        let (_, err) =
+            capture(|out, err| PrettyFormatter::default().render_report(out, err, &report));
+        assert!(
+            err.contains("  label.zpl  field data runs past the label edge\n"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn wrap_text_breaks_at_whitespace() {
+        assert_eq!(
+            wrap_text("one two three four", 9),
+            vec!["one two", "three", "four"]
+        );
+        assert_eq!(wrap_text("unbreakable", 4), vec!["unbreakable"]);
+        assert_eq!(wrap_text("", 10), vec![""]);
+    }
 }
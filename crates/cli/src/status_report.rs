@@ -0,0 +1,135 @@
+//! Standalone printer status report for `zpl status`.
+//!
+//! The report is built from a [`PrinterSnapshot`], so a sub-query that
+//! failed shows up as "no response" rather than hiding the others.
+
+use std::fmt::Write as _;
+
+use zpl_toolchain_print_client::PrinterSnapshot;
+
+const NO_RESPONSE: &str = "  (no response)";
+
+/// Render `snapshot` as the plain-text report printed by `zpl status`.
+pub(crate) fn render_status_report(printer: &str, snapshot: &PrinterSnapshot) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "printer: {printer}");
+
+    out.push_str("status (~HS):\n");
+    match &snapshot.status {
+        Some(hs) => {
+            let alerts: Vec<&str> = hs.alerts().iter().map(|alert| alert.name()).collect();
+            if alerts.is_empty() {
+                out.push_str("  health:           ok\n");
+            } else {
+                let _ = writeln!(out, "  alerts:           {}", alerts.join(", "));
+            }
+            let _ = writeln!(out, "  mode:             {:?}", hs.print_mode);
+            let _ = writeln!(out, "  labels remaining: {}", hs.labels_remaining);
+            let _ = writeln!(out, "  formats queued:   {}", hs.formats_in_buffer);
+            let _ = writeln!(out, "  label length:     {} dots", hs.label_length_dots);
+        }
+        None => {
+            out.push_str(NO_RESPONSE);
+            out.push('\n');
+        }
+    }
+
+    out.push_str("info (~HI):\n");
+    match &snapshot.info {
+        Some(pi) => {
+            let _ = writeln!(out, "  model:    {}", pi.model);
+            let _ = writeln!(out, "  firmware: {}", pi.firmware);
+            let _ = writeln!(out, "  dpi:      {}", pi.dpi);
+            let _ = writeln!(out, "  memory:   {} KB", pi.memory_kb);
+        }
+        None => {
+            out.push_str(NO_RESPONSE);
+            out.push('\n');
+        }
+    }
+
+    out.push_str("memory (~HM):\n");
+    match &snapshot.memory {
+        Some(mem) => {
+            let _ = writeln!(out, "  total:         {} KB", mem.total_kb);
+            let _ = writeln!(out, "  max available: {} KB", mem.max_available_kb);
+            let _ = writeln!(out, "  available:     {} KB", mem.available_kb);
+        }
+        None => {
+            out.push_str(NO_RESPONSE);
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// JSON object printed by `zpl status --output json`.
+pub(crate) fn status_report_json(printer: &str, snapshot: &PrinterSnapshot) -> serde_json::Value {
+    serde_json::json!({
+        "printer": printer,
+        "complete": snapshot.is_complete(),
+        "status": snapshot.status,
+        "info": snapshot.info,
+        "memory": snapshot.memory,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zpl_toolchain_print_client::{HostStatus, MemoryStatus, PrinterInfo};
+
+    fn snapshot() -> PrinterSnapshot {
+        let mut snapshot = PrinterSnapshot::default();
+        snapshot.status = Some(
+            HostStatus::parse(&[
+                b"030,1,0,1245,002,0,0,0,000,0,0,0".to_vec(),
+                b"000,0,0,0,0,2,0,0,00000000,0,000".to_vec(),
+                b"1234,0".to_vec(),
+            ])
+            .expect("valid ~HS"),
+        );
+        snapshot.info = Some(
+            PrinterInfo::parse(&[b"ZD421-300dpi,V84.20.18Z,12,8192KB".to_vec()])
+                .expect("valid ~HI"),
+        );
+        snapshot
+    }
+
+    #[test]
+    fn report_lists_each_section_and_marks_missing_ones() {
+        let report = render_status_report("192.168.1.55:9100", &snapshot());
+        assert_eq!(
+            report,
+            "printer: 192.168.1.55:9100\n\
+             status (~HS):\n\
+             \x20 alerts:           paper_out\n\
+             \x20 mode:             TearOff\n\
+             \x20 labels remaining: 0\n\
+             \x20 formats queued:   2\n\
+             \x20 label length:     1245 dots\n\
+             info (~HI):\n\
+             \x20 model:    ZD421-300dpi\n\
+             \x20 firmware: V84.20.18Z\n\
+             \x20 dpi:      300\n\
+             \x20 memory:   8192 KB\n\
+             memory (~HM):\n\
+             \x20 (no response)\n"
+        );
+    }
+
+    #[test]
+    fn report_json_keeps_missing_sections_as_null() {
+        let mut snapshot = snapshot();
+        snapshot.memory = Some(MemoryStatus::parse(&[b"1024,0780,0512".to_vec()]).unwrap());
+        let json = status_report_json("usb", &snapshot);
+        assert_eq!(json["complete"], true);
+        assert_eq!(json["memory"]["available_kb"], 512);
+
+        snapshot.status = None;
+        let json = status_report_json("usb", &snapshot);
+        assert_eq!(json["complete"], false);
+        assert!(json["status"].is_null());
+    }
+}
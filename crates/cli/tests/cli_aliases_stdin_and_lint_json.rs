@@ -189,3 +189,157 @@ fn lint_note_audience_problem_filters_contextual_notes() {
         "expected contextual notes to be filtered out in problem mode"
     );
 }
+
+const REPEATED_RANGE_ZPL: &str = "^XA\n^FO40000,10^FDa^FS\n^FO40000,20^FDb^FS\n^XZ\n";
+
+fn lint_json(path: &str, extra: &[&str]) -> serde_json::Value {
+    let tables = tables_path();
+    let mut args = vec!["lint", path, "--tables", &tables, "--output", "json"];
+    args.extend_from_slice(extra);
+    let output = zpl_cmd().args(&args).output().expect("run lint");
+    serde_json::from_slice(&output.stdout).expect("valid lint json")
+}
+
+#[test]
+fn lint_group_by_code_json_counts_per_code() {
+    let (_dir, path) = write_temp_zpl(REPEATED_RANGE_ZPL);
+    let json = lint_json(&path, &["--group-by-code"]);
+
+    assert_eq!(json["ok"], false);
+    assert!(json.get("diagnostics").is_none(), "{json}");
+    let group = &json["by_code"]["ZPL1201"];
+    assert_eq!(group["count"], 2);
+    let diags = group["diagnostics"].as_array().expect("diagnostics array");
+    assert_eq!(diags.len(), 2);
+    assert!(diags.iter().all(|d| d["id"] == "ZPL1201"));
+    // Source order is kept within a group.
+    assert!(diags[0]["span"]["start"].as_u64() < diags[1]["span"]["start"].as_u64());
+}
+
+#[test]
+fn lint_without_group_by_code_keeps_flat_output() {
+    let (_dir, path) = write_temp_zpl(REPEATED_RANGE_ZPL);
+    let json = lint_json(&path, &[]);
+
+    assert!(json.get("by_code").is_none(), "{json}");
+    let diags = json["diagnostics"].as_array().expect("diagnostics array");
+    assert_eq!(diags.iter().filter(|d| d["id"] == "ZPL1201").count(), 2);
+    assert_eq!(json["issues"], json["diagnostics"]);
+}
+
+#[test]
+fn lint_group_by_code_pretty_prints_heading_with_count() {
+    let (_dir, path) = write_temp_zpl(REPEATED_RANGE_ZPL);
+    let output = zpl_cmd()
+        .args([
+            "lint",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--output",
+            "pretty",
+            "--group-by-code",
+        ])
+        .output()
+        .expect("run lint");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("[ZPL1201]") && stderr.contains("×2"),
+        "{stderr}"
+    );
+    assert!(stderr.contains(&format!("{path}:2:")), "{stderr}");
+    assert!(stderr.contains(&format!("{path}:3:")), "{stderr}");
+}
+
+fn severity_tally(diagnostics: &serde_json::Value, severity: &str) -> usize {
+    diagnostics
+        .as_array()
+        .expect("diagnostics array")
+        .iter()
+        .filter(|d| d["severity"] == severity)
+        .count()
+}
+
+#[test]
+fn lint_count_json_matches_severity_tallies() {
+    let (_dir, path) = write_temp_zpl(REPEATED_RANGE_ZPL);
+    let full = lint_json(&path, &[]);
+    let tables = tables_path();
+    let output = zpl_cmd()
+        .args([
+            "lint", &path, "--tables", &tables, "--output", "json", "--count",
+        ])
+        .output()
+        .expect("run lint --count");
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid count json");
+    assert_eq!(json["ok"], full["ok"]);
+    let diags = &full["diagnostics"];
+    assert_eq!(json["counts"]["errors"], severity_tally(diags, "error"));
+    assert_eq!(json["counts"]["warnings"], severity_tally(diags, "warn"));
+    assert_eq!(json["counts"]["infos"], severity_tally(diags, "info"));
+    assert!(json.get("diagnostics").is_none(), "{json}");
+    assert_eq!(
+        output.status.success(),
+        severity_tally(diags, "error") == 0,
+        "exit code should still reflect errors"
+    );
+}
+
+#[test]
+fn lint_count_pretty_prints_only_the_tally_line() {
+    let (_dir, path) = write_temp_zpl(REPEATED_RANGE_ZPL);
+    let full = lint_json(&path, &[]);
+    let diags = &full["diagnostics"];
+    let output = zpl_cmd()
+        .args([
+            "lint",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--output",
+            "pretty",
+            "--count",
+        ])
+        .output()
+        .expect("run lint --count");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        format!(
+            "errors={} warnings={} infos={}\n",
+            severity_tally(diags, "error"),
+            severity_tally(diags, "warn"),
+            severity_tally(diags, "info"),
+        )
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("[ZPL1201]"), "{stderr}");
+    assert!(!stderr.contains("lint ok"), "{stderr}");
+}
+
+#[test]
+fn syntax_check_count_reports_zero_for_clean_input() {
+    let (_dir, path) = write_temp_zpl(SAMPLE_ZPL);
+    let output = zpl_cmd()
+        .args([
+            "syntax-check",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--output",
+            "pretty",
+            "--count",
+        ])
+        .output()
+        .expect("run syntax-check --count");
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "errors=0 warnings=0 infos=0\n"
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("syntax ok"), "{stderr}");
+}
@@ -68,3 +68,36 @@ fn explain_sarif_output_shape() {
     );
     assert!(sarif["runs"][0]["results"].is_array());
 }
+
+#[test]
+fn explain_exit_codes_lists_the_contract() {
+    let output = zpl_cmd()
+        .args(["--explain-exit-codes", "--output", "json"])
+        .output()
+        .expect("run --explain-exit-codes");
+
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid json");
+    let names: Vec<(i64, &str)> = json
+        .as_array()
+        .expect("array")
+        .iter()
+        .map(|e| (e["code"].as_i64().unwrap(), e["name"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            (0, "success"),
+            (1, "lint-errors"),
+            (2, "connection-failed"),
+            (3, "verify-failed"),
+            (4, "usage"),
+        ]
+    );
+
+    let conflict = zpl_cmd()
+        .args(["--explain-exit-codes", "explain", "ZPL1201"])
+        .output()
+        .expect("run --explain-exit-codes with a subcommand");
+    assert_eq!(conflict.status.code(), Some(4));
+}
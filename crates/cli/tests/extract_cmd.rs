@@ -0,0 +1,71 @@
+//! CLI tests for the `zpl extract` subcommand.
+
+use std::fs;
+use std::process::{Command, Output};
+
+use assert_cmd::cargo;
+
+const THREE_LABELS: &str =
+    "^XA^FO10,10^FDOne^FS^XZ\n^XA\n^FO20,20^FDTwo^FS\n^XZ\n^XA^FO30,30^FDThree^FS^XZ\n";
+
+fn zpl_cmd() -> Command {
+    Command::new(cargo::cargo_bin!("zpl"))
+}
+
+fn tables_path() -> String {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../generated/parser_tables.json");
+    path.to_string_lossy().to_string()
+}
+
+fn extract(extra: &[&str]) -> Output {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("labels.zpl");
+    fs::write(&path, THREE_LABELS).expect("write temp zpl");
+    let path = path.to_string_lossy().to_string();
+    zpl_cmd()
+        .args(["extract", &path, "--tables", &tables_path()])
+        .args(extra)
+        .output()
+        .expect("run extract")
+}
+
+#[test]
+fn extract_prints_the_selected_label_verbatim() {
+    // No --output: stdout is a pipe here, as in `zpl extract batch.zpl --label 2 > second.zpl`.
+    let output = extract(&["--label", "2"]);
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "^XA\n^FO20,20^FDTwo^FS\n^XZ\n"
+    );
+
+    let last = extract(&["--label", "-1"]);
+    assert_eq!(
+        String::from_utf8_lossy(&last.stdout),
+        "^XA^FO30,30^FDThree^FS^XZ\n"
+    );
+}
+
+#[test]
+fn extract_formatted_json_reports_position() {
+    let output = extract(&["--label", "-2", "--formatted", "--output", "json"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["label"], 2);
+    assert_eq!(json["labels"], 3);
+    assert_eq!(json["zpl"], "^XA\n^FO20,20\n^FDTwo\n^FS\n^XZ\n");
+}
+
+#[test]
+fn extract_out_of_range_label_is_a_usage_error() {
+    let output = extract(&["--label", "4"]);
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("label 4 not found"), "{stderr}");
+    assert!(stderr.contains("contains 3 label(s)"), "{stderr}");
+}
@@ -227,3 +227,169 @@ fn format_semicolon_is_treated_as_plain_data() {
         "expected semicolon to remain part of field data, got:\n{stdout}"
     );
 }
+
+#[test]
+fn format_piped_without_output_flag_prints_raw_zpl() {
+    let (_dir, path) = write_temp_zpl("^XA\n^FO10,10^FDA^FS\n^XZ\n");
+
+    // Test stdout is a pipe, as in `zpl format label.zpl > out.zpl`.
+    let output = zpl_cmd()
+        .args(["format", &path, "--tables", &tables_path()])
+        .output()
+        .expect("run format");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "^XA\n^FO10,10\n^FDA\n^FS\n^XZ\n"
+    );
+
+    // --check still reports a status, as JSON when piped.
+    let check = zpl_cmd()
+        .args(["format", &path, "--tables", &tables_path(), "--check"])
+        .output()
+        .expect("run format --check");
+    let json: serde_json::Value = serde_json::from_slice(&check.stdout).expect("valid JSON");
+    assert_eq!(json["mode"], "check");
+}
+
+#[test]
+fn format_preserve_blank_lines_keeps_group_separators() {
+    let input = "^XA\n^PW609\n\n\n^FO30,30^FDA^FS\n^XZ\n";
+    let (_dir, path) = write_temp_zpl(input);
+
+    let output = zpl_cmd()
+        .args([
+            "format",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--preserve-blank-lines",
+            "--output",
+            "json",
+        ])
+        .output()
+        .expect("run format with --preserve-blank-lines");
+
+    assert!(
+        output.status.success(),
+        "expected format to succeed, stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid format json");
+    let formatted = json["formatted"]
+        .as_str()
+        .expect("formatted string in json output");
+    assert!(
+        formatted.contains("^PW609\n\n^FO30,30\n"),
+        "expected a single blank line between groups, got:\n{formatted}"
+    );
+}
+
+#[test]
+fn format_wrap_bare_commands_adds_label_bounds() {
+    let (_dir, path) = write_temp_zpl("^FO30,30^FDA^FS\n");
+
+    let output = zpl_cmd()
+        .args([
+            "format",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--wrap-bare-commands",
+            "--output",
+            "json",
+        ])
+        .output()
+        .expect("run format with --wrap-bare-commands");
+
+    assert!(
+        output.status.success(),
+        "expected format to succeed, stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid format json");
+    let formatted = json["formatted"]
+        .as_str()
+        .expect("formatted string in json output");
+    assert_eq!(formatted, "^XA\n^FO30,30\n^FDA\n^FS\n^XZ\n");
+}
+
+#[test]
+fn format_line_ending_preserve_keeps_crlf() {
+    let input = "^XA\r\n^FO30,30^FDA^FS\r\n^XZ\r\n";
+    let (_dir, path) = write_temp_zpl(input);
+
+    let run = |line_ending: &str| {
+        let output = zpl_cmd()
+            .args([
+                "format",
+                &path,
+                "--tables",
+                &tables_path(),
+                "--line-ending",
+                line_ending,
+                "--output",
+                "json",
+            ])
+            .output()
+            .expect("run format with --line-ending");
+        assert!(
+            output.status.success(),
+            "expected format to succeed, stderr={}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("valid format json");
+        json["formatted"]
+            .as_str()
+            .expect("formatted string in json output")
+            .to_string()
+    };
+
+    let preserved = run("preserve");
+    assert!(
+        preserved.contains("^XA\r\n") && !preserved.replace("\r\n", "").contains('\n'),
+        "expected CRLF-only output, got: {preserved:?}"
+    );
+    let lf = run("lf");
+    assert!(!lf.contains('\r'), "expected LF-only output, got: {lf:?}");
+}
+
+#[test]
+fn format_check_idempotent_passes_for_sample() {
+    let sample = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../samples/shipping_label.zpl")
+        .to_string_lossy()
+        .into_owned();
+
+    let output = zpl_cmd()
+        .args([
+            "format",
+            &sample,
+            "--tables",
+            &tables_path(),
+            "--indent",
+            "label",
+            "--compaction",
+            "field",
+            "--check-idempotent",
+            "--output",
+            "pretty",
+        ])
+        .output()
+        .expect("run format with --check-idempotent");
+
+    assert!(
+        output.status.success(),
+        "expected idempotency check to pass, stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stdout).starts_with("^XA"),
+        "formatted output should still be printed"
+    );
+}
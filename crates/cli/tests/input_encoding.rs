@@ -0,0 +1,118 @@
+//! CLI tests for `--input-encoding` with legacy single-byte ZPL files.
+
+use std::fs;
+use std::process::Command;
+
+use assert_cmd::cargo;
+
+fn zpl_cmd() -> Command {
+    Command::new(cargo::cargo_bin!("zpl"))
+}
+
+fn write_temp_bytes(dir: &tempfile::TempDir, name: &str, content: &[u8]) -> String {
+    let path = dir.path().join(name);
+    fs::write(&path, content).expect("write temp zpl");
+    path.to_string_lossy().to_string()
+}
+
+fn tables_path() -> String {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../generated/parser_tables.json");
+    path.to_string_lossy().to_string()
+}
+
+/// Latin-1 label with accented field data and a field interrupted by `^XZ`.
+const LATIN1_LABEL: &[u8] = b"^XA\n^FO10,10^FDCaf\xe9 cr\xe8me^FS\n^FO10,50^FDx\n^XZ\n";
+
+#[test]
+fn utf8_default_rejects_latin1_bytes_with_a_hint() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_temp_bytes(&dir, "label.zpl", LATIN1_LABEL);
+
+    let output = zpl_cmd()
+        .args([
+            "lint",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--output",
+            "json",
+        ])
+        .output()
+        .expect("run lint");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("--input-encoding") || stderr.contains("--input-encoding"),
+        "stdout={stdout} stderr={stderr}"
+    );
+}
+
+#[test]
+fn latin1_input_lints_with_spans_into_file_bytes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_temp_bytes(&dir, "label.zpl", LATIN1_LABEL);
+
+    let output = zpl_cmd()
+        .args([
+            "lint",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--input-encoding",
+            "latin1",
+            "--output",
+            "json",
+        ])
+        .output()
+        .expect("run lint");
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let diags = json["diagnostics"].as_array().expect("diagnostics array");
+    let interrupted = diags
+        .iter()
+        .find(|d| d["id"] == "ZPL.PARSER.1203")
+        .unwrap_or_else(|| panic!("expected interrupted field diagnostic: {json}"));
+
+    let start = interrupted["span"]["start"].as_u64().unwrap() as usize;
+    assert!(
+        LATIN1_LABEL[start..].starts_with(b"^XZ"),
+        "span start {start}"
+    );
+}
+
+#[test]
+fn binary_payload_bytes_survive_format() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut label = b"^XA\n^FO0,0^GFB,4,4,1,".to_vec();
+    let payload = [0x00, 0xFF, 0x80, 0x9B];
+    label.extend_from_slice(&payload);
+    label.extend_from_slice(b"^FS\n^XZ\n");
+
+    for encoding in ["latin1", "cp437"] {
+        let path = write_temp_bytes(&dir, &format!("{encoding}.zpl"), &label);
+        let output = zpl_cmd()
+            .args([
+                "format",
+                &path,
+                "--tables",
+                &tables_path(),
+                "--input-encoding",
+                encoding,
+                "--output",
+                "pretty",
+            ])
+            .output()
+            .expect("run format");
+        assert!(
+            output.status.success(),
+            "{encoding}: stderr={}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(
+            output.stdout.windows(payload.len()).any(|w| w == payload),
+            "{encoding}: payload bytes changed: {:?}",
+            output.stdout
+        );
+    }
+}
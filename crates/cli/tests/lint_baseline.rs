@@ -0,0 +1,153 @@
+//! CLI regression tests for `lint --baseline` / `--update-baseline`.
+
+use std::fs;
+use std::process::Command;
+
+use assert_cmd::cargo;
+
+/// Label with a pre-existing error (^BY module width out of range).
+const LEGACY_ZPL: &str = "^XA\n^BY20\n^FO50,50^A0N,30,30^FDHello^FS\n^XZ\n";
+
+fn zpl_cmd() -> Command {
+    Command::new(cargo::cargo_bin!("zpl"))
+}
+
+fn tables_path() -> String {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../generated/parser_tables.json");
+    path.to_string_lossy().to_string()
+}
+
+fn lint_json(zpl_path: &str, extra: &[&str]) -> (bool, serde_json::Value) {
+    let tables = tables_path();
+    let mut args = vec![
+        "--output",
+        "json",
+        "lint",
+        zpl_path,
+        "--tables",
+        tables.as_str(),
+    ];
+    args.extend_from_slice(extra);
+    let output = zpl_cmd().args(&args).output().expect("run lint");
+    let json = serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "lint output should be JSON ({e}): stdout={} stderr={}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+    (output.status.success(), json)
+}
+
+fn diagnostic_ids(json: &serde_json::Value) -> Vec<String> {
+    json["diagnostics"]
+        .as_array()
+        .expect("diagnostics array")
+        .iter()
+        .map(|d| d["id"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn update_baseline_records_current_diagnostics() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let zpl = dir.path().join("legacy.zpl");
+    let baseline = dir.path().join("baseline.json");
+    fs::write(&zpl, LEGACY_ZPL).expect("write zpl");
+    let zpl = zpl.to_string_lossy().to_string();
+    let baseline = baseline.to_string_lossy().to_string();
+
+    let (ok, json) = lint_json(&zpl, &["--baseline", &baseline, "--update-baseline"]);
+    assert!(ok, "--update-baseline should succeed: {json}");
+    let recorded = json["recorded"].as_u64().expect("recorded count");
+    assert!(recorded > 0, "legacy label should record diagnostics");
+
+    let stored: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&baseline).expect("read baseline"))
+            .expect("baseline json");
+    assert_eq!(stored["version"], 1);
+    let entries = stored["diagnostics"].as_array().expect("entries");
+    assert_eq!(entries.len() as u64, recorded);
+    assert!(
+        entries
+            .iter()
+            .all(|e| { e["fingerprint"].as_str().is_some_and(|fp| fp.len() == 16) })
+    );
+}
+
+#[test]
+fn unchanged_file_reports_no_new_diagnostics() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let zpl = dir.path().join("legacy.zpl");
+    let baseline = dir.path().join("baseline.json");
+    fs::write(&zpl, LEGACY_ZPL).expect("write zpl");
+    let zpl = zpl.to_string_lossy().to_string();
+    let baseline = baseline.to_string_lossy().to_string();
+
+    let (ok, json) = lint_json(&zpl, &[]);
+    assert!(!ok, "legacy label should fail without a baseline: {json}");
+
+    lint_json(&zpl, &["--baseline", &baseline, "--update-baseline"]);
+    let (ok, json) = lint_json(&zpl, &["--baseline", &baseline]);
+    assert!(
+        ok,
+        "unchanged file should pass against its baseline: {json}"
+    );
+    assert!(diagnostic_ids(&json).is_empty(), "{json}");
+    assert_eq!(json["ok"], true);
+    assert!(json["baselined"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn baseline_survives_unrelated_line_shifts() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let zpl = dir.path().join("legacy.zpl");
+    let baseline = dir.path().join("baseline.json");
+    fs::write(&zpl, LEGACY_ZPL).expect("write zpl");
+    let zpl_path = zpl.to_string_lossy().to_string();
+    let baseline = baseline.to_string_lossy().to_string();
+    lint_json(&zpl_path, &["--baseline", &baseline, "--update-baseline"]);
+
+    fs::write(&zpl, format!("^FX header comment\n{LEGACY_ZPL}")).expect("rewrite zpl");
+    let (ok, json) = lint_json(&zpl_path, &["--baseline", &baseline]);
+    assert!(
+        ok,
+        "inserted lines should not create new diagnostics: {json}"
+    );
+}
+
+#[test]
+fn added_error_is_reported_despite_baseline() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let zpl = dir.path().join("legacy.zpl");
+    let baseline = dir.path().join("baseline.json");
+    fs::write(&zpl, LEGACY_ZPL).expect("write zpl");
+    let zpl_path = zpl.to_string_lossy().to_string();
+    let baseline = baseline.to_string_lossy().to_string();
+    lint_json(&zpl_path, &["--baseline", &baseline, "--update-baseline"]);
+
+    // A second out-of-range ^BY is a new occurrence even though the first is known.
+    fs::write(
+        &zpl,
+        "^XA\n^BY20\n^FO50,50^A0N,30,30^FDHello^FS\n^XZ\n^XA\n^BY20\n^FO50,50^A0N,30,30^FDWorld^FS\n^XZ\n",
+    )
+    .expect("rewrite zpl");
+    let (ok, json) = lint_json(&zpl_path, &["--baseline", &baseline]);
+    assert!(!ok, "new error should fail lint: {json}");
+    assert_eq!(json["ok"], false);
+    assert!(
+        diagnostic_ids(&json).iter().any(|id| id == "ZPL1201"),
+        "new ^BY error should be reported: {json}"
+    );
+}
+
+#[test]
+fn update_baseline_requires_baseline_path() {
+    let output = zpl_cmd()
+        .args(["lint", "-", "--update-baseline"])
+        .output()
+        .expect("run lint");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--baseline"));
+}
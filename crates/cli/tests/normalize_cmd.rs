@@ -0,0 +1,96 @@
+//! CLI tests for the `zpl normalize` subcommand.
+
+use std::fs;
+use std::process::Command;
+
+use assert_cmd::cargo;
+
+fn zpl_cmd() -> Command {
+    Command::new(cargo::cargo_bin!("zpl"))
+}
+
+fn write_temp_zpl(dir: &tempfile::TempDir, name: &str, content: &str) -> String {
+    let path = dir.path().join(name);
+    fs::write(&path, content).expect("write temp zpl");
+    path.to_string_lossy().to_string()
+}
+
+fn tables_path() -> String {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../generated/parser_tables.json");
+    path.to_string_lossy().to_string()
+}
+
+fn normalize(path: &str) -> String {
+    let output = zpl_cmd()
+        .args([
+            "normalize",
+            path,
+            "--tables",
+            &tables_path(),
+            "--output",
+            "pretty",
+        ])
+        .output()
+        .expect("run normalize");
+    assert!(
+        output.status.success(),
+        "expected normalize to succeed, stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("utf-8 stdout")
+}
+
+#[test]
+fn normalize_equivalent_labels_produce_identical_output() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let loose = write_temp_zpl(
+        &dir,
+        "loose.zpl",
+        "^XA\r\n  ^FO030,030\r\n  ^FH^FD_7e Widget^FS\r\n\r\n^XZ\r\n",
+    );
+    let tight = write_temp_zpl(&dir, "tight.zpl", "^XA^FO30,30^FH^FD_7E Widget^FS^XZ");
+
+    let expected = "^XA\n^FO30,30\n^FH\n^FD_7E Widget\n^FS\n^XZ\n";
+    assert_eq!(normalize(&loose), expected);
+    assert_eq!(normalize(&tight), expected);
+}
+
+#[test]
+fn normalize_piped_without_output_flag_prints_raw_zpl() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_temp_zpl(&dir, "label.zpl", "^XA\n  ^FO010,010^FDA^FS\n^XZ\n");
+
+    // Test stdout is a pipe, as in `zpl normalize label.zpl > label.golden.zpl`.
+    let output = zpl_cmd()
+        .args(["normalize", &path, "--tables", &tables_path()])
+        .output()
+        .expect("run normalize");
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "^XA\n^FO10,10\n^FDA\n^FS\n^XZ\n"
+    );
+}
+
+#[test]
+fn normalize_json_reports_normalized_text() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_temp_zpl(&dir, "label.zpl", "^XA\n  ^FO010,010^FDA^FS\n^XZ\n");
+
+    let output = zpl_cmd()
+        .args([
+            "normalize",
+            &path,
+            "--tables",
+            &tables_path(),
+            "--output",
+            "json",
+        ])
+        .output()
+        .expect("run normalize json");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    assert_eq!(json["normalized"], "^XA\n^FO10,10\n^FDA\n^FS\n^XZ\n");
+}
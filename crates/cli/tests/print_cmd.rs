@@ -1,6 +1,8 @@
 //! CLI tests for the `zpl print` subcommand.
 
 use std::fs;
+use std::io::Read;
+use std::net::TcpListener;
 use std::process::{Command, Output};
 
 use assert_cmd::cargo;
@@ -66,6 +68,10 @@ fn print_help_shows_flags() {
     assert!(stdout.contains("--verify"), "missing --verify flag in help");
     assert!(stdout.contains("--info"), "missing --info flag in help");
     assert!(stdout.contains("--wait"), "missing --wait flag in help");
+    assert!(
+        stdout.contains("--coalesce"),
+        "missing --coalesce flag in help"
+    );
     #[cfg(feature = "serial")]
     {
         assert!(stdout.contains("--serial"), "missing --serial flag in help");
@@ -103,8 +109,8 @@ fn print_requires_files() {
     cmd.args(["print", "--printer", "192.168.1.100"]);
     let output = cmd.output().unwrap();
 
-    // clap should error with exit code 2
-    assert_eq!(output.status.code(), Some(2));
+    // clap usage errors map to the usage exit code (4)
+    assert_eq!(output.status.code(), Some(4));
 
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
@@ -121,7 +127,7 @@ fn print_requires_printer_flag() {
     cmd.args(["print", &path]);
     let output = cmd.output().unwrap();
 
-    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(output.status.code(), Some(4));
 
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
@@ -146,7 +152,7 @@ fn print_verify_conflicts_with_dry_run() {
         .output()
         .unwrap();
 
-    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(output.status.code(), Some(4));
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
         stderr.contains("cannot be used with")
@@ -337,7 +343,7 @@ fn print_dry_run_mac_without_serial_shows_helpful_error() {
         .output()
         .unwrap();
 
-    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.status.code(), Some(4));
     let stderr = error_text(&output);
     assert!(
         stderr.contains("Bluetooth MAC address") && stderr.contains("--serial"),
@@ -825,3 +831,49 @@ fn print_connection_failure_sarif() {
         serde_json::from_str(&stdout).expect("connection failure should still emit SARIF JSON");
     assert_eq!(sarif["version"].as_str(), Some("2.1.0"));
 }
+
+#[test]
+fn print_send_failure_json_names_the_failed_file() {
+    let (_first_dir, first) = write_temp_zpl(SAMPLE_ZPL);
+    // Larger than the loopback socket buffers, so the write cannot complete
+    // before the printer side drops the connection.
+    let big = format!("^XA^FO0,0^FD{}^FS^XZ\n", "A".repeat(32 * 1024 * 1024));
+    let (_second_dir, second) = write_temp_zpl(&big);
+
+    // Accept one connection, read the first file, then hang up.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut received = vec![0; SAMPLE_ZPL.len()];
+        stream.read_exact(&mut received).unwrap();
+        received
+    });
+
+    let output = zpl_cmd()
+        .args([
+            "print",
+            &first,
+            &second,
+            "--printer",
+            &addr,
+            "--no-lint",
+            "--output",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(server.join().unwrap(), SAMPLE_ZPL.as_bytes());
+
+    assert!(!output.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("send failure should emit JSON");
+    assert_eq!(json["error"], "send_failed");
+    assert_eq!(json["file"], second.as_str());
+    assert_eq!(json["files_sent"], serde_json::json!([first]));
+    let message = json["message"].as_str().unwrap();
+    assert!(
+        message.starts_with(&format!("failed to send '{second}': ")),
+        "{message}"
+    );
+}
@@ -0,0 +1,58 @@
+//! CLI tests for the `zpl render` subcommand (requires the `render` feature).
+#![cfg(feature = "render")]
+
+use std::fs;
+use std::process::Command;
+
+use assert_cmd::cargo;
+
+fn zpl_cmd() -> Command {
+    Command::new(cargo::cargo_bin!("zpl"))
+}
+
+fn tables_path() -> String {
+    let path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../generated/parser_tables.json");
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn render_graphic_box_label_writes_png_with_label_dimensions() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let zpl_path = dir.path().join("box.zpl");
+    let png_path = dir.path().join("box.png");
+    fs::write(&zpl_path, "^XA^PW400^LL200^FO20,20^GB100,50,3^FS^XZ").expect("write temp zpl");
+
+    let output = zpl_cmd()
+        .args([
+            "render",
+            zpl_path.to_str().unwrap(),
+            "-o",
+            png_path.to_str().unwrap(),
+            "--tables",
+            &tables_path(),
+            "--output",
+            "json",
+        ])
+        .output()
+        .expect("run render");
+    assert!(
+        output.status.success(),
+        "render failed, stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let png = fs::read(&png_path).expect("read png");
+    assert!(
+        png.starts_with(b"\x89PNG\r\n\x1a\n"),
+        "missing PNG signature"
+    );
+    assert_eq!(&png[12..16], b"IHDR");
+    let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+    let height = u32::from_be_bytes(png[20..24].try_into().unwrap());
+    assert_eq!((width, height), (400, 200));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json output");
+    assert_eq!(json["width"], 400);
+    assert_eq!(json["bytes"], png.len());
+}
@@ -0,0 +1,42 @@
+//! CLI tests for the `zpl schema` subcommand.
+
+use std::process::Command;
+
+use assert_cmd::cargo;
+
+fn zpl_cmd() -> Command {
+    Command::new(cargo::cargo_bin!("zpl"))
+}
+
+fn schema_json(kind: &str) -> serde_json::Value {
+    let output = zpl_cmd()
+        .args(["schema", kind])
+        .output()
+        .expect("run schema command");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    serde_json::from_str(&stdout).expect("valid json")
+}
+
+#[test]
+fn schema_diagnostic_describes_diagnostic_fields() {
+    let schema = schema_json("diagnostic");
+    assert_eq!(schema["title"], "Diagnostic");
+    let required = schema["required"].as_array().expect("required list");
+    for field in ["id", "severity", "message"] {
+        assert!(
+            required.iter().any(|r| r == field),
+            "missing required {field}"
+        );
+    }
+    assert!(schema["properties"]["span"].is_object());
+}
+
+#[test]
+fn schema_result_kinds_emit_titled_schemas() {
+    assert_eq!(
+        schema_json("validation-result")["title"],
+        "ValidationResult"
+    );
+    assert_eq!(schema_json("parse-result")["title"], "ParseResult");
+}
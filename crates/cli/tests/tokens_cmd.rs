@@ -0,0 +1,42 @@
+//! CLI tests for the `zpl tokens` subcommand.
+
+use std::fs;
+use std::process::Command;
+
+use assert_cmd::cargo;
+
+fn zpl_cmd() -> Command {
+    Command::new(cargo::cargo_bin!("zpl"))
+}
+
+#[test]
+fn tokens_json_lists_kinds_in_order() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("label.zpl");
+    fs::write(&path, "^XA^FO10,20^XZ\n").expect("write temp zpl");
+
+    let output = zpl_cmd()
+        .args(["tokens", path.to_str().unwrap(), "--output", "json"])
+        .output()
+        .expect("run tokens");
+    assert!(
+        output.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("valid JSON");
+    let tokens = json.as_array().expect("token array");
+    let kinds: Vec<&str> = tokens
+        .iter()
+        .map(|t| t["kind"].as_str().expect("kind"))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "leader", "value", "leader", "value", "comma", "value", "leader", "value", "newline",
+        ]
+    );
+    assert_eq!(tokens[4]["start"], 8);
+    assert_eq!(tokens[4]["end"], 9);
+}
@@ -0,0 +1,314 @@
+//! Zebra alternative compression scheme (ACS) for ASCII hex graphic data.
+//!
+//! ACS is the run-length encoding the printer accepts inside ASCII hex graphic
+//! payloads (`^GFA`, `~DG`). It is not zlib: it only shortens the hex text.
+//!
+//! - `G`–`Y` repeat the following hex digit 1–19 times; `g`–`z` repeat it
+//!   20–400 times (multiples of 20). Count characters add up, so `hB` is
+//!   `B` repeated 42 times.
+//! - `,` fills the rest of the row with `0` (white).
+//! - `!` fills the rest of the row with `F` (black).
+//! - `:` repeats the previous row.
+//!
+//! Labels with large white areas typically shrink several times over.
+
+/// An ACS decoding error at a specific byte offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GfRleError {
+    /// Byte offset within the compressed data.
+    pub offset: usize,
+    /// Human-readable description of the error.
+    pub message: String,
+}
+
+/// Largest repeat count expressible with one lowercase and one uppercase
+/// count character (`z` = 400, `Y` = 19).
+const MAX_COUNT_GROUP: usize = 419;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Compress graphic rows into ACS-encoded ASCII hex.
+///
+/// `rows` holds the bitmap row by row, `row_bytes` bytes per row. A trailing
+/// partial row is padded with zero (white) bytes, which is how the printer
+/// treats a short row. Returns an empty string if `row_bytes` is zero.
+pub fn compress_gf_rle(rows: &[u8], row_bytes: usize) -> String {
+    let mut out = String::new();
+    if row_bytes == 0 {
+        return out;
+    }
+
+    let mut prev: Option<Vec<u8>> = None;
+    for row in rows.chunks(row_bytes) {
+        let mut hex = Vec::with_capacity(row_bytes * 2);
+        for &b in row {
+            hex.push(HEX_DIGITS[usize::from(b >> 4)]);
+            hex.push(HEX_DIGITS[usize::from(b & 0x0F)]);
+        }
+        hex.resize(row_bytes * 2, b'0');
+
+        if prev.as_ref() == Some(&hex) {
+            out.push(':');
+            continue;
+        }
+        encode_row(&hex, &mut out);
+        prev = Some(hex);
+    }
+    out
+}
+
+/// Decompress ACS-encoded ASCII hex into raw graphic bytes.
+///
+/// Plain (uncompressed) ASCII hex is valid ACS input, so this also decodes
+/// ordinary `^GFA` data. Whitespace is ignored.
+///
+/// # Errors
+///
+/// Returns every problem found: unknown characters, a row overflowing
+/// `row_bytes`, `:` without a previous row, a dangling repeat count, or an
+/// incomplete final row.
+pub fn decompress_gf_rle(data: &str, row_bytes: usize) -> Result<Vec<u8>, Vec<GfRleError>> {
+    let row_len = row_bytes * 2;
+    let mut errors = Vec::new();
+    let mut hex: Vec<u8> = Vec::with_capacity(data.len());
+    let mut row: Vec<u8> = Vec::with_capacity(row_len);
+    let mut prev: Option<Vec<u8>> = None;
+    let mut count = 0usize;
+
+    let finish_row = |row: &mut Vec<u8>, hex: &mut Vec<u8>, prev: &mut Option<Vec<u8>>| {
+        hex.extend_from_slice(row);
+        *prev = Some(std::mem::take(row));
+    };
+
+    for (offset, b) in data.bytes().enumerate() {
+        let err = |message: String| GfRleError { offset, message };
+        match b {
+            b if b.is_ascii_whitespace() => {}
+            b'G'..=b'Y' => count += usize::from(b - b'G') + 1,
+            b'g'..=b'z' => count += (usize::from(b - b'g') + 1) * 20,
+            b if b.is_ascii_hexdigit() => {
+                let n = count.max(1);
+                count = 0;
+                if row.len() + n > row_len {
+                    errors.push(err(format!(
+                        "run of {} '{}' overflows the {}-byte row",
+                        n, b as char, row_bytes
+                    )));
+                    continue;
+                }
+                row.extend(std::iter::repeat_n(b.to_ascii_uppercase(), n));
+                if row.len() == row_len {
+                    finish_row(&mut row, &mut hex, &mut prev);
+                }
+            }
+            b',' | b'!' => {
+                if count != 0 {
+                    errors.push(err(format!(
+                        "repeat count before '{}' has no hex digit",
+                        b as char
+                    )));
+                    count = 0;
+                }
+                let fill = if b == b',' { b'0' } else { b'F' };
+                row.resize(row_len, fill);
+                finish_row(&mut row, &mut hex, &mut prev);
+            }
+            b':' => {
+                if count != 0 || !row.is_empty() {
+                    errors.push(err("':' must start a row".into()));
+                    count = 0;
+                    row.clear();
+                    continue;
+                }
+                match &prev {
+                    Some(p) => hex.extend_from_slice(p),
+                    None => errors.push(err("':' has no previous row to repeat".into())),
+                }
+            }
+            _ => errors.push(err(format!(
+                "unexpected character '{}' in compressed graphic data",
+                b as char
+            ))),
+        }
+    }
+
+    if count != 0 {
+        errors.push(GfRleError {
+            offset: data.len(),
+            message: "repeat count at end of data has no hex digit".into(),
+        });
+    }
+    if !row.is_empty() {
+        errors.push(GfRleError {
+            offset: data.len(),
+            message: format!(
+                "incomplete final row ({} of {} hex digits)",
+                row.len(),
+                row_len
+            ),
+        });
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(hex
+        .chunks_exact(2)
+        .map(|pair| (hex_value(pair[0]) << 4) | hex_value(pair[1]))
+        .collect())
+}
+
+/// Encode one row of uppercase hex digits, appending to `out`.
+fn encode_row(hex: &[u8], out: &mut String) {
+    // A trailing run of 0 or F collapses to a single fill marker.
+    let last = hex[hex.len() - 1];
+    let (body, fill) = match last {
+        b'0' | b'F' => {
+            let end = hex.iter().rposition(|&c| c != last).map_or(0, |i| i + 1);
+            (&hex[..end], Some(if last == b'0' { ',' } else { '!' }))
+        }
+        _ => (hex, None),
+    };
+
+    let mut i = 0;
+    while i < body.len() {
+        let c = body[i];
+        let run = body[i..].iter().take_while(|&&x| x == c).count();
+        push_run(c, run, out);
+        i += run;
+    }
+    if let Some(fill) = fill {
+        out.push(fill);
+    }
+}
+
+/// Append `digit` repeated `run` times using ACS count characters.
+fn push_run(digit: u8, mut run: usize, out: &mut String) {
+    while run > 0 {
+        let n = run.min(MAX_COUNT_GROUP);
+        run -= n;
+        if n > 1 {
+            let (tens, ones) = (n / 20, n % 20);
+            if tens > 0 {
+                out.push(char::from(b'g' + (tens - 1) as u8));
+            }
+            if ones > 0 {
+                out.push(char::from(b'G' + (ones - 1) as u8));
+            }
+        }
+        out.push(char::from(digit));
+    }
+}
+
+fn hex_value(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        _ => c - b'A' + 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress_repeated_bytes_is_shorter_and_roundtrips() {
+        let rows = vec![0xAA; 32];
+        let compressed = compress_gf_rle(&rows, 32);
+        assert_eq!(compressed, "iJA"); // 60 + 4 = 64 x 'A'
+        assert!(compressed.len() < rows.len() * 2);
+        assert_eq!(decompress_gf_rle(&compressed, 32).unwrap(), rows);
+    }
+
+    #[test]
+    fn compress_uses_fill_markers() {
+        assert_eq!(compress_gf_rle(&[0x00; 8], 8), ",");
+        assert_eq!(compress_gf_rle(&[0xFF; 8], 8), "!");
+        assert_eq!(compress_gf_rle(&[0x81, 0, 0, 0], 4), "81,");
+        assert_eq!(compress_gf_rle(&[0x3F, 0xFF, 0xFF, 0xFF], 4), "3!");
+    }
+
+    #[test]
+    fn compress_repeats_previous_row() {
+        let mut rows = vec![0x12, 0x34, 0x00, 0x00];
+        rows.extend_from_slice(&[0x12, 0x34, 0x00, 0x00]);
+        rows.extend_from_slice(&[0x12, 0x34, 0x00, 0x00]);
+        assert_eq!(compress_gf_rle(&rows, 4), "1234,::");
+    }
+
+    #[test]
+    fn compress_long_runs_chain_count_groups() {
+        // 1000 hex digits of 'C' need more than one count group (max 419).
+        let rows = vec![0xCC; 500];
+        let compressed = compress_gf_rle(&rows, 500);
+        assert_eq!(compressed, "zYCzYCnHC"); // 419 + 419 + 162
+        assert_eq!(decompress_gf_rle(&compressed, 500).unwrap(), rows);
+    }
+
+    #[test]
+    fn compress_pads_partial_final_row() {
+        let compressed = compress_gf_rle(&[0xF0, 0x0F, 0xAB], 2);
+        assert_eq!(
+            decompress_gf_rle(&compressed, 2).unwrap(),
+            [0xF0, 0x0F, 0xAB, 0x00]
+        );
+    }
+
+    #[test]
+    fn compress_zero_row_bytes_is_empty() {
+        assert_eq!(compress_gf_rle(&[0xFF; 4], 0), "");
+    }
+
+    #[test]
+    fn roundtrip_mixed_bitmap() {
+        let row_bytes = 12;
+        let mut rows = Vec::new();
+        for y in 0..40u8 {
+            for x in 0..row_bytes as u8 {
+                rows.push(match (y / 10, x) {
+                    (0, _) => 0x00,
+                    (1, 0..=3) => 0xFF,
+                    (1, _) => 0x00,
+                    (2, _) => x.wrapping_mul(37) ^ y,
+                    _ => 0xFF,
+                });
+            }
+        }
+        let compressed = compress_gf_rle(&rows, row_bytes);
+        assert!(compressed.len() < rows.len() * 2);
+        assert_eq!(decompress_gf_rle(&compressed, row_bytes).unwrap(), rows);
+    }
+
+    #[test]
+    fn decompress_plain_hex_and_whitespace() {
+        assert_eq!(
+            decompress_gf_rle("00ff\n8001", 2).unwrap(),
+            [0x00, 0xFF, 0x80, 0x01]
+        );
+    }
+
+    #[test]
+    fn decompress_additive_counts() {
+        // 'h' (40) + 'H' (2) = 42 digits.
+        let bytes = decompress_gf_rle("hHB", 21).unwrap();
+        assert_eq!(bytes, vec![0xBB; 21]);
+    }
+
+    #[test]
+    fn decompress_errors() {
+        let errs = decompress_gf_rle(":", 2).unwrap_err();
+        assert!(errs[0].message.contains("no previous row"));
+
+        let errs = decompress_gf_rle("KA", 2).unwrap_err();
+        assert!(errs[0].message.contains("overflows"));
+
+        let errs = decompress_gf_rle("AB", 2).unwrap_err();
+        assert!(errs[0].message.contains("incomplete final row"));
+
+        let errs = decompress_gf_rle("AB#,", 2).unwrap_err();
+        assert_eq!(errs[0].offset, 2);
+
+        let errs = decompress_gf_rle("ABH", 2).unwrap_err();
+        assert!(errs.iter().any(|e| e.message.contains("repeat count")));
+    }
+}
@@ -1,22 +1,55 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use zpl_toolchain_diagnostics::Span;
 
 /// A parsed ZPL abstract syntax tree, consisting of one or more labels.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Ast {
     /// Ordered list of labels found in the input.
     pub labels: Vec<Label>,
+    /// Whether [`parse_bytes`](crate::grammar::parser::parse_bytes) decoded
+    /// non-UTF-8 input one byte per character (U+0000–U+00FF).
+    /// [`emit_zpl_bytes`](crate::grammar::emit::emit_zpl_bytes) encodes the
+    /// text back the same way, so binary payloads survive unchanged.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub byte_decoded: bool,
 }
 
 /// A single ZPL label, delimited by `^XA` and `^XZ`.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Label {
     /// Ordered list of nodes within this label.
     pub nodes: Vec<Node>,
 }
 
+impl Label {
+    /// Source span from the label's first node to its last, or `None` for a
+    /// label with no nodes.
+    pub fn span(&self) -> Option<Span> {
+        let first = self.nodes.first()?.span();
+        let last = self.nodes.last()?.span();
+        Some(Span::new(first.start, last.end))
+    }
+}
+
+/// Select one label of `ast` by 1-based position. Negative positions count
+/// from the end (`-1` is the last label).
+///
+/// Returns `None` for position `0` or a position outside the label list.
+pub fn select_label(ast: &Ast, n: isize) -> Option<&Label> {
+    let index = match n {
+        0 => return None,
+        1.. => n.unsigned_abs() - 1,
+        _ => ast.labels.len().checked_sub(n.unsigned_abs())?,
+    };
+    ast.labels.get(index)
+}
+
 /// A node in the ZPL AST representing a command, field data, raw payload, or trivia.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(tag = "kind")]
 #[non_exhaustive]
 pub enum Node {
@@ -27,7 +60,19 @@ pub enum Node {
         /// Parsed arguments for this command.
         args: Vec<ArgSlot>,
         /// Source span of the entire command.
+        #[serde(default = "empty_span")]
         span: Span,
+        /// Number of blank lines separating this command from the preceding
+        /// content in the source. Layout trivia only; used by the emitter when
+        /// [`EmitConfig::preserve_blank_lines`](crate::grammar::emit::EmitConfig::preserve_blank_lines)
+        /// is enabled.
+        #[serde(default, skip_serializing_if = "is_zero")]
+        blank_lines_before: u32,
+        /// Argument separator used in the source, recorded only when it
+        /// differs from the signature joiner (e.g. after a `^CD` delimiter
+        /// change). The emitter reproduces it so formatting stays byte-stable.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        joiner: Option<String>,
     },
     /// Field data content (text between ^FD/^FV and ^FS).
     FieldData {
@@ -36,6 +81,7 @@ pub enum Node {
         /// Whether ^FH hex escapes have been applied.
         hex_escaped: bool,
         /// Source span of the field data content.
+        #[serde(default = "empty_span")]
         span: Span,
     },
     /// Raw binary/hex payload (e.g., graphic data after ^GF or ~DG header).
@@ -46,6 +92,7 @@ pub enum Node {
         #[serde(skip_serializing_if = "Option::is_none")]
         data: Option<String>,
         /// Source span of the raw data content.
+        #[serde(default = "empty_span")]
         span: Span,
     },
     /// Preserved trivia: comments, whitespace, content outside labels.
@@ -53,12 +100,50 @@ pub enum Node {
         /// The trivia text content.
         text: String,
         /// Source span of the trivia.
+        #[serde(default = "empty_span")]
         span: Span,
     },
 }
 
+impl Node {
+    /// Command code including leader (e.g., `"^FO"`) for [`Node::Command`] nodes.
+    pub fn command_code(&self) -> Option<&str> {
+        match self {
+            Node::Command { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+
+    /// Parsed arguments for [`Node::Command`] nodes; empty for other kinds.
+    pub fn args(&self) -> &[ArgSlot] {
+        match self {
+            Node::Command { args, .. } => args,
+            _ => &[],
+        }
+    }
+
+    /// Source span of this node.
+    pub fn span(&self) -> Span {
+        match self {
+            Node::Command { span, .. }
+            | Node::FieldData { span, .. }
+            | Node::RawData { span, .. }
+            | Node::Trivia { span, .. } => *span,
+        }
+    }
+
+    /// Field content for [`Node::FieldData`] nodes.
+    pub fn field_data(&self) -> Option<&str> {
+        match self {
+            Node::FieldData { content, .. } => Some(content),
+            _ => None,
+        }
+    }
+}
+
 /// A single argument slot in a parsed ZPL command.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ArgSlot {
     /// Spec-defined parameter name, if known from the signature.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -70,8 +155,35 @@ pub struct ArgSlot {
     pub value: Option<String>,
 }
 
+impl ArgSlot {
+    /// Whether this argument was provided, empty, or absent.
+    pub fn presence(&self) -> Presence {
+        self.presence
+    }
+
+    /// Raw value of a provided argument.
+    pub fn as_str(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Value parsed as an integer, ignoring surrounding whitespace.
+    ///
+    /// `None` for empty or absent slots and for non-integer values.
+    pub fn as_int(&self) -> Option<i64> {
+        self.as_str()?.trim().parse().ok()
+    }
+
+    /// Value parsed as a number, ignoring surrounding whitespace.
+    ///
+    /// `None` for empty or absent slots and for non-numeric values.
+    pub fn as_float(&self) -> Option<f64> {
+        self.as_str()?.trim().parse().ok()
+    }
+}
+
 /// Indicates whether a command argument was provided, left empty, or absent.
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum Presence {
     /// Argument was not present in the source at all.
@@ -82,3 +194,29 @@ pub enum Presence {
     /// Argument was provided with a value.
     Value,
 }
+
+/// Count occurrences of each command code across all labels of `ast`.
+///
+/// Keys are canonical codes including the leader (e.g. `"^FO"`); only
+/// [`Node::Command`] nodes are counted.
+pub fn command_histogram(ast: &Ast) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for code in ast
+        .labels
+        .iter()
+        .flat_map(|label| &label.nodes)
+        .filter_map(Node::command_code)
+    {
+        *counts.entry(code.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Placeholder span for AST JSON serialized without spans.
+fn empty_span() -> Span {
+    Span::empty(0)
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
@@ -1,2 +1,4 @@
 /// Re-exported diagnostic types from the diagnostics crate.
-pub use zpl_toolchain_diagnostics::{Diagnostic, Severity, Span, codes};
+pub use zpl_toolchain_diagnostics::{
+    Diagnostic, DiagnosticSummary, RelatedSpan, Severity, Span, codes,
+};
@@ -1,6 +1,60 @@
 use super::ast::Ast;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Whether serialized JSON keeps source `span` fields.
+///
+/// Spans are essential for editor integrations but mostly noise in logs;
+/// [`WithSpans::No`] drops them before serialization. Spanless AST JSON still
+/// deserializes, with every span defaulting to `0..0`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WithSpans {
+    /// Keep `span` fields (default).
+    #[default]
+    Yes,
+    /// Omit `span` fields.
+    No,
+}
+
+impl From<bool> for WithSpans {
+    fn from(with_spans: bool) -> Self {
+        if with_spans { Self::Yes } else { Self::No }
+    }
+}
 
 /// Serialize an AST to a pretty-printed JSON string.
-pub fn to_pretty_json(ast: &Ast) -> String {
-    serde_json::to_string_pretty(ast).expect("Ast serialization cannot fail")
+pub fn to_pretty_json(ast: &Ast, spans: WithSpans) -> String {
+    serde_json::to_string_pretty(&to_json_value(ast, spans)).expect("Ast serialization cannot fail")
+}
+
+/// Serialize an AST to a single-line JSON string.
+pub fn to_compact_json(ast: &Ast, spans: WithSpans) -> String {
+    serde_json::to_string(&to_json_value(ast, spans)).expect("Ast serialization cannot fail")
+}
+
+/// Convert any serializable value (an [`Ast`], a
+/// [`ParseResult`](super::parser::ParseResult), …) to a JSON value, dropping
+/// every nested `span` field when `spans` is [`WithSpans::No`].
+pub fn to_json_value<T: Serialize + ?Sized>(value: &T, spans: WithSpans) -> Value {
+    let mut json = serde_json::to_value(value).expect("serialization to JSON value cannot fail");
+    if spans == WithSpans::No {
+        strip_span_fields(&mut json);
+    }
+    json
+}
+
+/// Recursively remove `span` fields from a JSON value.
+///
+/// This is the serialization-side counterpart to
+/// [`strip_spans`](super::emit::strip_spans): it reaches every nested object,
+/// including diagnostics alongside the AST.
+pub fn strip_span_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.remove("span");
+            map.values_mut().for_each(strip_span_fields);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_span_fields),
+        _ => {}
+    }
 }
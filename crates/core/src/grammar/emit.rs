@@ -3,13 +3,16 @@
 //! All formatting decisions are spec-driven: joiners, split-rule merging,
 //! trailing-arg handling, and structural indentation are derived from
 //! [`ParserTables`] metadata. Field data and raw payloads are preserved
-//! byte-for-byte.
+//! byte-for-byte, except for line breaks inside field data, which printers
+//! ignore.
 
 use std::borrow::Cow;
 
 use crate::grammar::ast::{ArgSlot, Ast, Label, Node, Presence};
-use zpl_toolchain_diagnostics::Span;
-use zpl_toolchain_spec_tables::{CommandCategory, CommandScope, ParserTables, SpacingPolicy};
+use zpl_toolchain_diagnostics::{LineEndingStyle, Span};
+use zpl_toolchain_spec_tables::{
+    ArgUnion, CommandCategory, CommandScope, ParserTables, SpacingPolicy,
+};
 
 // ── Configuration ───────────────────────────────────────────────────────
 
@@ -36,6 +39,20 @@ pub enum Compaction {
     Field,
 }
 
+/// Line terminator written between emitted lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Unix-style `\n`.
+    #[default]
+    Lf,
+    /// Windows-style `\r\n`.
+    CrLf,
+    /// Keep the style detected in the source text (see
+    /// [`emit_zpl_with_source`]). Mixed sources, and [`emit_zpl`] calls
+    /// without a source, fall back to `\n`.
+    Preserve,
+}
+
 /// Configuration for the ZPL emitter.
 #[derive(Debug, Clone, Default)]
 pub struct EmitConfig {
@@ -43,6 +60,40 @@ pub struct EmitConfig {
     pub indent: Indent,
     /// Optional compaction mode.
     pub compaction: Compaction,
+    /// Reproduce author blank-line grouping: emit a single blank line before
+    /// any command that was preceded by one or more blank lines in the source.
+    /// Blank lines at the very start of the output and inside field blocks
+    /// (`^FO`...`^FS`) are not reproduced.
+    pub preserve_blank_lines: bool,
+    /// Line terminator written after each emitted line. Line breaks inside
+    /// raw payloads (e.g. multi-line `^GF` data) are kept as they are.
+    pub line_ending: LineEnding,
+    /// Wrap a bare command fragment in `^XA`/`^XZ`. Applies only when the
+    /// input contains at least one command and no `^XA` or `^XZ` at all.
+    pub wrap_bare_commands: bool,
+    /// Rewrite argument text into a canonical spelling without changing its
+    /// meaning: leading zeros are dropped from integer args, hex digits in
+    /// `^GF` ASCII-hex payloads and `^FH` escape sequences are upper-cased,
+    /// and `^CD` delimiter changes are replaced by the default `,` joiner.
+    pub canonicalize: bool,
+}
+
+impl EmitConfig {
+    /// The fixed configuration used by `zpl normalize`: no indentation, no
+    /// compaction, no blank lines, `\n` line endings, and canonical args.
+    ///
+    /// Semantically identical inputs emit byte-identical output under this
+    /// configuration, which makes it suitable for golden-file comparisons.
+    pub fn normalized() -> Self {
+        Self {
+            indent: Indent::None,
+            compaction: Compaction::None,
+            preserve_blank_lines: false,
+            line_ending: LineEnding::Lf,
+            wrap_bare_commands: false,
+            canonicalize: true,
+        }
+    }
 }
 
 // ── Public API ──────────────────────────────────────────────────────────
@@ -53,33 +104,144 @@ pub struct EmitConfig {
 /// metadata (signature joiners, split rules, structural flags). Without
 /// tables the emitter falls back to comma-joined args.
 pub fn emit_zpl(ast: &Ast, tables: Option<&ParserTables>, config: &EmitConfig) -> String {
+    let newline = match config.line_ending {
+        LineEnding::CrLf => "\r\n",
+        LineEnding::Lf | LineEnding::Preserve => "\n",
+    };
+    emit_with_newline(ast, tables, config, newline)
+}
+
+/// Emit formatted ZPL as bytes.
+///
+/// For an AST from [`parse_bytes`](super::parser::parse_bytes) with
+/// [`Ast::byte_decoded`] set, each character is written back as the single
+/// byte it was decoded from, reproducing binary payloads exactly; otherwise
+/// this is the UTF-8 encoding of [`emit_zpl`].
+pub fn emit_zpl_bytes(ast: &Ast, tables: Option<&ParserTables>, config: &EmitConfig) -> Vec<u8> {
+    let text = emit_zpl(ast, tables, config);
+    if !ast.byte_decoded {
+        return text.into_bytes();
+    }
+    text.chars()
+        .map(|c| u8::try_from(c).unwrap_or(b'?'))
+        .collect()
+}
+
+/// Emit a formatted ZPL string, resolving [`LineEnding::Preserve`] against
+/// the line-ending style of `source` (the text `ast` was parsed from).
+///
+/// Behaves exactly like [`emit_zpl`] for the other line-ending settings.
+pub fn emit_zpl_with_source(
+    ast: &Ast,
+    tables: Option<&ParserTables>,
+    config: &EmitConfig,
+    source: &str,
+) -> String {
+    let newline = match config.line_ending {
+        LineEnding::Lf => "\n",
+        LineEnding::CrLf => "\r\n",
+        LineEnding::Preserve => {
+            match zpl_toolchain_diagnostics::LineIndex::new(source).line_ending_style() {
+                LineEndingStyle::CrLf => "\r\n",
+                _ => "\n",
+            }
+        }
+    };
+    emit_with_newline(ast, tables, config, newline)
+}
+
+/// Emit with `newline` written after every line the emitter produces.
+/// Line breaks inside raw payloads are part of the payload and kept as-is.
+fn emit_with_newline(
+    ast: &Ast,
+    tables: Option<&ParserTables>,
+    config: &EmitConfig,
+    newline: &str,
+) -> String {
     let mut out = String::new();
+    let wrap = config.wrap_bare_commands && is_bare_fragment(ast);
+    if wrap {
+        out.push_str("^XA");
+        out.push_str(newline);
+    }
+    // Track current command prefix (^CC changes it from '^'); like the
+    // parser's, it carries over from one label to the next.
+    let mut cmd_prefix = '^';
     for label in &ast.labels {
-        emit_label(&mut out, label, tables, config);
+        emit_label(
+            &mut out,
+            label,
+            tables,
+            config,
+            wrap,
+            newline,
+            &mut cmd_prefix,
+        );
+    }
+    if wrap {
+        out.push_str(&remap_prefix("^XZ", cmd_prefix));
+        out.push_str(newline);
     }
     if matches!(config.compaction, Compaction::Field) {
-        compact_printable_fields(&out, tables)
+        compact_printable_fields(&out, tables, config.preserve_blank_lines, newline)
     } else {
         out
     }
 }
 
+/// Whether `ast` holds commands but no `^XA`/`^XZ` label bounds.
+fn is_bare_fragment(ast: &Ast) -> bool {
+    let mut codes = ast
+        .labels
+        .iter()
+        .flat_map(|label| &label.nodes)
+        .filter_map(Node::command_code)
+        .peekable();
+    codes.peek().is_some() && codes.all(|code| !matches!(code, "^XA" | "^XZ"))
+}
+
 // ── Label emission ──────────────────────────────────────────────────────
 
-fn emit_label(out: &mut String, label: &Label, tables: Option<&ParserTables>, config: &EmitConfig) {
-    let mut in_label = false;
+fn emit_label(
+    out: &mut String,
+    label: &Label,
+    tables: Option<&ParserTables>,
+    config: &EmitConfig,
+    in_label: bool,
+    newline: &str,
+    cmd_prefix: &mut char,
+) {
+    let mut in_label = in_label;
     let mut in_field = false;
-    // Track current command prefix (^CC changes it from '^').
-    let mut cmd_prefix: char = '^';
+    // Canonicalization state: the active ^FH indicator for the current
+    // field, and whether the previous command carries an ASCII-hex payload.
+    let mut hex_indicator: Option<char> = None;
+    let mut hex_payload = false;
 
     for node in &label.nodes {
         match node {
-            Node::Command { code, args, .. } => {
+            Node::Command {
+                code,
+                args,
+                blank_lines_before,
+                joiner,
+                ..
+            } => {
                 // The parser normalizes all codes to canonical '^' prefix,
                 // so `code` is directly usable for table lookups.
                 let is_xa = code == "^XA";
                 let is_xz = code == "^XZ";
 
+                // Collapse any run of source blank lines into one separator.
+                if config.preserve_blank_lines
+                    && *blank_lines_before > 0
+                    && !in_field
+                    && !out.is_empty()
+                    && !out.ends_with(&newline.repeat(2))
+                {
+                    out.push_str(newline);
+                }
+
                 // Dedent BEFORE indenting for ^XZ.
                 if is_xz {
                     in_field = false;
@@ -99,18 +261,45 @@ fn emit_label(out: &mut String, label: &Label, tables: Option<&ParserTables>, co
                     in_field = false;
                 }
 
+                if code == "^FH" {
+                    hex_indicator = Some(
+                        args.first()
+                            .and_then(|a| a.value.as_deref())
+                            .and_then(|v| v.chars().next())
+                            .unwrap_or('_'),
+                    );
+                }
+
                 // Emit the command with the current prefix.
-                emit_command(out, code, cmd_prefix, args, tables);
-                out.push('\n');
+                let canonical = config
+                    .canonicalize
+                    .then(|| canonical_args(code, args, tables, hex_indicator));
+                // Canonical output resets ^CD delimiters (see `canonical_args`),
+                // so the source joiner is only reproduced in default mode.
+                let joiner = joiner.as_deref().filter(|_| !config.canonicalize);
+                emit_command(
+                    out,
+                    code,
+                    *cmd_prefix,
+                    canonical.as_deref().unwrap_or(args),
+                    joiner,
+                    tables,
+                );
+                out.push_str(newline);
+
+                hex_payload = code == "^GF" && is_ascii_hex_graphic(args);
+                if closes_field || is_xz {
+                    hex_indicator = None;
+                }
 
-                // Track prefix changes: ^CC sets the command (^) prefix.
-                if code == "^CC"
+                // Track prefix changes: ^CC/~CC set the command (^) prefix.
+                if matches!(code.as_str(), "^CC" | "~CC")
                     && let Some(arg) = args.first()
                     && arg.presence == Presence::Value
                     && let Some(val) = &arg.value
                     && let Some(ch) = val.chars().next()
                 {
-                    cmd_prefix = ch;
+                    *cmd_prefix = ch;
                 }
 
                 // Update nesting state AFTER emitting.
@@ -129,19 +318,30 @@ fn emit_label(out: &mut String, label: &Label, tables: Option<&ParserTables>, co
                 //
                 // AST pattern:  Command(^FD) → FieldData → Command(^FS)
                 // Output:       ^FDcontent\n^FS\n
-                trim_trailing_newline(out);
-                out.push_str(content);
-                out.push('\n');
+                let content = inline_field_data(content, indent_for(config, in_label, in_field));
+                if content.is_empty() {
+                    continue;
+                }
+                trim_trailing_newline(out, newline);
+                match hex_indicator.filter(|_| config.canonicalize) {
+                    Some(ind) => out.push_str(&uppercase_hex_escapes(&content, ind)),
+                    None => out.push_str(&content),
+                }
+                out.push_str(newline);
             }
 
             Node::RawData { data, .. } => {
                 // Raw payload data is emitted verbatim. It may contain
                 // newlines (multi-line hex data for ^GF).
                 if let Some(d) = data {
-                    trim_trailing_newline(out);
-                    out.push_str(d);
+                    trim_trailing_newline(out, newline);
+                    if config.canonicalize && hex_payload {
+                        out.push_str(&uppercase_hex_digits(d));
+                    } else {
+                        out.push_str(d);
+                    }
                     if !d.ends_with('\n') {
-                        out.push('\n');
+                        out.push_str(newline);
                     }
                 }
             }
@@ -154,7 +354,7 @@ fn emit_label(out: &mut String, label: &Label, tables: Option<&ParserTables>, co
                 }
                 push_indent(out, config, in_label, in_field);
                 out.push_str(trimmed);
-                out.push('\n');
+                out.push_str(newline);
             }
         }
     }
@@ -166,11 +366,14 @@ fn emit_label(out: &mut String, label: &Label, tables: Option<&ParserTables>, co
 ///
 /// `code` is the canonical code (e.g., `"^FO"`).
 /// `prefix` is the current command prefix character (default `'^'`).
+/// `source_joiner` overrides the signature joiner when the source used a
+/// different separator (recorded by the parser after a `^CD` change).
 fn emit_command(
     out: &mut String,
     code: &str,
     prefix: char,
     args: &[ArgSlot],
+    source_joiner: Option<&str>,
     tables: Option<&ParserTables>,
 ) {
     // Emit the command code, remapping the prefix if ^CC changed it.
@@ -190,7 +393,7 @@ fn emit_command(
             .or(e.signature.as_ref())
     });
 
-    let joiner = sig.map_or(",", |s| s.joiner.as_str());
+    let joiner = source_joiner.unwrap_or_else(|| sig.map_or(",", |s| s.joiner.as_str()));
     let split_rule = sig.and_then(|s| s.split_rule.as_ref());
     let spacing_policy = sig.map_or(SpacingPolicy::Forbid, |s| s.spacing_policy);
     if matches!(spacing_policy, SpacingPolicy::Require) {
@@ -215,7 +418,10 @@ fn emit_command(
         (MergedArgs::Borrowed(&arg_values), arg_values.len())
     };
 
-    // Trim trailing empty args after the last Value arg.
+    // Trailing empty args after the last Value arg are dropped when the
+    // signature allows it. Interior empties always keep their slot so later
+    // args stay in position; with `allow_empty_trailing` off, every slot the
+    // source carried is written back.
     let merged_idx_of = |orig_i: usize| -> usize {
         if let Some(rule) = split_rule {
             let split_count = rule.char_counts.len();
@@ -239,7 +445,9 @@ fn emit_command(
         .find(|(_, a)| a.presence == Presence::Value)
         .map(|(i, _)| merged_idx_of(i));
 
+    let allow_empty_trailing = sig.is_none_or(|s| s.allow_empty_trailing);
     let trim_to = match last_value {
+        _ if !allow_empty_trailing => merged_len,
         Some(idx) => (idx + 1).min(merged_len),
         None => return, // No values at all — emit no args.
     };
@@ -253,6 +461,139 @@ fn emit_command(
     }
 }
 
+// ── Canonicalization ────────────────────────────────────────────────────
+
+/// Rewrite `args` into their canonical spelling (see
+/// [`EmitConfig::canonicalize`]). Args covered by a split rule keep their
+/// exact text, since their width is significant. `^CD`/`~CD` delimiter
+/// changes are reset to `,`, matching the signature joiners the emitter
+/// falls back to in canonical mode.
+fn canonical_args(
+    code: &str,
+    args: &[ArgSlot],
+    tables: Option<&ParserTables>,
+    hex_indicator: Option<char>,
+) -> Vec<ArgSlot> {
+    let entry = tables.and_then(|t| t.cmd_by_code(code));
+    let split = entry
+        .and_then(|e| {
+            e.signature_overrides
+                .as_ref()
+                .and_then(|ov| ov.get(code))
+                .or(e.signature.as_ref())
+        })
+        .and_then(|s| s.split_rule.as_ref())
+        .map(|rule| rule.param_index..rule.param_index + rule.char_counts.len());
+    let spec_args = entry.and_then(|e| e.args.as_deref()).unwrap_or(&[]);
+    let hex_graphic = code == "^GF" && is_ascii_hex_graphic(args);
+
+    args.iter()
+        .enumerate()
+        .map(|(i, slot)| {
+            let Some(value) = slot.value.as_deref() else {
+                return slot.clone();
+            };
+            if split.as_ref().is_some_and(|r| r.contains(&i)) {
+                return slot.clone();
+            }
+            let key = slot.key.as_deref();
+            let canonical = if matches!(code, "^CD" | "~CD") {
+                Cow::Borrowed(",")
+            } else if key == Some("data") && hex_graphic {
+                uppercase_hex_digits(value)
+            } else if key == Some("data") && matches!(code, "^FD" | "^FV") {
+                match hex_indicator {
+                    Some(ind) => uppercase_hex_escapes(value, ind),
+                    None => Cow::Borrowed(value),
+                }
+            } else if spec_args.get(i).is_some_and(|u| accepts_int(u, value)) {
+                strip_leading_zeros(value)
+            } else {
+                Cow::Borrowed(value)
+            };
+            ArgSlot {
+                value: Some(canonical.into_owned()),
+                ..slot.clone()
+            }
+        })
+        .collect()
+}
+
+/// Whether `value` is a plain unsigned integer for an int-typed spec arg.
+fn accepts_int(arg: &ArgUnion, value: &str) -> bool {
+    let is_int = match arg {
+        ArgUnion::Single(a) => a.r#type == "int",
+        ArgUnion::OneOf { one_of } => one_of.iter().any(|a| a.r#type == "int"),
+    };
+    is_int && !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn strip_leading_zeros(value: &str) -> Cow<'_, str> {
+    let trimmed = value.trim_start_matches('0');
+    match trimmed.len() {
+        n if n == value.len() => Cow::Borrowed(value),
+        0 => Cow::Borrowed("0"),
+        _ => Cow::Borrowed(trimmed),
+    }
+}
+
+/// Whether a `^GF` command carries an ASCII-hex payload (format `A`, not
+/// a `:Z64:`/`:B64:` encoded one).
+fn is_ascii_hex_graphic(args: &[ArgSlot]) -> bool {
+    let format = args.first().and_then(|a| a.value.as_deref()).unwrap_or("A");
+    let data = args
+        .iter()
+        .find(|a| a.key.as_deref() == Some("data"))
+        .and_then(|a| a.value.as_deref())
+        .unwrap_or("");
+    format.eq_ignore_ascii_case("A") && !data.starts_with(':')
+}
+
+/// Upper-case the `a`-`f` hex digits in an ASCII-hex payload. Lower-case
+/// `g`-`z` are compression repeat counts and are left alone.
+fn uppercase_hex_digits(data: &str) -> Cow<'_, str> {
+    if !data.bytes().any(|b| matches!(b, b'a'..=b'f')) {
+        return Cow::Borrowed(data);
+    }
+    Cow::Owned(
+        data.chars()
+            .map(|c| match c {
+                'a'..='f' => c.to_ascii_uppercase(),
+                _ => c,
+            })
+            .collect(),
+    )
+}
+
+/// Upper-case the two hex digits following each `indicator` in `^FH` field
+/// data (e.g. `_1a` becomes `_1A`).
+fn uppercase_hex_escapes(data: &str, indicator: char) -> Cow<'_, str> {
+    let mut out = String::with_capacity(data.len());
+    let mut changed = false;
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        out.push(c);
+        if c != indicator {
+            continue;
+        }
+        for _ in 0..2 {
+            match chars.peek() {
+                Some(&d) if d.is_ascii_hexdigit() => {
+                    changed |= d.is_ascii_lowercase();
+                    out.push(d.to_ascii_uppercase());
+                    chars.next();
+                }
+                _ => break,
+            }
+        }
+    }
+    if changed {
+        Cow::Owned(out)
+    } else {
+        Cow::Borrowed(data)
+    }
+}
+
 /// Either borrowed `&[&str]` (no split rule) or owned `Vec<String>` (after merge).
 /// Avoids allocating a `Vec<String>` in the common case.
 enum MergedArgs<'a> {
@@ -293,37 +634,67 @@ fn merge_split_args(values: &[&str], param_index: usize, split_count: usize) ->
 // ── Indentation helpers ─────────────────────────────────────────────────
 
 fn push_indent(out: &mut String, config: &EmitConfig, in_label: bool, in_field: bool) {
-    match config.indent {
-        Indent::None => {}
-        Indent::Label => {
-            if in_label {
-                out.push_str("  ");
-            }
-        }
-        Indent::Field => {
-            if in_label {
-                out.push_str("  ");
-            }
-            if in_field {
-                out.push_str("  ");
-            }
-        }
+    out.push_str(indent_for(config, in_label, in_field));
+}
+
+/// The indentation [`push_indent`] writes for the given nesting state.
+fn indent_for(config: &EmitConfig, in_label: bool, in_field: bool) -> &'static str {
+    let levels = match config.indent {
+        Indent::None => 0,
+        Indent::Label => usize::from(in_label),
+        Indent::Field => usize::from(in_label) + usize::from(in_field),
+    };
+    &"    "[..levels * 2]
+}
+
+/// Field data as it is glued onto its `^FD`/`^FV` line.
+///
+/// Printers ignore line breaks in the data stream (`^FB` uses `\&` for a
+/// new line), and the parser already ends the `^FD` argument at the first
+/// one, so line breaks are dropped; every other character, spaces included,
+/// is kept. A trailing run equal to `indent` right after a line break is the
+/// indentation this emitter writes before `^FS`, so it is dropped too —
+/// keeping it would grow the data on every pass.
+fn inline_field_data<'a>(content: &'a str, indent: &str) -> Cow<'a, str> {
+    let content = match content.strip_suffix(indent) {
+        Some(rest) if !indent.is_empty() && (rest.is_empty() || rest.ends_with('\n')) => rest,
+        _ => content,
+    };
+    if content.contains(['\r', '\n']) {
+        Cow::Owned(
+            content
+                .chars()
+                .filter(|c| !matches!(c, '\r' | '\n'))
+                .collect(),
+        )
+    } else {
+        Cow::Borrowed(content)
     }
 }
 
-fn trim_trailing_newline(out: &mut String) {
-    if out.ends_with('\n') {
-        out.truncate(out.len() - 1);
+fn trim_trailing_newline(out: &mut String, newline: &str) {
+    if out.ends_with(newline) {
+        out.truncate(out.len() - newline.len());
     }
 }
 
-fn compact_printable_fields(formatted: &str, tables: Option<&ParserTables>) -> String {
+fn compact_printable_fields(
+    formatted: &str,
+    tables: Option<&ParserTables>,
+    preserve_blank_lines: bool,
+    newline: &str,
+) -> String {
     let mut output: Vec<String> = Vec::new();
     let mut field_lines: Vec<String> = Vec::new();
 
-    for raw_line in formatted.lines() {
+    let body = formatted.strip_suffix(newline).unwrap_or(formatted);
+    for raw_line in body.split(newline) {
         let trimmed = raw_line.trim();
         if trimmed.is_empty() {
+            // Group separators survive compaction only between field blocks.
+            if preserve_blank_lines && field_lines.is_empty() {
+                output.push(String::new());
+            }
             continue;
         }
         let opcode = detect_opcode(trimmed);
@@ -355,8 +726,8 @@ fn compact_printable_fields(formatted: &str, tables: Option<&ParserTables>) -> S
     flush_field_block(&mut output, &mut field_lines, tables);
     let output = inline_data_terminators(output, tables);
 
-    let mut result = output.join("\n");
-    result.push('\n');
+    let mut result = output.join(newline);
+    result.push_str(newline);
     result
 }
 
@@ -383,9 +754,12 @@ fn compact_field_block(lines: &[String]) -> String {
         .find_map(|(idx, ch)| (!ch.is_whitespace()).then_some(idx))
         .unwrap_or(first.len());
     let indent = &first[..indent_len];
+    // Strip only the indentation: trailing whitespace on a line can only be
+    // field data (the emitter never adds any, and field data never spans
+    // lines, see `inline_field_data`), and it must survive verbatim.
     let compacted = lines
         .iter()
-        .map(|line| line.trim())
+        .map(|line| line.trim_start())
         .collect::<Vec<_>>()
         .join("");
     format!("{indent}{compacted}")
@@ -575,7 +949,8 @@ fn remap_prefix<'a>(code: &'a str, prefix: char) -> Cow<'a, str> {
 
 /// Strip all spans from an AST for comparison (used in round-trip tests).
 ///
-/// Sets all spans to the sentinel value `Span { start: 0, end: 0 }`.
+/// Sets all spans to the sentinel value `Span { start: 0, end: 0 }` and
+/// clears blank-line layout trivia, which also changes after formatting.
 pub fn strip_spans(ast: &Ast) -> Ast {
     let sentinel = Span::new(0, 0);
     Ast {
@@ -587,10 +962,14 @@ pub fn strip_spans(ast: &Ast) -> Ast {
                     .nodes
                     .iter()
                     .map(|node| match node {
-                        Node::Command { code, args, .. } => Node::Command {
+                        Node::Command {
+                            code, args, joiner, ..
+                        } => Node::Command {
                             code: code.clone(),
                             args: args.clone(),
                             span: sentinel,
+                            blank_lines_before: 0,
+                            joiner: joiner.clone(),
                         },
                         Node::FieldData {
                             content,
@@ -614,5 +993,6 @@ pub fn strip_spans(ast: &Ast) -> Ast {
                     .collect(),
             })
             .collect(),
+        byte_decoded: ast.byte_decoded,
     }
 }
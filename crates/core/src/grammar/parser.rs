@@ -1,6 +1,6 @@
 use super::{
     ast::{ArgSlot, Ast, Label, Node, Presence},
-    diag::{Diagnostic, Span, codes},
+    diag::{Diagnostic, DiagnosticSummary, Span, codes},
     lexer::{TokKind, tokenize},
     tables::ParserTables,
 };
@@ -15,11 +15,37 @@ macro_rules! ctx {
 
 /// Result of parsing a ZPL input string.
 #[derive(serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ParseResult {
     /// The parsed abstract syntax tree.
     pub ast: Ast,
     /// Diagnostics (errors, warnings, info) produced during parsing.
     pub diagnostics: Vec<Diagnostic>,
+    /// Counts of [`diagnostics`](Self::diagnostics) by severity.
+    pub summary: DiagnosticSummary,
+}
+
+/// Options controlling which diagnostics the parser produces.
+///
+/// The AST does not depend on these options; they only trim the diagnostic
+/// work, which matters for large trusted inputs parsed just for their AST.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Report `PARSER_UNKNOWN_COMMAND` for commands missing from the spec
+    /// tables. Only meaningful when tables are supplied.
+    pub emit_unknown_command_diagnostics: bool,
+    /// Collect diagnostics at all. When `false`, the result carries no
+    /// diagnostics and an empty summary.
+    pub collect_diagnostics: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            emit_unknown_command_diagnostics: true,
+            collect_diagnostics: true,
+        }
+    }
 }
 
 // ─── Parser Mode State Machine ──────────────────────────────────────────────
@@ -55,7 +81,153 @@ pub fn parse_str(input: &str) -> ParseResult {
 
 /// Parse a ZPL input string with optional spec tables for opcode recognition.
 pub fn parse_with_tables(input: &str, tables: Option<&ParserTables>) -> ParseResult {
-    Parser::new(input, tables).parse()
+    parse_with_options(input, tables, &ParseOptions::default())
+}
+
+/// Parse a ZPL input string, choosing which diagnostics to produce.
+///
+/// The AST is the same as from [`parse_with_tables`]; `options` only
+/// suppresses diagnostics.
+pub fn parse_with_options(
+    input: &str,
+    tables: Option<&ParserTables>,
+    options: &ParseOptions,
+) -> ParseResult {
+    let mut parser = Parser::new(input, tables);
+    parser.options = options.clone();
+    parser.parse()
+}
+
+/// Parse raw ZPL bytes, keeping binary `^GF`/`~DG` payloads lossless.
+///
+/// Valid UTF-8 input is parsed exactly like [`parse_with_tables`]. Otherwise
+/// each byte is decoded as the character of the same value, the AST is
+/// marked [`Ast::byte_decoded`], and every span is a byte offset into
+/// `input`. Emit such an AST with
+/// [`emit_zpl_bytes`](super::emit::emit_zpl_bytes) to get the payload bytes
+/// back.
+pub fn parse_bytes(input: &[u8], tables: Option<&ParserTables>) -> ParseResult {
+    if let Ok(text) = std::str::from_utf8(input) {
+        return parse_with_tables(text, tables);
+    }
+    let mut result = parse_bytes_with_decoder(input, tables, char::from);
+    result.ast.byte_decoded = true;
+    result
+}
+
+/// Parse raw ZPL bytes in a single-byte character set.
+///
+/// Each byte is decoded to one character by `decode` (e.g. a code page 437
+/// table), and every span is a byte offset into `input`. The AST is not
+/// marked [`Ast::byte_decoded`]; encode emitted text back with the inverse
+/// of `decode`.
+pub fn parse_bytes_with_decoder(
+    input: &[u8],
+    tables: Option<&ParserTables>,
+    decode: impl Fn(u8) -> char,
+) -> ParseResult {
+    let text: String = input.iter().map(|&b| decode(b)).collect();
+    let mut result = parse_with_tables(&text, tables);
+
+    // Non-ASCII characters take two or three UTF-8 bytes in `text` but one in
+    // `input`; shift each offset back by the extra bytes before it.
+    let mut extra = 0;
+    let wide: Vec<(usize, usize)> = text
+        .char_indices()
+        .filter(|(_, c)| !c.is_ascii())
+        .map(|(i, c)| {
+            extra += c.len_utf8() - 1;
+            (i, extra)
+        })
+        .collect();
+    let shift = |offset: usize| match wide.partition_point(|&(i, _)| i < offset) {
+        0 => 0,
+        n => wide[n - 1].1,
+    };
+    let to_byte = |span: &mut Span| {
+        span.start -= shift(span.start);
+        span.end -= shift(span.end);
+    };
+    for label in &mut result.ast.labels {
+        for node in &mut label.nodes {
+            match node {
+                Node::Command { span, .. }
+                | Node::FieldData { span, .. }
+                | Node::RawData { span, .. }
+                | Node::Trivia { span, .. } => to_byte(span),
+            }
+        }
+    }
+    for diag in &mut result.diagnostics {
+        if let Some(span) = &mut diag.span {
+            to_byte(span);
+        }
+    }
+    result
+}
+
+/// Reparse `input` after an edit, reusing labels from a previous parse.
+///
+/// `edit` is the byte range replaced in the *old* input and `replacement_len`
+/// the byte length of the text inserted in its place; `input` is the full
+/// edited text. See [`reparse_with_tables`].
+pub fn reparse(old: &ParseResult, input: &str, edit: Span, replacement_len: usize) -> ParseResult {
+    reparse_with_tables(old, input, edit, replacement_len, None)
+}
+
+/// Reparse `input` after an edit, reusing labels from a previous parse.
+///
+/// Every leading label of `old` that is closed by `^XZ` and ends before
+/// `edit.start` is kept as-is, along with its diagnostics; parsing resumes
+/// after the last kept label, so the edited label and everything after it
+/// (whose spans shift) are parsed fresh. The result equals
+/// `parse_with_tables(input, tables)` as long as `old` was produced from the
+/// pre-edit input with the same `tables`. When nothing can be reused, this
+/// falls back to a full parse.
+pub fn reparse_with_tables(
+    old: &ParseResult,
+    input: &str,
+    edit: Span,
+    replacement_len: usize,
+    tables: Option<&ParserTables>,
+) -> ParseResult {
+    // The edited text must fit the new input; otherwise the caller's edit
+    // does not describe `input` and nothing can be trusted.
+    if edit.start.saturating_add(replacement_len) > input.len() {
+        return parse_with_tables(input, tables);
+    }
+    let reused = old
+        .ast
+        .labels
+        .iter()
+        .take_while(|label| match label.nodes.last() {
+            Some(Node::Command { code, span, .. }) => code == "^XZ" && span.end < edit.start,
+            _ => false,
+        })
+        .count();
+    let Some(boundary) =
+        old.ast.labels[..reused]
+            .last()
+            .and_then(|label| match label.nodes.last() {
+                Some(Node::Command { span, .. }) => Some(span.end),
+                _ => None,
+            })
+    else {
+        return parse_with_tables(input, tables);
+    };
+    if !input.is_char_boundary(boundary) {
+        return parse_with_tables(input, tables);
+    }
+
+    let mut parser = Parser::untokenized(input, tables);
+    parser.resume_after(old.ast.labels[..reused].to_vec(), boundary);
+    parser.diags = old
+        .diagnostics
+        .iter()
+        .filter(|d| d.span.is_some_and(|span| span.end <= boundary))
+        .cloned()
+        .collect();
+    parser.parse()
 }
 
 // ─── Parser Implementation ─────────────────────────────────────────────────
@@ -66,9 +238,12 @@ struct Parser<'a> {
     toks: Vec<super::lexer::Token<'a>>,
     pos: usize,
     diags: Vec<Diagnostic>,
+    /// Which diagnostics to produce.
+    options: ParseOptions,
     labels: Vec<Label>,
     nodes: Vec<Node>,
-    in_label: bool,
+    /// Span of the `^XA` that opened the current label, if one is open.
+    open_label: Option<Span>,
     mode: Mode,
     /// Whether ^FH was seen in the current field group (between field-opening and ^FS).
     fh_active: bool,
@@ -91,16 +266,81 @@ impl<'a> Parser<'a> {
         p.min(s.len())
     }
 
+    /// Byte offset where the most recently emitted node ends (0 if none).
+    fn last_node_end(&self) -> usize {
+        self.nodes
+            .last()
+            .or_else(|| self.labels.last().and_then(|l| l.nodes.last()))
+            .map_or(0, |node| match node {
+                Node::Command { span, .. }
+                | Node::FieldData { span, .. }
+                | Node::RawData { span, .. }
+                | Node::Trivia { span, .. } => span.end,
+            })
+    }
+
+    /// Count the blank lines between the previous node and a command leader.
+    ///
+    /// Only the whitespace run directly preceding the leader is inspected, so
+    /// stray content or field data never contributes. Command spans already
+    /// include their terminating line break, in which case every further line
+    /// break in the gap is a blank line; otherwise the first break only ends
+    /// the current line.
+    fn blank_lines_before(&self, leader_start: usize) -> u32 {
+        let floor = self.last_node_end();
+        let bytes = self.input.as_bytes();
+        let mut i = leader_start;
+        let mut breaks = 0u32;
+        while i > floor {
+            match bytes[i - 1] {
+                b'\n' => {
+                    breaks = breaks.saturating_add(1);
+                    // Treat CRLF as a single line break.
+                    if i - 1 > floor && bytes[i - 2] == b'\r' {
+                        i -= 1;
+                    }
+                }
+                b'\r' => breaks = breaks.saturating_add(1),
+                b if b.is_ascii_whitespace() => {}
+                _ => break,
+            }
+            i -= 1;
+        }
+        let at_line_start = i == 0 || (i == floor && matches!(bytes[i - 1], b'\n' | b'\r'));
+        if at_line_start {
+            breaks
+        } else {
+            breaks.saturating_sub(1)
+        }
+    }
+
     fn new(input: &'a str, tables: Option<&'a ParserTables>) -> Self {
+        Self {
+            toks: tokenize(input),
+            ..Self::untokenized(input, tables)
+        }
+    }
+
+    /// Record a diagnostic unless collection is disabled, in which case
+    /// `diag` is never called.
+    fn push_diag(&mut self, diag: impl FnOnce() -> Diagnostic) {
+        if self.options.collect_diagnostics {
+            self.diags.push(diag());
+        }
+    }
+
+    /// Parser with no tokens yet; callers fill `toks` before parsing.
+    fn untokenized(input: &'a str, tables: Option<&'a ParserTables>) -> Self {
         Self {
             input,
             tables,
-            toks: tokenize(input),
+            toks: Vec::new(),
             pos: 0,
             diags: Vec::new(),
+            options: ParseOptions::default(),
             labels: Vec::new(),
             nodes: Vec::new(),
-            in_label: false,
+            open_label: None,
             mode: Mode::Normal,
             fh_active: false,
             command_prefix: '^',
@@ -109,6 +349,72 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Seed the parser with already-parsed `labels` and continue from
+    /// `offset`, the end of the last one.
+    ///
+    /// Prefix, delimiter and `^FH` state are replayed from the labels so the
+    /// remaining input parses exactly as it would have in a full pass.
+    fn resume_after(&mut self, labels: Vec<Label>, offset: usize) {
+        for node in labels.iter().flat_map(|label| &label.nodes) {
+            match node {
+                Node::Command { code, args, .. } => {
+                    let ch = args
+                        .first()
+                        .and_then(|a| a.value.as_deref())
+                        .and_then(|v| v.chars().next())
+                        .filter(char::is_ascii);
+                    match (code.as_str(), ch) {
+                        ("^CC" | "~CC", Some(ch)) => self.command_prefix = ch,
+                        ("^CT" | "~CT", Some(ch)) => self.control_prefix = ch,
+                        ("^CD" | "~CD", Some(ch)) => self.delimiter = ch,
+                        ("^XA", _) => self.fh_active = false,
+                        _ => {
+                            if let Some(ce) = self.lookup_command(code) {
+                                if ce.hex_escape_modifier {
+                                    self.fh_active = true;
+                                }
+                                // Leaving field data (or closing the field)
+                                // always resets ^FH.
+                                if ce.closes_field || ce.field_data {
+                                    self.fh_active = false;
+                                }
+                            }
+                        }
+                    }
+                }
+                Node::FieldData { .. } => self.fh_active = false,
+                Node::RawData { .. } | Node::Trivia { .. } => {}
+            }
+        }
+        self.labels = labels;
+        self.push_tokens_from(offset);
+    }
+
+    /// Tokenize `input[offset..]` with the current prefixes and delimiter and
+    /// append the tokens with absolute offsets.
+    fn push_tokens_from(&mut self, offset: usize) {
+        let remaining = &self.input[offset..];
+        if remaining.is_empty() {
+            return;
+        }
+        let new_toks = super::lexer::tokenize_with_config(
+            remaining,
+            self.command_prefix,
+            self.control_prefix,
+            self.delimiter,
+        );
+        for t in new_toks {
+            let abs_start = offset + t.start;
+            let abs_end = offset + t.end;
+            self.toks.push(super::lexer::Token {
+                kind: t.kind,
+                text: &self.input[abs_start..abs_end],
+                start: abs_start,
+                end: abs_end,
+            });
+        }
+    }
+
     // ── Lookup helpers (O(1) via ParserTables cached index) ─────────────
 
     fn lookup_command(&self, code: &str) -> Option<&'a CommandEntry> {
@@ -177,7 +483,7 @@ impl<'a> Parser<'a> {
             } => {
                 let span = Span::new(content_start, self.input.len());
                 // Emit the diagnostic first (borrows command), then move into node.
-                self.diags.push(
+                self.push_diag(|| {
                     Diagnostic::error(
                         codes::PARSER_MISSING_FIELD_SEPARATOR,
                         format!("unterminated raw data for {} at end of input", &command),
@@ -190,8 +496,8 @@ impl<'a> Parser<'a> {
                         "suggested_edit.text" => "^FS",
                         "suggested_edit.position" => "range.end",
                         "suggested_edit.title" => "Insert ^FS (field separator)"
-                    )),
-                );
+                    ))
+                });
                 let data = self.input[content_start..].to_string();
                 if !data.is_empty() {
                     self.nodes.push(Node::RawData {
@@ -213,7 +519,7 @@ impl<'a> Parser<'a> {
                         span: Span::new(content_start, self.input.len()),
                     });
                 }
-                self.diags.push(
+                self.push_diag(|| {
                     Diagnostic::error(
                         codes::PARSER_MISSING_FIELD_SEPARATOR,
                         "missing field separator (^FS) before end of input",
@@ -225,18 +531,18 @@ impl<'a> Parser<'a> {
                         "suggested_edit.text" => "^FS",
                         "suggested_edit.position" => "range.end",
                         "suggested_edit.title" => "Insert ^FS (field separator)"
-                    )),
-                );
+                    ))
+                });
             }
             Mode::Normal => {} // nothing to clean up
         }
 
-        if self.in_label {
-            self.diags.push(
+        if let Some(xa_span) = self.open_label {
+            self.push_diag(|| {
                 Diagnostic::error(
                     codes::PARSER_MISSING_TERMINATOR,
-                    "missing terminator (^XZ)",
-                    Some(Span::new(self.input.len(), self.input.len())),
+                    "missing terminator (^XZ) for label started here",
+                    Some(xa_span),
                 )
                 .with_context(ctx!(
                     "expected" => "^XZ",
@@ -244,8 +550,8 @@ impl<'a> Parser<'a> {
                     "suggested_edit.text" => "^XZ",
                     "suggested_edit.position" => "document.end",
                     "suggested_edit.title" => "Insert ^XZ (label terminator)"
-                )),
-            );
+                ))
+            });
             self.labels.push(Label {
                 nodes: std::mem::take(&mut self.nodes),
             });
@@ -261,17 +567,17 @@ impl<'a> Parser<'a> {
             } else {
                 Span::new(0, self.input.len())
             };
-            self.diags.push(Diagnostic::info(
-                codes::PARSER_NO_LABELS,
-                "no labels detected",
-                Some(span),
-            ));
+            self.push_diag(|| {
+                Diagnostic::info(codes::PARSER_NO_LABELS, "no labels detected", Some(span))
+            });
         }
 
         ParseResult {
             ast: Ast {
                 labels: self.labels,
+                byte_decoded: false,
             },
+            summary: DiagnosticSummary::from_diagnostics(&self.diags),
             diagnostics: self.diags,
         }
     }
@@ -302,11 +608,13 @@ impl<'a> Parser<'a> {
                         _ => break,
                     }
                 }
-                self.diags.push(Diagnostic::warn(
-                    codes::PARSER_STRAY_CONTENT,
-                    "stray content outside of command context",
-                    Some(Span::new(start, end)),
-                ));
+                self.push_diag(|| {
+                    Diagnostic::warn(
+                        codes::PARSER_STRAY_CONTENT,
+                        "stray content outside of command context",
+                        Some(Span::new(start, end)),
+                    )
+                });
             }
         }
     }
@@ -330,14 +638,14 @@ impl<'a> Parser<'a> {
         // If not, emit an error and resync to the next leader so we don't
         // waste time advancing one token at a time through stray content.
         if self.at_end() || !matches!(self.toks[self.pos].kind, TokKind::Value) {
-            self.diags.push(
+            self.push_diag(|| {
                 Diagnostic::error(
                     codes::PARSER_INVALID_COMMAND,
                     "invalid command: expected command code after leader",
                     Some(Span::new(leader_start, leader_start + leader_text.len())),
                 )
-                .with_context(ctx!("command" => leader_text)),
-            );
+                .with_context(ctx!("command" => leader_text))
+            });
             self.skip_to_next_leader();
             return;
         }
@@ -352,14 +660,14 @@ impl<'a> Parser<'a> {
         if head.is_empty() {
             // Snap span end to the next char boundary to avoid panics on multi-byte UTF-8.
             let span_end = Self::next_char_boundary(self.input, code_tok_start + 1);
-            self.diags.push(
+            self.push_diag(|| {
                 Diagnostic::error(
                     codes::PARSER_INVALID_COMMAND,
                     "missing command code after leader",
                     Some(Span::new(leader_start, span_end)),
                 )
-                .with_context(ctx!("command" => leader_text)),
-            );
+                .with_context(ctx!("command" => leader_text))
+            });
             // Resync to next leader — skip past the bad token(s).
             self.skip_to_next_leader();
             return;
@@ -391,14 +699,14 @@ impl<'a> Parser<'a> {
             // Apply the prefix/delimiter change (only ASCII characters allowed)
             if let Some(ch) = arg_char {
                 if !ch.is_ascii() {
-                    self.diags.push(
+                    self.push_diag(|| {
                         Diagnostic::error(
                             codes::PARSER_NON_ASCII_ARG,
                             format!("{} argument must be an ASCII character, got '{}'", code, ch),
                             Some(cmd_span),
                         )
-                        .with_context(ctx!("command" => code.clone())),
-                    );
+                        .with_context(ctx!("command" => code.clone()))
+                    });
                 } else {
                     match code.as_str() {
                         "^CC" | "~CC" => {
@@ -421,10 +729,13 @@ impl<'a> Parser<'a> {
                 }
             }
 
+            let blank_lines_before = self.blank_lines_before(leader_start);
             self.nodes.push(Node::Command {
                 code,
                 args,
                 span: cmd_span,
+                blank_lines_before,
+                joiner: None,
             });
 
             // Re-tokenize remaining input starting after the single-char argument
@@ -434,26 +745,8 @@ impl<'a> Parser<'a> {
                 self.pos += 1;
             }
             // Re-tokenize from arg_end onward with updated prefixes and delimiter.
-            let remaining = &self.input[arg_end..];
-            if !remaining.is_empty() {
-                self.toks.truncate(self.pos);
-                let new_toks = super::lexer::tokenize_with_config(
-                    remaining,
-                    self.command_prefix,
-                    self.control_prefix,
-                    self.delimiter,
-                );
-                for t in new_toks {
-                    let abs_start = arg_end + t.start;
-                    let abs_end = arg_end + t.end;
-                    self.toks.push(super::lexer::Token {
-                        kind: t.kind,
-                        text: &self.input[abs_start..abs_end],
-                        start: abs_start,
-                        end: abs_end,
-                    });
-                }
-            }
+            self.toks.truncate(self.pos);
+            self.push_tokens_from(arg_end);
             return;
         }
 
@@ -520,7 +813,7 @@ impl<'a> Parser<'a> {
                 if has_opcode_head {
                     break;
                 }
-                self.diags.push(
+                self.push_diag(||
                     Diagnostic::error(
                         codes::PARSER_INVALID_COMMAND,
                         format!(
@@ -551,27 +844,42 @@ impl<'a> Parser<'a> {
             rem_end
         };
         let cmd_span = Span::new(leader_start, command_end);
+        let blank_lines_before = self.blank_lines_before(leader_start);
 
         // ── Emit unknown-command warning (distinct code: ZPL.PARSER.1002) ──
-        if self.has_tables() && !self.is_known_code(&code) {
-            self.diags.push(
+        if self.options.emit_unknown_command_diagnostics
+            && self.options.collect_diagnostics
+            && self.has_tables()
+            && !self.is_known_code(&code)
+        {
+            self.push_diag(|| {
                 Diagnostic::warn(
                     codes::PARSER_UNKNOWN_COMMAND,
                     format!("unknown command {}", code),
                     Some(cmd_span),
                 )
-                .with_context(ctx!("command" => code.clone())),
-            );
+                .with_context(ctx!("command" => code.clone()))
+            });
         }
 
         // ── Label delimiters (^XA / ^XZ) ───────────────────────────
         if code == "^XA" {
-            if self.in_label {
+            // Printers treat a new ^XA as the start of a fresh format, so an
+            // unterminated label is closed implicitly (and reported).
+            if let Some(xa_span) = self.open_label {
+                self.push_diag(|| {
+                    Diagnostic::error(
+                        codes::PARSER_MISSING_TERMINATOR,
+                        "missing terminator (^XZ) before the next ^XA",
+                        Some(xa_span),
+                    )
+                    .with_context(ctx!("expected" => "^XZ"))
+                });
                 self.labels.push(Label {
                     nodes: std::mem::take(&mut self.nodes),
                 });
             }
-            self.in_label = true;
+            self.open_label = Some(cmd_span);
             // nodes is already empty after `take` above; no need to reallocate
             self.fh_active = false;
             self.mode = Mode::Normal;
@@ -579,6 +887,8 @@ impl<'a> Parser<'a> {
                 code,
                 args: Vec::new(),
                 span: cmd_span,
+                blank_lines_before,
+                joiner: None,
             });
             return;
         }
@@ -587,7 +897,7 @@ impl<'a> Parser<'a> {
             // interruption and switches back to Normal before we get here.
             // This check is a safety net for edge cases.
             if matches!(self.mode, Mode::FieldData { .. }) {
-                self.diags.push(
+                self.push_diag(|| {
                     Diagnostic::error(
                         codes::PARSER_MISSING_FIELD_SEPARATOR,
                         "missing field separator (^FS) before ^XZ",
@@ -599,8 +909,8 @@ impl<'a> Parser<'a> {
                         "suggested_edit.text" => "^FS",
                         "suggested_edit.position" => "range.start",
                         "suggested_edit.title" => "Insert ^FS (field separator)"
-                    )),
-                );
+                    ))
+                });
                 self.mode = Mode::Normal;
                 self.fh_active = false;
             }
@@ -608,11 +918,13 @@ impl<'a> Parser<'a> {
                 code,
                 args: Vec::new(),
                 span: cmd_span,
+                blank_lines_before,
+                joiner: None,
             });
             self.labels.push(Label {
                 nodes: std::mem::take(&mut self.nodes),
             });
-            self.in_label = false;
+            self.open_label = None;
             return;
         }
 
@@ -635,7 +947,7 @@ impl<'a> Parser<'a> {
                     .unwrap_or(SpacingPolicy::Forbid);
                 match spacing_policy {
                     SpacingPolicy::Forbid if starts_with_ws => {
-                        self.diags.push(
+                        self.push_diag(||
                             Diagnostic::error(
                                 codes::PARSER_INVALID_COMMAND,
                                 format!(
@@ -650,7 +962,7 @@ impl<'a> Parser<'a> {
                         );
                     }
                     SpacingPolicy::Require if !starts_with_ws => {
-                        self.diags.push(
+                        self.push_diag(||
                             Diagnostic::error(
                                 codes::PARSER_INVALID_COMMAND,
                                 format!("{} expects a space between opcode and arguments", code),
@@ -667,9 +979,9 @@ impl<'a> Parser<'a> {
         }
 
         // ── Handle field data commands (^FD, ^FV): entire raw content is a single arg ──
-        let args = if is_field_data {
+        let (args, joiner) = if is_field_data {
             // Field data: entire raw content is literal text, not comma-separated
-            if raw.is_empty() {
+            let args = if raw.is_empty() {
                 Vec::new()
             } else {
                 vec![ArgSlot {
@@ -677,7 +989,8 @@ impl<'a> Parser<'a> {
                     presence: Presence::Value,
                     value: Some(raw.to_string()),
                 }]
-            }
+            };
+            (args, None)
         } else {
             self.parse_args(&code, &raw)
         };
@@ -696,6 +1009,8 @@ impl<'a> Parser<'a> {
                 code: code.clone(),
                 args,
                 span: cmd_span,
+                blank_lines_before,
+                joiner,
             });
             let content_start = if self.at_end() {
                 self.input.len()
@@ -712,6 +1027,8 @@ impl<'a> Parser<'a> {
                 code,
                 args,
                 span: cmd_span,
+                blank_lines_before,
+                joiner,
             });
             if is_field_data {
                 let content_start = if self.at_end() {
@@ -813,7 +1130,10 @@ impl<'a> Parser<'a> {
 
     // ── Argument parsing ────────────────────────────────────────────────
 
-    fn parse_args(&self, code: &str, raw: &str) -> Vec<ArgSlot> {
+    /// Split `raw` into argument slots. Also returns the joiner actually
+    /// used when it differs from the signature joiner and separated more
+    /// than one argument, so the emitter can reproduce it.
+    fn parse_args(&self, code: &str, raw: &str) -> (Vec<ArgSlot>, Option<String>) {
         let (sig_joiner, param_keys) = self.get_signature(code);
 
         // If the command's signature uses the default comma joiner, apply
@@ -823,10 +1143,10 @@ impl<'a> Parser<'a> {
         let joiner = if sig_joiner == "," {
             self.delimiter.to_string()
         } else {
-            sig_joiner
+            sig_joiner.clone()
         };
 
-        let raw_trimmed = raw.trim();
+        let raw_trimmed = raw.trim_ascii();
         let preserve_verbatim = joiner.is_empty();
 
         let mut parts: Vec<String> = if raw_trimmed.is_empty() {
@@ -839,6 +1159,16 @@ impl<'a> Parser<'a> {
             raw_trimmed.split(&joiner).map(|s| s.to_string()).collect()
         };
 
+        // A quoted final parameter (e.g. the ^FN prompt) may contain the
+        // joiner itself; keep it whole instead of overflowing the signature.
+        if let Some(last) = param_keys.len().checked_sub(1)
+            && parts.len() > param_keys.len()
+            && parts[last].trim_start().starts_with('"')
+        {
+            let tail = parts.split_off(last).join(&joiner);
+            parts.push(tail);
+        }
+
         // Spec-driven parameter splitting (e.g., ^A font+orientation → two parts)
         if let Some(split_rule) = self
             .lookup_command(code)
@@ -901,7 +1231,7 @@ impl<'a> Parser<'a> {
             let normalized = if preserve_verbatim {
                 p.as_str()
             } else {
-                p.trim()
+                p.trim_ascii()
             };
             if normalized.is_empty() {
                 args.push(ArgSlot {
@@ -917,7 +1247,9 @@ impl<'a> Parser<'a> {
                 });
             }
         }
-        args
+        let source_joiner =
+            (joiner != sig_joiner && raw_trimmed.contains(joiner.as_str())).then_some(joiner);
+        (args, source_joiner)
     }
 
     fn get_signature(&self, code: &str) -> (String, Vec<String>) {
@@ -1012,7 +1344,7 @@ impl<'a> Parser<'a> {
                             });
                         }
                         let leader_len = self.toks[self.pos].text.len();
-                        self.diags.push(
+                        self.push_diag(||
                             Diagnostic::error(
                                 codes::PARSER_INVALID_COMMAND,
                                 format!(
@@ -1045,14 +1377,14 @@ impl<'a> Parser<'a> {
                             span: Span::new(content_start, leader_start),
                         });
                     }
-                    self.diags.push(
+                    self.push_diag(|| {
                         Diagnostic::warn(
                             codes::PARSER_FIELD_DATA_INTERRUPTED,
                             format!("field data interrupted by {} before ^FS", interrupter),
                             Some(Span::new(leader_start, leader_start + 1)),
                         )
-                        .with_context(ctx!("command" => interrupter)),
-                    );
+                        .with_context(ctx!("command" => interrupter))
+                    });
                     self.mode = Mode::Normal;
                     self.fh_active = false; // Reset ^FH on interruption
                     return;
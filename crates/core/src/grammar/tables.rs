@@ -1,2 +1,4 @@
 /// Re-exported spec table types used by the parser and validator.
-pub use zpl_toolchain_spec_tables::{CommandEntry, Constraint, ParserTables, Signature};
+pub use zpl_toolchain_spec_tables::{
+    CommandEntry, Constraint, ParserTables, Signature, TableLoadError,
+};
@@ -5,12 +5,22 @@
 //! [`validate_with_profile`] for validation, and [`emit_zpl`] for formatted
 //! output.
 
+/// ACS run-length compression for `^GFA`/`~DG` ASCII hex graphics.
+pub mod gf_rle;
 /// ZPL grammar: lexer, parser, AST, emitter, and related utilities.
 pub mod grammar;
 /// Hex escape processing for `^FH` field data.
 pub mod hex_escape;
+/// Monochrome PNG rendering of labels (requires the `render` feature).
+#[cfg(feature = "render")]
+pub mod render;
+/// JSON Schemas for serialized result types (requires the `schema` feature).
+#[cfg(feature = "schema")]
+pub mod schema;
 /// Shared typed state tracking for validator/renderer.
 pub mod state;
+/// `{{name}}` template variable substitution in field data and comments.
+pub mod template;
 /// AST validation against spec tables and printer profiles.
 pub mod validate;
 
@@ -19,25 +29,37 @@ pub mod validate;
 // remain available for less common types.
 
 // Parser
-pub use grammar::parser::{ParseResult, parse_str, parse_with_tables};
+pub use grammar::parser::{
+    ParseOptions, ParseResult, parse_bytes, parse_bytes_with_decoder, parse_str,
+    parse_with_options, parse_with_tables, reparse, reparse_with_tables,
+};
 
 // AST
-pub use grammar::ast::{ArgSlot, Ast, Label, Node, Presence};
+pub use grammar::ast::{ArgSlot, Ast, Label, Node, Presence, command_histogram, select_label};
 
 // Emitter
-pub use grammar::emit::{Compaction, EmitConfig, Indent, emit_zpl, strip_spans};
+pub use grammar::emit::{
+    Compaction, EmitConfig, Indent, LineEnding, emit_zpl, emit_zpl_bytes, emit_zpl_with_source,
+    strip_spans,
+};
 
 // Diagnostics (re-exported from the diagnostics crate)
-pub use grammar::diag::{Diagnostic, Severity, Span, codes};
+pub use grammar::diag::{Diagnostic, DiagnosticSummary, Severity, Span, codes};
 
 // Validator
-pub use validate::{ValidationResult, validate_with_profile};
+pub use validate::{
+    ConstraintCtx, CustomConstraints, ValidationOptions, ValidationResult, diagnostics_for,
+    validate_against_profiles, validate_with_options, validate_with_profile,
+};
+
+// Templates
+pub use template::substitute_variables;
 
 // Shared state contracts
-pub use state::{LabelValueState, ResolvedLabelState};
+pub use state::{LabelValueState, NamedField, ResolvedLabelState};
 
 // Tables
-pub use grammar::tables::ParserTables;
+pub use grammar::tables::{ParserTables, TableLoadError};
 
 // Serialization helpers
-pub use grammar::dump::to_pretty_json;
+pub use grammar::dump::{WithSpans, to_compact_json, to_pretty_json};
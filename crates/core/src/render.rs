@@ -0,0 +1,510 @@
+//! Monochrome label preview rasterizer and PNG encoder.
+//!
+//! This is an approximation for previews, not a printer emulator: text is
+//! drawn as solid glyph cells, barcodes as data-derived bar/module patterns
+//! of the right footprint, and `^GB` boxes/lines exactly. Field orientation
+//! is ignored. Output is deterministic, so PNG hashes can serve as golden
+//! values for spec examples.
+
+use crate::grammar::ast::{ArgSlot, Label, Node};
+use crate::grammar::parser::parse_with_tables;
+use crate::grammar::tables::ParserTables;
+use zpl_toolchain_profile::Profile;
+
+/// Errors that can occur when rendering ZPL to PNG.
+#[derive(Debug, thiserror::Error)]
+pub enum RenderError {
+    /// The requested label does not exist in the input.
+    #[error("label {index} not found (input contains {count} label(s))")]
+    LabelNotFound {
+        /// Zero-based index of the requested label.
+        index: usize,
+        /// Number of labels in the input.
+        count: usize,
+    },
+}
+
+/// Default label size (4x6 in) at the given resolution, in dots.
+pub fn default_label_size(dpi: u32) -> (u32, u32) {
+    (dpi * 4, dpi * 6)
+}
+
+/// Default bitmap size for a profile: its page size, else 4x6 in at its DPI.
+pub fn profile_label_size(profile: Option<&Profile>) -> (u32, u32) {
+    let (width, height) = default_label_size(profile.map_or(203, |p| p.dpi));
+    let page = profile.and_then(|p| p.page.as_ref());
+    (
+        page.and_then(|p| p.width_dots).unwrap_or(width),
+        page.and_then(|p| p.height_dots).unwrap_or(height),
+    )
+}
+
+/// Parse `zpl` and rasterize the label at zero-based `index`.
+pub fn render_label(
+    zpl: &str,
+    tables: &ParserTables,
+    profile: Option<&Profile>,
+    index: usize,
+) -> Result<Bitmap, RenderError> {
+    let ast = parse_with_tables(zpl, Some(tables)).ast;
+    let label = ast.labels.get(index).ok_or(RenderError::LabelNotFound {
+        index,
+        count: ast.labels.len(),
+    })?;
+    Ok(rasterize_label(label, profile_label_size(profile)))
+}
+
+/// BLAKE3 hash (lowercase hex) of the first label of `zpl` rendered to PNG.
+///
+/// This is the value stored in a spec example's `pngHash`.
+pub fn example_png_hash(
+    zpl: &str,
+    tables: &ParserTables,
+    profile: &Profile,
+) -> Result<String, RenderError> {
+    let bitmap = render_label(zpl, tables, Some(profile), 0)?;
+    Ok(blake3::hash(&encode_png(&bitmap)).to_hex().to_string())
+}
+
+/// Upper bound for the bitmap width, in dots: the widest printheads are
+/// 8.5 inches at 600 dpi.
+const MAX_WIDTH: u32 = 5_120;
+
+/// Upper bound for width × height, in dots (one byte per dot in memory).
+/// Longer labels are cut off at the bottom.
+const MAX_AREA: u32 = 16_000_000;
+
+/// Largest magnitude accepted for a numeric argument. ZPL dimensions and
+/// positions top out at 32000 dots, and clamping keeps the drawing
+/// arithmetic far from `i64` overflow on hostile input.
+const MAX_ARG: i64 = 32_000;
+
+/// A 1-bit label bitmap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitmap {
+    width: u32,
+    height: u32,
+    pixels: Vec<bool>,
+}
+
+/// How a drawing operation combines with existing pixels.
+#[derive(Clone, Copy)]
+enum Ink {
+    Black,
+    White,
+    /// `^FR` field reverse.
+    Invert,
+}
+
+impl Bitmap {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width as usize * height as usize],
+        }
+    }
+
+    /// Bitmap width in dots.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Bitmap height in dots.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Whether the dot at (`x`, `y`) is black. Out-of-range dots are white.
+    pub fn is_black(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height && self.pixels[(y * self.width + x) as usize]
+    }
+
+    fn fill_rect(&mut self, x: i64, y: i64, w: i64, h: i64, ink: Ink) {
+        let x0 = x.clamp(0, self.width as i64) as usize;
+        let y0 = y.clamp(0, self.height as i64) as usize;
+        let x1 = (x + w).clamp(0, self.width as i64) as usize;
+        let y1 = (y + h).clamp(0, self.height as i64) as usize;
+        for row in y0..y1 {
+            let start = row * self.width as usize;
+            for px in &mut self.pixels[start + x0..start + x1] {
+                *px = match ink {
+                    Ink::Black => true,
+                    Ink::White => false,
+                    Ink::Invert => !*px,
+                };
+            }
+        }
+    }
+}
+
+/// Field state accumulated between `^FO`/`^FT` and `^FS`.
+#[derive(Default)]
+struct Field {
+    x: i64,
+    y: i64,
+    baseline: bool,
+    reverse: bool,
+    font: Option<(i64, i64)>,
+    barcode: Option<Barcode>,
+    data: String,
+}
+
+/// The barcode command active in the current field.
+struct Barcode {
+    matrix: bool,
+    height: Option<i64>,
+    module: Option<i64>,
+}
+
+/// 2D symbologies drawn as a square module matrix.
+const MATRIX_BARCODES: &[&str] = &["^BQ", "^BX", "^B0", "^BO", "^BD", "^B7", "^BF"];
+
+/// Rasterize one label onto a bitmap of the given size.
+///
+/// `^PW`/`^LL` in the label override `default_size`.
+pub fn rasterize_label(label: &Label, default_size: (u32, u32)) -> Bitmap {
+    let (mut width, mut height) = default_size;
+    for node in &label.nodes {
+        if let Node::Command { code, args, .. } = node {
+            match code.as_str() {
+                "^PW" => width = int_arg(args, 0).map_or(width, |v| v as u32),
+                "^LL" => height = int_arg(args, 0).map_or(height, |v| v as u32),
+                _ => {}
+            }
+        }
+    }
+    let width = width.clamp(1, MAX_WIDTH);
+    let mut bitmap = Bitmap::new(width, height.clamp(1, MAX_AREA / width));
+
+    let mut home = (0i64, 0i64);
+    let mut default_font = (9i64, 5i64);
+    let mut by = (2i64, 10i64);
+    let mut field = Field::default();
+    for node in &label.nodes {
+        match node {
+            Node::Command { code, args, .. } => match code.as_str() {
+                "^LH" => {
+                    home = (
+                        int_arg(args, 0).unwrap_or(home.0),
+                        int_arg(args, 1).unwrap_or(home.1),
+                    )
+                }
+                "^CF" => {
+                    let h = int_arg(args, 1).unwrap_or(default_font.0);
+                    default_font = (h, int_arg(args, 2).unwrap_or(h));
+                }
+                "^BY" => {
+                    by = (
+                        int_arg(args, 0).unwrap_or(by.0),
+                        int_arg(args, 2).unwrap_or(by.1),
+                    )
+                }
+                "^FO" | "^FT" => {
+                    field.x = home.0 + int_arg(args, 0).unwrap_or(0);
+                    field.y = home.1 + int_arg(args, 1).unwrap_or(0);
+                    field.baseline = code == "^FT";
+                }
+                "^FR" => field.reverse = true,
+                "^A" | "^A@" => {
+                    let h = keyed_int_arg(args, "h").unwrap_or(default_font.0);
+                    field.font = Some((h, keyed_int_arg(args, "w").unwrap_or(h)));
+                }
+                "^FD" | "^FV" => {
+                    if let Some(data) = args.first().and_then(|slot| slot.value.as_deref()) {
+                        field.data.push_str(data);
+                    }
+                }
+                "^GB" => draw_box(&mut bitmap, &field, args),
+                "^FS" => {
+                    draw_field(&mut bitmap, &field, default_font, by);
+                    field = Field::default();
+                }
+                code if code.starts_with("^B") && code != "^BY" => {
+                    let matrix = MATRIX_BARCODES.contains(&code);
+                    field.barcode = Some(Barcode {
+                        matrix,
+                        height: keyed_int_arg(args, "h").filter(|_| !matrix),
+                        module: match code {
+                            "^BQ" => int_arg(args, 2),
+                            "^BX" => int_arg(args, 1),
+                            _ => None,
+                        },
+                    });
+                }
+                _ => {}
+            },
+            Node::FieldData { content, .. } => field.data.push_str(content),
+            _ => {}
+        }
+    }
+    bitmap
+}
+
+/// Draw a `^GB` graphic box at the field origin.
+fn draw_box(bitmap: &mut Bitmap, field: &Field, args: &[ArgSlot]) {
+    let t = int_arg(args, 2).unwrap_or(1).max(1);
+    let w = int_arg(args, 0).unwrap_or(t).max(t);
+    let h = int_arg(args, 1).unwrap_or(t).max(t);
+    let ink = if field.reverse {
+        Ink::Invert
+    } else if str_arg(args, 3) == Some("W") {
+        Ink::White
+    } else {
+        Ink::Black
+    };
+    let (x, y) = (field.x, if field.baseline { field.y - h } else { field.y });
+    if t * 2 >= w || t * 2 >= h {
+        bitmap.fill_rect(x, y, w, h, ink);
+    } else {
+        bitmap.fill_rect(x, y, w, t, ink);
+        bitmap.fill_rect(x, y + h - t, w, t, ink);
+        bitmap.fill_rect(x, y + t, t, h - 2 * t, ink);
+        bitmap.fill_rect(x + w - t, y + t, t, h - 2 * t, ink);
+    }
+}
+
+/// Draw the field's text or barcode at `^FS`.
+fn draw_field(bitmap: &mut Bitmap, field: &Field, default_font: (i64, i64), by: (i64, i64)) {
+    if field.data.is_empty() {
+        return;
+    }
+    let ink = if field.reverse {
+        Ink::Invert
+    } else {
+        Ink::Black
+    };
+    match &field.barcode {
+        Some(barcode) if barcode.matrix => {
+            let module = barcode.module.unwrap_or(by.0).max(1);
+            // Square symbol with roughly one module per data bit.
+            let side = ((field.data.len() * 8) as f64).sqrt().ceil() as i64 + 4;
+            let top = if field.baseline {
+                field.y - side * module
+            } else {
+                field.y
+            };
+            let bits = pattern_bits(&field.data);
+            for row in 0..side {
+                for col in 0..side {
+                    let border = row == 0 || col == 0 || row == side - 1 || col == side - 1;
+                    let idx = (row * side + col) as usize;
+                    if border || bits(idx) {
+                        bitmap.fill_rect(
+                            field.x + col * module,
+                            top + row * module,
+                            module,
+                            module,
+                            ink,
+                        );
+                    }
+                }
+            }
+        }
+        Some(barcode) => {
+            let module = barcode.module.unwrap_or(by.0).max(1);
+            let height = barcode.height.unwrap_or(by.1).max(1);
+            let top = if field.baseline {
+                field.y - height
+            } else {
+                field.y
+            };
+            // Guard bars, then one bar per set bit of each data byte.
+            let mut modules = vec![true, false, true];
+            for byte in field.data.bytes() {
+                modules.extend((0..8).rev().map(|bit| byte >> bit & 1 == 1));
+                modules.push(false);
+            }
+            modules.extend([true, false, true]);
+            for (i, bar) in modules.iter().enumerate() {
+                if *bar {
+                    bitmap.fill_rect(field.x + i as i64 * module, top, module, height, ink);
+                }
+            }
+        }
+        None => {
+            let (h, w) = field.font.unwrap_or(default_font);
+            let top = if field.baseline { field.y - h } else { field.y };
+            for (i, ch) in field.data.chars().enumerate() {
+                if ch.is_whitespace() {
+                    continue;
+                }
+                // Solid glyph cell inset within the character box.
+                bitmap.fill_rect(
+                    field.x + i as i64 * w + w / 8,
+                    top + h / 8,
+                    (w - w / 4).max(1),
+                    (h - h / 4).max(1),
+                    ink,
+                );
+            }
+        }
+    }
+}
+
+/// Deterministic pseudo-random module pattern derived from field data.
+fn pattern_bits(data: &str) -> impl Fn(usize) -> bool {
+    let seed = data.bytes().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    });
+    move |idx| {
+        let mut x = seed ^ (idx as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        x ^= x >> 33;
+        x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        x ^= x >> 33;
+        x & 1 == 1
+    }
+}
+
+fn str_arg(args: &[ArgSlot], idx: usize) -> Option<&str> {
+    args.get(idx)
+        .and_then(|slot| slot.value.as_deref())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+fn int_arg(args: &[ArgSlot], idx: usize) -> Option<i64> {
+    str_arg(args, idx).and_then(parse_int)
+}
+
+fn keyed_int_arg(args: &[ArgSlot], key: &str) -> Option<i64> {
+    args.iter()
+        .find(|slot| slot.key.as_deref() == Some(key))
+        .and_then(|slot| slot.value.as_deref())
+        .and_then(|v| parse_int(v.trim()))
+}
+
+/// Parse an integer argument, clamped to `±MAX_ARG`.
+fn parse_int(value: &str) -> Option<i64> {
+    value
+        .parse::<i64>()
+        .ok()
+        .map(|v| v.clamp(-MAX_ARG, MAX_ARG))
+}
+
+// ── PNG encoding ────────────────────────────────────────────────────────
+
+/// Encode a bitmap as a 1-bit grayscale PNG.
+///
+/// Image data is stored in uncompressed deflate blocks, which keeps the
+/// encoder dependency-free at the cost of file size.
+pub fn encode_png(bitmap: &Bitmap) -> Vec<u8> {
+    let row_bytes = (bitmap.width as usize).div_ceil(8);
+    let mut raw = Vec::with_capacity((row_bytes + 1) * bitmap.height as usize);
+    for row in bitmap.pixels.chunks(bitmap.width as usize) {
+        raw.push(0); // filter: none
+        let mut packed = vec![0xffu8; row_bytes];
+        for (x, black) in row.iter().enumerate() {
+            if *black {
+                packed[x / 8] &= !(0x80 >> (x % 8));
+            }
+        }
+        raw.extend_from_slice(&packed);
+    }
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&bitmap.width.to_be_bytes());
+    ihdr.extend_from_slice(&bitmap.height.to_be_bytes());
+    ihdr.extend_from_slice(&[1, 0, 0, 0, 0]); // 1-bit grayscale, no interlace
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &ihdr);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wrap data in a zlib stream of stored (uncompressed) deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + u32::from(*byte)) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_reference_value() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+    }
+
+    #[test]
+    fn graphic_box_outline_leaves_interior_white() {
+        let label = crate::grammar::parser::parse_str("^XA^FO10,10^GB50,40,3^FS^XZ")
+            .ast
+            .labels
+            .remove(0);
+        let bitmap = rasterize_label(&label, (100, 80));
+        let at = |x, y| bitmap.is_black(x, y);
+        assert!(at(10, 10) && at(59, 49) && at(12, 30));
+        assert!(!at(30, 30) && !at(9, 10) && !at(60, 10));
+    }
+
+    #[test]
+    fn oversized_label_is_capped() {
+        let label = crate::grammar::parser::parse_str("^XA^PW32000^LL32000^XZ")
+            .ast
+            .labels
+            .remove(0);
+        let bitmap = rasterize_label(&label, (100, 80));
+        assert_eq!(bitmap.width(), MAX_WIDTH);
+        assert!(bitmap.width() * bitmap.height() <= MAX_AREA);
+    }
+
+    #[test]
+    fn huge_arguments_are_clamped() {
+        let label = crate::grammar::parser::parse_str(
+            "^XA^FO10,10^GB5000000000000000000,-9223372036854775808,3^FS\
+             ^FO9223372036854775807,10^GB1,1,5000000000000000000^FS\
+             ^BY9223372036854775807^FO0,90^BCN,9223372036854775807^FDX^FS\
+             ^LH9000000000000000000,1^FO1,1^FDX^FS^XZ",
+        )
+        .ast
+        .labels
+        .remove(0);
+        let bitmap = rasterize_label(&label, (100, 80));
+        assert!(bitmap.is_black(10, 10));
+    }
+}
@@ -0,0 +1,23 @@
+//! JSON Schema documents for the toolchain's serialized output types.
+//!
+//! The schemas are derived from the same serde types the CLI and bindings
+//! serialize, so they always describe the current JSON contract. Available
+//! with the `schema` feature.
+
+use crate::{Diagnostic, ParseResult, ValidationResult};
+use serde_json::Value;
+
+/// JSON Schema for a single [`Diagnostic`].
+pub fn diagnostic_schema() -> Value {
+    schemars::schema_for!(Diagnostic).to_value()
+}
+
+/// JSON Schema for a [`ValidationResult`].
+pub fn validation_result_schema() -> Value {
+    schemars::schema_for!(ValidationResult).to_value()
+}
+
+/// JSON Schema for a [`ParseResult`] (AST plus parser diagnostics).
+pub fn parse_result_schema() -> Value {
+    schemars::schema_for!(ParseResult).to_value()
+}
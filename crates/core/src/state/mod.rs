@@ -6,7 +6,8 @@
 
 use crate::grammar::ast::ArgSlot;
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use zpl_toolchain_diagnostics::Span;
 
 /// Unit system for measurement conversion.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
@@ -38,6 +39,48 @@ pub struct DeviceState {
     pub units: Units,
     /// DPI for unit conversion (from profile or `^MU`).
     pub dpi: Option<u32>,
+    /// Absolute darkness from the most recent `~SD`, if any.
+    pub darkness: Option<f64>,
+    /// Character set selected by the most recent `^CI`, if any.
+    pub character_set: Option<u32>,
+    /// Formats saved with `^DF` earlier in the job, keyed by normalized name.
+    pub stored_formats: HashMap<String, StoredFormat>,
+    /// Fonts downloaded earlier in the job, as normalized `D:NAME.EXT` paths.
+    pub downloaded_fonts: HashSet<String>,
+    /// `^CW` font aliases, mapping each letter to its normalized font path.
+    pub font_aliases: HashMap<char, String>,
+    /// Whether the most recent `^MC` was `^MCN`, so the printed image stays
+    /// as the background of the next label.
+    pub map_retained: bool,
+    /// `^FD` fields in the image retained by `^MCN` (`^FV` data is cleared
+    /// after each label, so it is never retained).
+    pub retained_fields: Vec<RetainedField>,
+    /// Relative darkness from the most recent `^MD`, if any.
+    pub darkness_delta: Option<f64>,
+    /// Whether `^JMB` selected half density, where each format dot prints
+    /// as two printhead dots in each direction.
+    pub half_density: bool,
+}
+
+/// A `^FD` field printed into the image retained by `^MCN`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RetainedField {
+    /// Positioning command of the field (`^FO` or `^FT`).
+    pub command: String,
+    /// Field origin x, as written (before `^LH`).
+    pub x: f64,
+    /// Field origin y, as written (before `^LH`).
+    pub y: f64,
+}
+
+/// Field numbers of a format saved with `^DF`.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct StoredFormat {
+    /// Every `^FN` number defined by the format.
+    pub field_numbers: BTreeSet<u32>,
+    /// `^FN` numbers without default field data; these print blank unless
+    /// the recalling label supplies data.
+    pub placeholders: BTreeSet<u32>,
 }
 
 impl DeviceState {
@@ -66,10 +109,83 @@ impl DeviceState {
             self.dpi = Some(dpi);
         }
     }
+
+    /// Applies `^MC` map clear (`N` = keep the image for the next label).
+    pub fn apply_mc(&mut self, args: &[ArgSlot]) {
+        self.map_retained = args
+            .first()
+            .and_then(|a| a.value.as_deref())
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("N"));
+    }
+
+    /// Applies `^MD` relative darkness.
+    pub fn apply_md(&mut self, args: &[ArgSlot]) {
+        if let Some(delta) = args
+            .first()
+            .and_then(|a| a.value.as_deref())
+            .and_then(|s| s.trim().parse::<f64>().ok())
+        {
+            self.darkness_delta = Some(delta);
+        }
+    }
+
+    /// Applies `^JM` dots-per-millimeter mode (`A` = full, `B` = half density).
+    pub fn apply_jm(&mut self, args: &[ArgSlot]) {
+        self.half_density = args
+            .first()
+            .and_then(|a| a.value.as_deref())
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("B"));
+    }
+
+    /// Convert a printhead dot count (e.g. a profile page size) to the
+    /// format dot grid, which `^JMB` coarsens by half.
+    pub fn printhead_to_format_dots(&self, dots: f64) -> f64 {
+        if self.half_density { dots / 2.0 } else { dots }
+    }
+
+    /// Applies `~SD` absolute darkness.
+    pub fn apply_sd(&mut self, args: &[ArgSlot]) {
+        if let Some(darkness) = args
+            .first()
+            .and_then(|a| a.value.as_deref())
+            .and_then(|s| s.trim().parse::<f64>().ok())
+        {
+            self.darkness = Some(darkness);
+        }
+    }
+
+    /// Applies `^CI` character set selection.
+    pub fn apply_ci(&mut self, args: &[ArgSlot]) {
+        if let Some(set) = args
+            .first()
+            .and_then(|a| a.value.as_deref())
+            .and_then(|s| s.trim().parse::<u32>().ok())
+        {
+            self.character_set = Some(set);
+        }
+    }
+
+    /// Whether the active `^CI` selects a Unicode encoding (`^CI28` UTF-8,
+    /// `^CI29`/`^CI30` UTF-16), where field data is decoded into characters
+    /// rather than consumed byte by byte.
+    pub fn is_unicode_encoding(&self) -> bool {
+        matches!(self.character_set, Some(28..=30))
+    }
+
+    /// Length of `data` as the printer counts it under the active encoding:
+    /// characters for Unicode encodings, UTF-8 bytes for single-byte sets.
+    pub fn field_data_len(&self, data: &str) -> usize {
+        if self.is_unicode_encoding() {
+            data.chars().count()
+        } else {
+            data.len()
+        }
+    }
 }
 
 /// Typed barcode defaults from `^BY`.
 #[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BarcodeDefaults {
     /// Default module width in dots.
     pub module_width: Option<u32>,
@@ -81,6 +197,7 @@ pub struct BarcodeDefaults {
 
 /// Typed font defaults from `^CF`.
 #[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FontDefaults {
     /// Default font identifier.
     pub font: Option<char>,
@@ -92,6 +209,7 @@ pub struct FontDefaults {
 
 /// Typed field orientation defaults from `^FW`.
 #[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct FieldOrientationDefaults {
     /// Default orientation (N/R/I/B).
     pub orientation: Option<char>,
@@ -101,6 +219,7 @@ pub struct FieldOrientationDefaults {
 
 /// Typed layout-affecting settings used by validator and renderer.
 #[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LayoutDefaults {
     /// Print width (`^PW`) in dots.
     pub print_width: Option<f64>,
@@ -120,6 +239,7 @@ pub struct LayoutDefaults {
 
 /// Typed label-home offset from `^LH` (stored in dots).
 #[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LabelHome {
     /// Home X offset in dots.
     pub x: f64,
@@ -135,6 +255,7 @@ impl Default for LabelHome {
 
 /// Per-label typed producer values.
 #[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LabelValueState {
     /// Barcode defaults resolved from `^BY`.
     pub barcode: BarcodeDefaults,
@@ -150,13 +271,36 @@ pub struct LabelValueState {
 
 /// Stable renderer-ready snapshot of resolved per-label state.
 #[derive(Debug, Default, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ResolvedLabelState {
+    /// Zero-based position of this label in the document.
+    pub index: usize,
+    /// Source span from the label's first node to its last node.
+    pub span: Option<Span>,
+    /// Number of command nodes in the label, including `^XA`/`^XZ`.
+    pub command_count: usize,
+    /// Number of error-severity validation diagnostics raised for this label.
+    pub error_count: usize,
     /// Typed values produced by stateful commands in this label.
     pub values: LabelValueState,
     /// Effective print width after profile + in-label overrides, in dots.
     pub effective_width: Option<f64>,
     /// Effective label length after profile + in-label overrides, in dots.
     pub effective_height: Option<f64>,
+    /// `^FN` fields in source order, for hosts presenting a data-merge form.
+    pub fields: Vec<NamedField>,
+}
+
+/// A numbered field declared by `^FN`, with its optional prompt name.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NamedField {
+    /// Field number (`^FN` first argument).
+    pub number: u32,
+    /// Prompt name (`^FN` second argument) with surrounding quotes removed.
+    pub name: Option<String>,
+    /// Source span of the `^FN` command.
+    pub span: Span,
 }
 
 impl LabelValueState {
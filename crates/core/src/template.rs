@@ -0,0 +1,357 @@
+//! `{{name}}` template variable substitution.
+//!
+//! Placeholders are replaced only inside free-text command content — field
+//! data (`^FD`, `^FV`) and comments (`^FX`) — so a substitution can never
+//! rewrite an opcode or an argument list. Content runs until the next
+//! command leader; `^CC`/`~CC` and `^CT`/`~CT` prefix changes are followed.
+//!
+//! A value may not end its content early: command prefix characters in a
+//! field-data value are written as `^FH` hex escapes, and a comment value
+//! that contains one is rejected.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use crate::grammar::diag::{Diagnostic, Span, codes};
+use crate::validate::ctx;
+
+/// Opcodes whose content may hold placeholders.
+const TEXT_OPCODES: [&str; 3] = ["FD", "FV", "FX"];
+
+/// Indicators tried, in order, when a field needs a `^FH` added.
+const HEX_INDICATORS: [char; 8] = ['_', '#', '|', '\\', '!', '@', '$', '&'];
+
+/// `^FH` state of the field being scanned.
+#[derive(Clone, Copy)]
+struct FieldHex {
+    indicator: char,
+    /// The `^FH` was added by substitution, so literal indicator characters
+    /// in the field's data must be escaped as well.
+    added: bool,
+}
+
+/// A run of literal text or a placeholder within command content.
+enum Piece<'a> {
+    Text(&'a str),
+    /// Placeholder name and its byte range within the content.
+    Placeholder(&'a str, usize, usize),
+}
+
+/// Replace `{{name}}` placeholders in field data and comments with values
+/// from `vars`.
+///
+/// Whitespace around the name is ignored (`{{ name }}`). Names are ASCII
+/// letters, digits, `_`, `-`, and `.`, starting with a letter or `_`; a
+/// `{{` that does not open such a placeholder is kept as literal text.
+/// Placeholders with no entry in `vars` are left in place and reported
+/// with a [`codes::TEMPLATE_UNRESOLVED_VARIABLE`] warning spanning the
+/// placeholder in `input`.
+///
+/// In field data, the active command and control prefixes (and the `^FH`
+/// indicator, if one is in effect) are hex-escaped in values; a `^FH` is
+/// added before the `^FD`/`^FV` when a value needs escaping and the field
+/// has none. A value that cannot be escaped — in a `^FX` comment, or when
+/// every candidate indicator already appears in the field data — is not
+/// substituted and is reported with a [`codes::TEMPLATE_UNSAFE_VALUE`] error.
+pub fn substitute_variables(
+    input: &str,
+    vars: &HashMap<String, String>,
+) -> (String, Vec<Diagnostic>) {
+    let mut out = String::with_capacity(input.len());
+    let mut diagnostics = Vec::new();
+    let mut cmd_prefix = '^';
+    let mut ctrl_prefix = '~';
+    let mut field_hex: Option<FieldHex> = None;
+    let mut i = 0;
+
+    while let Some(c) = input[i..].chars().next() {
+        if c != cmd_prefix && c != ctrl_prefix {
+            out.push(c);
+            i += c.len_utf8();
+            continue;
+        }
+
+        let opcode: String = input[i + 1..]
+            .chars()
+            .take(2)
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let mut end = i
+            + 1
+            + input[i + 1..]
+                .chars()
+                .take(2)
+                .map(char::len_utf8)
+                .sum::<usize>();
+        let next = input[end..]
+            .chars()
+            .next()
+            .filter(|next| next.is_ascii() && !next.is_ascii_whitespace());
+        match (opcode.as_str(), next) {
+            ("CC", Some(next)) => {
+                cmd_prefix = next;
+                end += 1;
+            }
+            ("CT", Some(next)) => {
+                ctrl_prefix = next;
+                end += 1;
+            }
+            ("FH", _) => {
+                field_hex = Some(FieldHex {
+                    indicator: next
+                        .filter(|&n| n != cmd_prefix && n != ctrl_prefix)
+                        .unwrap_or('_'),
+                    added: false,
+                });
+            }
+            ("FS" | "XA", _) => field_hex = None,
+            _ => {}
+        }
+        let command_start = out.len();
+        out.push_str(&input[i..end]);
+        i = end;
+        if !TEXT_OPCODES.contains(&opcode.as_str()) {
+            continue;
+        }
+
+        let content_end = input[i..]
+            .find([cmd_prefix, ctrl_prefix])
+            .map_or(input.len(), |n| i + n);
+        let pieces = split_placeholders(&input[i..content_end]);
+        let is_prefix = |c: char| c == cmd_prefix || c == ctrl_prefix;
+        let is_field_data = opcode != "FX";
+        let value_of = |name: &str| vars.get(name).map(String::as_str);
+
+        if is_field_data
+            && field_hex.is_none()
+            && pieces.iter().any(|piece| {
+                matches!(piece, Piece::Placeholder(name, ..)
+                    if value_of(name).is_some_and(|v| v.contains(is_prefix)))
+            })
+        {
+            let literal_has = |ind: char| {
+                pieces
+                    .iter()
+                    .any(|piece| matches!(piece, Piece::Text(text) if text.contains(ind)))
+            };
+            if let Some(indicator) = HEX_INDICATORS
+                .into_iter()
+                .find(|&ind| !is_prefix(ind) && !literal_has(ind))
+            {
+                out.insert_str(command_start, &format!("{cmd_prefix}FH{indicator}"));
+                field_hex = Some(FieldHex {
+                    indicator,
+                    added: true,
+                });
+            }
+        }
+
+        for piece in pieces {
+            let (name, start, len) = match piece {
+                Piece::Text(text) => {
+                    match field_hex.filter(|hex| hex.added && is_field_data) {
+                        Some(hex) => {
+                            hex_escape(&mut out, text, hex.indicator, |c| c == hex.indicator)
+                        }
+                        None => out.push_str(text),
+                    }
+                    continue;
+                }
+                Piece::Placeholder(name, start, len) => (name, i + start, len),
+            };
+            let placeholder = &input[start..start + len];
+            let Some(value) = value_of(name) else {
+                out.push_str(placeholder);
+                diagnostics.push(
+                    Diagnostic::warn(
+                        codes::TEMPLATE_UNRESOLVED_VARIABLE,
+                        format!("template variable '{name}' has no value"),
+                        Some(Span::new(start, start + len)),
+                    )
+                    .with_context(ctx!("variable" => name)),
+                );
+                continue;
+            };
+            match (field_hex.filter(|_| is_field_data), value.find(is_prefix)) {
+                (Some(hex), _) => hex_escape(&mut out, value, hex.indicator, |c| {
+                    is_prefix(c) || c == hex.indicator
+                }),
+                (None, None) => out.push_str(value),
+                (None, Some(at)) => {
+                    let prefix = value[at..].chars().next().unwrap_or(cmd_prefix);
+                    let command = format!("{cmd_prefix}{opcode}");
+                    out.push_str(placeholder);
+                    diagnostics.push(
+                        Diagnostic::error(
+                            codes::TEMPLATE_UNSAFE_VALUE,
+                            format!(
+                                "value of template variable '{name}' contains '{prefix}', which would end the {command} content and cannot be escaped there"
+                            ),
+                            Some(Span::new(start, start + len)),
+                        )
+                        .with_context(ctx!("variable" => name, "command" => command)),
+                    );
+                }
+            }
+        }
+        i = content_end;
+    }
+
+    (out, diagnostics)
+}
+
+/// Split command content into literal text and `{{name}}` placeholders.
+fn split_placeholders(content: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut literal_start = 0;
+    let mut pos = 0;
+    while let Some(found) = content[pos..].find("{{") {
+        let start = pos + found;
+        match placeholder_at(&content[start..]) {
+            Some((name, len)) => {
+                if literal_start < start {
+                    pieces.push(Piece::Text(&content[literal_start..start]));
+                }
+                pieces.push(Piece::Placeholder(name, start, len));
+                pos = start + len;
+                literal_start = pos;
+            }
+            None => pos = start + 1,
+        }
+    }
+    if literal_start < content.len() {
+        pieces.push(Piece::Text(&content[literal_start..]));
+    }
+    pieces
+}
+
+/// Append `text`, writing each character matched by `escape` as `^FH`
+/// hex escapes (one `indicator` + two hex digits per UTF-8 byte).
+fn hex_escape(out: &mut String, text: &str, indicator: char, escape: impl Fn(char) -> bool) {
+    for c in text.chars() {
+        if !escape(c) {
+            out.push(c);
+            continue;
+        }
+        for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+            let _ = write!(out, "{indicator}{byte:02X}");
+        }
+    }
+}
+
+/// Parse a `{{name}}` placeholder at the start of `text`, returning the
+/// trimmed name and the placeholder's byte length.
+fn placeholder_at(text: &str) -> Option<(&str, usize)> {
+    let body = &text[2..];
+    let close = body.find("}}")?;
+    let name = body[..close].trim();
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    let valid = (first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    valid.then_some((name, 2 + close + 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn resolves_placeholder_in_field_data() {
+        let (out, diags) = substitute_variables(
+            "^XA^FO10,10^FD{{ customer_name }}^FS^XZ",
+            &vars(&[("customer_name", "Acme")]),
+        );
+        assert_eq!(out, "^XA^FO10,10^FDAcme^FS^XZ");
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn unresolved_placeholder_is_kept_and_warned() {
+        let input = "^XA^FX{{note}}^FS^FO0,0^FV{{sku}}^FS^XZ";
+        let (out, diags) = substitute_variables(input, &vars(&[("note", "batch 7")]));
+        assert_eq!(out, "^XA^FXbatch 7^FS^FO0,0^FV{{sku}}^FS^XZ");
+        assert_eq!(diags.len(), 1);
+        let diag = &diags[0];
+        assert_eq!(diag.id, codes::TEMPLATE_UNRESOLVED_VARIABLE);
+        let span = diag.span.expect("span");
+        assert_eq!(&input[span.start..span.end], "{{sku}}");
+        assert_eq!(
+            diag.context
+                .as_ref()
+                .and_then(|c| c.get("variable"))
+                .map(String::as_str),
+            Some("sku")
+        );
+    }
+
+    #[test]
+    fn non_placeholder_braces_are_literal() {
+        let vars = vars(&[("a", "X")]);
+        let (out, diags) = substitute_variables("^FD{{ not a name }} {{a^FS", &vars);
+        assert_eq!(out, "^FD{{ not a name }} {{a^FS");
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn placeholders_outside_text_content_are_untouched() {
+        let vars = vars(&[("x", "99")]);
+        let (out, diags) = substitute_variables("^XA^FO{{x}},0^FD{{x}}^FS^XZ", &vars);
+        assert_eq!(out, "^XA^FO{{x}},0^FD99^FS^XZ");
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn prefix_in_field_value_is_hex_escaped() {
+        let vars = vars(&[("name", "x^XZ~JR")]);
+        let (out, diags) = substitute_variables("^XA^FO0,0^FD{{name}}^FS^XZ", &vars);
+        assert_eq!(out, "^XA^FO0,0^FH_^FDx_5EXZ_7EJR^FS^XZ");
+        assert!(diags.is_empty());
+        assert_eq!(
+            out.matches("^XZ").count(),
+            1,
+            "value must not end the label"
+        );
+        assert!(!out.contains("~JR"));
+    }
+
+    #[test]
+    fn escaping_reuses_an_existing_fh_and_picks_a_free_indicator() {
+        let value = vars(&[("v", "a^b_c")]);
+        // The field already has ^FH#: reuse it and escape its indicator too.
+        let (out, _) = substitute_variables("^FH#^FD{{v}}^FS", &value);
+        assert_eq!(out, "^FH#^FDa#5Eb_c^FS");
+        // `_` is literal text here, so the added ^FH uses the next candidate.
+        let (out, _) = substitute_variables("^FDid_{{v}}^FS", &value);
+        assert_eq!(out, "^FH#^FDid_a#5Eb_c^FS");
+        // Under ^CC*, `*` is the prefix to escape and `^` is plain text.
+        let (out, _) = substitute_variables("^CC*\n*FD{{v}}*FS", &vars(&[("v", "1*2^3")]));
+        assert_eq!(out, "^CC*\n*FH_*FD1_2A2^3*FS");
+    }
+
+    #[test]
+    fn prefix_in_comment_value_is_rejected() {
+        let input = "^XA^FX{{note}}^FS^XZ";
+        let (out, diags) = substitute_variables(input, &vars(&[("note", "a~JR")]));
+        assert_eq!(out, input);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].id, codes::TEMPLATE_UNSAFE_VALUE);
+        let span = diags[0].span.expect("span");
+        assert_eq!(&input[span.start..span.end], "{{note}}");
+    }
+
+    #[test]
+    fn follows_command_prefix_changes() {
+        let vars = vars(&[("x", "99")]);
+        // After ^CC*, `^FD` is literal text rather than a command.
+        let (out, _) = substitute_variables("^XA^CC*\n*FD{{x}}*FS^FD{{x}}*XZ", &vars);
+        assert_eq!(out, "^XA^CC*\n*FD99*FS^FD{{x}}*XZ");
+    }
+}
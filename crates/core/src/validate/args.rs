@@ -2,7 +2,7 @@ use super::context::{CommandCtx, ValidationContext};
 use super::ctx;
 use super::diagnostics_util::{diagnostic_with_spec_severity, render_diagnostic_message, trim_f64};
 use super::predicates::{enum_contains, predicate_matches};
-use super::profile_constraints::check_profile_op;
+use super::profile_constraints::{check_profile_op, check_profile_range, resolve_profile_range};
 use super::resolve_profile_field;
 use super::state::LabelState;
 use crate::grammar::diag::{Diagnostic, codes};
@@ -222,8 +222,7 @@ fn validate_arg_profile_constraint(
 ) {
     if let Some(pc) = &spec_arg.profile_constraint
         && let Some(p) = vctx.profile
-        && let Ok(n) = val.parse::<f64>()
-        && let Some(limit) = resolve_profile_field(p, &pc.field)
+        && let Some(n) = arg_numeric_value(val, spec_arg)
     {
         let effective_n =
             if spec_arg.unit.as_deref() == Some("dots") && vctx.device_state.units != Units::Dots {
@@ -237,6 +236,14 @@ fn validate_arg_profile_constraint(
                 n
             };
 
+        if pc.op == ComparisonOp::Within {
+            validate_arg_profile_range(cmd_ctx, lookup_key, &pc.field, p, effective_n, issues);
+            return;
+        }
+
+        let Some(limit) = resolve_profile_field(p, &pc.field) else {
+            return;
+        };
         if check_profile_op(effective_n, &pc.op, limit) {
             return;
         }
@@ -246,7 +253,7 @@ fn validate_arg_profile_constraint(
             ComparisonOp::Gte => "below",
             ComparisonOp::Lt => "exceeds or equals",
             ComparisonOp::Gt => "below or equals",
-            ComparisonOp::Eq => "violates",
+            ComparisonOp::Eq | ComparisonOp::Within => "violates",
         };
         issues.push(
             diagnostic_with_spec_severity(
@@ -273,6 +280,63 @@ fn validate_arg_profile_constraint(
     }
 }
 
+/// Check a value against an inclusive profile range (`<field>.min`..=`<field>.max`).
+fn validate_arg_profile_range(
+    cmd_ctx: &CommandCtx,
+    lookup_key: &str,
+    field: &str,
+    profile: &zpl_toolchain_profile::Profile,
+    value: f64,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let Some(range) = resolve_profile_range(profile, field) else {
+        return;
+    };
+    if check_profile_range(value, &range) {
+        return;
+    }
+
+    let (min, max) = (range.min as f64, range.max as f64);
+    // Float-to-int casts saturate, so values below zero clamp to `min`.
+    let limit = range.clamp(value as u32) as f64;
+    issues.push(
+        diagnostic_with_spec_severity(
+            codes::PROFILE_CONSTRAINT,
+            format!(
+                "{}.{} ({}) is outside profile {} ({}-{})",
+                cmd_ctx.code,
+                lookup_key,
+                trim_f64(value),
+                field,
+                trim_f64(min),
+                trim_f64(max),
+            ),
+            cmd_ctx.span,
+        )
+        .with_context(ctx!(
+            "command" => cmd_ctx.code,
+            "arg" => lookup_key,
+            "field" => field,
+            "op" => format!("{:?}", ComparisonOp::Within),
+            "min" => trim_f64(min),
+            "max" => trim_f64(max),
+            "limit" => trim_f64(limit),
+            "actual" => trim_f64(value),
+        )),
+    );
+}
+
+/// Numeric value of an argument, resolving spec `numericAliases` (e.g.,
+/// `^PR` speed letters) when the raw value is not a number.
+fn arg_numeric_value(val: &str, spec_arg: &zpl_toolchain_spec_tables::Arg) -> Option<f64> {
+    val.parse::<f64>().ok().or_else(|| {
+        spec_arg
+            .numeric_aliases
+            .as_ref()
+            .and_then(|aliases| aliases.get(val).copied())
+    })
+}
+
 /// Check enum value printer gate constraints on an argument value.
 fn validate_arg_enum_gates(
     cmd_ctx: &CommandCtx,
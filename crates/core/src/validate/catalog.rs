@@ -0,0 +1,147 @@
+use crate::grammar::diag::codes;
+use crate::grammar::tables::ParserTables;
+use std::collections::BTreeSet;
+use zpl_toolchain_spec_tables::{Arg, ArgUnion, CommandEntry, ConstraintKind, EnumValue, Plane};
+
+/// List the diagnostic IDs the validator can emit for a command, derived from
+/// its spec metadata (arity, args, constraints, field data rules, printer
+/// gates, placement, effects, and structural role flags).
+///
+/// The result is sorted and deduplicated. Checks driven by schema-selected
+/// structural rules (e.g. position bounds) are not included. Unknown codes
+/// return an empty list.
+pub fn diagnostics_for(tables: &ParserTables, code: &str) -> Vec<&'static str> {
+    let Some(cmd) = tables.cmd_by_code(code) else {
+        return Vec::new();
+    };
+
+    let mut ids = BTreeSet::new();
+    if !cmd.field_data {
+        ids.insert(codes::ARITY);
+    }
+    collect_structural(cmd, &mut ids);
+    for arg in cmd.args.iter().flatten() {
+        match arg {
+            ArgUnion::Single(a) => collect_arg(a, &mut ids),
+            ArgUnion::OneOf { one_of } => {
+                for a in one_of {
+                    collect_arg(a, &mut ids);
+                }
+            }
+        }
+    }
+    for c in cmd.constraints.iter().flatten() {
+        match c.kind {
+            ConstraintKind::Order => match c.expr.as_deref() {
+                Some(expr) if expr.starts_with("before:") => {
+                    ids.insert(codes::ORDER_BEFORE);
+                }
+                Some(expr) if expr.starts_with("after:") => {
+                    ids.insert(codes::ORDER_AFTER);
+                }
+                _ => {}
+            },
+            ConstraintKind::Requires => {
+                ids.insert(codes::REQUIRED_COMMAND);
+            }
+            ConstraintKind::Incompatible => {
+                ids.insert(codes::INCOMPATIBLE_COMMAND);
+            }
+            ConstraintKind::EmptyData => {
+                ids.insert(codes::EMPTY_FIELD_DATA);
+            }
+            ConstraintKind::Note => {
+                ids.insert(codes::NOTE);
+            }
+            ConstraintKind::Range | ConstraintKind::Custom => {}
+        }
+    }
+    if let Some(rules) = &cmd.field_data_rules {
+        if rules.character_set.is_some() {
+            ids.insert(codes::BARCODE_INVALID_CHAR);
+        }
+        if rules.exact_length.is_some()
+            || rules.allowed_lengths.is_some()
+            || rules.min_length.is_some()
+            || rules.max_length.is_some()
+            || rules.length_parity.is_some()
+        {
+            ids.insert(codes::BARCODE_DATA_LENGTH);
+        }
+    }
+    if cmd.printer_gates.as_ref().is_some_and(|g| !g.is_empty()) {
+        ids.insert(codes::PRINTER_GATE);
+    }
+    if cmd.placement.is_some() || matches!(cmd.plane, Some(Plane::Host | Plane::Device)) {
+        ids.insert(codes::HOST_COMMAND_IN_LABEL);
+    }
+    if cmd.effects.is_some() {
+        ids.insert(codes::REDUNDANT_STATE);
+    }
+
+    ids.into_iter().collect()
+}
+
+fn collect_structural(cmd: &CommandEntry, ids: &mut BTreeSet<&'static str>) {
+    if cmd.opens_field {
+        ids.insert(codes::FIELD_NOT_CLOSED);
+    }
+    if cmd.closes_field {
+        ids.insert(codes::ORPHANED_FIELD_SEPARATOR);
+    }
+    if cmd.field_data || cmd.requires_field {
+        ids.insert(codes::FIELD_DATA_WITHOUT_ORIGIN);
+    }
+    if cmd.hex_escape_modifier {
+        ids.insert(codes::INVALID_HEX_ESCAPE);
+    }
+}
+
+fn collect_arg(arg: &Arg, ids: &mut BTreeSet<&'static str>) {
+    if !arg.optional {
+        ids.insert(codes::REQUIRED_MISSING);
+        ids.insert(codes::REQUIRED_EMPTY);
+    }
+    match arg.r#type.as_str() {
+        "enum" => {
+            ids.insert(codes::INVALID_ENUM);
+        }
+        "int" => {
+            ids.insert(codes::EXPECTED_INTEGER);
+        }
+        "float" => {
+            ids.insert(codes::EXPECTED_NUMERIC);
+        }
+        "char" => {
+            ids.insert(codes::EXPECTED_CHAR);
+        }
+        _ => {}
+    }
+    if arg.range.is_some() || arg.range_when.is_some() {
+        ids.insert(codes::OUT_OF_RANGE);
+    }
+    if arg.min_length.is_some() {
+        ids.insert(codes::STRING_TOO_SHORT);
+    }
+    if arg.max_length.is_some() {
+        ids.insert(codes::STRING_TOO_LONG);
+    }
+    if arg.rounding_policy.is_some() || arg.rounding_policy_when.is_some() {
+        ids.insert(codes::ROUNDING_VIOLATION);
+    }
+    if arg.profile_constraint.is_some() {
+        ids.insert(codes::PROFILE_CONSTRAINT);
+    }
+    let gated_enum = arg.r#enum.iter().flatten().any(|ev| {
+        matches!(
+            ev,
+            EnumValue::Object {
+                printer_gates: Some(gates),
+                ..
+            } if !gates.is_empty()
+        )
+    });
+    if gated_enum {
+        ids.insert(codes::PRINTER_GATE);
+    }
+}
@@ -1,11 +1,28 @@
 use super::context::{CommandCtx, ValidationContext};
 use super::ctx;
+use super::custom::evaluate_custom_constraint;
 use super::diagnostics_util::map_sev;
 use super::predicates::{any_target_in_set, evaluate_note_when_expression};
-use crate::grammar::diag::codes;
+use crate::grammar::ast::Node;
+use crate::grammar::diag::{Span, codes};
 use std::collections::HashSet;
 use zpl_toolchain_spec_tables::{CommandScope, ConstraintKind, ConstraintScope, NoteAudience};
 
+/// First command in `nodes` whose code is one of the `|`-separated `targets`.
+fn find_order_target<'a>(
+    mut nodes: impl Iterator<Item = &'a Node>,
+    targets: &str,
+) -> Option<(&'a str, Span)> {
+    nodes.find_map(|node| match node {
+        Node::Command { code, span, .. }
+            if targets.split('|').map(str::trim).any(|t| t == code) =>
+        {
+            Some((code.as_str(), *span))
+        }
+        _ => None,
+    })
+}
+
 pub(super) fn validate_command_constraints(
     cmd_ctx: &CommandCtx,
     vctx: &ValidationContext,
@@ -46,27 +63,8 @@ pub(super) fn validate_command_constraints(
                     };
                     if let Some(targets) = expr.strip_prefix("before:") {
                         if any_target_in_set(targets, seen_codes) {
-                            issues.push(
-                                super::Diagnostic::new(
-                                    codes::ORDER_BEFORE,
-                                    map_sev(c.severity.as_ref(), constraint_default_severity),
-                                    c.message.clone(),
-                                    cmd_ctx.span,
-                                )
-                                .with_context(ctx!(
-                                    "command" => cmd_ctx.code,
-                                    "target" => targets,
-                                    "kind" => "order",
-                                    "scope" => if eval_scope == ConstraintScope::Field { "field" } else { "label" },
-                                )),
-                            );
-                        }
-                    } else if let Some(targets) = expr.strip_prefix("after:")
-                        && !any_target_in_set(targets, seen_codes)
-                    {
-                        issues.push(
-                            super::Diagnostic::new(
-                                codes::ORDER_AFTER,
+                            let mut diag = super::Diagnostic::new(
+                                codes::ORDER_BEFORE,
                                 map_sev(c.severity.as_ref(), constraint_default_severity),
                                 c.message.clone(),
                                 cmd_ctx.span,
@@ -76,8 +74,35 @@ pub(super) fn validate_command_constraints(
                                 "target" => targets,
                                 "kind" => "order",
                                 "scope" => if eval_scope == ConstraintScope::Field { "field" } else { "label" },
-                            )),
-                        );
+                            ));
+                            // Anchor: the nearest earlier target occurrence.
+                            let earlier = vctx.label.nodes[..cmd_ctx.node_idx].iter().rev();
+                            if let Some((code, span)) = find_order_target(earlier, targets) {
+                                diag = diag.with_related(span, format!("{code} appears here"));
+                            }
+                            issues.push(diag);
+                        }
+                    } else if let Some(targets) = expr.strip_prefix("after:")
+                        && !any_target_in_set(targets, seen_codes)
+                    {
+                        let mut diag = super::Diagnostic::new(
+                            codes::ORDER_AFTER,
+                            map_sev(c.severity.as_ref(), constraint_default_severity),
+                            c.message.clone(),
+                            cmd_ctx.span,
+                        )
+                        .with_context(ctx!(
+                            "command" => cmd_ctx.code,
+                            "target" => targets,
+                            "kind" => "order",
+                            "scope" => if eval_scope == ConstraintScope::Field { "field" } else { "label" },
+                        ));
+                        // Anchor: the first target occurrence that comes too late.
+                        let later = vctx.label.nodes.iter().skip(cmd_ctx.node_idx + 1);
+                        if let Some((code, span)) = find_order_target(later, targets) {
+                            diag = diag.with_related(span, format!("{code} appears here"));
+                        }
+                        issues.push(diag);
                     }
                 }
             }
@@ -146,7 +171,7 @@ pub(super) fn validate_command_constraints(
                     .and_then(|a| a.value.as_ref())
                     .is_some_and(|s| !s.is_empty());
                 let mut trailing_fd_has_content = false;
-                for n in &vctx.label_nodes[(cmd_ctx.node_idx + 1).min(vctx.label_nodes.len())..] {
+                for n in &vctx.label.nodes[(cmd_ctx.node_idx + 1).min(vctx.label.nodes.len())..] {
                     match n {
                         crate::grammar::ast::Node::FieldData { content, .. } => {
                             if !content.is_empty() {
@@ -238,7 +263,10 @@ pub(super) fn validate_command_constraints(
             // validation is handled through `args[].range` on each Arg definition.
             // When activated, the constraint's `expr` would specify the range
             // and `message` would provide context.
-            ConstraintKind::Range | ConstraintKind::Custom => {}
+            ConstraintKind::Range => {}
+            // Custom constraints are evaluated by caller-registered functions
+            // keyed by `expr` (see `validate_with_options`).
+            ConstraintKind::Custom => evaluate_custom_constraint(c, cmd_ctx, vctx, issues),
         }
     }
 }
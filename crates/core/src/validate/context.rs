@@ -7,11 +7,25 @@ use zpl_toolchain_profile::Profile;
 #[derive(Clone, Copy)]
 pub(super) struct ValidationContext<'a> {
     pub(super) profile: Option<&'a Profile>,
-    pub(super) label_nodes: &'a [crate::grammar::ast::Node],
+    pub(super) label: &'a crate::grammar::ast::Label,
+    pub(super) custom_constraints: &'a super::CustomConstraints,
     pub(super) label_codes: &'a HashSet<&'a str>,
     pub(super) device_state: &'a DeviceState,
 }
 
+impl ValidationContext<'_> {
+    /// Profile page bounds `(width, height)` on the format dot grid, scaled
+    /// for `^JMB` half density.
+    pub(super) fn profile_page_dots(&self) -> (Option<f64>, Option<f64>) {
+        let page = |path| {
+            self.profile
+                .and_then(|p| super::resolve_profile_field(p, path))
+                .map(|dots| self.device_state.printhead_to_format_dots(dots))
+        };
+        (page("page.width_dots"), page("page.height_dots"))
+    }
+}
+
 /// Per-command view used by validation helpers.
 #[derive(Clone, Copy)]
 pub(super) struct CommandCtx<'a> {
@@ -0,0 +1,65 @@
+use super::Diagnostic;
+use super::context::{CommandCtx, ValidationContext};
+use crate::grammar::ast::{Label, Node};
+use std::collections::HashMap;
+use zpl_toolchain_profile::Profile;
+use zpl_toolchain_spec_tables::Constraint;
+
+/// Evaluator for a `custom` spec constraint.
+///
+/// Returns a diagnostic when the constraint is violated. A diagnostic without
+/// a span is anchored to the constrained command.
+pub type CustomConstraintFn = Box<dyn Fn(&ConstraintCtx) -> Option<Diagnostic> + Send + Sync>;
+
+/// Registry of custom constraint evaluators, keyed by the constraint's `expr`.
+pub type CustomConstraints = HashMap<String, CustomConstraintFn>;
+
+/// View of the command being checked, passed to a [`CustomConstraintFn`].
+#[derive(Clone, Copy)]
+pub struct ConstraintCtx<'a> {
+    /// The label containing the command.
+    pub label: &'a Label,
+    /// The command node carrying the constraint.
+    pub node: &'a Node,
+    /// Index of [`node`](Self::node) within `label.nodes`.
+    pub node_index: usize,
+    /// The constraint being evaluated.
+    pub constraint: &'a Constraint,
+    /// Printer profile, if validation runs against one.
+    pub profile: Option<&'a Profile>,
+}
+
+/// Run the registered evaluator for a `custom` constraint, if any.
+///
+/// Constraints without an `expr` or without a registered evaluator are
+/// skipped, matching the behavior before evaluators could be registered.
+pub(super) fn evaluate_custom_constraint(
+    constraint: &Constraint,
+    cmd_ctx: &CommandCtx,
+    vctx: &ValidationContext,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let Some(evaluator) = constraint
+        .expr
+        .as_deref()
+        .and_then(|key| vctx.custom_constraints.get(key))
+    else {
+        return;
+    };
+    let Some(node) = vctx.label.nodes.get(cmd_ctx.node_idx) else {
+        return;
+    };
+    let ctx = ConstraintCtx {
+        label: vctx.label,
+        node,
+        node_index: cmd_ctx.node_idx,
+        constraint,
+        profile: vctx.profile,
+    };
+    if let Some(mut diagnostic) = evaluator(&ctx) {
+        if diagnostic.span.is_none() {
+            diagnostic.span = cmd_ctx.span;
+        }
+        issues.push(diagnostic);
+    }
+}
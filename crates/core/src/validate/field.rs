@@ -1,16 +1,17 @@
 use super::context::{CommandCtx, ValidationContext};
+use super::ctx;
 use super::diagnostics_util::{
     Diagnostic, diagnostic_with_constraint_severity, diagnostic_with_spec_severity,
     render_diagnostic_message, trim_f64,
 };
 use super::plan::StructuralFlags;
 use super::state::LabelState;
-use super::{ctx, resolve_profile_field};
 use crate::grammar::diag::{Severity, Span, codes};
 use zpl_toolchain_diagnostics::policy::{
     OBJECT_BOUNDS_LOW_CONFIDENCE_MAX_OVERFLOW_DOTS,
     OBJECT_BOUNDS_LOW_CONFIDENCE_MAX_OVERFLOW_RATIO, OBJECT_BOUNDS_LOW_CONFIDENCE_SEVERITY,
 };
+use zpl_toolchain_spec_tables::{ArgUnion, CommandEntry, PayloadFormat};
 
 /// Tracks field-level structural state within a label.
 /// Reset when a field-opening command is encountered.
@@ -25,12 +26,19 @@ pub(super) struct FieldTracker {
     has_fn: bool,
     /// Whether ^SN/^SF was seen in the current field.
     has_serial: bool,
+    /// Span of the first ^FD in the current field.
+    fd_span: Option<Span>,
+    /// Span of the first ^FV in the current field.
+    fv_span: Option<Span>,
     /// Node index of the field-opening command.
     pub(super) start_idx: usize,
     /// Barcode commands seen in this field, in order, with their node index.
     /// Used to attribute ^FD/^FV segments to the correct barcode when multiple
     /// barcode commands appear in a single field.
     active_barcodes: Vec<(usize, String, zpl_toolchain_spec_tables::FieldDataRules)>,
+    /// Orientation (N/R/I/B) set explicitly by a command in this field, e.g.
+    /// `^A0R` or `^BCB`. Falls back to the `^FW` default when unset.
+    orientation: Option<char>,
 }
 
 impl Default for FieldTracker {
@@ -41,8 +49,11 @@ impl Default for FieldTracker {
             fh_indicator: b'_',
             has_fn: false,
             has_serial: false,
+            fd_span: None,
+            fv_span: None,
             start_idx: 0,
             active_barcodes: Vec::new(),
+            orientation: None,
         }
     }
 }
@@ -54,7 +65,15 @@ impl FieldTracker {
         self.fh_indicator = b'_';
         self.has_fn = false;
         self.has_serial = false;
+        self.fd_span = None;
+        self.fv_span = None;
         self.active_barcodes.clear();
+        self.orientation = None;
+    }
+
+    /// Whether a barcode command has been seen in the current field.
+    pub(super) fn has_barcode(&self) -> bool {
+        !self.active_barcodes.is_empty()
     }
 
     /// Process a command's structural flags and emit diagnostics.
@@ -120,6 +139,31 @@ impl FieldTracker {
         if structural_flags.serialization {
             self.has_serial = true;
         }
+        if structural_flags.field_data
+            && let Some(span) = cmd_ctx.span
+        {
+            match cmd_ctx.code {
+                "^FD" => {
+                    self.fd_span.get_or_insert(span);
+                }
+                "^FV" => {
+                    self.fv_span.get_or_insert(span);
+                }
+                _ => {}
+            }
+        }
+
+        // Track explicit field rotation (^A, barcodes) for bounds checks.
+        if self.open
+            && let Some(idx) = field_orientation_arg_index(cmd_ctx.cmd)
+            && let Some(ch) = cmd_ctx
+                .args
+                .get(idx)
+                .and_then(|slot| slot.as_str())
+                .and_then(|v| v.trim().chars().next())
+        {
+            self.orientation = Some(ch.to_ascii_uppercase());
+        }
 
         // Track barcode commands for field data validation
         if let Some(rules) = &cmd_ctx.cmd.field_data_rules
@@ -128,7 +172,8 @@ impl FieldTracker {
                 || rules.allowed_lengths.is_some()
                 || rules.min_length.is_some()
                 || rules.max_length.is_some()
-                || rules.length_parity.is_some())
+                || rules.length_parity.is_some()
+                || rules.payload_format.is_some())
         {
             self.active_barcodes
                 .push((cmd_ctx.node_idx, cmd_ctx.code.to_string(), rules.clone()));
@@ -163,7 +208,7 @@ impl FieldTracker {
         // ZPL2304: Validate hex escapes in field data if ^FH was active
         if self.has_fh {
             let indicator = self.fh_indicator;
-            for field_node in &vctx.label_nodes[self.start_idx..cmd_ctx.node_idx] {
+            for field_node in &vctx.label.nodes[self.start_idx..cmd_ctx.node_idx] {
                 let content_and_span = match field_node {
                     crate::grammar::ast::Node::FieldData { content, span, .. } => {
                         Some((content.as_str(), Some(*span)))
@@ -206,6 +251,9 @@ impl FieldTracker {
             );
         }
 
+        // ZPL2313: ^FD/^FV mixed in one field, or ^FV without ^FN
+        self.validate_field_data_kind(issues);
+
         // ZPL2401/ZPL2402: Barcode field data validation
         // Skip when ^FH (hex escape) is active — raw content contains escape
         // sequences that alter the actual byte values, making character-set
@@ -230,7 +278,7 @@ impl FieldTracker {
                 let mut combined_fd = String::new();
                 let mut has_any_fd = false;
                 let mut first_fd_span: Option<Span> = None;
-                for field_node in &vctx.label_nodes[seg_start..seg_end] {
+                for field_node in &vctx.label.nodes[seg_start..seg_end] {
                     match field_node {
                         crate::grammar::ast::Node::Command {
                             code, args, span, ..
@@ -259,10 +307,47 @@ impl FieldTracker {
                     validate_barcode_field_data(
                         barcode_code,
                         &combined_fd,
+                        vctx.device_state.field_data_len(&combined_fd),
                         rules,
                         first_fd_span.or(cmd_ctx.span),
                         issues,
                     );
+                    let barcode_args = match vctx.label.nodes.get(seg_start) {
+                        Some(crate::grammar::ast::Node::Command { args, .. }) => args.as_slice(),
+                        _ => &[],
+                    };
+                    let barcode_arg = |idx: usize| {
+                        barcode_args
+                            .get(idx)
+                            .and_then(|slot| slot.value.as_deref())
+                            .map(str::trim)
+                            .filter(|v| !v.is_empty())
+                    };
+                    match rules.payload_format {
+                        Some(PayloadFormat::Qr) => validate_qr_capacity(
+                            barcode_code,
+                            &combined_fd,
+                            barcode_arg(1),
+                            rules,
+                            first_fd_span.or(cmd_ctx.span),
+                            issues,
+                        ),
+                        Some(PayloadFormat::DataMatrix) => validate_data_matrix_capacity(
+                            barcode_code,
+                            &combined_fd,
+                            DataMatrixParams {
+                                quality: barcode_arg(2),
+                                columns: barcode_arg(3),
+                                rows: barcode_arg(4),
+                                format_id: barcode_arg(5),
+                                aspect: barcode_arg(7),
+                            },
+                            rules,
+                            first_fd_span.or(cmd_ctx.span),
+                            issues,
+                        ),
+                        None => {}
+                    }
                 }
             }
         }
@@ -270,15 +355,152 @@ impl FieldTracker {
         // ZPL2311: Object bounds check (text/barcode overflow)
         validate_object_bounds(self, cmd_ctx, vctx, label_state, issues);
 
+        // ZPL2319: Net orientation when several rotation sources combine
+        validate_combined_orientation(self, vctx, label_state, issues);
+
         self.open = false;
         self.reset();
     }
 }
 
+impl FieldTracker {
+    /// ZPL2313: A field should carry either ^FD or ^FV data, not both, and
+    /// ^FV (variable data) is expected to be addressed by an ^FN number.
+    fn validate_field_data_kind(&self, issues: &mut Vec<Diagnostic>) {
+        match (self.fd_span, self.fv_span) {
+            (Some(fd), Some(fv)) => {
+                // Cover both commands so editors highlight the whole conflict.
+                let span = Span::new(fd.start.min(fv.start), fd.end.max(fv.end));
+                let second = if fv.start > fd.start { "^FV" } else { "^FD" };
+                issues.push(
+                    diagnostic_with_spec_severity(
+                        codes::FIELD_DATA_CONFLICT,
+                        "Field contains both ^FD and ^FV data; use one or the other",
+                        Some(span),
+                    )
+                    .with_context(ctx!(
+                        "command" => second,
+                        "kind" => "fd_and_fv",
+                        "fd_offset" => fd.start.to_string(),
+                        "fv_offset" => fv.start.to_string(),
+                    )),
+                );
+            }
+            (None, Some(fv)) if !self.has_fn => {
+                issues.push(
+                    Diagnostic::new(
+                        codes::FIELD_DATA_CONFLICT,
+                        Severity::Info,
+                        "^FV field has no ^FN field number; variable data is normally addressed by ^FN",
+                        Some(fv),
+                    )
+                    .with_context(ctx!(
+                        "command" => "^FV",
+                        "kind" => "fv_without_fn",
+                        "fv_offset" => fv.start.to_string(),
+                    )),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Index of the argument that defaults from the `^FW` field orientation.
+fn field_orientation_arg_index(cmd: &CommandEntry) -> Option<usize> {
+    cmd.args.as_ref()?.iter().position(|arg| {
+        let is_orientation = |a: &zpl_toolchain_spec_tables::Arg| {
+            a.default_from_state_key.as_deref() == Some("field.orientation")
+        };
+        match arg {
+            ArgUnion::Single(a) => is_orientation(a),
+            ArgUnion::OneOf { one_of } => one_of.iter().any(is_orientation),
+        }
+    })
+}
+
+/// Clockwise rotation in degrees for a field orientation letter.
+fn orientation_degrees(orientation: char) -> u32 {
+    match orientation {
+        'R' => 90,
+        'I' => 180,
+        'B' => 270,
+        _ => 0,
+    }
+}
+
+/// ZPL2319 (info): Summarize the net rotation of a field when `^POI`, a
+/// `^FW` default, and an explicit per-field rotation combine.
+///
+/// A single rotation source on its own, or a field rotation that repeats the
+/// `^FW` default, is not reported.
+fn validate_combined_orientation(
+    field: &FieldTracker,
+    vctx: &ValidationContext,
+    label_state: &LabelState,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let default = label_state
+        .value_state
+        .field
+        .orientation
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|&c| c != 'N');
+    // An explicit rotation matching the default changes nothing.
+    let explicit = field.orientation.filter(|&c| c != default.unwrap_or('N'));
+
+    let mut sources = Vec::new();
+    if label_state.inverted {
+        sources.push("^POI");
+    }
+    if default.is_some() {
+        sources.push("^FW");
+    }
+    if explicit.is_some() {
+        sources.push("field");
+    }
+    if sources.len() < 2 {
+        return;
+    }
+
+    let orientation = explicit.or(default).unwrap_or('N');
+    let print_orientation = if label_state.inverted { 'I' } else { 'N' };
+    let net = (orientation_degrees(orientation) + orientation_degrees(print_orientation)) % 360;
+    let Some(opener) = vctx.label.nodes.get(field.start_idx) else {
+        return;
+    };
+    let code = match opener {
+        crate::grammar::ast::Node::Command { code, .. } => code.as_str(),
+        _ => "",
+    };
+    issues.push(
+        diagnostic_with_spec_severity(
+            codes::FIELD_ORIENTATION_COMBINED,
+            format!(
+                "field at {} prints rotated {}° (orientation {} with {})",
+                code,
+                net,
+                orientation,
+                sources.join(" + "),
+            ),
+            Some(opener.span()),
+        )
+        .with_context(ctx!(
+            "command" => code,
+            "field_orientation" => orientation.to_string(),
+            "source" => sources.join("+"),
+            "print_orientation" => print_orientation.to_string(),
+            "net_rotation" => net.to_string(),
+        )),
+    );
+}
+
 /// ZPL2311: Check if text or barcode content extends beyond label bounds.
 ///
 /// Uses conservative estimates: text width = chars × char_width (height if
 /// width unset); barcode dimensions from ^BY + data-length heuristics.
+/// Fields rotated 90°/270° (`R`/`B`, explicitly or via `^FW`) swap the
+/// estimated width and height.
 fn validate_object_bounds(
     field: &FieldTracker,
     cmd_ctx: &CommandCtx,
@@ -292,21 +514,16 @@ fn validate_object_bounds(
     let Some(fo_y) = label_state.last_fo_y else {
         return;
     };
-    let max_x = label_state.effective_width.or_else(|| {
-        vctx.profile
-            .and_then(|p| resolve_profile_field(p, "page.width_dots"))
-    });
-    let max_y = label_state.effective_height.or_else(|| {
-        vctx.profile
-            .and_then(|p| resolve_profile_field(p, "page.height_dots"))
-    });
+    let (page_width, page_height) = vctx.profile_page_dots();
+    let max_x = label_state.effective_width.or(page_width);
+    let max_y = label_state.effective_height.or(page_height);
     let (Some(max_x), Some(max_y)) = (max_x, max_y) else {
         return;
     };
 
     // Gather full field content (all ^FD/^FV + FieldData)
     let mut combined_fd = String::new();
-    for node in &vctx.label_nodes[field.start_idx..cmd_ctx.node_idx] {
+    for node in &vctx.label.nodes[field.start_idx..cmd_ctx.node_idx] {
         match node {
             crate::grammar::ast::Node::Command { code, args, .. }
                 if code == "^FD" || code == "^FV" =>
@@ -346,6 +563,15 @@ fn validate_object_bounds(
         let height = fh;
         (width, height, "text")
     };
+    let orientation = field
+        .orientation
+        .or(label_state.value_state.field.orientation)
+        .unwrap_or('N');
+    let (est_width, est_height) = if matches!(orientation, 'R' | 'B') {
+        (est_height, est_width)
+    } else {
+        (est_width, est_height)
+    };
 
     let overflows_x = fo_x + est_width > max_x;
     let overflows_y = fo_y + est_height > max_y;
@@ -426,6 +652,7 @@ fn validate_object_bounds(
                 "overflow_y" => trim_f64(overflow_y),
                 "overflow_x_ratio" => trim_f64(overflow_x_ratio),
                 "overflow_y_ratio" => trim_f64(overflow_y_ratio),
+                "orientation" => orientation.to_string(),
                 "confidence" => confidence,
                 "audience" => "problem",
             )),
@@ -436,10 +663,12 @@ fn validate_object_bounds(
 /// Validate field data content against the active barcode's `fieldDataRules`.
 ///
 /// Called from `validate_field_close()` when a barcode command was seen in the
-/// current field and field data is present.
+/// current field and field data is present. `len` is the data length under the
+/// active `^CI` encoding (see [`crate::state::DeviceState::field_data_len`]).
 fn validate_barcode_field_data(
     barcode_code: &str,
     fd_content: &str,
+    len: usize,
     rules: &zpl_toolchain_spec_tables::FieldDataRules,
     dspan: Option<Span>,
     issues: &mut Vec<Diagnostic>,
@@ -491,8 +720,6 @@ fn validate_barcode_field_data(
     }
 
     // Length validation
-    let len = fd_content.chars().count();
-
     // allowedLengths takes precedence over exact/min/max.
     if let Some(allowed) = &rules.allowed_lengths {
         if !allowed.contains(&len) {
@@ -671,6 +898,312 @@ fn validate_barcode_field_data(
     }
 }
 
+/// QR Code character modes, in capacity-table order.
+#[derive(Clone, Copy)]
+enum QrMode {
+    Numeric,
+    Alphanumeric,
+    Byte,
+    Kanji,
+}
+
+impl QrMode {
+    fn name(self) -> &'static str {
+        match self {
+            QrMode::Numeric => "numeric",
+            QrMode::Alphanumeric => "alphanumeric",
+            QrMode::Byte => "byte",
+            QrMode::Kanji => "kanji",
+        }
+    }
+}
+
+/// Maximum data capacity of the largest QR Code Model 2 symbol (version 40),
+/// per error correction level (L, M, Q, H) and mode.
+const QR_MODEL2_CAPACITY: [[usize; 4]; 4] = [
+    [7089, 5596, 3993, 3057],
+    [4296, 3391, 2420, 1852],
+    [2953, 2331, 1663, 1273],
+    [1817, 1435, 1024, 784],
+];
+
+/// Maximum data capacity of the largest QR Code Model 1 symbol (version 14),
+/// per error correction level (L, M, Q, H) and mode.
+const QR_MODEL1_CAPACITY: [[usize; 4]; 4] = [
+    [1167, 919, 707, 483],
+    [707, 557, 428, 292],
+    [486, 382, 294, 200],
+    [299, 236, 181, 123],
+];
+
+/// Characters encodable in QR alphanumeric mode.
+const QR_ALPHANUMERIC: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// ZPL2402: QR Code `^FD` data exceeding the symbol capacity.
+///
+/// Parses the `^FD` switch fields (`[D<codeNo><divs><parity>,]<errCorr><inputMode>,<data>`)
+/// and compares the data length against a coarse per-mode capacity table for
+/// the largest symbol of the `^BQ` model. Automatic input mode is assumed to
+/// pick the densest mode that fits all characters. Unparseable switch fields
+/// are skipped.
+fn validate_qr_capacity(
+    barcode_code: &str,
+    fd_content: &str,
+    model: Option<&str>,
+    rules: &zpl_toolchain_spec_tables::FieldDataRules,
+    dspan: Option<Span>,
+    issues: &mut Vec<Diagnostic>,
+) {
+    // Structured append header: D<codeNo><divs><parity>,
+    let payload = match fd_content.strip_prefix('D') {
+        Some(rest) => match rest.split_once(',') {
+            Some((_, after)) => after,
+            None => return,
+        },
+        None => fd_content,
+    };
+    let mut chars = payload.chars();
+    let (Some(ec), Some(input_mode), Some(',')) = (chars.next(), chars.next(), chars.next()) else {
+        return;
+    };
+    let Some(ec_index) = "LMQH".find(ec) else {
+        return;
+    };
+    let data = chars.as_str();
+
+    let (mode, data) = match input_mode {
+        'A' => {
+            let mode = if data.chars().all(|c| c.is_ascii_digit()) {
+                QrMode::Numeric
+            } else if data.chars().all(|c| QR_ALPHANUMERIC.contains(c)) {
+                QrMode::Alphanumeric
+            } else {
+                QrMode::Byte
+            };
+            (mode, data)
+        }
+        'M' => match data.chars().next() {
+            Some('N') => (QrMode::Numeric, &data[1..]),
+            Some('A') => (QrMode::Alphanumeric, &data[1..]),
+            // Byte mode carries a 4-digit byte count before the data.
+            Some('B') => (QrMode::Byte, data.get(5..).unwrap_or("")),
+            Some('K') => (QrMode::Kanji, &data[1..]),
+            _ => return,
+        },
+        _ => return,
+    };
+    let len = match mode {
+        QrMode::Byte => data.len(),
+        _ => data.chars().count(),
+    };
+    let table = if model == Some("1") {
+        &QR_MODEL1_CAPACITY
+    } else {
+        &QR_MODEL2_CAPACITY
+    };
+    let capacity = table[mode as usize][ec_index];
+    if len <= capacity {
+        return;
+    }
+
+    let actual = len.to_string();
+    let max = capacity.to_string();
+    let message = render_diagnostic_message(
+        codes::BARCODE_DATA_LENGTH,
+        "qrCapacity",
+        &[
+            ("command", barcode_code.to_string()),
+            ("mode", mode.name().to_string()),
+            ("errorCorrection", ec.to_string()),
+            ("actual", actual.clone()),
+            ("max", max.clone()),
+        ],
+        format!(
+            "{} {} data too long for error correction {}: {} chars (capacity {})",
+            barcode_code,
+            mode.name(),
+            ec,
+            actual,
+            max
+        ),
+    );
+    issues.push(
+        diagnostic_with_constraint_severity(
+            codes::BARCODE_DATA_LENGTH,
+            rules
+                .length_severity
+                .unwrap_or(zpl_toolchain_spec_tables::ConstraintSeverity::Warn),
+            message,
+            dspan,
+        )
+        .with_context(ctx!(
+            "command" => barcode_code,
+            "mode" => mode.name(),
+            "errorCorrection" => ec.to_string(),
+            "actual" => actual,
+            "max" => max,
+        )),
+    );
+}
+
+/// `^BX` arguments that determine Data Matrix symbol capacity.
+struct DataMatrixParams<'a> {
+    quality: Option<&'a str>,
+    columns: Option<&'a str>,
+    rows: Option<&'a str>,
+    format_id: Option<&'a str>,
+    aspect: Option<&'a str>,
+}
+
+/// Maximum field sizes for Data Matrix ECC 000-140, per quality level and
+/// `^BX` format ID (1-6), from the ZPL II Programming Guide.
+const DATA_MATRIX_ECC140_CAPACITY: [(&str, [usize; 6]); 5] = [
+    ("0", [596, 452, 394, 413, 310, 271]),
+    ("50", [457, 333, 291, 305, 228, 200]),
+    ("80", [402, 293, 256, 268, 201, 176]),
+    ("100", [300, 218, 190, 200, 150, 131]),
+    ("140", [144, 105, 91, 96, 72, 63]),
+];
+
+/// ECC 200 square symbol sizes and their data codeword counts.
+const DATA_MATRIX_ECC200_SQUARE: [(u32, usize); 24] = [
+    (10, 3),
+    (12, 5),
+    (14, 8),
+    (16, 12),
+    (18, 18),
+    (20, 22),
+    (22, 30),
+    (24, 36),
+    (26, 44),
+    (32, 62),
+    (36, 86),
+    (40, 114),
+    (44, 144),
+    (48, 174),
+    (52, 204),
+    (64, 280),
+    (72, 368),
+    (80, 456),
+    (88, 576),
+    (96, 696),
+    (104, 816),
+    (120, 1050),
+    (132, 1304),
+    (144, 1558),
+];
+
+/// ECC 200 rectangular symbol sizes (rows, columns) and their data codeword counts.
+const DATA_MATRIX_ECC200_RECT: [(u32, u32, usize); 6] = [
+    (8, 18, 5),
+    (8, 32, 10),
+    (12, 26, 16),
+    (12, 36, 22),
+    (16, 36, 32),
+    (16, 48, 49),
+];
+
+/// ZPL2402: Data Matrix `^FD` data exceeding the symbol capacity.
+///
+/// ECC 000-140 use the guide's maximum field sizes by format ID. ECC 200
+/// estimates capacity from data codewords (two digits, or 1.5 C40 characters,
+/// or one other character per codeword) for the requested rows/columns, or
+/// for the largest symbol of the aspect ratio when no listed size is requested.
+fn validate_data_matrix_capacity(
+    barcode_code: &str,
+    fd_content: &str,
+    params: DataMatrixParams<'_>,
+    rules: &zpl_toolchain_spec_tables::FieldDataRules,
+    dspan: Option<Span>,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let quality = params.quality.unwrap_or("0");
+    let len = fd_content.chars().count();
+    let (capacity, symbol) = if quality == "200" {
+        let rectangular = params.aspect == Some("2");
+        let rows = params.rows.and_then(|v| v.parse::<u32>().ok());
+        let columns = params.columns.and_then(|v| v.parse::<u32>().ok());
+        let sized = if rectangular {
+            DATA_MATRIX_ECC200_RECT
+                .iter()
+                .find(|(r, c, _)| Some(*r) == rows && Some(*c) == columns)
+                .map(|&(r, c, cw)| (format!("{}x{}", r, c), cw))
+        } else {
+            DATA_MATRIX_ECC200_SQUARE
+                .iter()
+                .find(|(size, _)| Some(*size) == rows && columns.is_none_or(|c| c == *size))
+                .map(|&(size, cw)| (format!("{}x{}", size, size), cw))
+        };
+        let (symbol, codewords) = sized.unwrap_or_else(|| {
+            let largest = if rectangular { 49 } else { 1558 };
+            ("largest symbol".to_string(), largest)
+        });
+        let capacity = if fd_content.chars().all(|c| c.is_ascii_digit()) {
+            codewords * 2
+        } else if fd_content
+            .chars()
+            .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase() || c == ' ')
+        {
+            codewords * 3 / 2
+        } else {
+            codewords
+        };
+        (capacity, symbol)
+    } else {
+        let Some((_, sizes)) = DATA_MATRIX_ECC140_CAPACITY
+            .iter()
+            .find(|(level, _)| *level == quality)
+        else {
+            // Invalid quality levels are reported by enum validation.
+            return;
+        };
+        let format_id = params
+            .format_id
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|id| (1..=6).contains(id))
+            .unwrap_or(6);
+        (sizes[format_id - 1], "largest symbol".to_string())
+    };
+    if len <= capacity {
+        return;
+    }
+
+    let actual = len.to_string();
+    let max = capacity.to_string();
+    let message = render_diagnostic_message(
+        codes::BARCODE_DATA_LENGTH,
+        "dataMatrixCapacity",
+        &[
+            ("command", barcode_code.to_string()),
+            ("quality", quality.to_string()),
+            ("symbol", symbol.clone()),
+            ("actual", actual.clone()),
+            ("max", max.clone()),
+        ],
+        format!(
+            "{} data too long for quality {} ({}): {} chars (capacity {})",
+            barcode_code, quality, symbol, actual, max
+        ),
+    );
+    issues.push(
+        diagnostic_with_constraint_severity(
+            codes::BARCODE_DATA_LENGTH,
+            rules
+                .length_severity
+                .unwrap_or(zpl_toolchain_spec_tables::ConstraintSeverity::Warn),
+            message,
+            dspan,
+        )
+        .with_context(ctx!(
+            "command" => barcode_code,
+            "quality" => quality,
+            "symbol" => symbol,
+            "actual" => actual,
+            "max" => max,
+        )),
+    );
+}
+
 /// Check if a character is in a compact character set notation.
 ///
 /// Supports:
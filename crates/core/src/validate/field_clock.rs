@@ -0,0 +1,99 @@
+use super::ctx;
+use super::diagnostics_util::diagnostic_with_spec_severity;
+use crate::grammar::ast::{ArgSlot, Label, Node};
+use crate::grammar::diag::{Diagnostic, Span, codes};
+
+/// Clock indicators declared by a `^FC`, waiting for the field's data.
+struct PendingClock {
+    span: Span,
+    indicators: Vec<char>,
+    used: bool,
+}
+
+impl PendingClock {
+    fn scan(&mut self, data: &str) {
+        self.used |= data.chars().any(|c| self.indicators.contains(&c));
+    }
+}
+
+/// ZPL2322: Flag a `^FC` whose clock indicators never appear in the data of
+/// the same field.
+///
+/// The printer only substitutes date and time where an indicator (`%` by
+/// default, plus the optional secondary and third characters) is followed by
+/// a clock code in `^FD`/`^FV`, so a `^FC` with no indicator in the field's
+/// data has no effect.
+pub(super) fn validate_field_clock(label: &Label, issues: &mut Vec<Diagnostic>) {
+    let mut pending: Option<PendingClock> = None;
+    for node in &label.nodes {
+        match node {
+            Node::FieldData { content, .. } => {
+                if let Some(clock) = pending.as_mut() {
+                    clock.scan(content);
+                }
+            }
+            Node::Command {
+                code, args, span, ..
+            } => match code.as_str() {
+                "^FC" => {
+                    flush(pending.take(), issues);
+                    pending = Some(PendingClock {
+                        span: *span,
+                        indicators: declared_indicators(args),
+                        used: false,
+                    });
+                }
+                "^FD" | "^FV" => {
+                    if let (Some(clock), Some(data)) =
+                        (pending.as_mut(), args.first().and_then(ArgSlot::as_str))
+                    {
+                        clock.scan(data);
+                    }
+                }
+                "^FS" => flush(pending.take(), issues),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    flush(pending, issues);
+}
+
+/// Primary indicator (default `%`) followed by any secondary and third
+/// indicators given.
+fn declared_indicators(args: &[ArgSlot]) -> Vec<char> {
+    let indicator = |i: usize| {
+        args.get(i)
+            .and_then(ArgSlot::as_str)
+            .and_then(|s| s.chars().next())
+    };
+    let mut indicators = vec![indicator(0).unwrap_or('%')];
+    indicators.extend([indicator(1), indicator(2)].into_iter().flatten());
+    indicators
+}
+
+fn flush(clock: Option<PendingClock>, issues: &mut Vec<Diagnostic>) {
+    let Some(clock) = clock else {
+        return;
+    };
+    if clock.used {
+        return;
+    }
+    let indicators: String = clock.indicators.iter().collect();
+    let declared = if clock.indicators.len() == 1 {
+        format!("clock indicator '{indicators}' does")
+    } else {
+        format!("clock indicators '{indicators}' do")
+    };
+    issues.push(
+        diagnostic_with_spec_severity(
+            codes::FIELD_CLOCK_UNUSED,
+            format!("^FC {declared} not appear in the field data; no date or time is substituted"),
+            Some(clock.span),
+        )
+        .with_context(ctx!(
+            "command" => "^FC",
+            "indicators" => indicators,
+        )),
+    );
+}
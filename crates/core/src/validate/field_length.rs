@@ -0,0 +1,66 @@
+use super::ctx;
+use super::diagnostics_util::diagnostic_with_spec_severity;
+use crate::grammar::ast::{Label, Node};
+use crate::grammar::diag::{Diagnostic, Span, codes};
+
+/// ZPL2318: Flag field data longer than the caller's configured limit.
+///
+/// The data of each `^FD`/`^FV` is measured in characters, including
+/// continuation lines that the parser keeps as separate field-data nodes.
+/// This is a policy guardrail independent of any barcode's length rules.
+pub(super) fn validate_field_data_length(
+    label: &Label,
+    max_len: Option<usize>,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let Some(max_len) = max_len else {
+        return;
+    };
+    // (command, character count, span) of the field data being collected.
+    let mut current: Option<(&str, usize, Span)> = None;
+    for node in &label.nodes {
+        match node {
+            Node::FieldData { content, span, .. } => {
+                if let Some((_, len, data_span)) = current.as_mut() {
+                    *len += content.chars().count();
+                    data_span.end = span.end;
+                }
+            }
+            Node::Command {
+                code, args, span, ..
+            } => {
+                flush(current.take(), max_len, issues);
+                if code == "^FD" || code == "^FV" {
+                    let len = args
+                        .first()
+                        .and_then(|slot| slot.value.as_deref())
+                        .map_or(0, |v| v.chars().count());
+                    current = Some((code, len, *span));
+                }
+            }
+            _ => {}
+        }
+    }
+    flush(current, max_len, issues);
+}
+
+fn flush(data: Option<(&str, usize, Span)>, max_len: usize, issues: &mut Vec<Diagnostic>) {
+    let Some((code, len, span)) = data else {
+        return;
+    };
+    if len <= max_len {
+        return;
+    }
+    issues.push(
+        diagnostic_with_spec_severity(
+            codes::FIELD_DATA_TOO_LONG,
+            format!("{code} data is {len} characters, over the configured limit of {max_len}"),
+            Some(span),
+        )
+        .with_context(ctx!(
+            "command" => code,
+            "length" => len.to_string(),
+            "limit" => max_len.to_string(),
+        )),
+    );
+}
@@ -0,0 +1,126 @@
+use super::ctx;
+use super::diagnostics_util::diagnostic_with_spec_severity;
+use crate::grammar::ast::{ArgSlot, Label, Node};
+use crate::grammar::diag::{Diagnostic, codes};
+use crate::state::DeviceState;
+
+/// Default extension a font download command stores its font under.
+fn download_extension(code: &str, args: &[ArgSlot]) -> Option<&'static str> {
+    match code {
+        "~DU" | "~DS" | "~DB" => Some("FNT"),
+        "~DT" => Some("DAT"),
+        // `~DY` stores many object kinds; only TrueType ones are fonts.
+        "~DY" => match args.get(2).and_then(ArgSlot::as_str).map(str::trim) {
+            Some(x) if x.eq_ignore_ascii_case("T") => Some("TTF"),
+            Some(x) if x.eq_ignore_ascii_case("E") => Some("TTE"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Normalize a font path to `D:NAME.EXT`, filling in the default drive
+/// (`R:`) and the given default extension.
+fn normalize_font_path(path: &str, default_ext: &str) -> String {
+    let path = path.trim().to_ascii_uppercase();
+    let (drive, name) = match path.split_once(':') {
+        Some((drive, name)) if !drive.is_empty() => (drive.to_string(), name.to_string()),
+        Some((_, name)) => ("R".to_string(), name.to_string()),
+        None => ("R".to_string(), path),
+    };
+    if name.contains('.') {
+        format!("{drive}:{name}")
+    } else {
+        format!("{drive}:{name}.{default_ext}")
+    }
+}
+
+/// ZPL2316/ZPL2317: Track `^CW` font aliases across the job.
+///
+/// Font downloads (`~DU`, `~DT`, `~DS`, `~DB`, and TrueType `~DY`) are
+/// recorded in `device_state`. A `^CW` that reassigns a letter already
+/// aliased to a different font is reported as a redefinition; one that
+/// aliases a RAM (`R:`) font not downloaded earlier in the job is reported
+/// as missing. Fonts on other drives may be resident or stored before the
+/// job, so they are not checked.
+pub(super) fn validate_font_aliases(
+    label: &Label,
+    device_state: &mut DeviceState,
+    issues: &mut Vec<Diagnostic>,
+) {
+    for node in &label.nodes {
+        let Node::Command {
+            code, args, span, ..
+        } = node
+        else {
+            continue;
+        };
+        if let Some(ext) = download_extension(code, args) {
+            if let Some(path) = args
+                .first()
+                .and_then(ArgSlot::as_str)
+                .filter(|p| !p.trim().is_empty())
+            {
+                device_state
+                    .downloaded_fonts
+                    .insert(normalize_font_path(path, ext));
+            }
+            continue;
+        }
+        if code != "^CW" {
+            continue;
+        }
+        let Some(letter) = args
+            .first()
+            .and_then(ArgSlot::as_str)
+            .and_then(|v| v.trim().chars().next())
+        else {
+            continue;
+        };
+        let Some(path) = args
+            .get(1)
+            .and_then(ArgSlot::as_str)
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| normalize_font_path(p, "FNT"))
+        else {
+            continue;
+        };
+
+        if path.starts_with("R:") && !device_state.downloaded_fonts.contains(&path) {
+            issues.push(
+                diagnostic_with_spec_severity(
+                    codes::FONT_ALIAS_MISSING_FONT,
+                    format!(
+                        "^CW aliases font '{letter}' to {path}, which was not downloaded earlier in the job"
+                    ),
+                    Some(*span),
+                )
+                .with_context(ctx!(
+                    "command" => "^CW",
+                    "font" => letter.to_string(),
+                    "path" => path.clone(),
+                )),
+            );
+        }
+
+        if let Some(previous) = device_state.font_aliases.insert(letter, path.clone())
+            && previous != path
+        {
+            issues.push(
+                diagnostic_with_spec_severity(
+                    codes::FONT_ALIAS_REDEFINED,
+                    format!(
+                        "^CW redefines font '{letter}' as {path}, replacing {previous} for the rest of the job"
+                    ),
+                    Some(*span),
+                )
+                .with_context(ctx!(
+                    "command" => "^CW",
+                    "font" => letter.to_string(),
+                    "path" => path,
+                    "previous" => previous,
+                )),
+            );
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use super::ctx;
+use super::diagnostics_util::{diagnostic_with_spec_severity, trim_f64};
+use crate::grammar::ast::{ArgSlot, Label, Node};
+use crate::grammar::diag::{Diagnostic, Span, codes};
+use crate::state::{DeviceState, RetainedField};
+
+/// ZPL2323 / ZPL2324: Check a label printed over an image kept by `^MCN`,
+/// then record what this label leaves in the image for the next one.
+///
+/// With `^MCN` the bitmap is not cleared after printing, so `^FD` data
+/// stays in it (only `^FV` data is cleared). A later field drawn at the
+/// same origin prints over that stale data (ZPL2323), and an `^MD` change
+/// reprints the retained content at the new darkness too (ZPL2324). `^MC`
+/// applies to the label that sets it and every later one until `^MCY`.
+pub(super) fn validate_map_clear(
+    label: &Label,
+    device_state: &mut DeviceState,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let over_retained_map = device_state.map_retained;
+    let fields = printed_fields(label);
+
+    if over_retained_map {
+        for field in &fields {
+            if device_state.retained_fields.contains(&field.origin) {
+                issues.push(
+                    diagnostic_with_spec_severity(
+                        codes::MAP_RETAINED_OVERPRINT,
+                        format!(
+                            "field at {}{},{} prints over ^FD data kept by ^MCN from an earlier label; use ^FV there so it is cleared after each print",
+                            field.origin.command,
+                            trim_f64(field.origin.x),
+                            trim_f64(field.origin.y),
+                        ),
+                        Some(field.span),
+                    )
+                    .with_context(ctx!(
+                        "command" => field.origin.command.as_str(),
+                        "x" => trim_f64(field.origin.x),
+                        "y" => trim_f64(field.origin.y),
+                    )),
+                );
+            }
+        }
+    }
+
+    for node in &label.nodes {
+        let Node::Command {
+            code, args, span, ..
+        } = node
+        else {
+            continue;
+        };
+        match code.as_str() {
+            "^MC" => device_state.apply_mc(args),
+            "^MD" => {
+                let previous = device_state.darkness_delta.unwrap_or(0.0);
+                device_state.apply_md(args);
+                let current = device_state.darkness_delta.unwrap_or(0.0);
+                if over_retained_map && current != previous {
+                    issues.push(
+                        diagnostic_with_spec_severity(
+                            codes::MAP_RETAINED_DARKNESS_CHANGE,
+                            format!(
+                                "^MD changes darkness from {} to {} while ^MCN keeps the previous image, which reprints at the new darkness too",
+                                trim_f64(previous),
+                                trim_f64(current),
+                            ),
+                            Some(*span),
+                        )
+                        .with_context(ctx!(
+                            "command" => "^MD",
+                            "previous" => trim_f64(previous),
+                            "value" => trim_f64(current),
+                        )),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if device_state.map_retained {
+        device_state.retained_fields.extend(
+            fields
+                .into_iter()
+                .filter(|field| field.field_data)
+                .map(|field| field.origin),
+        );
+    } else {
+        device_state.retained_fields.clear();
+    }
+}
+
+/// A positioned field and whether it prints `^FD` (rather than `^FV`) data.
+struct PrintedField {
+    origin: RetainedField,
+    span: Span,
+    field_data: bool,
+}
+
+/// Fields closed by `^FS`, in source order.
+fn printed_fields(label: &Label) -> Vec<PrintedField> {
+    let mut fields = Vec::new();
+    let mut open: Option<PrintedField> = None;
+    for node in &label.nodes {
+        let Node::Command {
+            code, args, span, ..
+        } = node
+        else {
+            continue;
+        };
+        match code.as_str() {
+            "^FO" | "^FT" => {
+                open = Some(PrintedField {
+                    origin: RetainedField {
+                        command: code.clone(),
+                        x: coordinate(args, 0),
+                        y: coordinate(args, 1),
+                    },
+                    span: *span,
+                    field_data: false,
+                });
+            }
+            "^FD" => {
+                if let Some(field) = open.as_mut() {
+                    field.field_data = true;
+                }
+            }
+            "^FS" => fields.extend(open.take()),
+            _ => {}
+        }
+    }
+    fields
+}
+
+fn coordinate(args: &[ArgSlot], index: usize) -> f64 {
+    args.get(index)
+        .and_then(ArgSlot::as_str)
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0.0)
+}
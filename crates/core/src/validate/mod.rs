@@ -2,28 +2,45 @@ pub use crate::grammar::diag::Diagnostic;
 use crate::grammar::{ast::Ast, diag::Severity, tables::ParserTables};
 use crate::state::{DeviceState, ResolvedLabelState};
 use serde::Serialize;
+use std::collections::HashSet;
 use zpl_toolchain_profile::Profile;
 
 mod args;
+mod catalog;
 mod constraints;
 mod context;
+mod custom;
 mod diagnostics_util;
 mod field;
+mod field_clock;
+mod field_length;
+mod font_aliases;
+mod map_clear;
+mod object_delete;
 mod pipeline;
 mod plan;
+mod policy;
 mod predicates;
 mod preflight;
 mod profile_constraints;
+mod resource_names;
 mod semantic;
 mod state;
+mod stored_formats;
 
+pub use self::catalog::diagnostics_for;
+pub use self::custom::{ConstraintCtx, CustomConstraintFn, CustomConstraints};
 use self::diagnostics_util::sort_diagnostics_deterministically;
+use self::field_length::validate_field_data_length;
 use self::pipeline::validate_label;
 use self::plan::ValidationPlanContext;
 #[cfg(test)]
 use self::plan::{EffectIndexView, SemanticIndexView, StructuralIndexView};
+use self::policy::{validate_forbidden_commands, validate_required_commands};
 #[cfg(test)]
-pub(crate) use self::predicates::{firmware_version_gte, profile_predicate_matches};
+pub(crate) use self::predicates::{
+    firmware_version_gte, firmware_version_lt, profile_predicate_matches,
+};
 pub use self::profile_constraints::resolve_profile_field;
 
 /// Shorthand for building a `BTreeMap<String, String>` context from key-value pairs.
@@ -40,6 +57,7 @@ pub(super) use ctx;
 
 /// Result of validating a ZPL AST against spec tables and an optional printer profile.
 #[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ValidationResult {
     /// `true` if no errors were found (warnings and info are allowed).
     pub ok: bool,
@@ -49,6 +67,21 @@ pub struct ValidationResult {
     pub resolved_labels: Vec<ResolvedLabelState>,
 }
 
+impl ValidationResult {
+    /// Merge parser diagnostics into this result.
+    ///
+    /// All diagnostics are ordered by span start (unspanned ones last), with
+    /// parse diagnostics ahead of validation diagnostics at the same offset.
+    /// `ok` is recomputed, so a parse error fails the result too.
+    pub fn merge_parse(&mut self, parse_diagnostics: Vec<Diagnostic>) {
+        let mut issues = parse_diagnostics;
+        issues.append(&mut self.issues);
+        issues.sort_by_key(|d| d.span.map_or(usize::MAX, |s| s.start));
+        self.ok = !issues.iter().any(|d| matches!(d.severity, Severity::Error));
+        self.issues = issues;
+    }
+}
+
 // ─── Main validation entry points ──────────────────────────────────────────
 
 /// Validate a ZPL AST using spec tables and an optional printer profile.
@@ -58,6 +91,61 @@ pub fn validate_with_profile(
     ast: &Ast,
     tables: &ParserTables,
     profile: Option<&Profile>,
+) -> ValidationResult {
+    validate_with_options(ast, tables, profile, &ValidationOptions::default())
+}
+
+/// Validate a ZPL AST once per printer profile, e.g. to build a fleet
+/// compatibility matrix.
+///
+/// Returns one `(profile id, result)` pair per profile, in input order.
+pub fn validate_against_profiles(
+    ast: &Ast,
+    tables: &ParserTables,
+    profiles: &[Profile],
+) -> Vec<(String, ValidationResult)> {
+    profiles
+        .iter()
+        .map(|profile| {
+            (
+                profile.id.clone(),
+                validate_with_profile(ast, tables, Some(profile)),
+            )
+        })
+        .collect()
+}
+
+/// Caller policy for [`validate_with_options`]. The default adds nothing to
+/// [`validate_with_profile`].
+#[derive(Default)]
+pub struct ValidationOptions {
+    /// Evaluators for `custom` spec constraints, keyed by the constraint's
+    /// `expr`; constraints without a registered evaluator are skipped.
+    pub custom_constraints: CustomConstraints,
+    /// Command codes (e.g. `~JR`, `^ID`) reported as
+    /// [`FORBIDDEN_COMMAND`](crate::grammar::diag::codes::FORBIDDEN_COMMAND)
+    /// errors wherever they appear.
+    pub forbidden_commands: HashSet<String>,
+    /// Command codes every label must contain; each one missing is reported
+    /// as a [`REQUIRED_COMMAND`](crate::grammar::diag::codes::REQUIRED_COMMAND)
+    /// warning.
+    pub required_commands: Vec<String>,
+    /// Longest `^FD`/`^FV` data allowed, in characters; longer data is
+    /// reported as
+    /// [`FIELD_DATA_TOO_LONG`](crate::grammar::diag::codes::FIELD_DATA_TOO_LONG).
+    pub max_field_data_len: Option<usize>,
+}
+
+/// Validate a ZPL AST with caller policy on top of the spec and profile
+/// checks: custom constraint evaluators, forbidden and required commands,
+/// and a field data length limit (see [`ValidationOptions`]).
+///
+/// Otherwise behaves like [`validate_with_profile`].
+pub fn validate_with_options(
+    ast: &Ast,
+    tables: &ParserTables,
+    profile: Option<&Profile>,
+    options: &ValidationOptions,
 ) -> ValidationResult {
     let mut issues = Vec::new();
     let mut resolved_labels = Vec::new();
@@ -70,16 +158,21 @@ pub fn validate_with_profile(
         device_state.dpi = Some(p.dpi);
     }
 
-    for label in &ast.labels {
-        resolved_labels.push(validate_label(
+    for (index, label) in ast.labels.iter().enumerate() {
+        validate_forbidden_commands(label, &options.forbidden_commands, &mut issues);
+        validate_required_commands(label, &options.required_commands, &mut issues);
+        validate_field_data_length(label, options.max_field_data_len, &mut issues);
+        let resolved = validate_label(
             label,
             tables,
             known,
             &plan_ctx,
             profile,
+            &options.custom_constraints,
             &mut device_state,
             &mut issues,
-        ));
+        );
+        resolved_labels.push(ResolvedLabelState { index, ..resolved });
     }
 
     sort_diagnostics_deterministically(&mut issues);
@@ -201,6 +294,18 @@ mod tests {
             Some(&p)
         ));
         assert!(!profile_predicate_matches("profile:firmware:V50", Some(&p)));
+        assert!(profile_predicate_matches(
+            "profile:firmwareLt:V60.20",
+            Some(&p)
+        ));
+        assert!(!profile_predicate_matches(
+            "profile:firmwareLt:V60.14",
+            Some(&p)
+        ));
+        assert!(!profile_predicate_matches(
+            "profile:firmwareLt:V60.14",
+            None
+        ));
     }
 
     #[test]
@@ -211,6 +316,15 @@ mod tests {
         assert!(!firmware_version_gte("V60.13.9", "V60.14"));
         assert!(!firmware_version_gte("V50.20.0", "V60.14"));
         assert!(firmware_version_gte("X60.16.0", "V60.16"));
+        assert!(firmware_version_lt("V60.13.9", "V60.14"));
+        assert!(!firmware_version_lt("V60.14.0", "V60.14"));
+        assert!(!firmware_version_lt("unknown", "V60.14"));
+        // One threshold per firmware line.
+        assert!(!firmware_version_lt("V50.14.1", "V60.14|V50.14"));
+        assert!(firmware_version_lt("V50.13.2", "V60.14|V50.14"));
+        assert!(firmware_version_lt("V60.13.9", "V60.14|V50.14"));
+        assert!(firmware_version_gte("V50.14.0", "V60.14|V50.14"));
+        assert!(!firmware_version_gte("V53.13.0", "V60.14|V50.14"));
     }
 
     #[test]
@@ -0,0 +1,67 @@
+use super::ctx;
+use crate::grammar::ast::{ArgSlot, Label, Node};
+use crate::grammar::diag::{Diagnostic, Severity, codes};
+
+/// ZPL2321: Report every `^ID` object delete.
+///
+/// A wildcard pattern (`^IDR:*.GRF`) can wipe fonts and graphics other jobs
+/// rely on, so it is a warning; deleting one named object is an info note.
+/// Callers that never want `^ID` in a job can forbid it outright through
+/// `validate_with_options`, which reports it as an error.
+pub(super) fn validate_object_deletes(label: &Label, issues: &mut Vec<Diagnostic>) {
+    for node in &label.nodes {
+        let Node::Command {
+            code, args, span, ..
+        } = node
+        else {
+            continue;
+        };
+        if code != "^ID" {
+            continue;
+        }
+        let pattern = delete_pattern(args);
+        let (severity, message) = if pattern.contains('*') {
+            (
+                Severity::Warn,
+                format!("^ID deletes every stored object matching '{pattern}'"),
+            )
+        } else {
+            (
+                Severity::Info,
+                format!("^ID deletes stored object '{pattern}'"),
+            )
+        };
+        issues.push(
+            Diagnostic::new(codes::OBJECT_DELETE, severity, message, Some(*span)).with_context(
+                ctx!(
+                    "command" => code.as_str(),
+                    "pattern" => pattern,
+                ),
+            ),
+        );
+    }
+}
+
+/// Rebuild the `d:o.x` path from the drive and name arguments, filling in
+/// the printer defaults (`R:`, `UNKNOWN`, `.GRF`) for missing parts.
+fn delete_pattern(args: &[ArgSlot]) -> String {
+    let part = |i: usize| {
+        args.get(i)
+            .and_then(ArgSlot::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    let (drive, name) = match (part(0), part(1)) {
+        (Some(drive), Some(name)) => (drive, name),
+        // `^ID*.*` or `^IDLOGO.GRF`: no drive, so the path lands in the first slot.
+        (Some(path), None) => ("R", path),
+        (None, Some(name)) => ("R", name),
+        (None, None) => ("R", "UNKNOWN"),
+    };
+    if name.contains('.') {
+        format!("{drive}:{name}")
+    } else {
+        let ext = part(2).unwrap_or("GRF");
+        format!("{drive}:{name}.{ext}")
+    }
+}
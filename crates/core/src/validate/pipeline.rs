@@ -1,20 +1,27 @@
 use super::args::validate_command_args;
 use super::constraints::validate_command_constraints;
 use super::context::{CommandCtx, ValidationContext};
-use super::diagnostics_util::diagnostic_with_spec_severity;
+use super::custom::CustomConstraints;
+use super::diagnostics_util::{diagnostic_with_spec_severity, trim_f64};
 use super::field::FieldTracker;
+use super::field_clock::validate_field_clock;
+use super::font_aliases::validate_font_aliases;
+use super::map_clear::validate_map_clear;
+use super::object_delete::validate_object_deletes;
 use super::plan::{LabelExecutionPlan, StructuralFlags, ValidationPlanContext};
 use super::preflight::validate_preflight;
+use super::resource_names::validate_resource_names;
 use super::semantic::{consume_default_from_refs, validate_structural_semantics};
 use super::state::LabelState;
+use super::stored_formats::validate_stored_formats;
 use super::{Diagnostic, ctx};
 use crate::grammar::ast::{ArgSlot, Label, Node};
-use crate::grammar::diag::codes;
+use crate::grammar::diag::{Severity, codes};
 use crate::grammar::tables::ParserTables;
-use crate::state::{DeviceState, ResolvedLabelState};
+use crate::state::{BarcodeDefaults, DeviceState, NamedField, ResolvedLabelState};
 use std::collections::HashSet;
 use zpl_toolchain_profile::Profile;
-use zpl_toolchain_spec_tables::{CommandEntry, CommandScope, Plane};
+use zpl_toolchain_spec_tables::{ArgUnion, CommandEntry, CommandScope, Plane};
 
 struct FieldMembership<'a> {
     field_id_by_node: Vec<Option<usize>>,
@@ -36,6 +43,7 @@ struct ConstraintSets<'a> {
 struct KnownCommandEnv<'a> {
     label: &'a Label,
     profile: Option<&'a Profile>,
+    custom_constraints: &'a CustomConstraints,
     label_codes: &'a HashSet<&'a str>,
     field_membership: &'a FieldMembership<'a>,
     inside_format_bounds: bool,
@@ -47,6 +55,7 @@ struct LabelCommandEnv<'a> {
     tables: &'a ParserTables,
     known: &'a HashSet<String>,
     profile: Option<&'a Profile>,
+    custom_constraints: &'a CustomConstraints,
     label_codes: &'a HashSet<&'a str>,
     field_membership: &'a FieldMembership<'a>,
     planning: PlanningContext<'a>,
@@ -67,26 +76,34 @@ struct CommandNode<'a> {
     cmd: &'a CommandEntry,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn validate_label(
     label: &Label,
     tables: &ParserTables,
     known: &HashSet<String>,
     plan_ctx: &ValidationPlanContext,
     profile: Option<&Profile>,
+    custom_constraints: &CustomConstraints,
     device_state: &mut DeviceState,
     issues: &mut Vec<Diagnostic>,
 ) -> ResolvedLabelState {
+    let issues_before = issues.len();
     let label_codes = collect_label_codes(label);
     let plan = plan_ctx.plan_for_label(&label_codes, profile);
     let field_membership = build_field_membership(label, tables, known, plan_ctx);
 
-    let mut label_state = LabelState::default();
+    let mut label_state = LabelState {
+        inverted: last_arg_is(label, "^PO", "I"),
+        mirrored: last_arg_is(label, "^PM", "Y"),
+        ..LabelState::default()
+    };
     let mut field_tracker = FieldTracker::default();
     let command_env = LabelCommandEnv {
         label,
         tables,
         known,
         profile,
+        custom_constraints,
         label_codes: &label_codes,
         field_membership: &field_membership,
         planning: PlanningContext {
@@ -103,6 +120,12 @@ pub(super) fn validate_label(
     let has_printable = process_label_commands(&command_env, &mut command_state);
 
     emit_unclosed_field_diagnostic(label, &field_tracker, issues);
+    validate_stored_formats(label, tables, device_state, issues);
+    validate_font_aliases(label, device_state, issues);
+    validate_resource_names(label, issues);
+    validate_object_deletes(label, issues);
+    validate_field_clock(label, issues);
+    validate_map_clear(label, device_state, issues);
     run_label_preflight(
         label,
         profile,
@@ -115,6 +138,18 @@ pub(super) fn validate_label(
     emit_empty_label_diagnostic(label, has_printable, issues);
 
     ResolvedLabelState {
+        // Position in the document is assigned by `validate_with_profile`.
+        index: 0,
+        span: label.span(),
+        command_count: label
+            .nodes
+            .iter()
+            .filter(|n| matches!(n, Node::Command { .. }))
+            .count(),
+        error_count: issues[issues_before..]
+            .iter()
+            .filter(|d| matches!(d.severity, Severity::Error))
+            .count(),
         values: label_state.value_state.clone(),
         // Keep effective dimensions populated for downstream consumers even if
         // semantic rule indexing is sparse; typed producer state remains canonical.
@@ -124,9 +159,46 @@ pub(super) fn validate_label(
         effective_height: label_state
             .effective_height
             .or(label_state.value_state.layout.label_length),
+        fields: named_fields(label, tables),
     }
 }
 
+/// Collect `^FN` field numbers and prompt names in source order.
+fn named_fields(label: &Label, tables: &ParserTables) -> Vec<NamedField> {
+    label
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            let Node::Command {
+                code, args, span, ..
+            } = node
+            else {
+                return None;
+            };
+            if !tables.cmd_by_code(code).is_some_and(|c| c.field_number) {
+                return None;
+            }
+            let number = args.first()?.as_str()?.trim().parse().ok()?;
+            let name = args
+                .get(1)
+                .and_then(ArgSlot::as_str)
+                .map(|p| {
+                    let p = p.trim();
+                    p.strip_prefix('"')
+                        .and_then(|p| p.strip_suffix('"'))
+                        .unwrap_or(p)
+                })
+                .filter(|p| !p.is_empty())
+                .map(str::to_string);
+            Some(NamedField {
+                number,
+                name,
+                span: *span,
+            })
+        })
+        .collect()
+}
+
 fn collect_label_codes(label: &Label) -> HashSet<&str> {
     label
         .nodes
@@ -141,6 +213,24 @@ fn collect_label_codes(label: &Label) -> HashSet<&str> {
         .collect()
 }
 
+/// Whether the first argument of the last `code` command in the label is
+/// `value`. Used for whole-format settings such as `^POI` and `^PMY`.
+fn last_arg_is(label: &Label, code: &str, value: &str) -> bool {
+    label
+        .nodes
+        .iter()
+        .rev()
+        .find_map(|n| match n {
+            Node::Command { code: c, args, .. } if c == code => Some(
+                args.first()
+                    .and_then(|a| a.value.as_deref())
+                    .is_some_and(|v| v.trim().eq_ignore_ascii_case(value)),
+            ),
+            _ => None,
+        })
+        .unwrap_or(false)
+}
+
 fn build_field_membership<'a>(
     label: &'a Label,
     tables: &ParserTables,
@@ -206,7 +296,10 @@ fn process_label_commands<'a>(
     let mut inside_format_bounds = false;
 
     for (node_idx, node) in env.label.nodes.iter().enumerate() {
-        if let Node::Command { code, args, span } = node {
+        if let Node::Command {
+            code, args, span, ..
+        } = node
+        {
             if code == "^XA" {
                 inside_format_bounds = true;
             } else if code == "^XZ" {
@@ -233,6 +326,7 @@ fn process_label_commands<'a>(
                     &KnownCommandEnv {
                         label: env.label,
                         profile: env.profile,
+                        custom_constraints: env.custom_constraints,
                         label_codes: env.label_codes,
                         field_membership: env.field_membership,
                         inside_format_bounds,
@@ -262,6 +356,7 @@ fn process_known_command<'a>(
     seen_field_codes: &mut HashSet<&'a str>,
 ) {
     let dspan = Some(command.span);
+    let issues_before = state.issues.len();
     let structural_flags = env
         .planning
         .plan_ctx
@@ -295,7 +390,8 @@ fn process_known_command<'a>(
     );
     let vctx = ValidationContext {
         profile: env.profile,
-        label_nodes: &env.label.nodes,
+        label: env.label,
+        custom_constraints: env.custom_constraints,
         label_codes: env.label_codes,
         device_state: state.device_state,
     };
@@ -322,7 +418,19 @@ fn process_known_command<'a>(
         dspan,
         state.issues,
     );
+    if env.inside_format_bounds {
+        enforce_field_precedence(
+            command.code,
+            command.cmd,
+            state.label_state,
+            dspan,
+            state.issues,
+        );
+    }
 
+    let closes_barcode_field = structural_flags.closes_field
+        && state.field_tracker.open
+        && state.field_tracker.has_barcode();
     let maybe_field_command = structural_flags.is_field_related();
     if env.planning.plan.run_field_batch || maybe_field_command {
         state.field_tracker.process_command(
@@ -334,6 +442,14 @@ fn process_known_command<'a>(
         );
     }
 
+    if closes_barcode_field || inherits_barcode_defaults(command.cmd) {
+        let effective = env
+            .planning
+            .plan_ctx
+            .effective_barcode_defaults(&state.label_state.value_state.barcode);
+        annotate_effective_barcode_defaults(&mut state.issues[issues_before..], &effective);
+    }
+
     update_session_state(
         command.code,
         command.args,
@@ -349,6 +465,42 @@ fn process_known_command<'a>(
     }
 }
 
+/// Whether any of the command's arguments default from `^BY`.
+fn inherits_barcode_defaults(cmd: &CommandEntry) -> bool {
+    cmd.args.as_ref().is_some_and(|args| {
+        args.iter().any(|arg| {
+            let arg = match arg {
+                ArgUnion::Single(a) => Some(a.as_ref()),
+                ArgUnion::OneOf { one_of } => one_of.first(),
+            };
+            arg.and_then(|a| a.default_from.as_deref()) == Some("^BY")
+        })
+    })
+}
+
+/// Add `effective_module_width`, `effective_ratio`, and `effective_height`
+/// context keys so barcode diagnostics show the inherited `^BY` values.
+fn annotate_effective_barcode_defaults(issues: &mut [Diagnostic], effective: &BarcodeDefaults) {
+    let entries = [
+        (
+            "effective_module_width",
+            effective.module_width.map(|v| v.to_string()),
+        ),
+        ("effective_ratio", effective.ratio.map(trim_f64)),
+        ("effective_height", effective.height.map(|v| v.to_string())),
+    ];
+    for issue in issues {
+        let context = issue.context.get_or_insert_with(Default::default);
+        for (key, value) in &entries {
+            if let Some(value) = value {
+                context
+                    .entry((*key).to_string())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+}
+
 fn apply_effects_and_arity(
     cmd_ctx: &CommandCtx<'_>,
     structural_flags: StructuralFlags,
@@ -523,6 +675,34 @@ fn enforce_placement(
     }
 }
 
+/// Warn when a header-only command (`placement.mustPrecedeFields`) follows
+/// the label's first field-opening command, and record that opener.
+fn enforce_field_precedence(
+    code: &str,
+    cmd: &CommandEntry,
+    label_state: &mut LabelState,
+    dspan: Option<zpl_toolchain_diagnostics::Span>,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let header_only = cmd.placement.as_ref().and_then(|p| p.must_precede_fields) == Some(true);
+    if header_only && let Some(field) = label_state.first_field_opener.as_deref() {
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::HEADER_COMMAND_AFTER_FIELD,
+                format!(
+                    "{} should appear before the first field ({}) in the label",
+                    code, field
+                ),
+                dspan,
+            )
+            .with_context(ctx!("command" => code, "field" => field)),
+        );
+    }
+    if cmd.opens_field && label_state.first_field_opener.is_none() {
+        label_state.first_field_opener = Some(code.to_string());
+    }
+}
+
 fn update_session_state(
     code: &str,
     args: &[crate::grammar::ast::ArgSlot],
@@ -531,8 +711,12 @@ fn update_session_state(
     device_state: &mut DeviceState,
 ) {
     if cmd.scope == Some(CommandScope::Session) {
-        if code == "^MU" {
-            device_state.apply_mu(args);
+        match code {
+            "^MU" => device_state.apply_mu(args),
+            "^JM" => device_state.apply_jm(args),
+            "~SD" => device_state.apply_sd(args),
+            "^CI" => device_state.apply_ci(args),
+            _ => {}
         }
         device_state
             .session_producers
@@ -576,7 +760,8 @@ fn run_label_preflight(
 
     let vctx = ValidationContext {
         profile,
-        label_nodes: &label.nodes,
+        label,
+        custom_constraints: &CustomConstraints::new(),
         label_codes,
         device_state,
     };
@@ -601,6 +786,7 @@ fn emit_empty_label_diagnostic(label: &Label, has_printable: bool, issues: &mut
     ));
 }
 
+/// Span of the label's first node, if that node is a command.
 fn first_command_span(label: &Label) -> Option<zpl_toolchain_diagnostics::Span> {
     label.nodes.first().and_then(|n| {
         if let Node::Command { span, .. } = n {
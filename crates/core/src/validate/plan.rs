@@ -1,7 +1,8 @@
 use crate::grammar::tables::ParserTables;
+use crate::state::BarcodeDefaults;
 use std::collections::HashSet;
 use zpl_toolchain_profile::Profile;
-use zpl_toolchain_spec_tables::{CommandEntry, StructuralRuleIndex, StructuralTrigger};
+use zpl_toolchain_spec_tables::{ArgUnion, CommandEntry, StructuralRuleIndex, StructuralTrigger};
 
 use super::resolve_profile_field;
 
@@ -60,6 +61,31 @@ impl EffectIndexView {
     }
 }
 
+/// Read the `^BY` argument defaults from the spec tables.
+fn barcode_spec_defaults(tables: &ParserTables) -> BarcodeDefaults {
+    let mut defaults = BarcodeDefaults::default();
+    let Some(args) = tables.cmd_by_code("^BY").and_then(|cmd| cmd.args.as_ref()) else {
+        return defaults;
+    };
+    for arg in args {
+        let ArgUnion::Single(arg) = arg else {
+            continue;
+        };
+        let Some(value) = arg.default.as_ref() else {
+            continue;
+        };
+        match arg.key.as_deref() {
+            Some("w") => {
+                defaults.module_width = value.as_u64().and_then(|v| u32::try_from(v).ok());
+            }
+            Some("r") => defaults.ratio = value.as_f64(),
+            Some("h") => defaults.height = value.as_u64().and_then(|v| u32::try_from(v).ok()),
+            _ => {}
+        }
+    }
+    defaults
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub(super) struct StructuralFlags {
     pub(super) opens_field: bool,
@@ -88,6 +114,8 @@ pub(super) struct ValidationPlanContext {
     semantic_index: Option<SemanticIndexView>,
     effect_index: Option<EffectIndexView>,
     structural_index: Option<StructuralIndexView>,
+    /// Spec defaults for `^BY`, used when a label never sets them.
+    barcode_defaults: BarcodeDefaults,
 }
 
 impl ValidationPlanContext {
@@ -96,6 +124,7 @@ impl ValidationPlanContext {
             semantic_index: SemanticIndexView::from_tables(tables),
             effect_index: EffectIndexView::from_tables(tables),
             structural_index: StructuralIndexView::from_tables(tables),
+            barcode_defaults: barcode_spec_defaults(tables),
         }
     }
 
@@ -109,6 +138,17 @@ impl ValidationPlanContext {
             semantic_index,
             effect_index,
             structural_index,
+            barcode_defaults: BarcodeDefaults::default(),
+        }
+    }
+
+    /// Barcode defaults in effect for the label: tracked `^BY` values first,
+    /// falling back to the `^BY` spec defaults.
+    pub(super) fn effective_barcode_defaults(&self, tracked: &BarcodeDefaults) -> BarcodeDefaults {
+        BarcodeDefaults {
+            module_width: tracked.module_width.or(self.barcode_defaults.module_width),
+            ratio: tracked.ratio.or(self.barcode_defaults.ratio),
+            height: tracked.height.or(self.barcode_defaults.height),
         }
     }
 
@@ -0,0 +1,76 @@
+use super::ctx;
+use super::diagnostics_util::diagnostic_with_spec_severity;
+use crate::grammar::ast::{Label, Node};
+use crate::grammar::diag::{Diagnostic, codes};
+use std::collections::HashSet;
+
+/// ZPL2207: Flag every command whose code is in the caller's deny-list.
+///
+/// Codes include their leader (`~JR`, `^ID`) and are compared
+/// case-insensitively.
+pub(super) fn validate_forbidden_commands(
+    label: &Label,
+    forbidden: &HashSet<String>,
+    issues: &mut Vec<Diagnostic>,
+) {
+    if forbidden.is_empty() {
+        return;
+    }
+    for node in &label.nodes {
+        let Node::Command { code, span, .. } = node else {
+            continue;
+        };
+        if !forbidden.iter().any(|f| f.eq_ignore_ascii_case(code)) {
+            continue;
+        }
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::FORBIDDEN_COMMAND,
+                format!("{code} is forbidden by the validation policy"),
+                Some(*span),
+            )
+            .with_context(ctx!("command" => code.as_str())),
+        );
+    }
+}
+
+/// ZPL2101: Flag every command in the caller's required list that the label
+/// does not contain.
+///
+/// Compared case-insensitively like the deny-list. The diagnostic is
+/// anchored on the label's first node (normally its `^XA`).
+pub(super) fn validate_required_commands(
+    label: &Label,
+    required: &[String],
+    issues: &mut Vec<Diagnostic>,
+) {
+    if required.is_empty() {
+        return;
+    }
+    let present: Vec<&str> = label
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Command { code, .. } => Some(code.as_str()),
+            _ => None,
+        })
+        .collect();
+    let anchor = label.nodes.first().map(Node::span);
+    for target in required {
+        if present.iter().any(|code| code.eq_ignore_ascii_case(target)) {
+            continue;
+        }
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::REQUIRED_COMMAND,
+                format!("label is missing {target}, required by the validation policy"),
+                anchor,
+            )
+            .with_context(ctx!(
+                "target" => target.as_str(),
+                "kind" => "policy",
+                "scope" => "label",
+            )),
+        );
+    }
+}
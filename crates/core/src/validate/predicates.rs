@@ -19,35 +19,58 @@ pub(super) fn enum_contains(values: &[EnumValue], target: &str) -> bool {
     })
 }
 
+/// Parse a Zebra firmware version string into `(major, minor)`.
+fn parse_firmware_version(s: &str) -> Option<(u32, u32)> {
+    let s = s
+        .strip_prefix('V')
+        .or_else(|| s.strip_prefix('X'))
+        .unwrap_or(s);
+    let mut parts = s.split('.');
+    let major: u32 = parts.next()?.parse().ok()?;
+    let minor: u32 = parts
+        .next()
+        .and_then(|p| {
+            p.chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse::<u32>()
+                .ok()
+        })
+        .unwrap_or(0);
+    Some((major, minor))
+}
+
+/// Pick the version to compare `fw` against from a `|`-separated list such
+/// as `V60.14|V50.14`, where each entry is the threshold for one firmware
+/// line: the entry with the same major version, else the first entry.
+fn threshold_for_line(fw: (u32, u32), versions: &str) -> Option<(u32, u32)> {
+    let thresholds: Vec<(u32, u32)> = versions
+        .split('|')
+        .filter_map(|v| parse_firmware_version(v.trim()))
+        .collect();
+    thresholds
+        .iter()
+        .find(|t| t.0 == fw.0)
+        .or(thresholds.first())
+        .copied()
+}
+
 /// Compare Zebra firmware version strings (e.g. V60.19.15Z, X60.14.3).
 /// Returns true if fw >= min_ver when both parse, false otherwise.
+/// `min_ver` may list one threshold per firmware line (`V60.14|V50.14`).
 #[allow(dead_code)]
 pub(crate) fn firmware_version_gte(fw: &str, min_ver: &str) -> bool {
-    fn parse_version(s: &str) -> Option<(u32, u32)> {
-        let s = s
-            .strip_prefix('V')
-            .or_else(|| s.strip_prefix('X'))
-            .unwrap_or(s);
-        let mut parts = s.split('.');
-        let major: u32 = parts.next()?.parse().ok()?;
-        let minor: u32 = parts
-            .next()
-            .and_then(|p| {
-                p.chars()
-                    .take_while(|c| c.is_ascii_digit())
-                    .collect::<String>()
-                    .parse::<u32>()
-                    .ok()
-            })
-            .unwrap_or(0);
-        Some((major, minor))
-    }
-    match (parse_version(fw), parse_version(min_ver)) {
-        (Some((fw_maj, fw_min)), Some((min_maj, min_min))) => {
-            (fw_maj, fw_min) >= (min_maj, min_min)
-        }
-        _ => false,
-    }
+    parse_firmware_version(fw)
+        .is_some_and(|fw| threshold_for_line(fw, min_ver).is_some_and(|min| fw >= min))
+}
+
+/// Returns true if fw < max_ver when both parse, false otherwise.
+/// `max_ver` may list one threshold per firmware line (`V60.14|V50.14`).
+///
+/// Unlike `!firmware_version_gte`, an unparseable version never matches.
+pub(crate) fn firmware_version_lt(fw: &str, max_ver: &str) -> bool {
+    parse_firmware_version(fw)
+        .is_some_and(|fw| threshold_for_line(fw, max_ver).is_some_and(|max| fw < max))
 }
 
 /// Profile predicate support for note when: expressions.
@@ -119,6 +142,16 @@ pub(crate) fn profile_predicate_matches(predicate: &str, profile: Option<&Profil
             .map(|v| firmware_version_gte(v, min_ver))
             .unwrap_or(false);
     }
+    // profile:firmwareLt: only matches a known firmware that parses and is
+    // older than the given version, so profiles without firmware stay quiet.
+    if let Some(rest) = predicate.strip_prefix("profile:firmwareLt:") {
+        let max_ver = rest.trim();
+        let fw = p
+            .memory
+            .as_ref()
+            .and_then(|m| m.firmware_version.as_deref());
+        return fw.map(|v| firmware_version_lt(v, max_ver)).unwrap_or(false);
+    }
     // profile:model: is an alias for profile:id: (profile id often encodes model)
     if let Some(rest) = predicate.strip_prefix("profile:model:") {
         let accepted: Vec<&str> = rest
@@ -132,8 +165,14 @@ pub(crate) fn profile_predicate_matches(predicate: &str, profile: Option<&Profil
 }
 
 // Very small predicate support for conditionalRange / roundingPolicyWhen
-// MVP: support keys like "arg:keyIsValue:X" or "arg:keyPresent" or "arg:keyEmpty"
+// MVP: support keys like "arg:keyIsValue:X", "arg:keyPresent", "arg:keyEmpty"
+// or "arg:keyIsNonLeapYear", optionally joined with "&&"
 pub(super) fn predicate_matches(when: &str, args: &[ArgSlot]) -> bool {
+    if when.contains("&&") {
+        return when
+            .split("&&")
+            .all(|term| predicate_matches(term.trim(), args));
+    }
     if let Some(rest) = when.strip_prefix("arg:") {
         if let Some((k, rhs)) = rest.split_once("IsValue:") {
             let accepted: Vec<&str> = rhs.split('|').collect();
@@ -154,10 +193,24 @@ pub(super) fn predicate_matches(when: &str, args: &[ArgSlot]) -> bool {
                 .iter()
                 .any(|a| a.key.as_deref() == Some(k) && a.presence == Presence::Empty);
         }
+        if let Some(k) = rest.strip_suffix("IsNonLeapYear") {
+            return args.iter().any(|a| {
+                a.key.as_deref() == Some(k)
+                    && a.value
+                        .as_deref()
+                        .and_then(|value| value.parse::<u32>().ok())
+                        .is_some_and(|year| !is_leap_year(year))
+            });
+        }
     }
     false
 }
 
+/// Gregorian leap year rule.
+fn is_leap_year(year: u32) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
 pub(super) fn evaluate_note_when_expression(
     expression: &str,
     args: &[ArgSlot],
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::sync::OnceLock;
-use zpl_toolchain_profile::Profile;
+use zpl_toolchain_profile::{Profile, Range};
 use zpl_toolchain_spec_tables::ComparisonOp;
 
 /// Type alias for profile field accessor functions.
@@ -82,5 +82,26 @@ pub(super) fn check_profile_op(value: f64, op: &ComparisonOp, limit: f64) -> boo
         ComparisonOp::Lt => value < limit,
         ComparisonOp::Gt => value > limit,
         ComparisonOp::Eq => (value - limit).abs() < 0.5,
+        // A range has two bounds; callers resolve both and use
+        // `check_profile_range` instead.
+        ComparisonOp::Within => false,
     }
 }
+
+/// Resolve a `<field>.min`/`<field>.max` pair (e.g. `speed_range`) as a
+/// [`Range`], or `None` if either bound is not set.
+pub(super) fn resolve_profile_range(profile: &Profile, field: &str) -> Option<Range> {
+    let min = resolve_profile_field(profile, &format!("{field}.min"))?;
+    let max = resolve_profile_field(profile, &format!("{field}.max"))?;
+    Some(Range {
+        min: min as u32,
+        max: max as u32,
+    })
+}
+
+/// Check that a value lies within an inclusive profile range.
+///
+/// Non-finite inputs fail, matching [`check_profile_op`].
+pub(super) fn check_profile_range(value: f64, range: &Range) -> bool {
+    range.contains(value)
+}
@@ -0,0 +1,156 @@
+use super::ctx;
+use super::diagnostics_util::diagnostic_with_spec_severity;
+use crate::grammar::ast::{ArgSlot, Label, Node};
+use crate::grammar::diag::{Diagnostic, Span, codes};
+
+/// Storage drives accepted in an object path.
+const DRIVES: [&str; 4] = ["R", "E", "B", "A"];
+
+/// Naming rules for a command that stores or recalls a named object.
+struct ObjectNameRule {
+    /// Index of the argument holding the object path.
+    arg: usize,
+    /// Longest accepted name, excluding drive and extension.
+    max_len: usize,
+    /// Accepted extensions, upper-case and without the dot.
+    extensions: &'static [&'static str],
+}
+
+fn object_name_rule(code: &str) -> Option<ObjectNameRule> {
+    match code {
+        // `~DGd:o.x` keeps the whole path in its first argument.
+        "~DG" => Some(ObjectNameRule {
+            arg: 0,
+            max_len: 8,
+            extensions: &["GRF"],
+        }),
+        "^XG" => Some(ObjectNameRule {
+            arg: 0,
+            max_len: 8,
+            extensions: &["GRF", "PNG"],
+        }),
+        // `^DFd:o.x` splits the drive into its own enum-checked argument.
+        "^DF" => Some(ObjectNameRule {
+            arg: 1,
+            max_len: 16,
+            extensions: &["ZPL"],
+        }),
+        _ => None,
+    }
+}
+
+/// ZPL2315: Check object paths stored by `~DG`/`^DF` or recalled by `^XG`.
+///
+/// Flags drives other than `R:`/`E:`/`B:`/`A:`, names longer than the
+/// command accepts (the printer truncates or rejects them), and extensions
+/// the command does not use. A missing drive or extension is not reported:
+/// the printer supplies its default.
+pub(super) fn validate_resource_names(label: &Label, issues: &mut Vec<Diagnostic>) {
+    for node in &label.nodes {
+        let Node::Command {
+            code, args, span, ..
+        } = node
+        else {
+            continue;
+        };
+        let Some(rule) = object_name_rule(code) else {
+            continue;
+        };
+        let Some(path) = args
+            .get(rule.arg)
+            .and_then(ArgSlot::as_str)
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        else {
+            continue;
+        };
+        check_object_path(code, path, &rule, *span, issues);
+    }
+}
+
+fn check_object_path(
+    code: &str,
+    path: &str,
+    rule: &ObjectNameRule,
+    span: Span,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let (drive, rest) = match path.split_once(':') {
+        Some((drive, rest)) => (Some(drive), rest),
+        None => (None, path),
+    };
+    let (name, extension) = match rest.rsplit_once('.') {
+        Some((name, ext)) => (name, Some(ext)),
+        None => (rest, None),
+    };
+
+    if let Some(drive) = drive
+        && !DRIVES.contains(&drive.to_ascii_uppercase().as_str())
+    {
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::INVALID_RESOURCE_NAME,
+                format!(
+                    "{} object '{}' uses drive '{}:'; expected R:, E:, B:, or A:",
+                    code, path, drive
+                ),
+                Some(span),
+            )
+            .with_context(ctx!(
+                "command" => code,
+                "path" => path,
+                "kind" => "drive",
+                "expected" => "R,E,B,A",
+            )),
+        );
+    }
+
+    let name_len = name.chars().count();
+    if name_len > rule.max_len {
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::INVALID_RESOURCE_NAME,
+                format!(
+                    "{} object name '{}' is {} characters; at most {} are accepted",
+                    code, name, name_len, rule.max_len
+                ),
+                Some(span),
+            )
+            .with_context(ctx!(
+                "command" => code,
+                "path" => path,
+                "kind" => "length",
+                "limit" => rule.max_len.to_string(),
+            )),
+        );
+    }
+
+    if let Some(extension) = extension
+        && !rule
+            .extensions
+            .contains(&extension.to_ascii_uppercase().as_str())
+    {
+        let expected = rule
+            .extensions
+            .iter()
+            .map(|e| format!(".{e}"))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::INVALID_RESOURCE_NAME,
+                format!(
+                    "{} object '{}' has extension '.{}'; expected {}",
+                    code, path, extension, expected
+                ),
+                Some(span),
+            )
+            .with_context(ctx!(
+                "command" => code,
+                "path" => path,
+                "kind" => "extension",
+                "expected" => rule.extensions.join(","),
+            )),
+        );
+    }
+}
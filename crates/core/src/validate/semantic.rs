@@ -1,9 +1,10 @@
 use super::context::{CommandCtx, ValidationContext};
 use super::ctx;
 use super::diagnostics_util::{diagnostic_with_spec_severity, trim_f64};
-use super::resolve_profile_field;
+use super::profile_constraints::check_profile_range;
 use super::state::LabelState;
-use crate::grammar::diag::{Diagnostic, codes};
+use crate::grammar::ast::ArgSlot;
+use crate::grammar::diag::{Diagnostic, Severity, codes};
 use crate::state::{Units, convert_to_dots};
 use zpl_toolchain_spec_tables::ArgUnion;
 use zpl_toolchain_spec_tables::{
@@ -13,6 +14,7 @@ use zpl_toolchain_spec_tables::{
 /// ZPL2301: Duplicate ^FN field number detection.
 fn validate_field_number(
     cmd_ctx: &CommandCtx,
+    vctx: &ValidationContext,
     arg_index: usize,
     label_state: &mut LabelState,
     issues: &mut Vec<Diagnostic>,
@@ -21,17 +23,19 @@ fn validate_field_number(
         && let Some(n) = slot.value.as_ref()
     {
         if let Some(&first_idx) = label_state.field_numbers.get(n) {
-            issues.push(
-                diagnostic_with_spec_severity(
-                    codes::DUPLICATE_FIELD_NUMBER,
-                    format!(
-                        "Duplicate field number {} (first used at node {})",
-                        n, first_idx
-                    ),
-                    cmd_ctx.span,
-                )
-                .with_context(ctx!("command" => cmd_ctx.code, "field_number" => n.clone())),
-            );
+            let mut diag = diagnostic_with_spec_severity(
+                codes::DUPLICATE_FIELD_NUMBER,
+                format!(
+                    "Duplicate field number {} (first used at node {})",
+                    n, first_idx
+                ),
+                cmd_ctx.span,
+            )
+            .with_context(ctx!("command" => cmd_ctx.code, "field_number" => n.clone()));
+            if let Some(first) = vctx.label.nodes.get(first_idx) {
+                diag = diag.with_related(first.span(), format!("field number {n} first used here"));
+            }
+            issues.push(diag);
         } else {
             label_state
                 .field_numbers
@@ -67,6 +71,7 @@ fn validate_position_bounds(
             // Reset to defaults before parsing — ZPL defaults to (0,0)
             label_state.last_fo_x = Some(label_state.value_state.label_home.x);
             label_state.last_fo_y = Some(label_state.value_state.label_home.y);
+            label_state.origin_is_bottom = cmd_ctx.code == "^FT";
             if let Some(x_slot) = cmd_ctx.args.first()
                 && let Some(x_val) = x_slot.value.as_ref()
                 && let Ok(x) = x_val.parse::<f64>()
@@ -100,57 +105,81 @@ fn validate_position_bounds(
     }
 
     // Determine effective bounds: label ^PW/^LL > profile > none
-    let max_x = label_state.effective_width.or_else(|| {
-        vctx.profile
-            .and_then(|p| resolve_profile_field(p, "page.width_dots"))
-    });
-    let max_y = label_state.effective_height.or_else(|| {
-        vctx.profile
-            .and_then(|p| resolve_profile_field(p, "page.height_dots"))
-    });
-
-    if let (Some(fo_x), Some(w)) = (label_state.last_fo_x, max_x)
-        && fo_x > w
-    {
-        issues.push(
-            diagnostic_with_spec_severity(
-                codes::POSITION_OUT_OF_BOUNDS,
-                format!(
-                    "{} x position {} exceeds label width {}",
-                    cmd_ctx.code,
-                    trim_f64(fo_x),
-                    trim_f64(w)
-                ),
-                cmd_ctx.span,
+    let (page_width, page_height) = vctx.profile_page_dots();
+    let max_x = label_state.effective_width.or(page_width);
+    let max_y = label_state.effective_height.or(page_height);
+
+    let home = &label_state.value_state.label_home;
+    for (axis, pos, limit, home_offset, dimension, far_edge) in [
+        ("x", label_state.last_fo_x, max_x, home.x, "width", "left"),
+        ("y", label_state.last_fo_y, max_y, home.y, "height", "top"),
+    ] {
+        let (Some(pos), Some(limit)) = (pos, limit) else {
+            continue;
+        };
+        if pos <= limit {
+            continue;
+        }
+        // ^POI rotates the format 180° within the label and ^PMY mirrors it
+        // across the width. Flipping maps the label onto itself, so it never
+        // changes whether a position fits; it only moves an out-of-bounds
+        // origin to `limit - pos`, past the opposite edge, which the message
+        // reports. Both together cancel out on x.
+        let mirrored = axis == "x" && label_state.mirrored;
+        let flipped = label_state.inverted != mirrored;
+        let printed = if flipped { limit - pos } else { pos };
+        // Positions already include the ^LH home offset; say so, since the
+        // reported value then differs from the literal ^FO/^FT argument.
+        let home_note = if home_offset != 0.0 {
+            format!(" (includes ^LH offset {})", trim_f64(home_offset))
+        } else {
+            String::new()
+        };
+        let flip_note = if flipped {
+            format!(
+                "; {} by {}, it prints {} dots beyond the {} edge",
+                if mirrored { "mirrored" } else { "inverted" },
+                if mirrored { "^PMY" } else { "^POI" },
+                trim_f64(-printed),
+                far_edge
             )
-            .with_context(ctx!(
-                "command" => cmd_ctx.code,
-                "axis" => "x",
-                "value" => trim_f64(fo_x),
-                "limit" => trim_f64(w),
-            )),
+        } else {
+            String::new()
+        };
+        let mut context = ctx!(
+            "command" => cmd_ctx.code,
+            "axis" => axis,
+            "value" => trim_f64(pos),
+            "limit" => trim_f64(limit),
         );
-    }
-    if let (Some(fo_y), Some(h)) = (label_state.last_fo_y, max_y)
-        && fo_y > h
-    {
+        if home_offset != 0.0 {
+            context.insert("label_home".into(), trim_f64(home_offset));
+        }
+        if label_state.inverted {
+            context.insert("orientation".into(), "I".into());
+        }
+        if mirrored {
+            context.insert("mirror".into(), "Y".into());
+        }
+        if label_state.inverted || mirrored {
+            context.insert("printed_at".into(), trim_f64(printed));
+        }
         issues.push(
             diagnostic_with_spec_severity(
                 codes::POSITION_OUT_OF_BOUNDS,
                 format!(
-                    "{} y position {} exceeds label height {}",
+                    "{} {} position {}{} exceeds label {} {}{}",
                     cmd_ctx.code,
-                    trim_f64(fo_y),
-                    trim_f64(h)
+                    axis,
+                    trim_f64(pos),
+                    home_note,
+                    dimension,
+                    trim_f64(limit),
+                    flip_note
                 ),
                 cmd_ctx.span,
             )
-            .with_context(ctx!(
-                "command" => cmd_ctx.code,
-                "axis" => "y",
-                "value" => trim_f64(fo_y),
-                "limit" => trim_f64(h),
-            )),
+            .with_context(context),
         );
     }
 }
@@ -192,6 +221,114 @@ fn validate_font_reference(
     }
 }
 
+/// ZPL2314: Font size sanity checks for ^A/^A@ (zero dimension, oversize).
+fn validate_font_size(
+    cmd_ctx: &CommandCtx,
+    vctx: &ValidationContext,
+    height_arg_index: usize,
+    width_arg_index: usize,
+    label_state: &LabelState,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let arg_value = |idx: usize| cmd_ctx.args.get(idx).and_then(ArgSlot::as_float);
+    let height = arg_value(height_arg_index);
+    let width = arg_value(width_arg_index);
+    let fmt_dim = |v: Option<f64>| v.map(trim_f64).unwrap_or_else(|| "-".into());
+
+    let zero_dims: Vec<&str> = [("height", height), ("width", width)]
+        .into_iter()
+        .filter(|(_, v)| *v == Some(0.0))
+        .map(|(name, _)| name)
+        .collect();
+    if !zero_dims.is_empty() {
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::SUSPICIOUS_FONT_SIZE,
+                format!(
+                    "{} font {} of 0 leaves scaling to firmware-dependent defaults; specify both height and width",
+                    cmd_ctx.code,
+                    zero_dims.join(" and ")
+                ),
+                cmd_ctx.span,
+            )
+            .with_context(ctx!(
+                "command" => cmd_ctx.code,
+                "kind" => "zero_dimension",
+                "height" => fmt_dim(height),
+                "width" => fmt_dim(width),
+            )),
+        );
+        return;
+    }
+
+    // Bitmap fonts only magnify their base cell in whole steps up to a
+    // fixed limit, so only scalable fonts can outgrow the label.
+    if !selects_scalable_font(cmd_ctx) {
+        return;
+    }
+
+    // Font dimensions follow ^MU; compare in dots against the label, which
+    // may be rotated, so the larger label dimension is the ceiling.
+    let to_dots = |v: f64| match vctx.device_state.dpi {
+        Some(dpi) => convert_to_dots(v, vctx.device_state.units, dpi),
+        None => v,
+    };
+    let (page_width, page_height) = vctx.profile_page_dots();
+    let max_x = label_state.effective_width.or(page_width);
+    let max_y = label_state.effective_height.or(page_height);
+    let Some(limit) = max_x.into_iter().chain(max_y).reduce(f64::max) else {
+        return;
+    };
+    let largest = [height, width]
+        .into_iter()
+        .flatten()
+        .map(to_dots)
+        .reduce(f64::max);
+    let Some(largest) = largest.filter(|&dots| dots > limit) else {
+        return;
+    };
+    issues.push(
+        diagnostic_with_spec_severity(
+            codes::SUSPICIOUS_FONT_SIZE,
+            format!(
+                "{} font size {} dots exceeds the largest label dimension {}",
+                cmd_ctx.code,
+                trim_f64(largest),
+                trim_f64(limit)
+            ),
+            cmd_ctx.span,
+        )
+        .with_context(ctx!(
+            "command" => cmd_ctx.code,
+            "kind" => "oversize",
+            "height" => fmt_dim(height),
+            "width" => fmt_dim(width),
+            "limit" => trim_f64(limit),
+        )),
+    );
+}
+
+/// Whether a ^A/^A@ command selects a scalable font: ^A font 0, or a
+/// TrueType (`.TTF`/`.TTE`) font named by ^A@.
+fn selects_scalable_font(cmd_ctx: &CommandCtx) -> bool {
+    let arg = |key: &str| {
+        cmd_ctx
+            .args
+            .iter()
+            .find(|a| a.key.as_deref() == Some(key))
+            .and_then(|a| a.value.as_deref())
+    };
+    match cmd_ctx.code {
+        "^A" => arg("f") == Some("0"),
+        "^A@" => arg("n")
+            .and_then(|name| name.rsplit_once('.'))
+            .is_some_and(|(_, ext)| {
+                ext.eq_ignore_ascii_case("TTF") || ext.eq_ignore_ascii_case("TTE")
+            }),
+        _ => true,
+    }
+}
+
 /// ZPL1403: Media mode validation (^MM, ^MN, ^MT).
 fn validate_media_modes(
     cmd_ctx: &CommandCtx,
@@ -286,6 +423,187 @@ fn validate_media_modes(
     }
 }
 
+/// Whether `inc` is a valid `^SF` increment character for the mask placeholder
+/// it is aligned with. `%` in the increment leaves a position unchanged, so it
+/// fits anywhere; skipped (`%`) mask positions otherwise only take `0`.
+fn sf_increment_fits(placeholder: char, inc: char) -> bool {
+    if inc == '%' {
+        return true;
+    }
+    let inc = inc.to_ascii_uppercase();
+    match placeholder.to_ascii_uppercase() {
+        'D' => inc.is_ascii_digit(),
+        'H' => inc.is_ascii_hexdigit(),
+        'O' => ('0'..='7').contains(&inc),
+        'A' => inc.is_ascii_uppercase(),
+        'N' => inc.is_ascii_digit() || inc.is_ascii_uppercase(),
+        '%' => inc == '0',
+        _ => true,
+    }
+}
+
+/// ZPL2320: ^SF mask placeholders and increment alignment.
+///
+/// The increment string is right-aligned to the mask, so its last character
+/// adjusts the last mask position. Each problem kind is reported once, at the
+/// first offending character.
+fn validate_serialization_mask(
+    cmd_ctx: &CommandCtx,
+    mask_arg_index: usize,
+    increment_arg_index: usize,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let arg_str = |idx: usize| {
+        cmd_ctx
+            .args
+            .get(idx)
+            .and_then(|slot| slot.value.as_deref())
+            .filter(|v| !v.is_empty())
+    };
+    let Some(mask) = arg_str(mask_arg_index) else {
+        return;
+    };
+    let increment = arg_str(increment_arg_index);
+    let diag = |message: String, kind: &str, character: Option<char>, position: Option<usize>| {
+        let mut context = ctx!(
+            "command" => cmd_ctx.code,
+            "kind" => kind,
+            "mask" => mask,
+            "increment" => increment.unwrap_or(""),
+        );
+        if let Some(ch) = character {
+            context.insert("character".into(), ch.to_string());
+        }
+        if let Some(pos) = position {
+            context.insert("position".into(), pos.to_string());
+        }
+        diagnostic_with_spec_severity(codes::SERIALIZATION_MASK_INVALID, message, cmd_ctx.span)
+            .with_context(context)
+    };
+
+    let mask_chars: Vec<char> = mask.chars().collect();
+    if let Some((pos, &ch)) = mask_chars
+        .iter()
+        .enumerate()
+        .find(|(_, c)| !matches!(c.to_ascii_uppercase(), 'D' | 'H' | 'O' | 'A' | 'N' | '%'))
+    {
+        issues.push(diag(
+            format!(
+                "{} mask character '{}' at position {} is not a placeholder (D, H, O, A, N or %)",
+                cmd_ctx.code, ch, pos
+            ),
+            "invalid_mask_char",
+            Some(ch),
+            Some(pos),
+        ));
+    }
+
+    let Some(increment) = increment else {
+        return;
+    };
+    let inc_chars: Vec<char> = increment.chars().collect();
+    if inc_chars.len() > mask_chars.len() {
+        issues.push(diag(
+            format!(
+                "{} increment '{}' is longer than its mask '{}' ({} > {} characters)",
+                cmd_ctx.code,
+                increment,
+                mask,
+                inc_chars.len(),
+                mask_chars.len()
+            ),
+            "increment_too_long",
+            None,
+            None,
+        ));
+        return;
+    }
+    let offset = mask_chars.len() - inc_chars.len();
+    if let Some((pos, &ch)) = inc_chars
+        .iter()
+        .enumerate()
+        .find(|&(i, &c)| !sf_increment_fits(mask_chars[offset + i], c))
+    {
+        issues.push(diag(
+            format!(
+                "{} increment character '{}' at position {} does not fit mask placeholder '{}'",
+                cmd_ctx.code,
+                ch,
+                pos,
+                mask_chars[offset + pos]
+            ),
+            "increment_misaligned",
+            Some(ch),
+            Some(pos),
+        ));
+    }
+}
+
+/// ZPL1401 (warn): ^MD relative darkness checked against the profile's
+/// `darkness_range`.
+///
+/// The adjustment is applied to the most recent `~SD` value in the session.
+/// Without one, the midpoint of the profile range stands in for the printer's
+/// stored darkness, so only adjustments that would leave the range from a
+/// typical setting are reported.
+fn validate_darkness_adjustment(
+    cmd_ctx: &CommandCtx,
+    vctx: &ValidationContext,
+    arg_index: usize,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let Some(p) = vctx.profile else {
+        return;
+    };
+    let Some(ref range) = p.darkness_range else {
+        return;
+    };
+    let Some(adjustment) = cmd_ctx.args.get(arg_index).and_then(ArgSlot::as_float) else {
+        return;
+    };
+
+    let (min, max) = (range.min as f64, range.max as f64);
+    let (base, base_source) = match vctx.device_state.darkness {
+        Some(d) => (d, "~SD"),
+        None => ((min + max) / 2.0, "assumed"),
+    };
+    let effective = base + adjustment;
+    if check_profile_range(effective, range) {
+        return;
+    }
+
+    // Float-to-int casts saturate, so values below zero clamp to `min`.
+    let limit = range.clamp(effective as u32) as f64;
+    issues.push(
+        Diagnostic::new(
+            codes::PROFILE_CONSTRAINT,
+            Severity::Warn,
+            format!(
+                "{} adjustment {} from darkness {} gives {}, outside profile darkness_range ({}-{})",
+                cmd_ctx.code,
+                trim_f64(adjustment),
+                trim_f64(base),
+                trim_f64(effective),
+                trim_f64(min),
+                trim_f64(max),
+            ),
+            cmd_ctx.span,
+        )
+        .with_context(ctx!(
+            "command" => cmd_ctx.code,
+            "field" => "darkness_range",
+            "min" => trim_f64(min),
+            "max" => trim_f64(max),
+            "limit" => trim_f64(limit),
+            "base" => trim_f64(base),
+            "baseSource" => base_source,
+            "adjustment" => trim_f64(adjustment),
+            "actual" => trim_f64(effective),
+            "profile" => &p.id,
+        )),
+    );
+}
+
 /// ZPL2307: ^GF data length validation.
 fn validate_gf_data_length(
     cmd_ctx: &CommandCtx,
@@ -318,7 +636,7 @@ fn validate_gf_data_length(
             }
         };
         let mut total_data_len = effective_len(data);
-        for continuation in &vctx.label_nodes[cmd_ctx.node_idx + 1..] {
+        for continuation in &vctx.label.nodes[cmd_ctx.node_idx + 1..] {
             if let crate::grammar::ast::Node::RawData {
                 command,
                 data: raw_data,
@@ -415,14 +733,9 @@ fn validate_gf_preflight_tracking(
             let graphic_height = graphic_field_count.div_ceil(bytes_per_row);
 
             // Determine effective bounds: label ^PW/^LL > profile > none
-            let max_x = label_state.effective_width.or_else(|| {
-                vctx.profile
-                    .and_then(|p| resolve_profile_field(p, "page.width_dots"))
-            });
-            let max_y = label_state.effective_height.or_else(|| {
-                vctx.profile
-                    .and_then(|p| resolve_profile_field(p, "page.height_dots"))
-            });
+            let (page_width, page_height) = vctx.profile_page_dots();
+            let max_x = label_state.effective_width.or(page_width);
+            let max_y = label_state.effective_height.or(page_height);
 
             // Skip bounds check when units are non-dots and DPI is unknown —
             // we can't reliably compare since graphic dimensions are in dots
@@ -467,6 +780,94 @@ fn validate_gf_preflight_tracking(
     }
 }
 
+/// ZPL2302: ^GC/^GD/^GE extent check — the primitive drawn at the field
+/// origin must end inside the label, not just start there.
+fn validate_graphic_extent(
+    cmd_ctx: &CommandCtx,
+    vctx: &ValidationContext,
+    width_arg_index: usize,
+    height_arg_index: usize,
+    label_state: &LabelState,
+    issues: &mut Vec<Diagnostic>,
+) {
+    // Same caveat as ^GF: without a DPI, non-dot units cannot be compared.
+    let dpi = vctx.device_state.dpi;
+    if dpi.is_none() && vctx.device_state.units != Units::Dots {
+        return;
+    }
+    let extent_arg = |idx: usize| {
+        let v = cmd_ctx
+            .args
+            .get(idx)
+            .and_then(|s| s.value.as_deref())?
+            .trim()
+            .parse::<f64>()
+            .ok()?;
+        Some(match dpi {
+            Some(dpi) => convert_to_dots(v, vctx.device_state.units, dpi),
+            None => v,
+        })
+    };
+
+    let (page_width, page_height) = vctx.profile_page_dots();
+    let max_x = label_state.effective_width.or(page_width);
+    let max_y = label_state.effective_height.or(page_height);
+
+    for (axis, pos, extent, limit, dimension) in [
+        (
+            "x",
+            label_state.last_fo_x,
+            extent_arg(width_arg_index),
+            max_x,
+            "width",
+        ),
+        (
+            "y",
+            // ^FT graphics grow upward from the origin, so y cannot overflow.
+            label_state
+                .last_fo_y
+                .filter(|_| !label_state.origin_is_bottom),
+            extent_arg(height_arg_index),
+            max_y,
+            "height",
+        ),
+    ] {
+        let (Some(pos), Some(extent), Some(limit)) = (pos, extent, limit) else {
+            continue;
+        };
+        let far_edge = pos + extent;
+        // An origin already past the edge is reported by the ^FO/^FT check.
+        if far_edge <= limit || pos > limit {
+            continue;
+        }
+        issues.push(
+            diagnostic_with_spec_severity(
+                codes::POSITION_OUT_OF_BOUNDS,
+                format!(
+                    "{} at {} {} with {} {} ends at {}, beyond label {} {}",
+                    cmd_ctx.code,
+                    axis,
+                    trim_f64(pos),
+                    dimension,
+                    trim_f64(extent),
+                    trim_f64(far_edge),
+                    dimension,
+                    trim_f64(limit)
+                ),
+                cmd_ctx.span,
+            )
+            .with_context(ctx!(
+                "command" => cmd_ctx.code,
+                "axis" => axis,
+                "value" => trim_f64(far_edge),
+                "limit" => trim_f64(limit),
+                "origin" => trim_f64(pos),
+                "extent" => trim_f64(extent),
+            )),
+        );
+    }
+}
+
 pub(super) fn consume_default_from_refs(cmd_ctx: &CommandCtx, label_state: &mut LabelState) {
     // Mark consumed producers via defaultFrom references.
     if let Some(spec_args) = cmd_ctx.cmd.args.as_ref() {
@@ -493,7 +894,7 @@ fn run_semantic_rule(
 ) {
     match rule {
         StructuralRule::DuplicateFieldNumber { arg_index } => {
-            validate_field_number(cmd_ctx, *arg_index, label_state, issues);
+            validate_field_number(cmd_ctx, vctx, *arg_index, label_state, issues);
         }
         StructuralRule::PositionBounds { action } => {
             validate_position_bounds(cmd_ctx, vctx, *action, label_state, issues);
@@ -527,6 +928,35 @@ fn run_semantic_rule(
             label_state,
             issues,
         ),
+        StructuralRule::DarknessAdjustment { arg_index } => {
+            validate_darkness_adjustment(cmd_ctx, vctx, *arg_index, issues);
+        }
+        StructuralRule::SerializationMask {
+            mask_arg_index,
+            increment_arg_index,
+        } => validate_serialization_mask(cmd_ctx, *mask_arg_index, *increment_arg_index, issues),
+        StructuralRule::GraphicExtent {
+            width_arg_index,
+            height_arg_index,
+        } => validate_graphic_extent(
+            cmd_ctx,
+            vctx,
+            *width_arg_index,
+            *height_arg_index,
+            label_state,
+            issues,
+        ),
+        StructuralRule::FontSize {
+            height_arg_index,
+            width_arg_index,
+        } => validate_font_size(
+            cmd_ctx,
+            vctx,
+            *height_arg_index,
+            *width_arg_index,
+            label_state,
+            issues,
+        ),
     }
 }
 
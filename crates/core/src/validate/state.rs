@@ -21,10 +21,23 @@ pub(super) struct LabelState {
     pub(super) has_explicit_pw: bool,
     /// Whether ^LL was explicitly set in this label (vs inherited from profile).
     pub(super) has_explicit_ll: bool,
+    /// The first field-opening command seen in this label (for header-only
+    /// placement checks).
+    pub(super) first_field_opener: Option<String>,
     /// Last ^FO x position (for graphic bounds checking).
     pub(super) last_fo_x: Option<f64>,
     /// Last ^FO y position (for graphic bounds checking).
     pub(super) last_fo_y: Option<f64>,
+    /// Whether the last origin came from `^FT`, whose y is the bottom of the
+    /// object rather than its top.
+    pub(super) origin_is_bottom: bool,
+    /// Whether the label prints inverted (`^POI`). `^PO` applies to the whole
+    /// format wherever it appears, so this is resolved before the walk.
+    pub(super) inverted: bool,
+    /// Whether the label prints mirrored across its width (`^PMY`), resolved
+    /// before the walk like `inverted`. Like inversion, it only changes where
+    /// ZPL2302 reports an out-of-bounds origin as printing.
+    pub(super) mirrored: bool,
     /// Accumulated total graphic bytes from ^GF commands (for memory estimation).
     pub(super) gf_total_bytes: u32,
     /// Typed producer values for renderer/validator default resolution.
@@ -0,0 +1,153 @@
+use super::ctx;
+use super::diagnostics_util::diagnostic_with_spec_severity;
+use crate::grammar::ast::{ArgSlot, Label, Node};
+use crate::grammar::diag::{Diagnostic, Span, codes};
+use crate::grammar::tables::ParserTables;
+use crate::state::{DeviceState, StoredFormat};
+use std::collections::BTreeSet;
+
+/// `^FN` usage collected from one label.
+#[derive(Default)]
+struct LabelFieldNumbers {
+    /// Every `^FN` number in the label.
+    all: BTreeSet<u32>,
+    /// `^FN` numbers whose field also carries `^FD`/`^FV` data.
+    with_data: BTreeSet<u32>,
+}
+
+/// ZPL2312: Match `^XF` recalls against formats saved with `^DF` in the same job.
+///
+/// A label containing `^DF` records its `^FN` numbers in `device_state`. A
+/// later label containing `^XF` for the same format name is checked for
+/// placeholders it leaves unfilled and for data it supplies to field numbers
+/// the saved format does not define. Recalls of formats not saved in this
+/// job (e.g., already stored on the printer) are not checked.
+pub(super) fn validate_stored_formats(
+    label: &Label,
+    tables: &ParserTables,
+    device_state: &mut DeviceState,
+    issues: &mut Vec<Diagnostic>,
+) {
+    let mut saved_as = None;
+    let mut recalls: Vec<(String, Span)> = Vec::new();
+    for node in &label.nodes {
+        if let Node::Command {
+            code, args, span, ..
+        } = node
+        {
+            match code.as_str() {
+                "^DF" => saved_as = Some(format_name(args)),
+                "^XF" => recalls.push((format_name(args), *span)),
+                _ => {}
+            }
+        }
+    }
+    if saved_as.is_none() && recalls.is_empty() {
+        return;
+    }
+
+    let fields = collect_field_numbers(label, tables);
+
+    for (name, span) in &recalls {
+        let Some(stored) = device_state.stored_formats.get(name) else {
+            continue;
+        };
+        for n in stored.placeholders.difference(&fields.with_data) {
+            issues.push(
+                diagnostic_with_spec_severity(
+                    codes::STORED_FORMAT_FIELD_MISMATCH,
+                    format!(
+                        "^XF recalls format '{}' but supplies no data for ^FN{} (field prints blank)",
+                        name, n
+                    ),
+                    Some(*span),
+                )
+                .with_context(ctx!(
+                    "command" => "^XF",
+                    "format" => name.clone(),
+                    "field_number" => n.to_string(),
+                    "kind" => "unfilled",
+                )),
+            );
+        }
+        for n in fields.with_data.difference(&stored.field_numbers) {
+            issues.push(
+                diagnostic_with_spec_severity(
+                    codes::STORED_FORMAT_FIELD_MISMATCH,
+                    format!(
+                        "^FN{} data is not used: recalled format '{}' does not define ^FN{}",
+                        n, name, n
+                    ),
+                    Some(*span),
+                )
+                .with_context(ctx!(
+                    "command" => "^XF",
+                    "format" => name.clone(),
+                    "field_number" => n.to_string(),
+                    "kind" => "undefined",
+                )),
+            );
+        }
+    }
+
+    if let Some(name) = saved_as {
+        let placeholders = fields.all.difference(&fields.with_data).copied().collect();
+        device_state.stored_formats.insert(
+            name,
+            StoredFormat {
+                field_numbers: fields.all,
+                placeholders,
+            },
+        );
+    }
+}
+
+/// Normalized stored-format name: upper-cased, without drive or extension.
+///
+/// `^XF` searches every drive, so `R:LABEL.ZPL` and `E:LABEL.ZPL` refer to
+/// the same format for matching purposes.
+fn format_name(args: &[ArgSlot]) -> String {
+    let name = args
+        .get(1)
+        .and_then(|a| a.value.as_deref())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or("UNKNOWN");
+    let stem = name.split('.').next().unwrap_or(name);
+    stem.to_ascii_uppercase()
+}
+
+/// Collect `^FN` numbers per field, noting which fields carry data.
+fn collect_field_numbers(label: &Label, tables: &ParserTables) -> LabelFieldNumbers {
+    let mut out = LabelFieldNumbers::default();
+    let mut current_fn: Option<u32> = None;
+    let mut has_data = false;
+    for node in &label.nodes {
+        let Node::Command { code, args, .. } = node else {
+            continue;
+        };
+        let Some(cmd) = tables.cmd_by_code(code) else {
+            continue;
+        };
+        if cmd.field_number {
+            current_fn = args
+                .first()
+                .and_then(|a| a.value.as_deref())
+                .and_then(|v| v.trim().parse::<u32>().ok());
+            if let Some(n) = current_fn {
+                out.all.insert(n);
+            }
+        }
+        if cmd.field_data && !args.is_empty() {
+            has_data = true;
+        }
+        if cmd.closes_field {
+            if has_data && let Some(n) = current_fn {
+                out.with_data.insert(n);
+            }
+            current_fn = None;
+            has_data = false;
+        }
+    }
+    out
+}
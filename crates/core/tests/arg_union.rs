@@ -42,6 +42,7 @@ fn arg_union_accepts_either_shape() {
                         default_from: None,
                         default_from_state_key: None,
                         profile_constraint: None,
+                        numeric_aliases: None,
                         range_when: None,
                         rounding_policy: None,
                         rounding_policy_when: None,
@@ -63,6 +64,7 @@ fn arg_union_accepts_either_shape() {
                         default_from: None,
                         default_from_state_key: None,
                         profile_constraint: None,
+                        numeric_aliases: None,
                         range_when: None,
                         rounding_policy: None,
                         rounding_policy_when: None,
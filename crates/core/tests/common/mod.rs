@@ -4,7 +4,8 @@
 
 use std::sync::LazyLock;
 use zpl_toolchain_core::grammar::ast::{ArgSlot, Node};
-use zpl_toolchain_core::grammar::parser::ParseResult;
+use zpl_toolchain_core::grammar::parser::{ParseResult, parse_with_tables};
+use zpl_toolchain_core::validate::{ValidationOptions, validate_with_options};
 use zpl_toolchain_diagnostics::Diagnostic;
 use zpl_toolchain_profile::Profile;
 use zpl_toolchain_spec_tables::ParserTables;
@@ -83,6 +84,34 @@ pub fn find_diag<'a>(issues: &'a [Diagnostic], code: &str) -> &'a Diagnostic {
         .unwrap_or_else(|| panic!("expected diagnostic {code}"))
 }
 
+// ─── Validation helpers ──────────────────────────────────────────────────────
+
+/// Parse and validate `input`, keeping only diagnostics whose id is in `codes`.
+#[allow(dead_code)]
+pub fn issues_with_codes(
+    input: &str,
+    profile: Option<&Profile>,
+    codes: &[&str],
+) -> Vec<Diagnostic> {
+    issues_with_codes_and_options(input, profile, &ValidationOptions::default(), codes)
+}
+
+/// Like [`issues_with_codes`], validating with explicit `options`.
+#[allow(dead_code)]
+pub fn issues_with_codes_and_options(
+    input: &str,
+    profile: Option<&Profile>,
+    options: &ValidationOptions,
+    codes: &[&str],
+) -> Vec<Diagnostic> {
+    let result = parse_with_tables(input, Some(&TABLES));
+    validate_with_options(&result.ast, &TABLES, profile, options)
+        .issues
+        .into_iter()
+        .filter(|d| codes.contains(&&*d.id))
+        .collect()
+}
+
 // ─── Severity helpers ────────────────────────────────────────────────────────
 
 #[allow(dead_code)]
@@ -5,8 +5,14 @@
 
 mod common;
 
-use zpl_toolchain_core::grammar::emit::{EmitConfig, Indent, emit_zpl, strip_spans};
-use zpl_toolchain_core::grammar::parser::{parse_str, parse_with_tables};
+use zpl_toolchain_core::Node;
+use zpl_toolchain_core::grammar::emit::{
+    Compaction, EmitConfig, Indent, LineEnding, emit_zpl, emit_zpl_bytes, emit_zpl_with_source,
+    strip_spans,
+};
+use zpl_toolchain_core::grammar::parser::{
+    parse_bytes, parse_bytes_with_decoder, parse_str, parse_with_tables,
+};
 use zpl_toolchain_spec_tables::ParserTables;
 
 /// Assert that formatting + re-parsing produces a semantically identical AST.
@@ -23,6 +29,19 @@ fn assert_roundtrip(input: &str, tables: &ParserTables) {
     );
 }
 
+/// Assert that formatting already-formatted output leaves it unchanged.
+fn assert_idempotent(input: &str, tables: &ParserTables, config: &EmitConfig) {
+    let res1 = parse_with_tables(input, Some(tables));
+    let fmt1 = emit_zpl_with_source(&res1.ast, Some(tables), config, input);
+    let res2 = parse_with_tables(&fmt1, Some(tables));
+    let fmt2 = emit_zpl_with_source(&res2.ast, Some(tables), config, &fmt1);
+    assert_eq!(
+        fmt1, fmt2,
+        "\n--- Formatting is not idempotent ---\nConfig: {:?}\nInput:\n{}\n",
+        config, input
+    );
+}
+
 /// Assert round-trip without tables (graceful degradation).
 fn assert_roundtrip_no_tables(input: &str) {
     let res1 = parse_str(input);
@@ -94,6 +113,134 @@ fn field_data_with_leading_space_roundtrip() {
     assert_roundtrip("^XA^FO10,10^FD Leading^FS^XZ", &common::TABLES);
 }
 
+#[test]
+fn field_data_whitespace_preserved_under_every_compaction() {
+    let tables = &common::TABLES;
+    let input = "^XA^FO10,10^FD  spaced   text  ^FS^XZ";
+    let res = parse_with_tables(input, Some(tables));
+    for config in every_layout() {
+        let formatted = emit_zpl(&res.ast, Some(tables), &config);
+        assert!(
+            formatted.contains("^FD  spaced   text  \n")
+                || formatted.contains("^FD  spaced   text  ^FS"),
+            "{config:?} altered field data:\n{formatted:?}"
+        );
+        assert_idempotent(input, tables, &config);
+    }
+}
+
+#[test]
+fn compaction_joins_multi_line_field_data_onto_one_line() {
+    let tables = &common::TABLES;
+    let input = "^XA\n^FO1,1\n^FDfirst \n  second  \n^FS\n^XZ\n";
+    let res = parse_with_tables(input, Some(tables));
+    for indent in [Indent::None, Indent::Label, Indent::Field] {
+        let config = EmitConfig {
+            indent,
+            compaction: Compaction::Field,
+            ..EmitConfig::default()
+        };
+        let formatted = emit_zpl(&res.ast, Some(tables), &config);
+        let field = formatted
+            .lines()
+            .find(|line| line.contains("^FO1,1"))
+            .expect("field line");
+        assert_eq!(
+            field.trim_start(),
+            "^FO1,1^FDfirst   second  ^FS",
+            "{indent:?}"
+        );
+        assert_idempotent(input, tables, &config);
+    }
+}
+
+/// The text a printer sees for each `^FD`: its argument plus any trailing
+/// field data, with the line breaks printers ignore removed.
+fn printed_field_data(input: &str, tables: &ParserTables) -> Vec<String> {
+    let res = parse_with_tables(input, Some(tables));
+    let mut fields: Vec<String> = Vec::new();
+    for node in res.ast.labels.iter().flat_map(|l| &l.nodes) {
+        match node {
+            Node::Command { code, args, .. } if code == "^FD" => fields.push(
+                args.first()
+                    .and_then(|a| a.value.clone())
+                    .unwrap_or_default(),
+            ),
+            Node::FieldData { content, .. } => {
+                if let Some(field) = fields.last_mut() {
+                    field.extend(content.chars().filter(|c| !matches!(c, '\r' | '\n')));
+                }
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
+fn every_layout() -> Vec<EmitConfig> {
+    let mut configs = Vec::new();
+    for compaction in [Compaction::None, Compaction::Field] {
+        for indent in [Indent::None, Indent::Label, Indent::Field] {
+            configs.push(EmitConfig {
+                indent,
+                compaction,
+                ..EmitConfig::default()
+            });
+        }
+    }
+    configs
+}
+
+#[test]
+fn multi_line_field_data_keeps_user_spaces() {
+    let tables = &common::TABLES;
+    for (input, expected) in [
+        ("^XA^FO1,1^FDa\n   ^FS^XZ", "a   "),
+        ("^XA^FO1,1^FD  \n  ^FS^XZ", "    "),
+        (
+            "^XA^FO1,1^FDline one\n  line two  \n^FS^XZ",
+            "line one  line two  ",
+        ),
+        ("^XA^FO1,1^FDa\r\n b \r\n  ^FS^XZ", "a b   "),
+    ] {
+        assert_eq!(printed_field_data(input, tables), [expected], "{input:?}");
+        for config in every_layout() {
+            assert_idempotent(input, tables, &config);
+            // Indented layouts write spaces before ^FS, which a printer would
+            // also read as data, so only flat output is checked byte-for-byte.
+            if config.indent != Indent::None {
+                continue;
+            }
+            let res = parse_with_tables(input, Some(tables));
+            let formatted = emit_zpl(&res.ast, Some(tables), &config);
+            assert!(
+                formatted.contains(&format!("^FD{expected}\n"))
+                    || formatted.contains(&format!("^FD{expected}^FS")),
+                "{config:?} altered field data of {input:?}:\n{formatted:?}"
+            );
+            assert_eq!(
+                printed_field_data(&formatted, tables),
+                [expected],
+                "{config:?} round-trip of {input:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn formatter_indent_before_fs_is_not_field_data() {
+    let tables = &common::TABLES;
+    let config = EmitConfig {
+        indent: Indent::Field,
+        ..EmitConfig::default()
+    };
+    let input = "^XA^FO1,1^FDab ^FS^XZ";
+    let res = parse_with_tables(input, Some(tables));
+    let formatted = emit_zpl(&res.ast, Some(tables), &config);
+    assert_eq!(formatted, "^XA\n  ^FO1,1\n    ^FDab \n    ^FS\n^XZ\n");
+    assert_idempotent(input, tables, &config);
+}
+
 #[test]
 fn field_value_fv_roundtrip() {
     assert_roundtrip(
@@ -149,6 +296,52 @@ fn trailing_args_trimmed() {
     );
 }
 
+#[test]
+fn interior_empty_args_keep_their_placeholder() {
+    let tables = &common::TABLES;
+    for (input, expected) in [
+        ("^XA^GB100,,3^XZ", "^GB100,,3\n"),
+        ("^XA^BC,,100,,,^FD1^FS^XZ", "^BC,,100\n"),
+        ("^XA^A0N,,20^FDx^FS^XZ", "^A0N,,20\n"),
+    ] {
+        let res = parse_with_tables(input, Some(tables));
+        let formatted = emit_zpl(&res.ast, Some(tables), &EmitConfig::default());
+        assert!(
+            formatted.contains(expected),
+            "Expected {expected} in:\n{formatted}"
+        );
+        assert_roundtrip(input, tables);
+    }
+}
+
+#[test]
+fn trailing_empty_args_kept_when_signature_disallows_trimming() {
+    let mut tables = common::TABLES.clone();
+    let fo = tables
+        .commands
+        .iter_mut()
+        .find(|c| c.codes.iter().any(|code| code == "^FO"))
+        .expect("^FO in tables");
+    fo.signature
+        .as_mut()
+        .expect("^FO signature")
+        .allow_empty_trailing = false;
+
+    for (input, expected) in [
+        ("^XA^FO50,100,^XZ", "^FO50,100,\n"),
+        ("^XA^FO50,,^XZ", "^FO50,,\n"),
+        ("^XA^FO50,100^XZ", "^FO50,100\n"),
+    ] {
+        let res = parse_with_tables(input, Some(&tables));
+        let formatted = emit_zpl(&res.ast, Some(&tables), &EmitConfig::default());
+        assert!(
+            formatted.contains(expected),
+            "Expected {expected:?} in:\n{formatted}"
+        );
+        assert_roundtrip(input, &tables);
+    }
+}
+
 // ── No-tables fallback ──────────────────────────────────────────────────
 
 #[test]
@@ -375,17 +568,313 @@ fn usps_surepost_sample_roundtrip() {
 
 #[test]
 fn format_is_idempotent() {
-    let tables = &common::TABLES;
     let input = "^XA^FO50,100^A0N,30,30^FDHello World^FS^GB200,100,3^FS^XZ";
-    let config = EmitConfig::default();
+    assert_idempotent(input, &common::TABLES, &EmitConfig::default());
+}
 
-    let res1 = parse_with_tables(input, Some(tables));
-    let fmt1 = emit_zpl(&res1.ast, Some(tables), &config);
+#[test]
+fn samples_format_idempotently_under_all_configs() {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../samples");
+    let mut samples: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("sample dir entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "zpl"))
+        .collect();
+    samples.sort();
+    assert!(
+        !samples.is_empty(),
+        "expected sample labels in {}",
+        dir.display()
+    );
 
-    let res2 = parse_with_tables(&fmt1, Some(tables));
-    let fmt2 = emit_zpl(&res2.ast, Some(tables), &config);
+    for path in &samples {
+        let input = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+        for indent in [Indent::None, Indent::Label, Indent::Field] {
+            for compaction in [Compaction::None, Compaction::Field] {
+                for preserve_blank_lines in [false, true] {
+                    let config = EmitConfig {
+                        indent,
+                        compaction,
+                        preserve_blank_lines,
+                        ..EmitConfig::default()
+                    };
+                    assert_idempotent(&input, &common::TABLES, &config);
+                }
+            }
+        }
+    }
+}
+
+// ── Blank-line grouping ─────────────────────────────────────────────────
 
-    assert_eq!(fmt1, fmt2, "Formatting should be idempotent");
+#[test]
+fn parser_records_blank_lines_before_commands() {
+    let tables = &common::TABLES;
+    let input = "^XA\n^PW812\n\n\n^FO10,10^FDA^FS\r\n\r\n^XZ\n";
+    let res = parse_with_tables(input, Some(tables));
+    let blanks: Vec<(String, u32)> = res.ast.labels[0]
+        .nodes
+        .iter()
+        .filter_map(|n| match n {
+            zpl_toolchain_core::Node::Command {
+                code,
+                blank_lines_before,
+                ..
+            } => Some((code.clone(), *blank_lines_before)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        blanks,
+        vec![
+            ("^XA".to_string(), 0),
+            ("^PW".to_string(), 0),
+            ("^FO".to_string(), 2),
+            ("^FD".to_string(), 0),
+            ("^FS".to_string(), 0),
+            ("^XZ".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn preserve_blank_lines_round_trips_group_separators() {
+    let tables = &common::TABLES;
+    let input = "\n\n^XA\n^PW812\n^LL1218\n\n\n^FO10,10^A0N,30,30^FDName^FS\n^FO10,50^A0N,30,30^FDCity^FS\n\n^FO10,90\n\n^BCN,80^FD12345^FS\n^XZ\n\n";
+    let config = EmitConfig {
+        preserve_blank_lines: true,
+        ..EmitConfig::default()
+    };
+    let res = parse_with_tables(input, Some(tables));
+    let formatted = emit_zpl(&res.ast, Some(tables), &config);
+    assert_eq!(
+        formatted,
+        "^XA\n^PW812\n^LL1218\n\n^FO10,10\n^A0N,30,30\n^FDName\n^FS\n^FO10,50\n^A0N,30,30\n^FDCity\n^FS\n\n^FO10,90\n^BCN,80\n^FD12345\n^FS\n^XZ\n",
+        "leading/trailing blanks dropped, in-field blanks dropped, runs collapsed"
+    );
+
+    let res2 = parse_with_tables(&formatted, Some(tables));
+    let formatted2 = emit_zpl(&res2.ast, Some(tables), &config);
+    assert_eq!(
+        formatted, formatted2,
+        "blank-line preservation must be idempotent"
+    );
+}
+
+#[test]
+fn blank_lines_are_collapsed_by_default() {
+    let tables = &common::TABLES;
+    let input = "^XA\n^PW812\n\n^FO10,10^FDA^FS\n^XZ\n";
+    let res = parse_with_tables(input, Some(tables));
+    let formatted = emit_zpl(&res.ast, Some(tables), &EmitConfig::default());
+    assert!(
+        !formatted.contains("\n\n"),
+        "Default emission should not keep blank lines, got:\n{}",
+        formatted
+    );
+    assert_roundtrip(input, tables);
+}
+
+#[test]
+fn preserve_blank_lines_survives_field_compaction() {
+    let tables = &common::TABLES;
+    let input = "^XA\n^PW812\n\n^FO10,10\n^A0N,30,30\n^FDA^FS\n\n^FO10,50^FDB^FS\n^XZ\n";
+    let config = EmitConfig {
+        compaction: zpl_toolchain_core::Compaction::Field,
+        preserve_blank_lines: true,
+        ..EmitConfig::default()
+    };
+    let res = parse_with_tables(input, Some(tables));
+    let formatted = emit_zpl(&res.ast, Some(tables), &config);
+    assert_eq!(
+        formatted,
+        "^XA\n^PW812\n\n^FO10,10^A0N,30,30^FDA^FS\n\n^FO10,50^FDB^FS\n^XZ\n"
+    );
+}
+
+// ── Line endings ─────────────────────────────────────────────────────────
+
+const LINE_ENDING_LF: &str = "^XA\n^FO10,10\n^FDA\n^FS\n^XZ\n";
+const LINE_ENDING_CRLF: &str = "^XA\r\n^FO10,10\r\n^FDA\r\n^FS\r\n^XZ\r\n";
+
+fn emit_line_ending(source: &str, line_ending: LineEnding) -> String {
+    let tables = &common::TABLES;
+    let res = parse_with_tables(source, Some(tables));
+    let config = EmitConfig {
+        line_ending,
+        ..EmitConfig::default()
+    };
+    emit_zpl_with_source(&res.ast, Some(tables), &config, source)
+}
+
+#[test]
+fn line_ending_lf_is_default() {
+    assert_eq!(EmitConfig::default().line_ending, LineEnding::Lf);
+    assert_eq!(
+        emit_line_ending(LINE_ENDING_CRLF, LineEnding::Lf),
+        LINE_ENDING_LF
+    );
+}
+
+#[test]
+fn line_ending_crlf_output() {
+    assert_eq!(
+        emit_line_ending(LINE_ENDING_LF, LineEnding::CrLf),
+        LINE_ENDING_CRLF
+    );
+    // `emit_zpl` honours CrLf without a source.
+    let tables = &common::TABLES;
+    let res = parse_with_tables(LINE_ENDING_LF, Some(tables));
+    let config = EmitConfig {
+        line_ending: LineEnding::CrLf,
+        ..EmitConfig::default()
+    };
+    assert_eq!(emit_zpl(&res.ast, Some(tables), &config), LINE_ENDING_CRLF);
+}
+
+#[test]
+fn line_ending_preserve_follows_source() {
+    assert_eq!(
+        emit_line_ending(LINE_ENDING_CRLF, LineEnding::Preserve),
+        LINE_ENDING_CRLF
+    );
+    assert_eq!(
+        emit_line_ending(LINE_ENDING_LF, LineEnding::Preserve),
+        LINE_ENDING_LF
+    );
+    // Mixed input falls back to LF.
+    assert_eq!(
+        emit_line_ending("^XA\r\n^FO10,10\n^FDA\n^FS\r\n^XZ\n", LineEnding::Preserve),
+        LINE_ENDING_LF
+    );
+}
+
+#[test]
+fn line_ending_crlf_with_field_compaction() {
+    let tables = &common::TABLES;
+    let res = parse_with_tables(LINE_ENDING_LF, Some(tables));
+    let config = EmitConfig {
+        compaction: zpl_toolchain_core::Compaction::Field,
+        line_ending: LineEnding::CrLf,
+        ..EmitConfig::default()
+    };
+    assert_eq!(
+        emit_zpl(&res.ast, Some(tables), &config),
+        "^XA\r\n^FO10,10^FDA^FS\r\n^XZ\r\n"
+    );
+}
+
+#[test]
+fn line_ending_crlf_leaves_raw_payload_line_breaks_alone() {
+    let tables = &common::TABLES;
+    let input = "^XA\n^GFA,6,6,2,FF00\nFF00\nFF00\n^XZ\n";
+    let res = parse_with_tables(input, Some(tables));
+    for compaction in [Compaction::None, Compaction::Field] {
+        let config = EmitConfig {
+            compaction,
+            line_ending: LineEnding::CrLf,
+            ..EmitConfig::default()
+        };
+        assert_eq!(
+            emit_zpl(&res.ast, Some(tables), &config),
+            "^XA\r\n^GFA,6,6,2,FF00FF00\nFF00\n^XZ\r\n",
+            "{compaction:?}"
+        );
+    }
+}
+
+// ── Bare command wrapping ───────────────────────────────────────────────
+
+fn emit_wrapped(input: &str, indent: Indent) -> String {
+    let tables = &common::TABLES;
+    let res = parse_with_tables(input, Some(tables));
+    let config = EmitConfig {
+        indent,
+        wrap_bare_commands: true,
+        ..EmitConfig::default()
+    };
+    emit_zpl(&res.ast, Some(tables), &config)
+}
+
+#[test]
+fn wrap_bare_commands_adds_label_bounds() {
+    assert_eq!(
+        emit_wrapped("^FO10,10^FDA^FS", Indent::Label),
+        "^XA\n  ^FO10,10\n  ^FDA\n  ^FS\n^XZ\n"
+    );
+}
+
+#[test]
+fn wrap_bare_commands_leaves_wrapped_label_unchanged() {
+    let input = "^XA\n^FO10,10\n^FDA\n^FS\n^XZ\n";
+    assert_eq!(emit_wrapped(input, Indent::None), input);
+}
+
+#[test]
+fn wrap_bare_commands_ignores_empty_input() {
+    assert_eq!(emit_wrapped("", Indent::None), "");
+}
+
+#[test]
+fn wrap_bare_commands_closes_with_the_active_prefix() {
+    for (input, expected) in [
+        (
+            "^CC+\n+FO10,10+FDA+FS",
+            "^XA\n^CC+\n+FO10,10\n+FDA\n+FS\n+XZ\n",
+        ),
+        (
+            "~CC+\n+FO10,10+FDA+FS",
+            "^XA\n~CC+\n+FO10,10\n+FDA\n+FS\n+XZ\n",
+        ),
+    ] {
+        let wrapped = emit_wrapped(input, Indent::None);
+        assert_eq!(wrapped, expected);
+        let reparsed = parse_with_tables(&wrapped, Some(&common::TABLES));
+        let codes: Vec<_> = reparsed.ast.labels[0]
+            .nodes
+            .iter()
+            .filter_map(Node::command_code)
+            .collect();
+        assert_eq!(codes.last(), Some(&"^XZ"), "{wrapped:?}");
+    }
+}
+
+#[test]
+fn command_prefix_carries_over_to_the_next_label() {
+    let input = "^XA^CC++XZ+XA+FO1,1+XZ";
+    let res = parse_with_tables(input, Some(&common::TABLES));
+    assert_eq!(
+        emit_zpl(&res.ast, Some(&common::TABLES), &EmitConfig::default()),
+        "^XA\n^CC+\n+XZ\n+XA\n+FO1,1\n+XZ\n"
+    );
+}
+
+// ── Normalization ───────────────────────────────────────────────────────
+
+fn normalize(input: &str) -> String {
+    let tables = &common::TABLES;
+    let res = parse_with_tables(input, Some(tables));
+    emit_zpl(&res.ast, Some(tables), &EmitConfig::normalized())
+}
+
+#[test]
+fn normalize_equivalent_labels_are_byte_identical() {
+    let loose = "^XA\r\n  ^FO050,0100\r\n  ^FH^FD_1a Hi^FS\r\n\r\n  ^GFA,4,4,1,ab12\r\n^XZ\r\n";
+    let tight = "^XA^FO50,100^FH^FD_1A Hi^FS^GFA,4,4,1,AB12^XZ";
+    let expected = "^XA\n^FO50,100\n^FH\n^FD_1A Hi\n^FS\n^GFA,4,4,1,AB12\n^XZ\n";
+    assert_eq!(normalize(loose), expected);
+    assert_eq!(normalize(tight), expected);
+    assert_idempotent(expected, &common::TABLES, &EmitConfig::normalized());
+}
+
+#[test]
+fn normalize_leaves_text_outside_hex_contexts_alone() {
+    // Without ^FH, `_1a` is literal text; compressed counts stay lower-case.
+    assert_eq!(
+        normalize("^XA^FO0,0^FD_1a^FS^GFA,4,4,1,gab^XZ"),
+        "^XA\n^FO0,0\n^FD_1a\n^FS\n^GFA,4,4,1,gAB\n^XZ\n"
+    );
 }
 
 // ── Prefix/delimiter change ─────────────────────────────────────────────
@@ -395,6 +884,80 @@ fn prefix_change_cc_roundtrip() {
     assert_roundtrip("^XA^CC*\n*FO50,100\n*FDTest\n*FS\n*XZ", &common::TABLES);
 }
 
+#[test]
+fn delimiter_change_cd_keeps_source_joiner() {
+    let tables = &common::TABLES;
+    let input = "^XA\n^CD;\n^FO10;20\n^BY2;3.0;80\n^FDx\n^FS\n^XZ\n";
+    let res = parse_with_tables(input, Some(tables));
+    assert_eq!(
+        emit_zpl(&res.ast, Some(tables), &EmitConfig::default()),
+        input
+    );
+    assert_eq!(
+        normalize(input),
+        "^XA\n^CD,\n^FO10,20\n^BY2,3.0,80\n^FDx\n^FS\n^XZ\n"
+    );
+}
+
+// ── Binary payloads ─────────────────────────────────────────────────────
+
+#[test]
+fn binary_dg_payload_roundtrips_byte_for_byte() {
+    let tables = &common::TABLES;
+    // Not valid UTF-8; 0xA0 would be trimmed as Unicode whitespace.
+    let payload = [0x00, 0xFF, 0x80, 0xC3, 0xA0];
+    let mut input = b"~DGR:LOGO.GRF,5,1,".to_vec();
+    input.extend_from_slice(&payload);
+    input.extend_from_slice(b"\n^XA\n^FO0,0\n^XGR:LOGO.GRF,1,1\n^FS\n^XZ\n");
+
+    let res = parse_bytes(&input, Some(tables));
+    assert!(res.ast.byte_decoded);
+    let Some(Node::Command { code, span, .. }) = res.ast.labels[0].nodes.first() else {
+        panic!("expected ~DG command");
+    };
+    assert_eq!(code, "~DG");
+    // The command span covers the header, the payload, and its line break.
+    let line_end = input.iter().position(|&b| b == b'\n').unwrap() + 1;
+    assert_eq!((span.start, span.end), (0, line_end));
+    let Some(Node::Command { span: xa, .. }) = res.ast.labels[0].nodes.get(1) else {
+        panic!("expected ^XA command");
+    };
+    assert_eq!(&input[xa.start..xa.start + 3], b"^XA");
+
+    let out = emit_zpl_bytes(&res.ast, Some(tables), &EmitConfig::default());
+    assert_eq!(out, input, "{}", String::from_utf8_lossy(&out));
+}
+
+#[test]
+fn parse_bytes_matches_parse_for_utf8_input() {
+    let tables = &common::TABLES;
+    let input = "^XA^FO10,10^FDCafé^FS^XZ";
+    let from_bytes = parse_bytes(input.as_bytes(), Some(tables));
+    assert!(!from_bytes.ast.byte_decoded);
+    assert_eq!(from_bytes.ast, parse_with_tables(input, Some(tables)).ast);
+}
+
+#[test]
+fn decoder_spans_are_byte_offsets_for_three_byte_characters() {
+    let tables = &common::TABLES;
+    // 0xB0 decodes to '░', which takes three bytes in UTF-8.
+    let input = b"^XA^FO1,1^FD\xB0\xB0^FS^XZ";
+    let decode = |b: u8| if b == 0xB0 { '░' } else { char::from(b) };
+    let res = parse_bytes_with_decoder(input, Some(tables), decode);
+    assert!(!res.ast.byte_decoded);
+    let spans: Vec<_> = res.ast.labels[0]
+        .nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Command { code, span, .. } => Some((code.as_str(), span.start)),
+            _ => None,
+        })
+        .collect();
+    let fs = input.windows(3).position(|w| w == b"^FS").unwrap();
+    assert!(spans.contains(&("^FS", fs)), "{spans:?}");
+    assert!(spans.contains(&("^XZ", fs + 3)), "{spans:?}");
+}
+
 // ── Commands with no args ───────────────────────────────────────────────
 
 #[test]
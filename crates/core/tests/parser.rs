@@ -12,9 +12,15 @@ use common::{
     extract_codes, extract_diag_codes, extract_label_codes, find_args, is_severity_error,
     is_severity_info, is_severity_warn,
 };
-use zpl_toolchain_core::grammar::ast::{Node, Presence};
+use zpl_toolchain_core::grammar::ast::{Ast, Node, Presence};
 use zpl_toolchain_core::grammar::diag::Span;
-use zpl_toolchain_core::grammar::parser::{parse_str, parse_with_tables};
+use zpl_toolchain_core::grammar::dump::{
+    WithSpans, to_compact_json, to_json_value, to_pretty_json,
+};
+use zpl_toolchain_core::grammar::emit::strip_spans;
+use zpl_toolchain_core::grammar::parser::{
+    ParseOptions, parse_str, parse_with_options, parse_with_tables, reparse, reparse_with_tables,
+};
 use zpl_toolchain_diagnostics::{Severity, codes};
 
 fn tables_with_spacing_command(
@@ -58,6 +64,7 @@ fn tables_with_spacing_command(
                     default_from: None,
                     default_from_state_key: None,
                     profile_constraint: None,
+                    numeric_aliases: None,
                     range_when: None,
                     rounding_policy: None,
                     rounding_policy_when: None,
@@ -242,6 +249,17 @@ fn comma_separated_args() {
     assert!(matches!(args[1].presence, Presence::Value));
 }
 
+#[test]
+fn quoted_final_arg_keeps_embedded_joiner() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^FN3,\"Ship to, city\"^FS^XZ", Some(tables));
+    let args = find_args(&result, "^FN");
+    assert_eq!(args.len(), 2, "{args:?}");
+    assert_eq!(args[0].value.as_deref(), Some("3"));
+    assert_eq!(args[1].value.as_deref(), Some("\"Ship to, city\""));
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+}
+
 #[test]
 fn empty_trailing_args() {
     // ^BC,,,,, → 6 comma-separated empty segments
@@ -646,6 +664,30 @@ fn semicolon_comments_are_not_parsed_as_trivia() {
     );
 }
 
+#[test]
+fn trailing_semicolon_note_stays_in_command_args() {
+    // ZPL's only comment form is ^FX; a `; note` after a command is part of
+    // its last argument, not a comment and not stray content.
+    let result = parse_with_tables(
+        "^XA\n^PW400; note\n^FO10,10^FDPart;A^FS\n^XZ",
+        Some(&common::TABLES),
+    );
+    assert!(
+        !result
+            .diagnostics
+            .iter()
+            .any(|d| d.id == codes::PARSER_STRAY_CONTENT),
+        "{:?}",
+        result.diagnostics
+    );
+    let nodes = &result.ast.labels[0].nodes;
+    assert!(!nodes.iter().any(|n| matches!(n, Node::Trivia { .. })));
+    let pw = find_args(&result, "^PW");
+    assert_eq!(pw[0].value.as_deref(), Some("400; note"));
+    let fd = find_args(&result, "^FD");
+    assert_eq!(fd[0].value.as_deref(), Some("Part;A"));
+}
+
 // ─── 8. Diagnostics ────────────────────────────────────────────────────────
 
 #[test]
@@ -1073,6 +1115,42 @@ fn diag_parser_1102_missing_xz() {
     );
 }
 
+#[test]
+fn diag_parser_1102_points_at_dangling_xa() {
+    let input = "^XA^FO10,10^FDOne^FS^XZ\n^XA^FO10,10^FDTwo^FS";
+    let result = parse_str(input);
+    let diags: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.id == codes::PARSER_MISSING_TERMINATOR)
+        .collect();
+    assert_eq!(diags.len(), 1, "only the second label is unterminated");
+    let span = diags[0].span.expect("1102 should have a span");
+    assert_eq!(&input[span.start..span.end], "^XA");
+    assert_eq!(span.start, input.rfind("^XA").unwrap());
+}
+
+#[test]
+fn diag_parser_1102_back_to_back_xa_starts_fresh_label() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO10,10^FDOne^FS\n^XA^FO10,10^FDTwo^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    assert_eq!(result.ast.labels.len(), 2, "second ^XA starts a new label");
+    assert_eq!(
+        extract_label_codes(&result, 1),
+        vec!["^XA", "^FO", "^FD", "^FS", "^XZ"]
+    );
+    let diags: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.id == codes::PARSER_MISSING_TERMINATOR)
+        .collect();
+    assert_eq!(diags.len(), 1, "{:?}", extract_diag_codes(&result));
+    let span = diags[0].span.expect("1102 should have a span");
+    assert_eq!(span.start, 0, "diagnostic should point at the first ^XA");
+    assert_eq!(span.end, 3);
+}
+
 #[test]
 fn diag_parser_1202_missing_fs_eof() {
     let tables = &*common::TABLES;
@@ -1544,3 +1622,306 @@ fn context_parser_missing_terminator() {
         .expect("parser diagnostic should have context");
     assert_eq!(ctx.get("expected").unwrap(), "^XZ");
 }
+
+// ─── 15. AST JSON Serialization ──────────────────────────────────────────────
+
+#[test]
+fn spanless_json_is_smaller_and_omits_spans() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA\n^FO10,10^FDHello^FS\n^XZ\n", Some(tables));
+    let with_spans = to_compact_json(&result.ast, WithSpans::Yes);
+    let without_spans = to_compact_json(&result.ast, WithSpans::No);
+    assert!(with_spans.contains("\"span\""));
+    assert!(!without_spans.contains("\"span\""));
+    assert!(without_spans.len() < with_spans.len());
+    assert!(
+        to_pretty_json(&result.ast, WithSpans::No).len()
+            < to_pretty_json(&result.ast, WithSpans::Yes).len()
+    );
+}
+
+#[test]
+fn spanless_json_round_trips_with_zero_spans() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA\n^FO10,10^FDHello^FS\n^XZ\n", Some(tables));
+    let json = to_compact_json(&result.ast, WithSpans::No);
+    let ast: Ast = serde_json::from_str(&json).expect("spanless AST should deserialize");
+    assert_eq!(ast, strip_spans(&result.ast));
+    for node in &ast.labels[0].nodes {
+        if let Node::Command { span, .. } = node {
+            assert_eq!(*span, Span::empty(0));
+        }
+    }
+}
+
+#[test]
+fn spanless_json_value_strips_nested_diagnostic_spans() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^QQ99^XZ", Some(tables));
+    assert!(result.diagnostics.iter().any(|d| d.span.is_some()));
+    let json = to_json_value(&result, WithSpans::No).to_string();
+    assert!(!json.contains("\"span\""));
+    assert!(json.contains("\"diagnostics\""));
+}
+
+#[test]
+fn parse_result_summary_matches_diagnostic_severities() {
+    let tables = &*common::TABLES;
+    // Unknown command, stray ^FS, unclosed field data, and no trailing ^XZ.
+    let result = parse_with_tables("^XA^QQ99^FS^FO10,10^FDopen", Some(tables));
+    let diags = &result.diagnostics;
+    assert!(!diags.is_empty());
+    let count = |pred: fn(&zpl_toolchain_diagnostics::Severity) -> bool| {
+        diags.iter().filter(|d| pred(&d.severity)).count()
+    };
+    assert_eq!(result.summary.errors, count(is_severity_error));
+    assert_eq!(result.summary.warns, count(is_severity_warn));
+    assert_eq!(result.summary.infos, count(is_severity_info));
+
+    let json = to_json_value(&result, WithSpans::No);
+    assert_eq!(json["summary"]["errors"], result.summary.errors);
+    assert_eq!(json["summary"]["warns"], result.summary.warns);
+    assert_eq!(json["summary"]["infos"], result.summary.infos);
+}
+
+// ─── 16. Node Accessors ──────────────────────────────────────────────────────
+
+#[test]
+fn node_accessors_on_command() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^FO10,20^FDHello^FS^XZ", Some(tables));
+    let fo = &result.ast.labels[0].nodes[1];
+    assert_eq!(fo.command_code(), Some("^FO"));
+    let values: Vec<_> = fo.args().iter().map(|a| a.value.as_deref()).collect();
+    assert_eq!(&values[..2], &[Some("10"), Some("20")]);
+    assert_eq!(fo.field_data(), None);
+}
+
+#[test]
+fn node_accessors_on_field_data() {
+    let tables = &*common::TABLES;
+    // A stray leader inside ^FD continues as a FieldData node.
+    let result = parse_with_tables("^XA^FO10,20^FDHello^ World^FS^XZ", Some(tables));
+    let fd = result.ast.labels[0]
+        .nodes
+        .iter()
+        .find(|n| n.field_data().is_some())
+        .expect("field data node");
+    assert_eq!(fd.field_data(), Some("^ World"));
+    assert_eq!(fd.command_code(), None);
+    assert!(fd.args().is_empty());
+}
+
+#[test]
+fn node_accessors_on_comments() {
+    let tables = &*common::TABLES;
+    // ^FX comments are ordinary commands.
+    let result = parse_with_tables("^XA^FXlabel header^XZ", Some(tables));
+    let fx = &result.ast.labels[0].nodes[1];
+    assert_eq!(fx.command_code(), Some("^FX"));
+    assert_eq!(fx.field_data(), None);
+
+    let trivia = Node::Trivia {
+        text: "; comment".into(),
+        span: Span::new(0, 9),
+    };
+    assert_eq!(trivia.command_code(), None);
+    assert!(trivia.args().is_empty());
+    assert_eq!(trivia.field_data(), None);
+}
+
+#[test]
+fn command_histogram_counts_repeated_commands() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables(
+        "^XA^FO10,10^FDOne^FS^FO10,40^FDTwo^FS^FO10,70^FDThree^FS^XZ^XA^FO5,5^FDFour^FS^XZ",
+        Some(tables),
+    );
+    let histogram = zpl_toolchain_core::command_histogram(&result.ast);
+    assert_eq!(histogram["^FO"], 4);
+    assert_eq!(histogram["^FD"], 4);
+    assert_eq!(histogram["^FS"], 4);
+    assert_eq!(histogram["^XA"], 2);
+    assert_eq!(histogram["^XZ"], 2);
+    assert_eq!(histogram.len(), 5, "field data nodes are not counted");
+    assert!(zpl_toolchain_core::command_histogram(&Ast::default()).is_empty());
+}
+
+#[test]
+fn select_label_picks_by_position_and_spans_its_source() {
+    let input = "^XA^FDOne^FS^XZ\n^XA^FDTwo^FS^XZ\n^XA^FDThree^FS^XZ";
+    let result = parse_with_tables(input, Some(&common::TABLES));
+    let source = |n: isize| {
+        zpl_toolchain_core::select_label(&result.ast, n)
+            .and_then(|label| label.span())
+            .map(|span| &input[span.start..span.end])
+    };
+    // Command spans include trailing whitespace, so the newline comes along.
+    assert_eq!(source(2), Some("^XA^FDTwo^FS^XZ\n"));
+    assert_eq!(source(-1), Some("^XA^FDThree^FS^XZ"));
+    assert_eq!(source(-3), Some("^XA^FDOne^FS^XZ\n"));
+    assert_eq!(source(0), None);
+    assert_eq!(source(4), None);
+    assert_eq!(source(-4), None);
+}
+
+#[test]
+fn arg_slot_typed_accessors() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^FO10,,0^A0N,22.5,20^XZ", Some(tables));
+
+    let fo = find_args(&result, "^FO");
+    assert_eq!(fo[0].presence(), Presence::Value);
+    assert_eq!(fo[0].as_int(), Some(10));
+    assert_eq!(fo[0].as_float(), Some(10.0));
+
+    assert_eq!(fo[1].presence(), Presence::Empty);
+    assert_eq!(fo[1].as_str(), None);
+    assert_eq!(fo[1].as_int(), None);
+    assert_eq!(fo[1].as_float(), None);
+
+    let a = find_args(&result, "^A");
+    let orientation = a
+        .iter()
+        .find(|slot| slot.key.as_deref() == Some("o"))
+        .expect("orientation arg");
+    assert_eq!(orientation.as_str(), Some("N"));
+    assert_eq!(orientation.as_int(), None);
+    let height = a
+        .iter()
+        .find(|slot| slot.key.as_deref() == Some("h"))
+        .expect("height arg");
+    assert_eq!(height.as_int(), None, "fractional values are not integers");
+    assert_eq!(height.as_float(), Some(22.5));
+}
+
+// ─── 17. Incremental Reparse ─────────────────────────────────────────────────
+
+/// Apply `replacement` over `old[start..end]`, then check that an incremental
+/// reparse matches a full parse of the edited text.
+fn assert_reparse_matches_full(old_input: &str, start: usize, end: usize, replacement: &str) {
+    let tables = &*common::TABLES;
+    let old = parse_with_tables(old_input, Some(tables));
+    let new_input = format!(
+        "{}{}{}",
+        &old_input[..start],
+        replacement,
+        &old_input[end..]
+    );
+    let incremental = reparse_with_tables(
+        &old,
+        &new_input,
+        Span::new(start, end),
+        replacement.len(),
+        Some(tables),
+    );
+    let full = parse_with_tables(&new_input, Some(tables));
+    assert_eq!(incremental.ast, full.ast, "AST differs for {new_input:?}");
+    assert_eq!(
+        incremental.diagnostics, full.diagnostics,
+        "diagnostics differ for {new_input:?}"
+    );
+}
+
+const THREE_LABELS: &str =
+    "^XA^FO10,10^FDOne^FS^XZ\n^XA^FO20,20^FDTwo^FS^XZ\n\n^XA^FO30,30^FDThree^FS^XZ\n";
+
+#[test]
+fn reparse_edit_inside_second_label_matches_full_parse() {
+    let start = THREE_LABELS.find("Two").unwrap();
+    assert_reparse_matches_full(THREE_LABELS, start, start + 3, "Second label");
+    // Shrinking edits shift the third label's spans the other way.
+    assert_reparse_matches_full(THREE_LABELS, start, start + 3, "");
+    // Edits that introduce errors produce the same diagnostics.
+    let fs = THREE_LABELS.find("Two^FS").unwrap() + 3;
+    assert_reparse_matches_full(THREE_LABELS, fs, fs + 3, "");
+}
+
+#[test]
+fn reparse_keeps_diagnostics_from_reused_labels() {
+    let input = "^XA^ZZ1^FO10,10^FDOne^FS^XZ\n^XA^FO20,20^FDTwo^FS^XZ\n";
+    let start = input.find("Two").unwrap();
+    assert_reparse_matches_full(input, start, start + 3, "2");
+}
+
+#[test]
+fn reparse_replays_prefix_and_hex_state() {
+    // ^CC changes the command prefix for every later label, so the reused
+    // labels' prefix and ^FH state must carry into the reparsed tail.
+    let input = "^XA^CC#^XZ\n#XA#FO1,1#FH#FDa_41#FS#XZ\n#XA#FO2,2#FDb#FS#XZ\n";
+    let start = input.find("b#FS").unwrap();
+    assert_reparse_matches_full(input, start, start + 1, "bb_42");
+    let start = input.find("a_41").unwrap();
+    assert_reparse_matches_full(input, start, start + 1, "A");
+}
+
+#[test]
+fn reparse_edit_in_first_label_or_boundary_matches_full_parse() {
+    assert_reparse_matches_full(THREE_LABELS, 4, 6, "99");
+    // Editing right at a label boundary reparses the label before it too.
+    let boundary = THREE_LABELS.find("\n^XA").unwrap();
+    assert_reparse_matches_full(THREE_LABELS, boundary, boundary, "^FX note");
+    assert_reparse_matches_full(THREE_LABELS, 0, THREE_LABELS.len(), "^XA^XZ");
+}
+
+#[test]
+fn reparse_without_tables_matches_full_parse() {
+    let old = parse_str(THREE_LABELS);
+    let start = THREE_LABELS.rfind("Three").unwrap();
+    let new_input = format!("{}3{}", &THREE_LABELS[..start], &THREE_LABELS[start + 5..]);
+    let incremental = reparse(&old, &new_input, Span::new(start, start + 5), 1);
+    let full = parse_str(&new_input);
+    assert_eq!(incremental.ast, full.ast);
+    assert_eq!(incremental.diagnostics, full.diagnostics);
+}
+
+#[test]
+fn parse_options_trim_diagnostics_without_changing_ast() {
+    let tables = &*common::TABLES;
+    // A large job full of vendor commands the tables do not know.
+    let label = "^XA^QQ1,2^FO10,10^FDvendor^FS^QW^QX9^FO10,40^FDok^FS^XZ\n";
+    let input = label.repeat(2_000) + "^XA^FO10,10^FDopen^FS";
+
+    let full = parse_with_tables(&input, Some(tables));
+    let unknown = |r: &zpl_toolchain_core::grammar::parser::ParseResult| {
+        r.diagnostics
+            .iter()
+            .filter(|d| d.id == codes::PARSER_UNKNOWN_COMMAND)
+            .count()
+    };
+    assert_eq!(unknown(&full), 6_000);
+    assert!(full.diagnostics.len() > unknown(&full));
+
+    let no_unknown = parse_with_options(
+        &input,
+        Some(tables),
+        &ParseOptions {
+            emit_unknown_command_diagnostics: false,
+            ..ParseOptions::default()
+        },
+    );
+    assert_eq!(unknown(&no_unknown), 0);
+    assert_eq!(
+        no_unknown.diagnostics.len(),
+        full.diagnostics.len() - unknown(&full)
+    );
+    assert_eq!(no_unknown.ast, full.ast);
+
+    let none = parse_with_options(
+        &input,
+        Some(tables),
+        &ParseOptions {
+            collect_diagnostics: false,
+            ..ParseOptions::default()
+        },
+    );
+    assert!(none.diagnostics.is_empty());
+    assert_eq!(
+        none.summary.errors + none.summary.warns + none.summary.infos,
+        0
+    );
+    assert_eq!(none.ast, full.ast);
+
+    let defaults = parse_with_options(&input, Some(tables), &ParseOptions::default());
+    assert_eq!(defaults.diagnostics, full.diagnostics);
+}
@@ -0,0 +1,45 @@
+//! Tests for PNG rendering and example hashes (`render` feature).
+
+#![cfg(feature = "render")]
+
+mod common;
+
+use zpl_toolchain_core::render::{RenderError, example_png_hash, render_label};
+use zpl_toolchain_profile::{Profile, load_profile_from_str};
+
+const EXAMPLE: &str = "^XA^PW400^LL200^FO20,20^GB100,50,3^FS^FO20,100^A0N,30,20^FDHello^FS^XZ";
+
+fn generic_203() -> Profile {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../profiles/zebra-generic-203.json");
+    let json = std::fs::read_to_string(&path).expect("read profile");
+    load_profile_from_str(&json).expect("valid profile")
+}
+
+#[test]
+fn example_png_hash_is_stable() {
+    let tables = &*common::TABLES;
+    let profile = generic_203();
+    let first = example_png_hash(EXAMPLE, tables, &profile).expect("hash");
+    let second = example_png_hash(EXAMPLE, tables, &profile).expect("hash");
+    assert_eq!(first, second);
+    assert_eq!(
+        first,
+        "1a1be21eb3ea3f145282483e4030866d8dc4c1aa429c91cec8d0b2d99b5cb2a3"
+    );
+}
+
+#[test]
+fn render_label_uses_label_dimensions_and_reports_missing_labels() {
+    let tables = &*common::TABLES;
+    let bitmap = render_label(EXAMPLE, tables, None, 0).expect("render");
+    assert_eq!((bitmap.width(), bitmap.height()), (400, 200));
+    assert!(bitmap.is_black(20, 20));
+    assert!(!bitmap.is_black(60, 45));
+
+    let err = render_label(EXAMPLE, tables, None, 1).unwrap_err();
+    assert!(matches!(
+        err,
+        RenderError::LabelNotFound { index: 1, count: 1 }
+    ));
+}
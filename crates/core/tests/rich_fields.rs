@@ -62,6 +62,7 @@ fn rounding_policy_to_multiple_warns() {
                     default_from: None,
                     default_from_state_key: None,
                     profile_constraint: None,
+                    numeric_aliases: None,
                     range_when: None,
                     rounding_policy: Some(zpl_toolchain_spec_tables::RoundingPolicy {
                         unit: None,
@@ -150,6 +151,7 @@ fn conditional_range_enforced() {
                         default_from: None,
                         default_from_state_key: None,
                         profile_constraint: None,
+                        numeric_aliases: None,
                         range_when: Some(vec![zpl_toolchain_spec_tables::ConditionalRange {
                             when: "arg:bIsValue:X".to_string(),
                             range: [50.0, 100.0],
@@ -176,6 +178,7 @@ fn conditional_range_enforced() {
                         default_from: None,
                         default_from_state_key: None,
                         profile_constraint: None,
+                        numeric_aliases: None,
                         range_when: None,
                         rounding_policy: None,
                         rounding_policy_when: None,
@@ -0,0 +1,67 @@
+//! Tests for the JSON Schemas emitted by the `schema` feature.
+//! Serialized output from the parser and validator must validate against them.
+
+#![cfg(feature = "schema")]
+
+mod common;
+
+use serde_json::json;
+use zpl_toolchain_core::schema::{
+    diagnostic_schema, parse_result_schema, validation_result_schema,
+};
+use zpl_toolchain_core::{parse_with_tables, validate_with_profile};
+
+fn assert_valid(schema: &serde_json::Value, instance: &serde_json::Value) {
+    let validator = jsonschema::validator_for(schema).expect("schema should compile");
+    let errors: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|e| format!("{} at {}", e, e.instance_path))
+        .collect();
+    assert!(errors.is_empty(), "instance did not validate: {errors:?}");
+}
+
+#[test]
+fn known_good_diagnostic_validates() {
+    let diag = json!({
+        "id": "ZPL1101",
+        "severity": "error",
+        "message": "^FO expects at most 3 arguments, got 4",
+        "span": { "start": 3, "end": 15 },
+        "context": { "command": "^FO", "arity": "3", "actual": "4" }
+    });
+    assert_valid(&diagnostic_schema(), &diag);
+
+    // Optional fields may be omitted.
+    let minimal = json!({ "id": "ZPL2101", "severity": "info", "message": "note" });
+    assert_valid(&diagnostic_schema(), &minimal);
+}
+
+#[test]
+fn diagnostic_schema_rejects_bad_severity() {
+    let schema = diagnostic_schema();
+    let validator = jsonschema::validator_for(&schema).expect("schema should compile");
+    let bad = json!({ "id": "ZPL1101", "severity": "fatal", "message": "x" });
+    assert!(!validator.is_valid(&bad));
+    assert!(!validator.is_valid(&json!({ "id": "ZPL1101", "severity": "error" })));
+}
+
+#[test]
+fn parse_and_validation_results_validate() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO50,50^A0N,30,30^FDHello^FS^FO10,10,9,9^FS^XZ";
+    let parsed = parse_with_tables(input, Some(tables));
+    let result = validate_with_profile(&parsed.ast, tables, None);
+    assert!(
+        !result.issues.is_empty(),
+        "fixture should produce diagnostics"
+    );
+
+    let parsed_json = serde_json::to_value(&parsed).unwrap();
+    assert_valid(&parse_result_schema(), &parsed_json);
+    let result_json = serde_json::to_value(&result).unwrap();
+    assert_valid(&validation_result_schema(), &result_json);
+
+    for issue in &result.issues {
+        assert_valid(&diagnostic_schema(), &serde_json::to_value(issue).unwrap());
+    }
+}
@@ -9,9 +9,11 @@
 
 mod common;
 
-use common::{extract_codes, find_args, find_diag};
+use common::{
+    extract_codes, find_args, find_diag, issues_with_codes, issues_with_codes_and_options,
+};
 use zpl_toolchain_core::grammar::parser::parse_with_tables;
-use zpl_toolchain_core::validate::{self, validate_with_profile};
+use zpl_toolchain_core::validate::{self, validate_against_profiles, validate_with_profile};
 use zpl_toolchain_diagnostics::{Severity, codes};
 use zpl_toolchain_spec_tables::{ArgUnion, Constraint, ConstraintKind};
 
@@ -147,6 +149,33 @@ fn validation_result_includes_resolved_labels_per_input_label() {
     assert_eq!(vr.resolved_labels[1].values.barcode.height, Some(60));
 }
 
+#[test]
+fn resolved_labels_report_index_span_and_counts() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO10,10^FDok^FS^XZ\n^XA^BY99^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert_eq!(vr.resolved_labels.len(), 2);
+
+    let first = &vr.resolved_labels[0];
+    assert_eq!(first.index, 0);
+    let span = first.span.expect("first label span");
+    assert_eq!(span.start, 0);
+    assert_eq!(
+        input[span.start..span.end].trim_end(),
+        "^XA^FO10,10^FDok^FS^XZ"
+    );
+    assert_eq!(first.command_count, 5);
+    assert_eq!(first.error_count, 0);
+
+    let second = &vr.resolved_labels[1];
+    assert_eq!(second.index, 1);
+    let span = second.span.expect("second label span");
+    assert_eq!(&input[span.start..span.end], "^XA^BY99^XZ");
+    assert_eq!(second.command_count, 3);
+    assert_eq!(second.error_count, 1, "issues: {:?}", vr.issues);
+}
+
 #[test]
 fn resolved_label_state_tracks_effective_dimensions() {
     let tables = &*common::TABLES;
@@ -157,6 +186,85 @@ fn resolved_label_state_tracks_effective_dimensions() {
     assert_eq!(vr.resolved_labels[0].effective_height, Some(1200.0));
 }
 
+#[test]
+fn merge_parse_orders_by_span_and_recomputes_ok() {
+    let tables = &*common::TABLES;
+    let command_of = |d: &zpl_toolchain_diagnostics::Diagnostic| {
+        d.context
+            .as_ref()
+            .and_then(|c| c.get("command"))
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    // Parse warning in the first label, validation error in the second.
+    let input = "^XA^FO10,10^FDok^XZ\n^XA^BY99^FO10,10^FDx^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let mut vr = validate::validate(&result.ast, tables);
+    let validation_count = vr.issues.len();
+    vr.merge_parse(result.diagnostics.clone());
+    assert_eq!(vr.issues.len(), validation_count + result.diagnostics.len());
+    let starts: Vec<usize> = vr
+        .issues
+        .iter()
+        .map(|d| d.span.map_or(usize::MAX, |s| s.start))
+        .collect();
+    assert!(starts.is_sorted(), "{starts:?}");
+    let parse_pos = vr
+        .issues
+        .iter()
+        .position(|d| d.id == codes::PARSER_FIELD_DATA_INTERRUPTED)
+        .expect("parse warning merged");
+    let by_pos = vr
+        .issues
+        .iter()
+        .position(|d| command_of(d) == "^BY")
+        .expect("^BY validation error");
+    assert!(parse_pos < by_pos);
+
+    // A parse error fails a result that validation alone passed.
+    let unterminated = parse_with_tables("^XA^FO10,10^FDok^FS", Some(tables));
+    let mut vr = validate::validate(&unterminated.ast, tables);
+    assert!(vr.ok, "{:?}", vr.issues);
+    vr.merge_parse(unterminated.diagnostics);
+    assert!(!vr.ok, "{:?}", vr.issues);
+}
+
+#[test]
+fn resolved_labels_list_named_fields() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO10,10^FN1,\"ORDER_ID\"^FS^FO10,50^FN2^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let fields = &vr.resolved_labels[0].fields;
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[0].number, 1);
+    assert_eq!(fields[0].name.as_deref(), Some("ORDER_ID"));
+    assert_eq!(
+        &input[fields[0].span.start..fields[0].span.end],
+        "^FN1,\"ORDER_ID\""
+    );
+    assert_eq!(fields[1].number, 2);
+    assert_eq!(fields[1].name, None);
+}
+
+#[test]
+fn fields_stay_per_label_under_map_clear_no() {
+    let tables = &*common::TABLES;
+    // ^MCN keeps the printed image, not the previous label's ^FN fields.
+    let result = parse_with_tables(
+        "^XA^MCN^FO10,10^FN1^FDtemplate^FS^XZ\n^XA^FO10,50^FN2^FVvariable^FS^XZ",
+        Some(tables),
+    );
+    let vr = validate::validate(&result.ast, tables);
+    let numbers: Vec<Vec<u32>> = vr
+        .resolved_labels
+        .iter()
+        .map(|l| l.fields.iter().map(|f| f.number).collect())
+        .collect();
+    assert_eq!(numbers, [vec![1], vec![2]]);
+}
+
 // ─── ZPL1101: Arity ─────────────────────────────────────────────────────────
 
 #[test]
@@ -213,6 +321,51 @@ fn diag_zpl1103_valid_enum_passes() {
     );
 }
 
+#[test]
+fn diag_zpl1103_sc_serial_bootstrap_valid() {
+    let tables = &*common::TABLES;
+    // Typical serial bootstrap label sent over TCP.
+    let result = parse_with_tables("^XA^SC9600,8,N,1,X,N^JUS^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        !vr.issues
+            .iter()
+            .any(|d| matches!(d.severity, Severity::Error | Severity::Warn)),
+        "valid ^SC bootstrap should not emit errors/warnings: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn diag_zpl1103_sc_invalid_parity() {
+    let tables = &*common::TABLES;
+    // Parity must be N/E/O; X is a common typo for the handshake slot.
+    let result = parse_with_tables("^XA^SC9600,8,X,1,X,N^JUS^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let d = find_diag(&vr.issues, codes::INVALID_ENUM);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^SC");
+    assert_eq!(ctx.get("value").unwrap(), "X");
+    assert_eq!(
+        vr.issues
+            .iter()
+            .filter(|d| d.id == codes::INVALID_ENUM)
+            .count(),
+        1,
+        "only parity is invalid: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn diag_zpl1103_sc_unsupported_baud() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^SC9601,8,N,1,X,N^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let d = find_diag(&vr.issues, codes::INVALID_ENUM);
+    assert_eq!(d.context.as_ref().unwrap().get("value").unwrap(), "9601");
+}
+
 // ─── ZPL1104: Empty Field Data ───────────────────────────────────────────────
 
 #[test]
@@ -296,6 +449,17 @@ fn diag_zpl1107_valid_integer_passes() {
     );
 }
 
+#[test]
+fn diag_zpl1107_semicolon_note_is_not_a_comment() {
+    // `;` does not start a comment in ZPL (use ^FX), so the note is read as
+    // part of the ^PW width and fails the integer check.
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA\n^PW400; note\n^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let diag = find_diag(&vr.issues, codes::EXPECTED_INTEGER);
+    assert_eq!(diag.context.as_ref().unwrap()["command"], "^PW");
+}
+
 #[test]
 fn diag_zpl1108_float_type_mismatch() {
     let tables = &*common::TABLES;
@@ -391,6 +555,47 @@ fn diag_zpl1201_in_range_passes() {
     );
 }
 
+#[test]
+fn diag_by_ratio_in_range_passes() {
+    for ratio in ["2.0", "2.3", "2.5", "3"] {
+        let issues = issues_with_codes(
+            &format!("^XA^BY2,{ratio},50^XZ"),
+            None,
+            &[codes::OUT_OF_RANGE, codes::ROUNDING_VIOLATION],
+        );
+        assert!(issues.is_empty(), "{ratio}: {issues:?}");
+    }
+}
+
+#[test]
+fn diag_by_ratio_out_of_range_reports_valid_range() {
+    let issues = issues_with_codes(
+        "^XA^BY2,4.0,50^XZ",
+        None,
+        &[codes::OUT_OF_RANGE, codes::ROUNDING_VIOLATION],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].id, codes::OUT_OF_RANGE);
+    let ctx = issues[0].context.as_ref().expect("context");
+    assert_eq!(ctx["command"], "^BY");
+    assert_eq!(ctx["min"], "2");
+    assert_eq!(ctx["max"], "3");
+}
+
+#[test]
+fn diag_by_ratio_off_increment_is_rounding_violation() {
+    let issues = issues_with_codes(
+        "^XA^BY2,2.55,50^XZ",
+        None,
+        &[codes::OUT_OF_RANGE, codes::ROUNDING_VIOLATION],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].id, codes::ROUNDING_VIOLATION);
+    let ctx = issues[0].context.as_ref().expect("context");
+    assert_eq!(ctx["value"], "2.55");
+    assert_eq!(ctx["multiple"], "0.1");
+}
+
 // ─── ZPL1401: Profile Constraint ─────────────────────────────────────────────
 
 #[test]
@@ -474,6 +679,63 @@ fn diag_profile_constraint_pw_respects_mu_desired_dpi_conversion() {
     );
 }
 
+fn speed_profile() -> zpl_toolchain_profile::Profile {
+    common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"speed_range":{"min":2,"max":6}}"#,
+    )
+}
+
+#[test]
+fn diag_profile_constraint_pr_numeric_speed_within_range() {
+    let tables = &*common::TABLES;
+    let profile = speed_profile();
+    let result = parse_with_tables("^XA^PR4,6,2^XZ", Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::PROFILE_CONSTRAINT),
+        "^PR4 is within speed_range 2-6: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn diag_profile_constraint_pr_numeric_speed_out_of_range() {
+    let tables = &*common::TABLES;
+    let profile = speed_profile();
+    let result = parse_with_tables("^XA^PR12^XZ", Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+    let d = find_diag(&vr.issues, codes::PROFILE_CONSTRAINT);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^PR");
+    assert_eq!(ctx.get("field").unwrap(), "speed_range");
+    assert_eq!(ctx.get("min").unwrap(), "2");
+    assert_eq!(ctx.get("max").unwrap(), "6");
+    assert_eq!(ctx.get("limit").unwrap(), "6");
+    assert_eq!(ctx.get("actual").unwrap(), "12");
+}
+
+#[test]
+fn diag_profile_constraint_pr_letter_speed_maps_to_ips() {
+    let tables = &*common::TABLES;
+    let profile = speed_profile();
+    // E = 8 ips, above the profile maximum of 6.
+    let result = parse_with_tables("^XA^PRE^XZ", Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+    let d = find_diag(&vr.issues, codes::PROFILE_CONSTRAINT);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("actual").unwrap(), "8");
+    assert_eq!(ctx.get("max").unwrap(), "6");
+
+    // C = 4 ips is fine.
+    let result = parse_with_tables("^XA^PRC^XZ", Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::PROFILE_CONSTRAINT),
+        "^PRC (4 ips) is within range: {:?}",
+        vr.issues,
+    );
+}
+
 // ─── ZPL1501/1502: Required Missing/Empty ────────────────────────────────────
 
 #[test]
@@ -615,6 +877,19 @@ fn diag_zpl2103_order_violation() {
     );
 }
 
+#[test]
+fn diag_zpl2103_order_violation_points_at_anchor() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO10,10^FDtest^FH^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let diag = common::find_diag(&vr.issues, codes::ORDER_BEFORE);
+    assert_eq!(diag.related.len(), 1, "{:?}", diag);
+    let related = &diag.related[0];
+    assert_eq!(&input[related.span.start..related.span.end], "^FDtest");
+    assert_eq!(related.message, "^FD appears here");
+}
+
 #[test]
 fn diag_zpl2103_correct_order_passes() {
     let tables = &*common::TABLES;
@@ -868,6 +1143,38 @@ fn diag_zpl2205_inside_xa_pw_ll_do_not_warn() {
     );
 }
 
+// ─── ZPL2206: Header Command After Field ─────────────────────────────────────
+
+#[test]
+fn diag_zpl2206_header_command_before_first_field_passes() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^MCY^FO10,10^FVA^FS^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        !vr.issues
+            .iter()
+            .any(|d| d.id == codes::HEADER_COMMAND_AFTER_FIELD),
+        "^MC before the first field should not emit ZPL2206: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn diag_zpl2206_header_command_after_first_field_warns() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^FO10,10^FVA^FS^MCY^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let diag = vr
+        .issues
+        .iter()
+        .find(|d| d.id == codes::HEADER_COMMAND_AFTER_FIELD)
+        .unwrap_or_else(|| panic!("^MC after ^FO should emit ZPL2206: {:?}", vr.issues));
+    assert_eq!(diag.severity, Severity::Warn);
+    let ctx = diag.context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^MC");
+    assert_eq!(ctx.get("field").unwrap(), "^FO");
+}
+
 #[test]
 fn diag_zpl2205_inside_xa_format_setup_commands_do_not_warn() {
     let tables = &*common::TABLES;
@@ -945,6 +1252,60 @@ fn diag_zpl2205_inside_flag_resets_after_xz() {
     );
 }
 
+#[test]
+fn diag_zpl2205_tilde_print_control_inside_xa_warns() {
+    let tables = &*common::TABLES;
+    for code in ["~PH", "~PP", "~PS"] {
+        let input = format!("^XA^FO10,20^FDok^FS{code}^XZ");
+        let result = parse_with_tables(&input, Some(tables));
+        let vr = validate::validate(&result.ast, tables);
+        assert!(
+            vr.issues
+                .iter()
+                .any(|d| d.id == codes::HOST_COMMAND_IN_LABEL
+                    && d.context
+                        .as_ref()
+                        .and_then(|c| c.get("command"))
+                        .map(String::as_str)
+                        == Some(code)),
+            "{code} inside label should emit ZPL2205: {:?}",
+            vr.issues,
+        );
+    }
+}
+
+#[test]
+fn diag_zpl2205_tilde_print_control_outside_xa_passes() {
+    let tables = &*common::TABLES;
+    for code in ["~PH", "~PP", "~PS"] {
+        let input = format!("{code}^XA^FO10,20^FDok^FS^XZ{code}");
+        let result = parse_with_tables(&input, Some(tables));
+        let vr = validate::validate(&result.ast, tables);
+        assert!(
+            !vr.issues
+                .iter()
+                .any(|d| d.id == codes::HOST_COMMAND_IN_LABEL),
+            "{code} outside label should not emit ZPL2205: {:?}",
+            vr.issues,
+        );
+    }
+}
+
+#[test]
+fn diag_zpl2205_caret_print_control_inside_xa_passes() {
+    let tables = &*common::TABLES;
+    // ^PH/^PP are format commands that act after the current label prints.
+    let result = parse_with_tables("^XA^FO10,20^FDok^FS^PH^PP^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        !vr.issues
+            .iter()
+            .any(|d| d.id == codes::HOST_COMMAND_IN_LABEL),
+        "^PH/^PP inside label should not emit ZPL2205: {:?}",
+        vr.issues,
+    );
+}
+
 // ─── ZPL2301: Duplicate Field Number ─────────────────────────────────────────
 
 #[test]
@@ -965,6 +1326,24 @@ fn diag_zpl2301_duplicate_fn() {
     );
 }
 
+#[test]
+fn diag_zpl2301_duplicate_fn_relates_first_definition() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO10,10^FN1^FDFirst^FS^FO10,50^FN1^FDSecond^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let diag = common::find_diag(&vr.issues, codes::DUPLICATE_FIELD_NUMBER);
+    let span = diag.span.expect("span");
+    assert_eq!(span.start, input.rfind("^FN1").unwrap());
+    assert_eq!(diag.related.len(), 1, "{:?}", diag);
+    assert_eq!(diag.related[0].span.start, input.find("^FN1").unwrap());
+
+    // Related spans survive a JSON round trip.
+    let json = serde_json::to_string(diag).unwrap();
+    let back: zpl_toolchain_diagnostics::Diagnostic = serde_json::from_str(&json).unwrap();
+    assert_eq!(&back, diag);
+}
+
 #[test]
 fn diag_zpl2301_unique_fn_passes() {
     let tables = &*common::TABLES;
@@ -1062,6 +1441,31 @@ fn diag_zpl2302_exact_boundary_is_currently_allowed() {
     );
 }
 
+#[test]
+fn diag_zpl2302_jmb_halves_profile_bounds() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_800x1200();
+    // x=500 fits the 800-dot page at full density, but ^JMB doubles the dot
+    // size so the page is only 400 format dots wide.
+    let has_bounds_issue = |input: &str| {
+        let result = parse_with_tables(input, Some(tables));
+        let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+        vr.issues
+            .iter()
+            .any(|d| d.id == codes::POSITION_OUT_OF_BOUNDS)
+    };
+    assert!(!has_bounds_issue("^XA^FO500,100^FDtest^FS^XZ"));
+    assert!(has_bounds_issue("^XA^JMB^FO500,100^FDtest^FS^XZ"));
+    assert!(!has_bounds_issue("^XA^JMB^FO300,100^FDtest^FS^XZ"));
+    // ^JMA restores full density; ^JM persists across labels until then.
+    assert!(has_bounds_issue(
+        "^XA^JMB^FO10,10^FDa^FS^XZ^XA^FO500,100^FDtest^FS^XZ"
+    ));
+    assert!(!has_bounds_issue(
+        "^XA^JMB^FO10,10^FDa^FS^XZ^XA^JMA^FO500,100^FDtest^FS^XZ"
+    ));
+}
+
 #[test]
 fn diag_zpl2302_pw_overrides_profile() {
     let tables = &*common::TABLES;
@@ -1095,76 +1499,348 @@ fn diag_zpl2302_lh_offsets_field_position() {
 }
 
 #[test]
-fn diag_zpl2302_lh_resets_per_label() {
+fn diag_zpl2302_lh_shifts_field_out_of_bounds() {
     let tables = &*common::TABLES;
     let profile = common::profile_800x1200();
-    // Label 1: effective x=900 -> out of bounds.
-    // Label 2: ^LH is reset; x=200 -> in bounds.
-    let result = parse_with_tables(
-        "^XA^LH700,0^FO200,50^FDL1^FS^XZ^XA^FO200,50^FDL2^FS^XZ",
-        Some(tables),
-    );
-    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
-    let out_of_bounds_count = vr
-        .issues
-        .iter()
-        .filter(|d| d.id == codes::POSITION_OUT_OF_BOUNDS)
-        .count();
-    assert_eq!(
-        out_of_bounds_count, 1,
-        "^LH should apply per-label only: {:?}",
-        vr.issues
-    );
+    let bounds = |zpl: &str| {
+        let result = parse_with_tables(zpl, Some(tables));
+        validate_with_profile(&result.ast, tables, Some(&profile))
+            .issues
+            .into_iter()
+            .filter(|d| d.id == codes::POSITION_OUT_OF_BOUNDS)
+            .collect::<Vec<_>>()
+    };
+
+    // Without ^LH both ^FO and ^FT fields fit inside 800x1200.
+    assert!(bounds("^XA^FO780,1180^FDa^FS^FT780,1180^FDb^FS^XZ").is_empty());
+
+    // ^LH50,50 pushes both axes of both fields past the page.
+    let shifted = bounds("^XA^LH50,50^FO780,1180^FDa^FS^FT780,1180^FDb^FS^XZ");
+    assert_eq!(shifted.len(), 4, "{shifted:?}");
+    let fo_x = &shifted[0];
+    let ctx = fo_x.context.as_ref().expect("context");
+    assert_eq!(ctx["axis"], "x");
+    assert_eq!(ctx["value"], "830");
+    assert_eq!(ctx["label_home"], "50");
+    assert!(fo_x.message.contains("includes ^LH offset 50"), "{fo_x:?}");
 }
 
 #[test]
-fn diag_zpl2302_lh_with_mu_inches_conversion() {
+fn diag_zpl2302_lh_keeps_field_in_bounds() {
     let tables = &*common::TABLES;
     let profile = common::profile_800x1200();
-    // ^MUI,203,203 => inches to dots. ^LH1,0 => x home = 203 dots.
-    // ^FO598,0 => effective x = 801 > 800.
-    let result = parse_with_tables("^XA^MUI,203,203^LH1,0^FO598,0^FDx^FS^XZ", Some(tables));
+    // 740 + 50 = 790 stays inside the 800-dot width; no ^LH context when
+    // the field is in bounds, and none for an axis with a zero home offset.
+    let result = parse_with_tables("^XA^LH50,0^FO740,1190^FDx^FS^XZ", Some(tables));
     let vr = validate_with_profile(&result.ast, tables, Some(&profile));
-    assert!(
-        vr.issues
-            .iter()
-            .any(|d| d.id == codes::POSITION_OUT_OF_BOUNDS),
-        "^LH should be normalized using active ^MU units: {:?}",
-        vr.issues,
+    let issues: Vec<_> = vr
+        .issues
+        .iter()
+        .filter(|d| d.id == codes::POSITION_OUT_OF_BOUNDS)
+        .collect();
+    assert!(issues.is_empty(), "{issues:?}");
+
+    let result = parse_with_tables("^XA^LH50,0^FO740,1250^FDx^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+    let y = vr
+        .issues
+        .iter()
+        .find(|d| d.id == codes::POSITION_OUT_OF_BOUNDS)
+        .expect("y out of bounds");
+    let ctx = y.context.as_ref().expect("context");
+    assert_eq!(ctx["axis"], "y");
+    assert!(!ctx.contains_key("label_home"));
+}
+
+#[test]
+fn diag_zpl2319_inverted_fw_rotation_reports_net_orientation() {
+    let issues = issues_with_codes(
+        "^XA^POI^FWR^FO50,50^FDx^FS^XZ",
+        None,
+        &[codes::FIELD_ORIENTATION_COMBINED],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    let d = &issues[0];
+    assert_eq!(d.severity, Severity::Info);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx["command"], "^FO");
+    assert_eq!(ctx["field_orientation"], "R");
+    assert_eq!(ctx["print_orientation"], "I");
+    assert_eq!(ctx["source"], "^POI+^FW");
+    assert_eq!(ctx["net_rotation"], "270");
+}
+
+#[test]
+fn diag_zpl2319_single_rotation_source_is_silent() {
+    for zpl in [
+        "^XA^POI^FO50,50^FDx^FS^XZ",
+        "^XA^FWR^FO50,50^FDx^FS^XZ",
+        "^XA^FO50,50^A0R,30^FDx^FS^XZ",
+        "^XA^FWR^FO50,50^A0R,30^FDx^FS^XZ",
+    ] {
+        let issues = issues_with_codes(zpl, None, &[codes::FIELD_ORIENTATION_COMBINED]);
+        assert!(issues.is_empty(), "{zpl}: {issues:?}");
+    }
+
+    // A per-field rotation overriding ^FW is reported with its own rotation.
+    let issues = issues_with_codes(
+        "^XA^FWR^FO50,50^A0N,30^FDx^FS^XZ",
+        None,
+        &[codes::FIELD_ORIENTATION_COMBINED],
     );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    let ctx = issues[0].context.as_ref().expect("context");
+    assert_eq!(ctx["source"], "^FW+field");
+    assert_eq!(ctx["net_rotation"], "0");
 }
 
-// ─── ZPL2303: Unknown Font ───────────────────────────────────────────────────
+#[test]
+fn diag_zpl2302_inverted_field_near_bottom_edge_stays_in_bounds() {
+    // 1190 is inside ^LL1200; inverted, the origin prints 10 dots from the top.
+    for po in ["^PON", "^POI"] {
+        let zpl = format!("^XA{po}^LL1200^FO10,1190^FDx^FS^XZ");
+        let issues = issues_with_codes(
+            &zpl,
+            Some(&common::profile_800x1200()),
+            &[codes::POSITION_OUT_OF_BOUNDS],
+        );
+        assert!(issues.is_empty(), "{po}: {issues:?}");
+    }
+}
 
 #[test]
-fn diag_zpl2303_unknown_font() {
-    let tables = &*common::TABLES;
-    // ^A with font 'x' (lowercase, not built-in A-Z or 0-9)
-    let result = parse_with_tables("^XA^FO10,10^Ax,30,30^FDtest^FS^XZ", Some(tables));
-    let vr = validate::validate(&result.ast, tables);
+fn diag_zpl2302_inverted_field_past_bottom_edge_reports_flip() {
+    let normal = issues_with_codes(
+        "^XA^PON^LL1200^FO10,1250^FDx^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(normal.len(), 1, "{normal:?}");
+    let ctx = normal[0].context.as_ref().expect("context");
+    assert!(!ctx.contains_key("orientation"));
+    assert!(!normal[0].message.contains("^POI"));
+
+    // ^PO applies to the whole format, even when it follows the field.
+    let inverted = issues_with_codes(
+        "^XA^LL1200^FO10,1250^FDx^FS^POI^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(inverted.len(), 1, "{inverted:?}");
+    let d = &inverted[0];
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx["axis"], "y");
+    assert_eq!(ctx["value"], "1250");
+    assert_eq!(ctx["orientation"], "I");
+    assert_eq!(ctx["printed_at"], "-50");
     assert!(
-        vr.issues.iter().any(|d| d.id == codes::UNKNOWN_FONT),
-        "unknown font should emit ZPL2303: {:?}",
-        vr.issues,
+        d.message.contains("50 dots beyond the top edge"),
+        "{}",
+        d.message
     );
 }
 
 #[test]
-fn diag_zpl2303_builtin_font_passes() {
-    let tables = &*common::TABLES;
-    // ^A with font 'A' (built-in)
-    let result = parse_with_tables("^XA^FO10,10^AA,30,30^FDtest^FS^XZ", Some(tables));
-    let vr = validate::validate(&result.ast, tables);
-    assert!(
-        !vr.issues.iter().any(|d| d.id == codes::UNKNOWN_FONT),
-        "built-in font should not emit ZPL2303: {:?}",
-        vr.issues,
-    );
+fn diag_zpl2302_mirroring_does_not_change_which_positions_fit() {
+    // Mirroring maps the label onto itself: 10 is inside ^PW800 either way
+    // (mirrored, the origin prints 10 dots from the right), and 850 is out
+    // either way (see the next test).
+    for pm in ["^PMN", "^PMY"] {
+        let zpl = format!("^XA{pm}^PW800^FO10,10^FDx^FS^XZ");
+        let issues = issues_with_codes(
+            &zpl,
+            Some(&common::profile_800x1200()),
+            &[codes::POSITION_OUT_OF_BOUNDS],
+        );
+        assert!(issues.is_empty(), "{pm}: {issues:?}");
+    }
 }
 
 #[test]
-fn diag_zpl2303_cw_loaded_font_passes() {
-    let tables = &*common::TABLES;
+fn diag_zpl2302_mirrored_field_past_right_edge_reports_flip() {
+    let normal = issues_with_codes(
+        "^XA^PMN^PW800^FO850,10^FDx^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(normal.len(), 1, "{normal:?}");
+    let ctx = normal[0].context.as_ref().expect("context");
+    assert!(!ctx.contains_key("mirror"));
+    assert!(!normal[0].message.contains("^PMY"));
+
+    let mirrored = issues_with_codes(
+        "^XA^PMY^PW800^FO850,10^FDx^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(mirrored.len(), 1, "{mirrored:?}");
+    let d = &mirrored[0];
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx["axis"], "x");
+    assert_eq!(ctx["mirror"], "Y");
+    assert_eq!(ctx["printed_at"], "-50");
+    assert!(
+        d.message
+            .contains("mirrored by ^PMY, it prints 50 dots beyond the left edge"),
+        "{}",
+        d.message
+    );
+
+    // ^PMY only flips x, and together with ^POI the two flips cancel out.
+    let both = issues_with_codes(
+        "^XA^PMY^POI^PW800^FO850,10^FDx^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(both.len(), 1, "{both:?}");
+    let ctx = both[0].context.as_ref().expect("context");
+    assert_eq!(ctx["printed_at"], "850");
+    assert!(
+        issues_with_codes(
+            "^XA^PMY^LL1200^FO10,1190^FDx^FS^XZ",
+            Some(&common::profile_800x1200()),
+            &[codes::POSITION_OUT_OF_BOUNDS]
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn diag_zpl2302_circle_that_fits_is_ok() {
+    // 700 + 100 ends exactly on the 800-dot right edge.
+    let issues = issues_with_codes(
+        "^XA^FO700,10^GC100,2,B^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert!(issues.is_empty(), "{issues:?}");
+}
+
+#[test]
+fn diag_zpl2302_circle_diameter_past_right_edge() {
+    let issues = issues_with_codes(
+        "^XA^FO750,10^GC100,2,B^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    let d = &issues[0];
+    assert_eq!(d.severity, Severity::Warn);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx["command"], "^GC");
+    assert_eq!(ctx["axis"], "x");
+    assert_eq!(ctx["origin"], "750");
+    assert_eq!(ctx["extent"], "100");
+    assert_eq!(ctx["value"], "850");
+    assert_eq!(ctx["limit"], "800");
+}
+
+#[test]
+fn diag_zpl2302_ellipse_and_diagonal_extents() {
+    let issues = issues_with_codes(
+        "^XA^FO10,1150^GE200,100,2^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    let ctx = issues[0].context.as_ref().expect("context");
+    assert_eq!(ctx["command"], "^GE");
+    assert_eq!(ctx["axis"], "y");
+    assert_eq!(ctx["value"], "1250");
+
+    let issues = issues_with_codes(
+        "^XA^FO700,10^GD200,50,2^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::POSITION_OUT_OF_BOUNDS],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(
+        issues[0].context.as_ref().expect("context")["command"],
+        "^GD"
+    );
+
+    // ^FT places the graphic's bottom edge at y, so it grows upward.
+    assert!(
+        issues_with_codes(
+            "^XA^FT10,1190^GE200,100,2^FS^XZ",
+            Some(&common::profile_800x1200()),
+            &[codes::POSITION_OUT_OF_BOUNDS]
+        )
+        .is_empty()
+    );
+}
+
+#[test]
+fn diag_zpl2302_lh_resets_per_label() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_800x1200();
+    // Label 1: effective x=900 -> out of bounds.
+    // Label 2: ^LH is reset; x=200 -> in bounds.
+    let result = parse_with_tables(
+        "^XA^LH700,0^FO200,50^FDL1^FS^XZ^XA^FO200,50^FDL2^FS^XZ",
+        Some(tables),
+    );
+    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+    let out_of_bounds_count = vr
+        .issues
+        .iter()
+        .filter(|d| d.id == codes::POSITION_OUT_OF_BOUNDS)
+        .count();
+    assert_eq!(
+        out_of_bounds_count, 1,
+        "^LH should apply per-label only: {:?}",
+        vr.issues
+    );
+}
+
+#[test]
+fn diag_zpl2302_lh_with_mu_inches_conversion() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_800x1200();
+    // ^MUI,203,203 => inches to dots. ^LH1,0 => x home = 203 dots.
+    // ^FO598,0 => effective x = 801 > 800.
+    let result = parse_with_tables("^XA^MUI,203,203^LH1,0^FO598,0^FDx^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+    assert!(
+        vr.issues
+            .iter()
+            .any(|d| d.id == codes::POSITION_OUT_OF_BOUNDS),
+        "^LH should be normalized using active ^MU units: {:?}",
+        vr.issues,
+    );
+}
+
+// ─── ZPL2303: Unknown Font ───────────────────────────────────────────────────
+
+#[test]
+fn diag_zpl2303_unknown_font() {
+    let tables = &*common::TABLES;
+    // ^A with font 'x' (lowercase, not built-in A-Z or 0-9)
+    let result = parse_with_tables("^XA^FO10,10^Ax,30,30^FDtest^FS^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        vr.issues.iter().any(|d| d.id == codes::UNKNOWN_FONT),
+        "unknown font should emit ZPL2303: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn diag_zpl2303_builtin_font_passes() {
+    let tables = &*common::TABLES;
+    // ^A with font 'A' (built-in)
+    let result = parse_with_tables("^XA^FO10,10^AA,30,30^FDtest^FS^XZ", Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::UNKNOWN_FONT),
+        "built-in font should not emit ZPL2303: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn diag_zpl2303_cw_loaded_font_passes() {
+    let tables = &*common::TABLES;
     // ^CW loads font 'x', then ^A uses it — should NOT trigger
     let result = parse_with_tables(
         "^XA^CWx,E:ARIAL.TTF^FO10,10^Ax,30,30^FDtest^FS^XZ",
@@ -1938,6 +2614,61 @@ fn diag_zpl2311_text_within_bounds_no_diagnostic() {
     );
 }
 
+#[test]
+fn diag_zpl2311_rotated_field_swaps_extents() {
+    let tables = &*common::TABLES;
+    // Label 400×1200. Eight 30-dot chars at x=300 run 240 dots: normal text
+    // overflows the right edge, but rotated 90° it is 30 wide and 240 tall.
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"page":{"width_dots":400,"height_dots":1200}}"#,
+    );
+    let overflow = |zpl: &str| {
+        let result = parse_with_tables(zpl, Some(tables));
+        let vr = validate_with_profile(&result.ast, tables, Some(&profile));
+        vr.issues
+            .into_iter()
+            .find(|d| d.id == codes::OBJECT_BOUNDS_OVERFLOW)
+    };
+
+    let normal = overflow("^XA^PW400^LL1200^CF0,30,30^FO300,100^A0N^FDABCDEFGH^FS^XZ")
+        .expect("unrotated text should overflow the right edge");
+    let ctx = normal.context.as_ref().expect("context");
+    assert_eq!(ctx.get("orientation").unwrap(), "N");
+
+    assert!(
+        overflow("^XA^PW400^LL1200^CF0,30,30^FO300,100^A0R^FDABCDEFGH^FS^XZ").is_none(),
+        "^A0R text should fit once width and height swap"
+    );
+    assert!(
+        overflow("^XA^PW400^LL1200^CF0,30,30^FWB^FO300,100^FDABCDEFGH^FS^XZ").is_none(),
+        "^FWB default rotation should apply to fields without ^A"
+    );
+    let explicit = overflow("^XA^PW400^LL1200^CF0,30,30^FWR^FO300,100^A0N^FDABCDEFGH^FS^XZ")
+        .expect("explicit ^A0N overrides the ^FW default");
+    assert_eq!(
+        explicit
+            .context
+            .as_ref()
+            .unwrap()
+            .get("orientation")
+            .unwrap(),
+        "N"
+    );
+
+    // Rotated near the bottom edge, the swapped height now overflows.
+    let rotated = overflow("^XA^PW400^LL1200^CF0,30,30^FO10,1000^A0R^FDABCDEFGH^FS^XZ")
+        .expect("rotated text should overflow the bottom edge");
+    assert_eq!(
+        rotated
+            .context
+            .as_ref()
+            .unwrap()
+            .get("orientation")
+            .unwrap(),
+        "R"
+    );
+}
+
 #[test]
 fn diag_zpl2311_no_bounds_skips_check() {
     let tables = &*common::TABLES;
@@ -2016,6 +2747,457 @@ fn diag_zpl2310_profile_no_page_no_diagnostic() {
     );
 }
 
+// ─── ZPL2312: Stored Format Field Mismatch ───────────────────────────────────
+
+const STORED_FORMAT: &str = "^XA^DFR:SHIP.ZPL^FS^FO20,20^A0N,30,30^FN1^FS^FO20,60^A0N,30,30^FN2^FS^FO20,100^A0N,30,30^FN3^FDdefault^FS^XZ";
+
+#[test]
+fn diag_zpl2312_fully_supplied_recall_ok() {
+    let diags = issues_with_codes(
+        &format!("{STORED_FORMAT}\n^XA^XFR:SHIP.ZPL^FS^FN1^FDACME^FS^FN2^FD123 Main St^FS^XZ"),
+        None,
+        &[codes::STORED_FORMAT_FIELD_MISMATCH],
+    );
+    assert!(diags.is_empty(), "all placeholders supplied: {:?}", diags);
+}
+
+#[test]
+fn diag_zpl2312_unfilled_placeholder_warns() {
+    // ^FN2 is never supplied; ^FN3 has default data in the stored format.
+    let diags = issues_with_codes(
+        &format!("{STORED_FORMAT}\n^XA^XFR:SHIP.ZPL^FS^FN1^FDACME^FS^XZ"),
+        None,
+        &[codes::STORED_FORMAT_FIELD_MISMATCH],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    let d = &diags[0];
+    assert_eq!(d.severity, Severity::Warn);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("format").unwrap(), "SHIP");
+    assert_eq!(ctx.get("field_number").unwrap(), "2");
+    assert_eq!(ctx.get("kind").unwrap(), "unfilled");
+}
+
+#[test]
+fn diag_zpl2312_undefined_field_number_warns() {
+    let diags = issues_with_codes(
+        &format!(
+            "{STORED_FORMAT}\n^XA^XFE:ship.zpl^FS^FN1^FDACME^FS^FN2^FDMain^FS^FN9^FDextra^FS^XZ"
+        ),
+        None,
+        &[codes::STORED_FORMAT_FIELD_MISMATCH],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("field_number").unwrap(), "9");
+    assert_eq!(ctx.get("kind").unwrap(), "undefined");
+}
+
+#[test]
+fn diag_zpl2312_recall_of_unknown_format_is_not_checked() {
+    let diags = issues_with_codes(
+        &format!("{STORED_FORMAT}\n^XA^XFR:OTHER.ZPL^FS^FN7^FDx^FS^XZ"),
+        None,
+        &[codes::STORED_FORMAT_FIELD_MISMATCH],
+    );
+    assert!(
+        diags.is_empty(),
+        "format not saved in this job: {:?}",
+        diags
+    );
+}
+
+// ─── ZPL2313: Field Data Conflict ────────────────────────────────────────────
+
+#[test]
+fn diag_zpl2313_fd_only_ok() {
+    let diags = issues_with_codes(
+        "^XA^FO50,50^A0N,30,30^FDHello^FS^XZ",
+        None,
+        &[codes::FIELD_DATA_CONFLICT],
+    );
+    assert!(diags.is_empty(), "^FD-only field: {:?}", diags);
+}
+
+#[test]
+fn diag_zpl2313_fv_with_fn_ok() {
+    let diags = issues_with_codes(
+        "^XA^FO50,50^A0N,30,30^FN1^FVHello^FS^XZ",
+        None,
+        &[codes::FIELD_DATA_CONFLICT],
+    );
+    assert!(diags.is_empty(), "^FV with ^FN: {:?}", diags);
+}
+
+#[test]
+fn diag_zpl2313_fd_and_fv_in_one_field_warns() {
+    let input = "^XA^FO50,50^A0N,30,30^FN1^FDHello^FVWorld^FS^XZ";
+    let diags = issues_with_codes(input, None, &[codes::FIELD_DATA_CONFLICT]);
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    let d = &diags[0];
+    assert_eq!(d.severity, Severity::Warn);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("kind").unwrap(), "fd_and_fv");
+    assert_eq!(ctx.get("command").unwrap(), "^FV");
+    let fd = input.find("^FD").unwrap();
+    let fv = input.find("^FV").unwrap();
+    assert_eq!(ctx.get("fd_offset").unwrap(), &fd.to_string());
+    assert_eq!(ctx.get("fv_offset").unwrap(), &fv.to_string());
+    // The span covers both commands.
+    let span = d.span.expect("span");
+    assert_eq!(span.start, fd);
+    assert!(span.end > fv, "span should reach ^FV: {:?}", span);
+}
+
+#[test]
+fn diag_zpl2313_fv_without_fn_is_info() {
+    let diags = issues_with_codes(
+        "^XA^FO50,50^A0N,30,30^FVHello^FS^XZ",
+        None,
+        &[codes::FIELD_DATA_CONFLICT],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert_eq!(diags[0].severity, Severity::Info);
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("kind").unwrap(), "fv_without_fn");
+}
+
+// ─── ZPL2314: Suspicious Font Size ───────────────────────────────────────────
+
+#[test]
+fn diag_zpl2314_normal_font_size_ok() {
+    let diags = issues_with_codes(
+        "^XA^FO50,50^A0N,30,30^FDHello^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::SUSPICIOUS_FONT_SIZE, codes::OUT_OF_RANGE],
+    );
+    assert!(diags.is_empty(), "normal font size: {:?}", diags);
+}
+
+#[test]
+fn diag_zpl2314_zero_height_with_width_warns() {
+    for input in [
+        "^XA^FO50,50^A0N,0,30^FDHello^FS^XZ",
+        "^XA^FO50,50^A@N,0,30,E:ARIAL.TTF^FDHello^FS^XZ",
+    ] {
+        let diags = issues_with_codes(
+            input,
+            Some(&common::profile_800x1200()),
+            &[codes::SUSPICIOUS_FONT_SIZE, codes::OUT_OF_RANGE],
+        );
+        assert_eq!(diags.len(), 1, "{input}: {:?}", diags);
+        let d = &diags[0];
+        assert_eq!(d.id, codes::SUSPICIOUS_FONT_SIZE);
+        assert_eq!(d.severity, Severity::Warn);
+        let ctx = d.context.as_ref().expect("context");
+        assert_eq!(ctx.get("kind").unwrap(), "zero_dimension");
+        assert_eq!(ctx.get("height").unwrap(), "0");
+        assert_eq!(ctx.get("width").unwrap(), "30");
+    }
+}
+
+#[test]
+fn diag_zpl2314_oversized_font_warns() {
+    let diags = issues_with_codes(
+        "^XA^FO50,50^A0N,5000,5000^FDHello^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::SUSPICIOUS_FONT_SIZE, codes::OUT_OF_RANGE],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("kind").unwrap(), "oversize");
+    assert_eq!(ctx.get("limit").unwrap(), "1200");
+
+    let diags = issues_with_codes(
+        "^XA^FO50,50^A@N,5000,5000,E:ARIAL.TTF^FDHello^FS^XZ",
+        Some(&common::profile_800x1200()),
+        &[codes::SUSPICIOUS_FONT_SIZE, codes::OUT_OF_RANGE],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+}
+
+#[test]
+fn diag_zpl2314_oversize_skips_bitmap_fonts() {
+    // Bitmap fonts magnify in whole steps up to a fixed limit, so a large
+    // request is capped by the printer rather than printed off the label.
+    for input in [
+        "^XA^FO50,50^ADN,5000,5000^FDHello^FS^XZ",
+        "^XA^FO50,50^A@N,5000,5000,E:CUSTOM.FNT^FDHello^FS^XZ",
+    ] {
+        let diags = issues_with_codes(
+            input,
+            Some(&common::profile_800x1200()),
+            &[codes::SUSPICIOUS_FONT_SIZE, codes::OUT_OF_RANGE],
+        );
+        assert!(diags.is_empty(), "{input}: {:?}", diags);
+    }
+}
+
+// ─── ZPL2315: Invalid Resource Name ──────────────────────────────────────────
+
+#[test]
+fn diag_zpl2315_valid_names_pass() {
+    let diags = issues_with_codes(
+        "~DGR:LOGO.GRF,4,1,FFFFFFFF\n^XA^FO10,10^XGR:LOGO.GRF,1,1^FS^XZ\n^XA^DFE:SHIPPING.ZPL^FS^XZ",
+        None,
+        &[codes::INVALID_RESOURCE_NAME],
+    );
+    assert!(diags.is_empty(), "{:?}", diags);
+}
+
+#[test]
+fn diag_zpl2315_over_length_name_warns() {
+    let diags = issues_with_codes(
+        "^XA^FO10,10^XGR:COMPANYLOGO.GRF,1,1^FS^XZ",
+        None,
+        &[codes::INVALID_RESOURCE_NAME],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert!(matches!(diags[0].severity, Severity::Warn));
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^XG");
+    assert_eq!(ctx.get("kind").unwrap(), "length");
+    assert_eq!(ctx.get("limit").unwrap(), "8");
+}
+
+#[test]
+fn diag_zpl2315_bad_drive_warns() {
+    let diags = issues_with_codes(
+        "~DGZ:LOGO.GRF,4,1,FFFFFFFF",
+        None,
+        &[codes::INVALID_RESOURCE_NAME],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "~DG");
+    assert_eq!(ctx.get("kind").unwrap(), "drive");
+    assert_eq!(ctx.get("path").unwrap(), "Z:LOGO.GRF");
+}
+
+#[test]
+fn diag_zpl2315_wrong_extension_warns() {
+    let diags = issues_with_codes(
+        "~DGR:LOGO.BMP,4,1,FFFFFFFF\n^XA^DFR:LABEL.GRF^FS^XZ",
+        None,
+        &[codes::INVALID_RESOURCE_NAME],
+    );
+    let kinds: Vec<(&str, &str)> = diags
+        .iter()
+        .map(|d| {
+            let ctx = d.context.as_ref().expect("context");
+            (ctx["command"].as_str(), ctx["kind"].as_str())
+        })
+        .collect();
+    assert_eq!(kinds, [("~DG", "extension"), ("^DF", "extension")]);
+}
+
+// ─── ZPL2321: ^ID Object Delete ──────────────────────────────────────────────
+
+#[test]
+fn diag_zpl2321_specific_object_delete_is_info() {
+    let diags = issues_with_codes(
+        "^XA^IDE:LOGO.PNG^XZ\n^XA^IDR:SHIP^XZ",
+        None,
+        &[codes::OBJECT_DELETE],
+    );
+    assert_eq!(diags.len(), 2, "{:?}", diags);
+    assert!(diags.iter().all(|d| matches!(d.severity, Severity::Info)));
+    let patterns: Vec<&str> = diags
+        .iter()
+        .map(|d| d.context.as_ref().expect("context")["pattern"].as_str())
+        .collect();
+    assert_eq!(patterns, ["E:LOGO.PNG", "R:SHIP.GRF"]);
+}
+
+#[test]
+fn diag_zpl2321_wildcard_delete_warns() {
+    let diags = issues_with_codes(
+        "^XA^IDR:*.GRF^XZ\n^XA^ID*.*^XZ",
+        None,
+        &[codes::OBJECT_DELETE],
+    );
+    assert_eq!(diags.len(), 2, "{:?}", diags);
+    assert!(diags.iter().all(|d| matches!(d.severity, Severity::Warn)));
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^ID");
+    assert_eq!(ctx.get("pattern").unwrap(), "R:*.GRF");
+    assert_eq!(
+        diags[1].context.as_ref().expect("context")["pattern"],
+        "R:*.*"
+    );
+}
+
+// ─── ZPL2322: ^FC Field Clock ────────────────────────────────────────────────
+
+#[test]
+fn fc_gated_without_rtc() {
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"features":{"rtc":false}}"#,
+    );
+    let issues = issues_with_codes(
+        "^XA^FO10,10^FC%^FD%m/%d/%y^FS^XZ",
+        Some(&profile),
+        &[codes::PRINTER_GATE],
+    );
+    let gate = find_diag(&issues, codes::PRINTER_GATE);
+    assert_eq!(gate.context.as_ref().unwrap()["command"], "^FC");
+    assert_eq!(gate.context.as_ref().unwrap()["gate"], "rtc");
+}
+
+#[test]
+fn diag_zpl2322_used_clock_indicator_passes() {
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"features":{"rtc":true}}"#,
+    );
+    let issues = issues_with_codes(
+        "^XA^FO10,10^FC%^FDShipped %m/%d/%y^FS\n^FO10,50^FC#,{^FDAt {H:{M^FS^XZ",
+        Some(&profile),
+        &[codes::FIELD_CLOCK_UNUSED, codes::PRINTER_GATE],
+    );
+    assert!(issues.is_empty(), "{:?}", issues);
+}
+
+#[test]
+fn diag_zpl2322_unused_clock_indicator_warns() {
+    let unused = issues_with_codes(
+        "^XA^FO10,10^FC%^FDShipped^FS^FO10,50^FD%m^FS^XZ",
+        None,
+        &[codes::FIELD_CLOCK_UNUSED],
+    );
+    assert_eq!(unused.len(), 1, "{:?}", unused);
+    assert!(matches!(unused[0].severity, Severity::Warn));
+    let ctx = unused[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^FC");
+    assert_eq!(ctx.get("indicators").unwrap(), "%");
+}
+
+// ─── ZPL2323/2324: ^MC Map Clear ─────────────────────────────────────────────
+
+#[test]
+fn diag_zpl2323_field_over_retained_field_data_warns() {
+    let input = "^XA^MCN^FO10,10^FDOld^FS^XZ\n^XA^FO10,10^FDNew^FS^FO10,50^FDOther^FS^XZ";
+    let diags = issues_with_codes(
+        input,
+        None,
+        &[
+            codes::MAP_RETAINED_OVERPRINT,
+            codes::MAP_RETAINED_DARKNESS_CHANGE,
+        ],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert_eq!(diags[0].id, codes::MAP_RETAINED_OVERPRINT);
+    assert!(matches!(diags[0].severity, Severity::Warn));
+    let span = diags[0].span.expect("span");
+    // The span points into the second label, not the one that set ^MCN.
+    assert!(span.start > input.find('\n').unwrap());
+    assert_eq!(&input[span.start..span.end], "^FO10,10");
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^FO");
+    assert_eq!(ctx.get("x").unwrap(), "10");
+    assert_eq!(ctx.get("y").unwrap(), "10");
+}
+
+#[test]
+fn diag_zpl2323_variable_data_is_not_retained() {
+    let diags = issues_with_codes(
+        "^XA^MCN^FO10,10^FVOld^FS^XZ\n^XA^FO10,10^FVNew^FS^XZ",
+        None,
+        &[
+            codes::MAP_RETAINED_OVERPRINT,
+            codes::MAP_RETAINED_DARKNESS_CHANGE,
+        ],
+    );
+    assert!(diags.is_empty(), "{:?}", diags);
+}
+
+#[test]
+fn diag_zpl2323_map_clear_yes_passes() {
+    let diags = issues_with_codes(
+        "^XA^MCY^FO10,10^FDOld^FS^XZ\n^XA^FO10,10^FDNew^FS^XZ\n\
+         ^XA^MCN^FO10,10^FDA^FS^XZ\n^XA^MCY^FO20,20^FDB^FS^XZ\n^XA^FO10,10^FDC^FS^XZ",
+        None,
+        &[
+            codes::MAP_RETAINED_OVERPRINT,
+            codes::MAP_RETAINED_DARKNESS_CHANGE,
+        ],
+    );
+    // Only the label printed over the ^MCN image is checked; the ^MCY label
+    // clears the map after it prints.
+    assert!(diags.is_empty(), "{:?}", diags);
+}
+
+#[test]
+fn diag_zpl2324_darkness_change_over_retained_map_is_info() {
+    let diags = issues_with_codes(
+        "^XA^MCN^MD5^FO10,10^FDA^FS^XZ\n^XA^MD10^FO20,20^FDB^FS^XZ",
+        None,
+        &[
+            codes::MAP_RETAINED_OVERPRINT,
+            codes::MAP_RETAINED_DARKNESS_CHANGE,
+        ],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert_eq!(diags[0].id, codes::MAP_RETAINED_DARKNESS_CHANGE);
+    assert!(matches!(diags[0].severity, Severity::Info));
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("previous").unwrap(), "5");
+    assert_eq!(ctx.get("value").unwrap(), "10");
+
+    let same = issues_with_codes(
+        "^XA^MCN^MD5^FO10,10^FDA^FS^XZ\n^XA^MD5^FO20,20^FDB^FS^XZ",
+        None,
+        &[
+            codes::MAP_RETAINED_OVERPRINT,
+            codes::MAP_RETAINED_DARKNESS_CHANGE,
+        ],
+    );
+    assert!(same.is_empty(), "{:?}", same);
+}
+
+// ─── ZPL2316/2317: ^CW Font Aliases ──────────────────────────────────────────
+
+#[test]
+fn diag_zpl2316_fresh_alias_to_downloaded_font_passes() {
+    let diags = issues_with_codes(
+        "~DUR:MYFONT.FNT,4,AAAA\n^XA^CWQ,R:MYFONT.FNT^FO10,10^AQN,30^FDHello^FS^XZ\n\
+         ^XA^CWQ,R:MYFONT.FNT^CWZ,E:TT0003M_.FNT^XZ",
+        None,
+        &[codes::FONT_ALIAS_REDEFINED, codes::FONT_ALIAS_MISSING_FONT],
+    );
+    assert!(diags.is_empty(), "{:?}", diags);
+}
+
+#[test]
+fn diag_zpl2316_redefined_alias_warns() {
+    let diags = issues_with_codes(
+        "^XA^CWQ,E:ARIAL.TTF^FO10,10^AQN,30^FDA^FS^XZ\n\
+         ^XA^CWQ,E:COUR.TTF^FO10,10^AQN,30^FDB^FS^XZ",
+        None,
+        &[codes::FONT_ALIAS_REDEFINED, codes::FONT_ALIAS_MISSING_FONT],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert_eq!(diags[0].id, codes::FONT_ALIAS_REDEFINED);
+    assert!(matches!(diags[0].severity, Severity::Warn));
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("font").unwrap(), "Q");
+    assert_eq!(ctx.get("path").unwrap(), "E:COUR.TTF");
+    assert_eq!(ctx.get("previous").unwrap(), "E:ARIAL.TTF");
+}
+
+#[test]
+fn diag_zpl2317_alias_to_missing_font_errors() {
+    let diags = issues_with_codes(
+        "^XA^CWQ,MISSING^FO10,10^AQN,30^FDHello^FS^XZ",
+        None,
+        &[codes::FONT_ALIAS_REDEFINED, codes::FONT_ALIAS_MISSING_FONT],
+    );
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert_eq!(diags[0].id, codes::FONT_ALIAS_MISSING_FONT);
+    assert!(matches!(diags[0].severity, Severity::Error));
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("path").unwrap(), "R:MISSING.FNT");
+}
+
 // ─── ZPL2401/2402: Barcode Validation ────────────────────────────────────────
 
 #[test]
@@ -2117,9 +3299,32 @@ fn barcode_fd_interleaved2of5_odd_parity() {
     let input = "^XA^FO10,10^B2,50^FD123^FS^XZ";
     let result = parse_with_tables(input, Some(tables));
     let vr = validate::validate(&result.ast, tables);
+    let d = vr
+        .issues
+        .iter()
+        .find(|d| d.id == codes::BARCODE_DATA_LENGTH)
+        .unwrap_or_else(|| {
+            panic!(
+                "odd digit count for I2of5 should trigger ZPL2402: {:?}",
+                vr.issues
+            )
+        });
+    assert!(d.message.contains("even"), "message: {}", d.message);
+    let ctx = d.context.as_ref().expect("parity diagnostic has context");
+    assert_eq!(ctx.get("parity").unwrap(), "even");
+    assert_eq!(ctx.get("actualParity").unwrap(), "odd");
+}
+
+#[test]
+fn barcode_fd_without_parity_rule_accepts_odd_length() {
+    let tables = &*common::TABLES;
+    // ^B3 (Code 39) has no lengthParity rule — odd length is fine
+    let input = "^XA^FO10,10^B3N,N,50^FD123^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
     assert!(
-        vr.issues.iter().any(|d| d.id == codes::BARCODE_DATA_LENGTH),
-        "odd digit count for I2of5 should trigger ZPL2402: {:?}",
+        !vr.issues.iter().any(|d| d.id == codes::BARCODE_DATA_LENGTH),
+        "odd length without a parity rule should pass: {:?}",
         vr.issues,
     );
 }
@@ -2155,6 +3360,95 @@ fn barcode_validation_skipped_when_fh_active() {
     );
 }
 
+#[test]
+fn barcode_fd_qr_within_capacity() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO10,10^BQN,2,5^FDQA,HELLO WORLD^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::BARCODE_DATA_LENGTH),
+        "short QR payload should fit: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn barcode_fd_qr_over_capacity_for_error_correction() {
+    let tables = &*common::TABLES;
+    // 1300 lowercase chars encode as bytes: fits L (2953) but not H (1273).
+    let data = "a".repeat(1300);
+    let fits = format!("^XA^FO10,10^BQN,2,5^FDLA,{data}^FS^XZ");
+    let result = parse_with_tables(&fits, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::BARCODE_DATA_LENGTH),
+        "byte payload should fit at EC level L: {:?}",
+        vr.issues,
+    );
+
+    let overflows = format!("^XA^FO10,10^BQN,2,5^FDHA,{data}^FS^XZ");
+    let result = parse_with_tables(&overflows, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let d = find_diag(&vr.issues, codes::BARCODE_DATA_LENGTH);
+    assert_eq!(d.severity, Severity::Warn);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("mode").map(String::as_str), Some("byte"));
+    assert_eq!(ctx.get("errorCorrection").map(String::as_str), Some("H"));
+    assert_eq!(ctx.get("max").map(String::as_str), Some("1273"));
+}
+
+#[test]
+fn barcode_fd_data_matrix_ecc200_within_capacity() {
+    let tables = &*common::TABLES;
+    // 24x24 ECC 200 holds 36 data codewords: 72 digits.
+    let data = "1".repeat(72);
+    let input = format!("^XA^FO10,10^BXN,5,200,24,24^FD{data}^FS^XZ");
+    let result = parse_with_tables(&input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::BARCODE_DATA_LENGTH),
+        "numeric data should fit a 24x24 ECC 200 symbol: {:?}",
+        vr.issues,
+    );
+}
+
+#[test]
+fn barcode_fd_data_matrix_over_capacity() {
+    let tables = &*common::TABLES;
+    let data = "a".repeat(40);
+    let input = format!("^XA^FO10,10^BXN,5,200,24,24^FD{data}^FS^XZ");
+    let result = parse_with_tables(&input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let d = find_diag(&vr.issues, codes::BARCODE_DATA_LENGTH);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("symbol").map(String::as_str), Some("24x24"));
+    assert_eq!(ctx.get("max").map(String::as_str), Some("36"));
+
+    // ECC 140 with the default format ID 6 holds at most 63 characters.
+    let data = "A".repeat(64);
+    let input = format!("^XA^FO10,10^BXN,5,140^FD{data}^FS^XZ");
+    let result = parse_with_tables(&input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    let d = find_diag(&vr.issues, codes::BARCODE_DATA_LENGTH);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("quality").map(String::as_str), Some("140"));
+    assert_eq!(ctx.get("max").map(String::as_str), Some("63"));
+}
+
+#[test]
+fn barcode_data_matrix_invalid_quality() {
+    let tables = &*common::TABLES;
+    let input = "^XA^FO10,10^BXN,5,60^FDABC^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate::validate(&result.ast, tables);
+    assert!(
+        vr.issues.iter().any(|d| d.id == codes::INVALID_ENUM),
+        "quality 60 is not a Data Matrix ECC level: {:?}",
+        vr.issues,
+    );
+}
+
 #[test]
 fn barcode_fd_bs_allowed_lengths_invalid_three() {
     let tables = &*common::TABLES;
@@ -2561,6 +3855,89 @@ fn note_ci_emits_only_for_relevant_charsets() {
     );
 }
 
+fn firmware_profile(firmware: &str) -> zpl_toolchain_profile::Profile {
+    common::profile_from_json(&format!(
+        r#"{{"id":"test","schema_version":"1.0.0","dpi":203,"memory":{{"firmware_version":"{firmware}"}}}}"#
+    ))
+}
+
+fn fo_justification_notes(zpl: &str, profile: Option<&zpl_toolchain_profile::Profile>) -> usize {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables(zpl, Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, profile);
+    vr.issues
+        .iter()
+        .filter(|d| {
+            d.id == codes::NOTE
+                && d.context
+                    .as_ref()
+                    .and_then(|c| c.get("command"))
+                    .is_some_and(|v| v == "^FO")
+        })
+        .count()
+}
+
+#[test]
+fn note_fo_two_args_has_no_firmware_note() {
+    let old = firmware_profile("V50.13.2");
+    assert_eq!(
+        fo_justification_notes("^XA^FO10,10^FDx^FS^XZ", Some(&old)),
+        0
+    );
+}
+
+#[test]
+fn note_fo_justification_on_new_firmware_passes() {
+    let tables = &*common::TABLES;
+    let result = parse_with_tables("^XA^FO10,10,1^FDx^FS^XZ", Some(tables));
+    let args = find_args(&result, "^FO");
+    assert_eq!(args.len(), 3);
+    assert_eq!(args[2].value.as_deref(), Some("1"));
+
+    let new = firmware_profile("V60.19.15Z");
+    assert_eq!(
+        fo_justification_notes("^XA^FO10,10,1^FDx^FS^XZ", Some(&new)),
+        0
+    );
+    // Without a known firmware the parameter is assumed supported.
+    assert_eq!(fo_justification_notes("^XA^FO10,10,1^FDx^FS^XZ", None), 0);
+}
+
+#[test]
+fn note_fo_justification_on_old_firmware_warns() {
+    let tables = &*common::TABLES;
+    let old = firmware_profile("V60.13.0");
+    let result = parse_with_tables("^XA^FO10,10,2^FDx^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&result.ast, tables, Some(&old));
+    let d = vr
+        .issues
+        .iter()
+        .find(|d| {
+            d.id == codes::NOTE
+                && d.context
+                    .as_ref()
+                    .and_then(|c| c.get("command"))
+                    .is_some_and(|v| v == "^FO")
+        })
+        .expect("^FO justification firmware note");
+    assert_eq!(d.severity, Severity::Warn);
+    assert!(d.message.contains("V60.14"), "{}", d.message);
+}
+
+#[test]
+fn note_fo_justification_checks_the_v50_firmware_line() {
+    let current = firmware_profile("V50.14.1");
+    assert_eq!(
+        fo_justification_notes("^XA^FO10,10,1^FDx^FS^XZ", Some(&current)),
+        0
+    );
+    let old = firmware_profile("V50.13.2");
+    assert_eq!(
+        fo_justification_notes("^XA^FO10,10,1^FDx^FS^XZ", Some(&old)),
+        1
+    );
+}
+
 #[test]
 fn note_mc_only_emits_when_label_has_no_fv() {
     let tables = &*common::TABLES;
@@ -2594,6 +3971,39 @@ fn note_mc_only_emits_when_label_has_no_fv() {
     );
 }
 
+#[test]
+fn note_ju_warns_on_factory_reset_and_notes_save() {
+    let tables = &*common::TABLES;
+    let ju_notes = |zpl: &str| -> Vec<zpl_toolchain_diagnostics::Diagnostic> {
+        let res = parse_with_tables(zpl, Some(tables));
+        validate::validate(&res.ast, tables)
+            .issues
+            .into_iter()
+            .filter(|d| {
+                d.id == codes::NOTE
+                    && d.context
+                        .as_ref()
+                        .and_then(|c| c.get("command"))
+                        .is_some_and(|v| v == "^JU")
+            })
+            .collect()
+    };
+
+    for zpl in ["^XA^JUF^XZ", "^XA^JUN^XZ"] {
+        let notes = ju_notes(zpl);
+        assert_eq!(notes.len(), 1, "{zpl}: {notes:?}");
+        assert_eq!(notes[0].severity, Severity::Warn, "{zpl}");
+        assert!(notes[0].message.contains("factory settings"), "{zpl}");
+    }
+
+    let notes = ju_notes("^XA^JUS^XZ");
+    assert_eq!(notes.len(), 1, "{notes:?}");
+    assert_eq!(notes[0].severity, Severity::Info);
+    assert!(notes[0].message.contains("power-on defaults"));
+
+    assert!(ju_notes("^XA^JUR^XZ").is_empty());
+}
+
 #[test]
 fn note_mw_only_emits_when_parameter_missing() {
     let tables = &*common::TABLES;
@@ -2860,7 +4270,12 @@ fn all_profile_constraint_fields_are_resolvable() {
                 };
                 for arg in args_to_check {
                     if let Some(pc) = &arg.profile_constraint {
-                        profile_fields.insert(pc.field.clone());
+                        if pc.op == zpl_toolchain_spec_tables::ComparisonOp::Within {
+                            profile_fields.insert(format!("{}.min", pc.field));
+                            profile_fields.insert(format!("{}.max", pc.field));
+                        } else {
+                            profile_fields.insert(pc.field.clone());
+                        }
                     }
                 }
             }
@@ -2886,33 +4301,100 @@ fn all_profile_constraint_fields_are_resolvable() {
 }
 
 #[test]
-fn diag_profile_constraint_sd_exceeds_darkness() {
+fn diag_profile_constraint_sd_exceeds_darkness() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"darkness_range":{"min":0,"max":30}}"#,
+    );
+
+    // ~SD31 exceeds darkness_range.max (30)
+    let ast = parse_with_tables("^XA~SD31^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
+    assert!(
+        vr.issues
+            .iter()
+            .any(|d| d.id == codes::PROFILE_CONSTRAINT && d.message.contains("darkness")),
+        "~SD31 should exceed darkness_range.max 30: {:?}",
+        vr.issues,
+    );
+
+    // ~SD25 is within darkness_range.max (30)
+    let ast2 = parse_with_tables("^XA~SD25^XZ", Some(tables));
+    let vr2 = validate_with_profile(&ast2.ast, tables, Some(&profile));
+    assert!(
+        !vr2.issues
+            .iter()
+            .any(|d| d.id == codes::PROFILE_CONSTRAINT && d.message.contains("darkness")),
+        "~SD25 should be within darkness_range.max 30: {:?}",
+        vr2.issues,
+    );
+}
+
+#[test]
+fn diag_profile_constraint_sd_below_darkness_min() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"darkness_range":{"min":5,"max":25}}"#,
+    );
+
+    let ast = parse_with_tables("^XA~SD3^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
+    let d = find_diag(&vr.issues, codes::PROFILE_CONSTRAINT);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("field").unwrap(), "darkness_range");
+    assert_eq!(ctx.get("limit").unwrap(), "5");
+    assert_eq!(ctx.get("actual").unwrap(), "3");
+}
+
+#[test]
+fn diag_profile_constraint_md_over_adjustment_warns() {
     let tables = &*common::TABLES;
     let profile = common::profile_from_json(
         r#"{"id":"test","schema_version":"1.0.0","dpi":203,"darkness_range":{"min":0,"max":30}}"#,
     );
 
-    // ~SD31 exceeds darkness_range.max (30)
-    let ast = parse_with_tables("^XA~SD31^XZ", Some(tables));
+    // ~SD25 then ^MD10 would print at 35.
+    let ast = parse_with_tables("~SD25^XA^MD10^FO10,10^FDX^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
+    let d = find_diag(&vr.issues, codes::PROFILE_CONSTRAINT);
+    assert_eq!(d.severity, Severity::Warn);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^MD");
+    assert_eq!(ctx.get("base").unwrap(), "25");
+    assert_eq!(ctx.get("baseSource").unwrap(), "~SD");
+    assert_eq!(ctx.get("actual").unwrap(), "35");
+    assert_eq!(ctx.get("limit").unwrap(), "30");
+
+    // Without ~SD the range midpoint (15) is assumed: +10 is fine, +20 is not.
+    let ast = parse_with_tables("^XA^MD10^FO10,10^FDX^FS^XZ", Some(tables));
     let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
     assert!(
-        vr.issues
-            .iter()
-            .any(|d| d.id == codes::PROFILE_CONSTRAINT && d.message.contains("darkness")),
-        "~SD31 should exceed darkness_range.max 30: {:?}",
+        !vr.issues.iter().any(|d| d.id == codes::PROFILE_CONSTRAINT),
+        "^MD10 from assumed 15 stays in range: {:?}",
         vr.issues,
     );
+    let ast = parse_with_tables("^XA^MD-20^FO10,10^FDX^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
+    let d = find_diag(&vr.issues, codes::PROFILE_CONSTRAINT);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("baseSource").unwrap(), "assumed");
+    assert_eq!(ctx.get("limit").unwrap(), "0");
+}
 
-    // ~SD25 is within darkness_range.max (30)
-    let ast2 = parse_with_tables("^XA~SD25^XZ", Some(tables));
-    let vr2 = validate_with_profile(&ast2.ast, tables, Some(&profile));
-    assert!(
-        !vr2.issues
-            .iter()
-            .any(|d| d.id == codes::PROFILE_CONSTRAINT && d.message.contains("darkness")),
-        "~SD25 should be within darkness_range.max 30: {:?}",
-        vr2.issues,
+#[test]
+fn diag_profile_constraint_md_uses_session_sd_across_labels() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"darkness_range":{"min":0,"max":30}}"#,
+    );
+
+    let ast = parse_with_tables(
+        "^XA~SD28^FO10,10^FDA^FS^XZ^XA^MD5^FO10,10^FDB^FS^XZ",
+        Some(tables),
     );
+    let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
+    let d = find_diag(&vr.issues, codes::PROFILE_CONSTRAINT);
+    assert_eq!(d.context.as_ref().unwrap().get("actual").unwrap(), "33");
 }
 
 #[test]
@@ -2995,6 +4477,105 @@ fn printer_gate_command_skips_when_feature_true() {
     );
 }
 
+#[test]
+fn st_gated_without_rtc() {
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"features":{"rtc":false}}"#,
+    );
+    let issues = issues_with_codes(
+        "^XA^ST06,15,2026,14,30,00,M^XZ",
+        Some(&profile),
+        &[codes::PRINTER_GATE],
+    );
+    let gate = find_diag(&issues, codes::PRINTER_GATE);
+    assert_eq!(gate.context.as_ref().unwrap()["gate"], "rtc");
+}
+
+#[test]
+fn st_valid_date_passes() {
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"features":{"rtc":true}}"#,
+    );
+    let issues = issues_with_codes(
+        "^XA^ST02,29,2028,11,59,30,P^XZ",
+        Some(&profile),
+        &[codes::PRINTER_GATE, codes::OUT_OF_RANGE],
+    );
+    assert!(
+        issues.is_empty(),
+        "valid ^ST date should pass: {:?}",
+        issues
+    );
+}
+
+#[test]
+fn st_impossible_dates_are_out_of_range() {
+    // OUT_OF_RANGE reports the positional arg index: 0 = month, 1 = day, 3 = hour.
+    let out_of_range_args = |input: &str| -> Vec<String> {
+        issues_with_codes(input, None, &[codes::OUT_OF_RANGE])
+            .iter()
+            .map(|d| d.context.as_ref().unwrap()["arg"].clone())
+            .collect()
+    };
+    assert_eq!(out_of_range_args("^XA^ST13,01,2026^XZ"), ["0"]);
+    assert_eq!(out_of_range_args("^XA^ST02,30,2026^XZ"), ["1"]);
+    assert_eq!(out_of_range_args("^XA^ST04,31,2026^XZ"), ["1"]);
+    assert!(out_of_range_args("^XA^ST12,31,2026^XZ").is_empty());
+    assert_eq!(
+        out_of_range_args("^XA^ST02,29,2025^XZ"),
+        ["1"],
+        "February 29 only exists in leap years"
+    );
+    assert!(out_of_range_args("^XA^ST02,28,2025^XZ").is_empty());
+    assert!(out_of_range_args("^XA^ST02,29,2000^XZ").is_empty());
+    assert!(
+        out_of_range_args("^XA^ST02,29^XZ").is_empty(),
+        "without a year the current year applies, so the 29th is allowed"
+    );
+    assert_eq!(
+        out_of_range_args("^XA^ST06,15,2026,14,00,00,P^XZ"),
+        ["3"],
+        "12-hour format limits the hour to 1-12"
+    );
+    assert!(out_of_range_args("^XA^ST06,15,2026,14,00,00,M^XZ").is_empty());
+}
+
+#[test]
+fn validate_against_profiles_reports_per_profile_gate_results() {
+    let tables = &*common::TABLES;
+    let profiles = [
+        common::profile_from_json(
+            r#"{"id":"rfid-model","schema_version":"1.0.0","dpi":203,"features":{"rfid":true}}"#,
+        ),
+        common::profile_from_json(
+            r#"{"id":"plain-model","schema_version":"1.0.0","dpi":203,"features":{"rfid":false}}"#,
+        ),
+    ];
+    let ast = parse_with_tables("^XA^RFW,H,0,1,E^XZ", Some(tables));
+    let results = validate_against_profiles(&ast.ast, tables, &profiles);
+
+    let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["rfid-model", "plain-model"]);
+    assert!(
+        !results[0]
+            .1
+            .issues
+            .iter()
+            .any(|d| d.id == codes::PRINTER_GATE),
+        "rfid-capable profile should not gate ^RF: {:?}",
+        results[0].1.issues,
+    );
+    assert!(
+        results[1]
+            .1
+            .issues
+            .iter()
+            .any(|d| d.id == codes::PRINTER_GATE),
+        "profile without rfid should gate ^RF: {:?}",
+        results[1].1.issues,
+    );
+}
+
 // ─── Enum Value-Level Gate Tests ─────────────────────────────────────────────
 
 #[test]
@@ -3043,6 +4624,35 @@ fn printer_gate_enum_value_skips_unknown_feature() {
     );
 }
 
+#[test]
+fn printer_gate_backfeed_fires_without_peel_or_cutter() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"features":{"peel":false,"cutter":false}}"#,
+    );
+    let ast = parse_with_tables("~JSA\n^XA^FO10,10^FDx^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
+    let d = find_diag(&vr.issues, codes::PRINTER_GATE);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "~JS");
+    assert_eq!(ctx.get("gate").unwrap(), "cutter|peel");
+}
+
+#[test]
+fn printer_gate_backfeed_skips_on_peel_capable_profile() {
+    let tables = &*common::TABLES;
+    let profile = common::profile_from_json(
+        r#"{"id":"test","schema_version":"1.0.0","dpi":203,"features":{"peel":true,"cutter":false}}"#,
+    );
+    let ast = parse_with_tables("~JSA\n^XA^FO10,10^FDx^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, Some(&profile));
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::PRINTER_GATE),
+        "~JSA with peel=true should not trigger ZPL1402: {:?}",
+        vr.issues,
+    );
+}
+
 // ─── Media Mode Validation (ZPL1403) ─────────────────────────────────────────
 
 #[test]
@@ -3348,6 +4958,47 @@ fn context_host_command_in_label() {
     assert_eq!(ctx.get("command").unwrap(), "~TA");
 }
 
+#[test]
+fn context_barcode_reports_effective_by_values() {
+    let tables = &*common::TABLES;
+    let ast = parse_with_tables("^XA^BY2,3,100^FO10,10^BCX^FDtest^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, None);
+    let d = find_diag(&vr.issues, codes::INVALID_ENUM);
+    let ctx = d
+        .context
+        .as_ref()
+        .expect("barcode diagnostic should have context");
+    assert_eq!(ctx.get("command").unwrap(), "^BC");
+    assert_eq!(ctx.get("effective_module_width").unwrap(), "2");
+    assert_eq!(ctx.get("effective_ratio").unwrap(), "3");
+    assert_eq!(ctx.get("effective_height").unwrap(), "100");
+}
+
+#[test]
+fn context_barcode_effective_values_fall_back_to_by_defaults() {
+    let tables = &*common::TABLES;
+    let ast = parse_with_tables("^XA^FO10,10^BEN^FDABC^FS^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, None);
+    let d = find_diag(&vr.issues, codes::BARCODE_INVALID_CHAR);
+    let ctx = d
+        .context
+        .as_ref()
+        .expect("barcode field data diagnostic should have context");
+    assert_eq!(ctx.get("effective_module_width").unwrap(), "2");
+    assert_eq!(ctx.get("effective_ratio").unwrap(), "3");
+    assert_eq!(ctx.get("effective_height").unwrap(), "10");
+}
+
+#[test]
+fn context_non_barcode_diagnostic_has_no_effective_by_values() {
+    let tables = &*common::TABLES;
+    let ast = parse_with_tables("^XA^BY2,3,100^PW0^XZ", Some(tables));
+    let vr = validate_with_profile(&ast.ast, tables, None);
+    let d = find_diag(&vr.issues, codes::OUT_OF_RANGE);
+    let ctx = d.context.as_ref().expect("context");
+    assert!(!ctx.contains_key("effective_height"));
+}
+
 // ─── requires_field enforcement ──────────────────────────────────────────────
 
 #[test]
@@ -3736,6 +5387,177 @@ fn diag_constraint_default_severity_applies_when_constraint_severity_omitted() {
     );
 }
 
+#[test]
+fn custom_constraint_evaluator_fires_on_matching_label_shape() {
+    use zpl_toolchain_core::grammar::ast::Node;
+    use zpl_toolchain_core::validate::{ValidationOptions, validate_with_options};
+
+    // Org rule: every label must carry a ^FN1 batch id.
+    let tables = mutate_command_in_tables(&common::TABLES, "^XZ", |cmd| {
+        let constraints = cmd.constraints.get_or_insert_with(Vec::new);
+        constraints.push(Constraint {
+            kind: ConstraintKind::Custom,
+            expr: Some("org:batch-id".to_string()),
+            message: "label is missing the ^FN1 batch id".to_string(),
+            severity: None,
+            scope: None,
+            audience: None,
+        });
+    });
+    let mut options = ValidationOptions::default();
+    options.custom_constraints.insert(
+        "org:batch-id".to_string(),
+        Box::new(|ctx| {
+            let has_batch_id = ctx.label.nodes[..ctx.node_index].iter().any(|n| {
+                matches!(n, Node::Command { code, args, .. }
+                    if code == "^FN" && args.first().and_then(|a| a.value.as_deref()) == Some("1"))
+            });
+            (!has_batch_id).then(|| {
+                zpl_toolchain_diagnostics::Diagnostic::new(
+                    "ORG0001",
+                    Severity::Warn,
+                    ctx.constraint.message.clone(),
+                    None,
+                )
+            })
+        }),
+    );
+
+    let input = "^XA^FO10,10^FN1^FDB-42^FS^XZ\n^XA^FO10,10^FDno batch^FS^XZ";
+    let result = parse_with_tables(input, Some(&tables));
+    let vr = validate_with_options(&result.ast, &tables, None, &options);
+    let fired: Vec<_> = vr.issues.iter().filter(|d| d.id == "ORG0001").collect();
+    assert_eq!(
+        fired.len(),
+        1,
+        "only the second label lacks ^FN1: {:?}",
+        vr.issues
+    );
+    let span = fired[0]
+        .span
+        .expect("span defaults to the constrained command");
+    assert_eq!(&input[span.start..span.end], "^XZ");
+    assert!(span.start > input.find('\n').unwrap());
+
+    // Without a registered evaluator the constraint is skipped.
+    let vr = validate::validate(&result.ast, &tables);
+    assert!(!vr.issues.iter().any(|d| d.id == "ORG0001"));
+}
+
+#[test]
+fn forbidden_commands_are_reported_with_their_code() {
+    use zpl_toolchain_core::validate::{ValidationOptions, validate_with_options};
+
+    let tables = &common::TABLES;
+    let options = ValidationOptions {
+        forbidden_commands: ["~JR".to_string(), "^ID".to_string()].into(),
+        ..ValidationOptions::default()
+    };
+    let input = "~JR\n^XA^FO10,10^FDok^FS^XZ";
+    let result = parse_with_tables(input, Some(tables));
+    let vr = validate_with_options(&result.ast, tables, None, &options);
+    let hits: Vec<_> = vr
+        .issues
+        .iter()
+        .filter(|d| d.id == codes::FORBIDDEN_COMMAND)
+        .collect();
+    assert_eq!(hits.len(), 1, "{:?}", vr.issues);
+    assert_eq!(hits[0].severity, Severity::Error);
+    assert_eq!(
+        hits[0].context.as_ref().and_then(|c| c.get("command")),
+        Some(&"~JR".to_string())
+    );
+    assert!(!vr.ok);
+}
+
+#[test]
+fn permitted_commands_are_not_forbidden() {
+    use zpl_toolchain_core::validate::{ValidationOptions, validate_with_options};
+
+    let tables = &common::TABLES;
+    let options = ValidationOptions {
+        forbidden_commands: ["~JR".to_string()].into(),
+        ..ValidationOptions::default()
+    };
+    let result = parse_with_tables("^XA^FO10,10^FDok^FS^XZ", Some(tables));
+    let vr = validate_with_options(&result.ast, tables, None, &options);
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::FORBIDDEN_COMMAND),
+        "{:?}",
+        vr.issues
+    );
+}
+
+#[test]
+fn required_commands_missing_from_a_label_are_reported() {
+    use zpl_toolchain_core::validate::ValidationOptions;
+
+    let input = "^XA^FO10,10^FN1^FDbatch^FS^PQ2^XZ\n^XA^FO10,10^FN1^FDbatch^FS^XZ";
+    let options = ValidationOptions {
+        required_commands: vec!["^PQ".to_string(), "^fn".to_string()],
+        ..ValidationOptions::default()
+    };
+    let issues = issues_with_codes_and_options(input, None, &options, &[codes::REQUIRED_COMMAND]);
+    assert_eq!(issues.len(), 1, "{:?}", issues);
+    assert_eq!(issues[0].severity, Severity::Warn);
+    let ctx = issues[0].context.as_ref().unwrap();
+    assert_eq!(ctx["kind"], "policy");
+    assert_eq!(ctx["target"], "^PQ");
+    assert_eq!(ctx["scope"], "label");
+    // Anchored on the second label's ^XA.
+    assert_eq!(issues[0].span.map(|s| &input[s.start..s.end]), Some("^XA"));
+}
+
+#[test]
+fn labels_with_every_required_command_are_clean() {
+    use zpl_toolchain_core::validate::ValidationOptions;
+
+    let options = ValidationOptions {
+        required_commands: vec!["^PQ".to_string(), "^FN".to_string()],
+        ..ValidationOptions::default()
+    };
+    let issues = issues_with_codes_and_options(
+        "^XA^FO10,10^FN1^FDbatch^FS^PQ2^XZ",
+        None,
+        &options,
+        &[codes::REQUIRED_COMMAND],
+    );
+    assert!(issues.is_empty(), "{:?}", issues);
+}
+
+#[test]
+fn field_data_under_or_at_limit_passes() {
+    use zpl_toolchain_core::validate::ValidationOptions;
+
+    let input = "^XA^FO10,10^FDabcd^FS^FO10,50^FDabcde^FS^XZ";
+    let options = ValidationOptions {
+        max_field_data_len: Some(5),
+        ..ValidationOptions::default()
+    };
+    let diags = issues_with_codes_and_options(input, None, &options, &[codes::FIELD_DATA_TOO_LONG]);
+    assert!(diags.is_empty(), "{:?}", diags);
+}
+
+#[test]
+fn field_data_over_limit_warns_with_length() {
+    use zpl_toolchain_core::validate::ValidationOptions;
+
+    let input = "^XA^FO10,10^FDabcdef^FS^FO10,50^FDok^FS^XZ";
+    let options = ValidationOptions {
+        max_field_data_len: Some(5),
+        ..ValidationOptions::default()
+    };
+    let diags = issues_with_codes_and_options(input, None, &options, &[codes::FIELD_DATA_TOO_LONG]);
+    assert_eq!(diags.len(), 1, "{:?}", diags);
+    assert_eq!(diags[0].severity, Severity::Warn);
+    let ctx = diags[0].context.as_ref().expect("context");
+    assert_eq!(ctx.get("command").unwrap(), "^FD");
+    assert_eq!(ctx.get("length").unwrap(), "6");
+    assert_eq!(ctx.get("limit").unwrap(), "5");
+    let span = diags[0].span.expect("span");
+    assert_eq!(&input[span.start..span.end], "^FDabcdef");
+}
+
 #[test]
 fn diag_barcode_field_data_character_set_severity_from_spec() {
     let tables = mutate_command_in_tables(&common::TABLES, "^BC", |cmd| {
@@ -3748,6 +5570,7 @@ fn diag_barcode_field_data_character_set_severity_from_spec() {
             allowed_lengths: None,
             length_parity: None,
             length_severity: None,
+            payload_format: None,
             notes: None,
         });
     });
@@ -3779,6 +5602,7 @@ fn diag_barcode_field_data_length_severity_from_spec() {
             allowed_lengths: None,
             length_parity: None,
             length_severity: Some(zpl_toolchain_spec_tables::ConstraintSeverity::Error),
+            payload_format: None,
             notes: None,
         });
     });
@@ -3798,6 +5622,81 @@ fn diag_barcode_field_data_length_severity_from_spec() {
     );
 }
 
+fn tables_with_bc_max_length(max: usize) -> zpl_toolchain_spec_tables::ParserTables {
+    mutate_command_in_tables(&common::TABLES, "^BC", |cmd| {
+        cmd.field_data_rules = Some(zpl_toolchain_spec_tables::FieldDataRules {
+            character_set: None,
+            character_set_severity: None,
+            min_length: None,
+            max_length: Some(max),
+            exact_length: None,
+            allowed_lengths: None,
+            length_parity: None,
+            length_severity: None,
+            payload_format: None,
+            notes: None,
+        });
+    })
+}
+
+#[test]
+fn diag_barcode_length_counts_characters_under_ci28() {
+    let tables = tables_with_bc_max_length(4);
+
+    // Four characters, eight UTF-8 bytes.
+    let result = parse_with_tables(
+        "^XA^CI28^FO10,10^BCN,30,Y,N,N^FD\u{e4}\u{f6}\u{fc}\u{df}^FS^XZ",
+        Some(&tables),
+    );
+    let vr = validate::validate(&result.ast, &tables);
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::BARCODE_DATA_LENGTH),
+        "^CI28 should count UTF-8 field data by character: {:?}",
+        vr.issues
+    );
+}
+
+#[test]
+fn diag_barcode_length_counts_bytes_without_unicode_ci() {
+    let tables = tables_with_bc_max_length(4);
+
+    for input in [
+        "^XA^FO10,10^BCN,30,Y,N,N^FD\u{e4}\u{f6}\u{fc}\u{df}^FS^XZ",
+        "^XA^CI13^FO10,10^BCN,30,Y,N,N^FD\u{e4}\u{f6}\u{fc}\u{df}^FS^XZ",
+    ] {
+        let result = parse_with_tables(input, Some(&tables));
+        let vr = validate::validate(&result.ast, &tables);
+        let diag = vr
+            .issues
+            .iter()
+            .find(|d| d.id == codes::BARCODE_DATA_LENGTH)
+            .unwrap_or_else(|| panic!("byte count should exceed maxLength for {input}"));
+        assert_eq!(
+            diag.context
+                .as_ref()
+                .and_then(|ctx| ctx.get("actual"))
+                .map(String::as_str),
+            Some("8"),
+            "single-byte encodings should count UTF-8 bytes: {:?}",
+            diag
+        );
+    }
+}
+
+#[test]
+fn diag_barcode_length_ci_persists_across_labels() {
+    let tables = tables_with_bc_max_length(4);
+
+    let input = "^XA^CI28^XZ\n^XA^FO10,10^BCN,30,Y,N,N^FD\u{e4}\u{f6}\u{fc}\u{df}^FS^XZ";
+    let result = parse_with_tables(input, Some(&tables));
+    let vr = validate::validate(&result.ast, &tables);
+    assert!(
+        !vr.issues.iter().any(|d| d.id == codes::BARCODE_DATA_LENGTH),
+        "session-scoped ^CI28 should carry into later labels: {:?}",
+        vr.issues
+    );
+}
+
 #[test]
 fn diag_rounding_policy_uses_spec_epsilon() {
     let tables = mutate_command_in_tables(&common::TABLES, "^BY", |cmd| {
@@ -3833,8 +5732,9 @@ fn diag_rounding_policy_uses_spec_epsilon() {
 fn all_diagnostic_ids_have_explanations() {
     use zpl_toolchain_diagnostics::explain;
 
-    // All diagnostic codes used in the validator and parser.
-    // Keep this list in sync when adding new codes.
+    // All diagnostic codes used in the validator, parser, and template
+    // substitution. Checked against the catalog below, so a new code must
+    // be added here.
     let validator_codes = [
         codes::ARITY,
         codes::INVALID_ENUM,
@@ -3871,8 +5771,23 @@ fn all_diagnostic_ids_have_explanations() {
         codes::GF_MEMORY_EXCEEDED,
         codes::MISSING_EXPLICIT_DIMENSIONS,
         codes::OBJECT_BOUNDS_OVERFLOW,
+        codes::STORED_FORMAT_FIELD_MISMATCH,
+        codes::FIELD_DATA_CONFLICT,
+        codes::INVALID_RESOURCE_NAME,
+        codes::FONT_ALIAS_REDEFINED,
+        codes::FONT_ALIAS_MISSING_FONT,
+        codes::FIELD_DATA_TOO_LONG,
+        codes::FIELD_ORIENTATION_COMBINED,
+        codes::SERIALIZATION_MASK_INVALID,
         codes::BARCODE_INVALID_CHAR,
         codes::BARCODE_DATA_LENGTH,
+        codes::HEADER_COMMAND_AFTER_FIELD,
+        codes::FORBIDDEN_COMMAND,
+        codes::SUSPICIOUS_FONT_SIZE,
+        codes::OBJECT_DELETE,
+        codes::FIELD_CLOCK_UNUSED,
+        codes::MAP_RETAINED_OVERPRINT,
+        codes::MAP_RETAINED_DARKNESS_CHANGE,
         codes::NOTE,
     ];
 
@@ -3887,8 +5802,20 @@ fn all_diagnostic_ids_have_explanations() {
         codes::PARSER_NON_ASCII_ARG,
     ];
 
+    let template_codes = [
+        codes::TEMPLATE_UNRESOLVED_VARIABLE,
+        codes::TEMPLATE_UNSAFE_VALUE,
+    ];
+
+    let all_codes: Vec<&str> = validator_codes
+        .iter()
+        .chain(&parser_codes)
+        .chain(&template_codes)
+        .copied()
+        .collect();
+
     let mut missing = Vec::new();
-    for code in validator_codes.iter().chain(parser_codes.iter()) {
+    for code in &all_codes {
         if explain(code).is_none() {
             missing.push(*code);
         }
@@ -3899,9 +5826,119 @@ fn all_diagnostic_ids_have_explanations() {
         "Diagnostic codes without explain() entries: {:?}",
         missing
     );
+
+    let unlisted: Vec<&str> = zpl_toolchain_diagnostics::catalog()
+        .iter()
+        .map(|info| info.id)
+        .filter(|id| !all_codes.contains(id))
+        .collect();
+    assert!(
+        unlisted.is_empty(),
+        "Catalog codes missing from this test: {:?}",
+        unlisted
+    );
 }
 
 // NOTE: Some validator code paths are not currently exercised by real spec data
 // (e.g., arg-level minLength/maxLength and certain constraint expressions). The
 // synthetic tests above intentionally mutate parser tables in-memory so those
 // diagnostic paths remain covered against regressions.
+
+// ─── Diagnostics catalog ────────────────────────────────────────────────────
+
+#[test]
+fn diagnostics_for_pw_reports_arity_range_and_profile() {
+    let ids = validate::diagnostics_for(&common::TABLES, "^PW");
+    for expected in [
+        codes::ARITY,
+        codes::OUT_OF_RANGE,
+        codes::PROFILE_CONSTRAINT,
+        codes::EXPECTED_INTEGER,
+    ] {
+        assert!(
+            ids.contains(&expected),
+            "^PW should list {expected}: {ids:?}"
+        );
+    }
+    assert!(
+        !ids.contains(&codes::REQUIRED_MISSING),
+        "^PW width is optional: {ids:?}"
+    );
+    let mut sorted = ids.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(ids, sorted, "diagnostic IDs should be sorted and unique");
+}
+
+#[test]
+fn diagnostics_for_covers_field_data_rules_and_unknown_codes() {
+    let ids = validate::diagnostics_for(&common::TABLES, "^BE");
+    assert!(ids.contains(&codes::BARCODE_INVALID_CHAR), "{ids:?}");
+    assert!(ids.contains(&codes::BARCODE_DATA_LENGTH), "{ids:?}");
+
+    let fd = validate::diagnostics_for(&common::TABLES, "^FD");
+    assert!(fd.contains(&codes::FIELD_DATA_WITHOUT_ORIGIN), "{fd:?}");
+    assert!(!fd.contains(&codes::ARITY), "{fd:?}");
+
+    assert!(validate::diagnostics_for(&common::TABLES, "^!!").is_empty());
+}
+
+#[test]
+fn diag_zpl2320_valid_mask_and_increment_pass() {
+    for sf in [
+        "SFdddd,1",
+        "SFdddd",
+        "SFhh%nn,F%%1",
+        "SFAAA,B",
+        "SFoo,07",
+        "SF%%dd,0001",
+    ] {
+        let issues = issues_with_codes(
+            &format!("^XA^FO10,10^FN1^FD0001^{sf}^FS^XZ"),
+            None,
+            &[codes::SERIALIZATION_MASK_INVALID],
+        );
+        assert!(issues.is_empty(), "{sf}: {issues:?}");
+    }
+}
+
+#[test]
+fn diag_zpl2320_invalid_mask_character() {
+    let issues = issues_with_codes(
+        "^XA^FO10,10^FN1^FD0001^SFddxd,1^FS^XZ",
+        None,
+        &[codes::SERIALIZATION_MASK_INVALID],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    let d = &issues[0];
+    assert_eq!(d.severity, Severity::Warn);
+    let ctx = d.context.as_ref().expect("context");
+    assert_eq!(ctx["kind"], "invalid_mask_char");
+    assert_eq!(ctx["character"], "x");
+    assert_eq!(ctx["position"], "2");
+    assert_eq!(ctx["mask"], "ddxd");
+}
+
+#[test]
+fn diag_zpl2320_increment_longer_or_misaligned() {
+    let issues = issues_with_codes(
+        "^XA^FO10,10^FN1^FD0001^SFdd,100^FS^XZ",
+        None,
+        &[codes::SERIALIZATION_MASK_INVALID],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    let ctx = issues[0].context.as_ref().expect("context");
+    assert_eq!(ctx["kind"], "increment_too_long");
+
+    // Right-aligned, the 'A' lands on the last decimal position.
+    let issues = issues_with_codes(
+        "^XA^FO10,10^FN1^FD0001^SFdd%d,1%A^FS^XZ",
+        None,
+        &[codes::SERIALIZATION_MASK_INVALID],
+    );
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    let ctx = issues[0].context.as_ref().expect("context");
+    assert_eq!(ctx["kind"], "increment_misaligned");
+    assert_eq!(ctx["character"], "A");
+    assert_eq!(ctx["position"], "2");
+}
@@ -6,6 +6,7 @@
 //! - `generated_policy.rs`: Contains policy constants derived from diagnostic spec metadata
 //! - `generated_severity.rs`: Contains code → default severity lookup
 //! - `generated_templates.rs`: Contains (code, variant) → message template lookup
+//! - `generated_catalog.rs`: Contains the full catalog as a `DiagnosticInfo` array literal
 
 use std::collections::HashSet;
 use std::env;
@@ -206,6 +207,29 @@ fn main() {
     templates.push_str("    _ => None,\n}\n");
     fs::write(out_path.join("generated_templates.rs"), &templates)
         .expect("failed to write generated_templates.rs");
+
+    // ── generated_catalog.rs ────────────────────────────────────────────
+    let mut catalog = String::from("[\n");
+    for (i, entry) in diagnostics.iter().enumerate() {
+        let id = entry["id"].as_str().unwrap();
+        let sev_rs = match entry["severity"].as_str().unwrap() {
+            "error" => "Severity::Error",
+            "warn" => "Severity::Warn",
+            _ => "Severity::Info",
+        };
+        let category = entry["category"]
+            .as_str()
+            .unwrap_or_else(|| panic!("diagnostics[{i}] (id={id}) missing `category`"));
+        let summary = escape_rust_string_literal(entry["summary"].as_str().unwrap());
+        let description = escape_rust_string_literal(entry["description"].as_str().unwrap());
+        catalog.push_str(&format!(
+            "    DiagnosticInfo {{ id: \"{id}\", severity: {sev_rs}, category: \"{}\", summary: \"{summary}\", description: \"{description}\" }},\n",
+            escape_rust_string_literal(category)
+        ));
+    }
+    catalog.push_str("]\n");
+    fs::write(out_path.join("generated_catalog.rs"), &catalog)
+        .expect("failed to write generated_catalog.rs");
 }
 
 fn escape_rust_string_literal(value: &str) -> String {
@@ -21,38 +21,92 @@ use std::collections::BTreeMap;
 /// The index is built in O(n) time and each lookup is O(log n) via binary
 /// search. This struct is intentionally dependency-free so it can be reused
 /// by WASM bindings, an LSP server, or any other consumer.
+///
+/// Both `\n` and `\r\n` end a line; the `\r` of a CRLF pair is part of the
+/// line terminator, so an offset on either byte maps to the end-of-line column.
 #[derive(Debug, Clone)]
 pub struct LineIndex {
     /// Byte offset of the start of each line.
     /// `line_starts[0]` is always 0.
     line_starts: Vec<usize>,
+    /// Whether each line is terminated by `\r\n` (the last line never is).
+    crlf: Vec<bool>,
+    /// Line-ending style detected in the source.
+    line_ending: LineEndingStyle,
+}
+
+/// Line-ending style of a source text, as reported by
+/// [`LineIndex::line_ending_style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LineEndingStyle {
+    /// Unix-style `\n` (also reported for input without line breaks).
+    Lf,
+    /// Windows-style `\r\n`.
+    CrLf,
+    /// Both `\n` and `\r\n` occur.
+    Mixed,
+}
+
+impl LineEndingStyle {
+    /// The line terminator as a string (`"\n"` for [`Mixed`](Self::Mixed)).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEndingStyle::CrLf => "\r\n",
+            LineEndingStyle::Lf | LineEndingStyle::Mixed => "\n",
+        }
+    }
 }
 
 impl LineIndex {
     /// Build a `LineIndex` from source text.
     pub fn new(text: &str) -> Self {
+        let bytes = text.as_bytes();
         let mut line_starts = vec![0usize];
-        for (i, b) in text.bytes().enumerate() {
+        let mut crlf = Vec::new();
+        for (i, &b) in bytes.iter().enumerate() {
             if b == b'\n' {
                 line_starts.push(i + 1);
+                crlf.push(i > 0 && bytes[i - 1] == b'\r');
             }
         }
-        Self { line_starts }
+        crlf.push(false);
+
+        let crlf_count = crlf.iter().filter(|&&c| c).count();
+        let line_ending = match (crlf_count, line_starts.len() - 1 - crlf_count) {
+            (0, _) => LineEndingStyle::Lf,
+            (_, 0) => LineEndingStyle::CrLf,
+            _ => LineEndingStyle::Mixed,
+        };
+        Self {
+            line_starts,
+            crlf,
+            line_ending,
+        }
     }
 
     /// Convert a byte offset to a 0-indexed `(line, column)` pair.
     ///
     /// If `offset` is past the end of the source, the last line is returned
-    /// with the column clamped to the line length.
+    /// with the column measured from its start.
     pub fn line_col(&self, offset: usize) -> (usize, usize) {
         let line = match self.line_starts.binary_search(&offset) {
             Ok(exact) => exact,
             Err(next) => next.saturating_sub(1),
         };
-        let col = offset.saturating_sub(self.line_starts[line]);
+        let mut col = offset.saturating_sub(self.line_starts[line]);
+        // The `\n` of a CRLF pair shares the end-of-line column with the `\r`.
+        if self.crlf[line] && offset + 1 == self.line_starts[line + 1] {
+            col -= 1;
+        }
         (line, col)
     }
 
+    /// Line-ending style of the indexed source.
+    pub fn line_ending_style(&self) -> LineEndingStyle {
+        self.line_ending
+    }
+
     /// Byte offset of the start of the given 0-indexed line.
     ///
     /// Returns `None` if `line` is out of bounds.
@@ -68,6 +122,7 @@ impl LineIndex {
 
 /// Severity level for a diagnostic message.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Severity {
@@ -81,6 +136,7 @@ pub enum Severity {
 
 /// Byte span in the source input.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Span {
     /// Byte offset of the first character (0-based).
     pub start: usize,
@@ -106,8 +162,20 @@ impl Span {
     }
 }
 
+/// A secondary source location that a diagnostic refers to, such as the
+/// first of two duplicate definitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RelatedSpan {
+    /// Byte span of the related location.
+    pub span: Span,
+    /// Short description of why the location is related (e.g. `"first defined here"`).
+    pub message: String,
+}
+
 /// A diagnostic message produced by the parser or validator.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Diagnostic {
     /// Unique diagnostic code (e.g., `"ZPL1101"`).
     pub id: Cow<'static, str>,
@@ -124,6 +192,10 @@ pub struct Diagnostic {
     /// Uses `BTreeMap` for deterministic key ordering in serialized output.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<BTreeMap<String, String>>,
+    /// Secondary locations for editors to mark alongside `span`.
+    /// Serialized only when non-empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedSpan>,
 }
 
 impl Diagnostic {
@@ -140,6 +212,7 @@ impl Diagnostic {
             message: message.into(),
             span,
             context: None,
+            related: Vec::new(),
         }
     }
 
@@ -180,6 +253,15 @@ impl Diagnostic {
         self
     }
 
+    /// Attach a secondary location (builder pattern).
+    pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.related.push(RelatedSpan {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
     /// Returns the human-readable explanation for this diagnostic's code, if available.
     pub fn explain(&self) -> Option<&'static str> {
         explain(&self.id)
@@ -202,6 +284,33 @@ impl std::fmt::Display for Diagnostic {
     }
 }
 
+/// Per-severity diagnostic counts, e.g. for rendering a status badge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DiagnosticSummary {
+    /// Number of `Error` diagnostics.
+    pub errors: usize,
+    /// Number of `Warn` diagnostics.
+    pub warns: usize,
+    /// Number of `Info` diagnostics.
+    pub infos: usize,
+}
+
+impl DiagnosticSummary {
+    /// Count diagnostics by severity.
+    pub fn from_diagnostics<'a>(diagnostics: impl IntoIterator<Item = &'a Diagnostic>) -> Self {
+        let mut summary = Self::default();
+        for d in diagnostics {
+            match d.severity {
+                Severity::Error => summary.errors += 1,
+                Severity::Warn => summary.warns += 1,
+                Severity::Info => summary.infos += 1,
+            }
+        }
+        summary
+    }
+}
+
 /// Returns the human-readable explanation for a diagnostic code, if known.
 ///
 /// Auto-generated from `spec/diagnostics.jsonc` at build time.
@@ -209,6 +318,30 @@ pub fn explain(id: &str) -> Option<&'static str> {
     include!(concat!(env!("OUT_DIR"), "/generated_explain.rs"))
 }
 
+/// Spec metadata for one diagnostic code, as listed by [`catalog`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiagnosticInfo {
+    /// Diagnostic code (e.g. `"ZPL1101"`).
+    pub id: &'static str,
+    /// Default severity.
+    pub severity: Severity,
+    /// Category (e.g. `"semantic-validation"`).
+    pub category: &'static str,
+    /// Short title.
+    pub summary: &'static str,
+    /// Full explanation, as returned by [`explain`].
+    pub description: &'static str,
+}
+
+/// Every diagnostic code with its spec metadata, in spec order.
+///
+/// Auto-generated from `spec/diagnostics.jsonc` at build time.
+pub fn catalog() -> &'static [DiagnosticInfo] {
+    static CATALOG: &[DiagnosticInfo] =
+        &include!(concat!(env!("OUT_DIR"), "/generated_catalog.rs"));
+    CATALOG
+}
+
 /// Policy constants derived from `spec/diagnostics.jsonc`.
 pub mod policy {
     use super::Severity;
@@ -299,6 +432,54 @@ mod tests {
         assert_eq!(idx.line_start(3), None);
     }
 
+    #[test]
+    fn line_index_lf_style() {
+        let idx = LineIndex::new("ab\ncd\n");
+        assert_eq!(idx.line_ending_style(), LineEndingStyle::Lf);
+        assert_eq!(
+            LineIndex::new("no newline").line_ending_style(),
+            LineEndingStyle::Lf
+        );
+    }
+
+    #[test]
+    fn line_index_crlf_columns() {
+        let idx = LineIndex::new("ab\r\ncd\r\nef");
+        assert_eq!(idx.line_ending_style(), LineEndingStyle::CrLf);
+        assert_eq!(idx.line_count(), 3);
+        assert_eq!(idx.line_col(1), (0, 1)); // 'b'
+        assert_eq!(idx.line_col(2), (0, 2)); // '\r'
+        assert_eq!(idx.line_col(3), (0, 2)); // '\n' — same end-of-line column
+        assert_eq!(idx.line_col(4), (1, 0)); // 'c'
+        assert_eq!(idx.line_col(5), (1, 1)); // 'd'
+        assert_eq!(idx.line_col(7), (1, 2)); // '\n'
+        assert_eq!(idx.line_col(8), (2, 0)); // 'e'
+        assert_eq!(idx.line_start(2), Some(8));
+    }
+
+    #[test]
+    fn line_index_mixed_style() {
+        let idx = LineIndex::new("a\r\nb\nc");
+        assert_eq!(idx.line_ending_style(), LineEndingStyle::Mixed);
+        assert_eq!(idx.line_col(2), (0, 1)); // '\n' of CRLF
+        assert_eq!(idx.line_col(4), (1, 1)); // '\n' of LF
+        assert_eq!(idx.line_col(5), (2, 0)); // 'c'
+    }
+
+    #[test]
+    fn line_index_lone_cr_is_not_a_line_break() {
+        let idx = LineIndex::new("a\rb");
+        assert_eq!(idx.line_count(), 1);
+        assert_eq!(idx.line_col(2), (0, 2));
+        assert_eq!(idx.line_ending_style(), LineEndingStyle::Lf);
+    }
+
+    #[test]
+    fn line_ending_as_str() {
+        assert_eq!(LineEndingStyle::Lf.as_str(), "\n");
+        assert_eq!(LineEndingStyle::CrLf.as_str(), "\r\n");
+    }
+
     #[test]
     fn line_index_offset_past_end() {
         let idx = LineIndex::new("hi");
@@ -476,6 +657,28 @@ mod tests {
 
     // ── Eq / PartialEq ─────────────────────────────────────────────────
 
+    #[test]
+    fn diagnostic_summary_counts_by_severity() {
+        let diags = [
+            Diagnostic::error("ZPL1101", "a", None),
+            Diagnostic::warn("ZPL2302", "b", None),
+            Diagnostic::warn("ZPL2302", "c", None),
+            Diagnostic::info("ZPL3001", "d", None),
+        ];
+        assert_eq!(
+            DiagnosticSummary::from_diagnostics(&diags),
+            DiagnosticSummary {
+                errors: 1,
+                warns: 2,
+                infos: 1,
+            }
+        );
+        assert_eq!(
+            DiagnosticSummary::from_diagnostics(&[]),
+            DiagnosticSummary::default()
+        );
+    }
+
     #[test]
     fn diagnostic_eq() {
         let a = Diagnostic::error(codes::ARITY, "msg", Some(Span::new(0, 5)));
@@ -512,6 +715,33 @@ mod tests {
             !json.contains("context"),
             "None context should be omitted: {json}"
         );
+        assert!(
+            !json.contains("related"),
+            "empty related should be omitted: {json}"
+        );
+    }
+
+    // ── Related spans ─────────────────────────────────────────────────────
+
+    #[test]
+    fn diagnostic_related_spans_serde_roundtrip() {
+        let d = Diagnostic::warn(
+            codes::DUPLICATE_FIELD_NUMBER,
+            "dup",
+            Some(Span::new(20, 25)),
+        )
+        .with_related(Span::new(3, 8), "first defined here")
+        .with_related(Span::new(11, 16), "also defined here");
+        let json = serde_json::to_value(&d).unwrap();
+        assert_eq!(
+            json["related"],
+            serde_json::json!([
+                { "span": { "start": 3, "end": 8 }, "message": "first defined here" },
+                { "span": { "start": 11, "end": 16 }, "message": "also defined here" },
+            ])
+        );
+        let d2: Diagnostic = serde_json::from_value(json).unwrap();
+        assert_eq!(d, d2);
     }
 
     // ── Context ───────────────────────────────────────────────────────────
@@ -563,4 +793,18 @@ mod tests {
             "BTreeMap should serialize in alphabetical key order: {json}"
         );
     }
+
+    #[test]
+    fn catalog_matches_explain_and_severity_lookups() {
+        let catalog = catalog();
+        let arity = catalog
+            .iter()
+            .find(|info| info.id == codes::ARITY)
+            .expect("ZPL1101 in catalog");
+        assert_eq!(Some(arity.description), explain(codes::ARITY));
+        assert!(!arity.summary.is_empty());
+        for info in catalog {
+            assert_eq!(Some(info.severity.clone()), severity_for_code(info.id));
+        }
+    }
 }
@@ -349,10 +349,7 @@ pub unsafe extern "C" fn zpl_print_with_options(
         match common::print_zpl_with_options(zpl, addr, profile_str, validate, timeout, config_str)
         {
             Ok(json) => to_c_string(&json),
-            Err(e) => {
-                let out = serde_json::json!({"error": e});
-                to_json_c(&out)
-            }
+            Err(e) => to_json_c(&e.to_json()),
         }
     })
 }
@@ -398,10 +395,7 @@ pub unsafe extern "C" fn zpl_query_status_with_options(
 
         match common::query_printer_status_with_options(addr, timeout, config_str) {
             Ok(json) => to_c_string(&json),
-            Err(e) => {
-                let out = serde_json::json!({"error": e});
-                to_json_c(&out)
-            }
+            Err(e) => to_json_c(&e.to_json()),
         }
     })
 }
@@ -445,10 +439,7 @@ pub unsafe extern "C" fn zpl_query_info_with_options(
 
         match common::query_printer_info_with_options(addr, timeout, config_str) {
             Ok(json) => to_c_string(&json),
-            Err(e) => {
-                let out = serde_json::json!({"error": e});
-                to_json_c(&out)
-            }
+            Err(e) => to_json_c(&e.to_json()),
         }
     })
 }
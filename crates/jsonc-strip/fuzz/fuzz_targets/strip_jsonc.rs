@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zpl_toolchain_jsonc_strip::strip_jsonc;
+
+fuzz_target!(|input: &str| {
+    let out = strip_jsonc(input);
+    // Stripping only removes bytes.
+    assert!(out.len() <= input.len());
+    // Input without a `/` has nothing to strip.
+    if !input.contains('/') {
+        assert_eq!(out, input);
+    }
+});
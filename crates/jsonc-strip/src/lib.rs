@@ -4,64 +4,68 @@
 //! - `//` line comments
 //! - `/* ... */` block comments
 //! - string literal preservation (including escapes)
+//!
+//! Malformed input never panics and has well-defined output:
+//! - an unterminated string (including one ending in `\`) is emitted
+//!   verbatim to the end of input;
+//! - an unterminated `/*` comment runs to the end of input and is dropped;
+//! - a `//` comment ends before the newline, which is kept.
 
 /// Strip `//` and `/* */` comments from JSONC input.
 ///
 /// Correctly handles escaped quotes inside strings and comment-like sequences
-/// embedded in string literals.
+/// embedded in string literals. All delimiters are ASCII, so the input is
+/// scanned byte-wise and copied in slices that always fall on UTF-8 character
+/// boundaries. Escape sequences (including lone-surrogate `\uD800` escapes)
+/// are copied as written, never decoded.
 #[must_use]
 pub fn strip_jsonc(input: &str) -> String {
-    let mut out = String::with_capacity(input.len());
-    let chars: Vec<char> = input.chars().collect();
-    let len = chars.len();
+    let bytes = input.as_bytes();
+    let len = bytes.len();
+    let mut out = String::with_capacity(len);
+    // Start of the pending run of bytes to copy to `out`.
+    let mut copy_from = 0usize;
     let mut i = 0usize;
-    let mut in_str = false;
 
     while i < len {
-        let c = chars[i];
-
-        if in_str {
-            out.push(c);
-            if c == '\\' && i + 1 < len {
-                // Escaped character inside string: keep next char verbatim.
+        match bytes[i] {
+            b'"' => {
+                // Skip to the closing quote; an unterminated string runs to
+                // the end of input and is copied verbatim.
                 i += 1;
-                out.push(chars[i]);
-            } else if c == '"' {
-                in_str = false;
+                while i < len {
+                    match bytes[i] {
+                        // Escaped character: skip it, unless `\` is the last byte.
+                        b'\\' => i = (i + 2).min(len),
+                        b'"' => {
+                            i += 1;
+                            break;
+                        }
+                        _ => i += 1,
+                    }
+                }
             }
-            i += 1;
-            continue;
-        }
-
-        if c == '"' {
-            in_str = true;
-            out.push(c);
-            i += 1;
-            continue;
-        }
-
-        if c == '/' && i + 1 < len {
-            let c2 = chars[i + 1];
-            if c2 == '/' {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                out.push_str(&input[copy_from..i]);
                 i += 2;
-                while i < len && chars[i] != '\n' {
+                while i < len && bytes[i] != b'\n' {
                     i += 1;
                 }
-                continue;
+                copy_from = i;
             }
-            if c2 == '*' {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                out.push_str(&input[copy_from..i]);
                 i += 2;
-                while i + 1 < len && !(chars[i] == '*' && chars[i + 1] == '/') {
+                while i < len && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
                     i += 1;
                 }
                 i = (i + 2).min(len);
-                continue;
+                copy_from = i;
             }
+            _ => i += 1,
         }
-
-        out.push(c);
-        i += 1;
     }
+    out.push_str(&input[copy_from..]);
     out
 }
 
@@ -91,4 +95,38 @@ mod tests {
         assert!(stripped.contains("http://example.com/*x*/"));
         assert!(stripped.contains("\"note\":\"//keep\""));
     }
+
+    #[test]
+    fn trailing_backslash_is_kept() {
+        assert_eq!(strip_jsonc(r#"{"a": "x\"#), r#"{"a": "x\"#);
+        assert_eq!(strip_jsonc("\\"), "\\");
+        // An escaped quote does not close the string, so `//` stays literal.
+        assert_eq!(strip_jsonc(r#""a\" // b"#), r#""a\" // b"#);
+    }
+
+    #[test]
+    fn unterminated_block_comment_runs_to_end() {
+        assert_eq!(strip_jsonc(r#"{"a": 1 /* never closed"#), r#"{"a": 1 "#);
+        assert_eq!(strip_jsonc("/*"), "");
+        assert_eq!(strip_jsonc("/*/"), "");
+        assert_eq!(strip_jsonc("x/**/y"), "xy");
+    }
+
+    #[test]
+    fn unterminated_string_is_emitted_verbatim() {
+        let input = "{\"a\": \"open /* not a comment */ // nor this\n\"b\": é";
+        assert_eq!(strip_jsonc(input), input);
+    }
+
+    #[test]
+    fn multibyte_and_surrogate_escapes_are_preserved() {
+        let input = "{ \"k\": \"\\uD800 ✓\" /* ü */, \"é\": 1 } // ☃";
+        assert_eq!(strip_jsonc(input), "{ \"k\": \"\\uD800 ✓\" , \"é\": 1 } ");
+    }
+
+    #[test]
+    fn line_comment_keeps_newline() {
+        assert_eq!(strip_jsonc("1 // a\n2 //"), "1 \n2 ");
+        assert_eq!(strip_jsonc("/"), "/");
+    }
 }
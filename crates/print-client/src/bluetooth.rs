@@ -0,0 +1,343 @@
+//! Bluetooth RFCOMM transport for Zebra printers on Linux (BlueZ sockets).
+//!
+//! Feature-gated behind the `bluetooth` Cargo feature and only compiled on
+//! Linux. Connects straight to the printer's RFCOMM channel with an
+//! `AF_BLUETOOTH` socket, so no `rfcomm bind` / `/dev/rfcomm*` node is needed.
+//!
+//! RFCOMM links are bidirectional, so `BluetoothPrinter` implements both
+//! `Printer` and `StatusQuery` traits.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::net::Shutdown;
+use std::time::Duration;
+
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+
+use crate::frame::{expected_frame_count, max_frame_size, read_frames};
+use crate::trace::{self, TraceDirection};
+use crate::{PrintError, Printer, PrinterConfig, StatusQuery};
+
+/// `AF_BLUETOOTH` address family (`<sys/socket.h>`).
+const AF_BLUETOOTH: i32 = 31;
+/// `BTPROTO_RFCOMM` protocol number (`<bluetooth/bluetooth.h>`).
+const BTPROTO_RFCOMM: i32 = 3;
+/// RFCOMM channel Zebra printers expose their serial port profile on.
+const DEFAULT_CHANNEL: u8 = 1;
+
+/// `struct sockaddr_rc` from `<bluetooth/rfcomm.h>`.
+#[repr(C)]
+struct SockaddrRc {
+    rc_family: u16,
+    /// Device address in little-endian byte order (BlueZ `bdaddr_t`).
+    rc_bdaddr: [u8; 6],
+    rc_channel: u8,
+}
+
+/// A Bluetooth device address plus RFCOMM channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BluetoothAddr {
+    /// Device address octets in display order (`AA:BB:CC:DD:EE:FF` → `[0xAA, …, 0xFF]`).
+    pub mac: [u8; 6],
+    /// RFCOMM channel (1–30).
+    pub channel: u8,
+}
+
+impl BluetoothAddr {
+    /// Parse `AA:BB:CC:DD:EE:FF` or `AA:BB:CC:DD:EE:FF:<channel>`.
+    ///
+    /// Octets may also be separated by `-`. The channel defaults to 1, the
+    /// channel Zebra printers use for their serial port profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PrintError::InvalidAddress` for a malformed MAC or a channel
+    /// outside 1–30.
+    pub fn parse(s: &str) -> Result<Self, PrintError> {
+        let s = s.trim();
+        let invalid = |why: &str| {
+            PrintError::InvalidAddress(format!(
+                "invalid Bluetooth address '{}': {} (expected AA:BB:CC:DD:EE:FF[:CHANNEL])",
+                s, why
+            ))
+        };
+
+        let parts: Vec<&str> = s.split([':', '-']).collect();
+        if parts.len() != 6 && parts.len() != 7 {
+            return Err(invalid("expected 6 octets"));
+        }
+
+        let mut mac = [0u8; 6];
+        for (octet, part) in mac.iter_mut().zip(&parts) {
+            if part.len() != 2 {
+                return Err(invalid("each octet must be two hex digits"));
+            }
+            *octet = u8::from_str_radix(part, 16)
+                .map_err(|_| invalid("each octet must be two hex digits"))?;
+        }
+
+        let channel = match parts.get(6) {
+            Some(ch) => ch
+                .parse::<u8>()
+                .ok()
+                .filter(|c| (1..=30).contains(c))
+                .ok_or_else(|| invalid("RFCOMM channel must be 1-30"))?,
+            None => DEFAULT_CHANNEL,
+        };
+
+        Ok(Self { mac, channel })
+    }
+
+    /// Build the `sockaddr_rc` for this address.
+    fn to_sock_addr(self) -> io::Result<SockAddr> {
+        let mut bdaddr = self.mac;
+        bdaddr.reverse();
+        let raw = SockaddrRc {
+            rc_family: AF_BLUETOOTH as u16,
+            rc_bdaddr: bdaddr,
+            rc_channel: self.channel,
+        };
+        // SAFETY: `storage` points to a zeroed `sockaddr_storage`, which is
+        // larger than and at least as aligned as `sockaddr_rc`; `len` is set
+        // to the exact size written.
+        let ((), addr) = unsafe {
+            SockAddr::try_init(|storage, len| {
+                storage.cast::<SockaddrRc>().write(raw);
+                *len = std::mem::size_of::<SockaddrRc>() as _;
+                Ok(())
+            })
+        }?;
+        Ok(addr)
+    }
+}
+
+impl fmt::Display for BluetoothAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.mac;
+        write!(
+            f,
+            "{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{}",
+            a, b, c, d, e, g, self.channel
+        )
+    }
+}
+
+impl std::str::FromStr for BluetoothAddr {
+    type Err = PrintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// A Zebra printer connected over a Bluetooth RFCOMM socket (Linux).
+pub struct BluetoothPrinter {
+    socket: Socket,
+    config: PrinterConfig,
+    addr: BluetoothAddr,
+    /// Unique id for trace output correlation; renewed on reconnect.
+    trace_session_id: u64,
+}
+
+impl BluetoothPrinter {
+    /// Connect to a printer by address string (see [`BluetoothAddr::parse`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PrintError::InvalidAddress` for a malformed address, or a
+    /// connection error if the RFCOMM channel cannot be opened.
+    pub fn connect(addr: &str, config: PrinterConfig) -> Result<Self, PrintError> {
+        Self::connect_addr(BluetoothAddr::parse(addr)?, config)
+    }
+
+    /// Connect to a printer at an already-parsed address.
+    ///
+    /// Uses the connect timeout from [`PrinterConfig`] and applies its
+    /// write/read timeouts to the socket.
+    pub fn connect_addr(addr: BluetoothAddr, config: PrinterConfig) -> Result<Self, PrintError> {
+        let socket = Self::open_socket(addr, &config)?;
+        Ok(Self {
+            socket,
+            config,
+            addr,
+            trace_session_id: trace::next_session_id(),
+        })
+    }
+
+    /// Re-establish the RFCOMM connection after a drop or error.
+    pub fn reconnect(&mut self) -> Result<(), PrintError> {
+        let _ = self.socket.shutdown(Shutdown::Both);
+        self.socket = Self::open_socket(self.addr, &self.config)?;
+        self.trace_session_id = trace::next_session_id();
+        Ok(())
+    }
+
+    /// Return the address this printer is connected to.
+    pub fn remote_addr(&self) -> BluetoothAddr {
+        self.addr
+    }
+
+    fn open_socket(addr: BluetoothAddr, config: &PrinterConfig) -> Result<Socket, PrintError> {
+        let failed = |e: io::Error| PrintError::ConnectionFailed {
+            addr: addr.to_string(),
+            source: e,
+        };
+        let socket = Socket::new(
+            Domain::from(AF_BLUETOOTH),
+            Type::STREAM,
+            Some(Protocol::from(BTPROTO_RFCOMM)),
+        )
+        .map_err(failed)?;
+        let sock_addr = addr.to_sock_addr().map_err(failed)?;
+
+        socket
+            .connect_timeout(&sock_addr, config.timeouts.connect)
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::ConnectionRefused => PrintError::ConnectionRefused {
+                    addr: addr.to_string(),
+                    source: e,
+                },
+                io::ErrorKind::TimedOut => PrintError::ConnectionTimeout {
+                    addr: addr.to_string(),
+                    timeout: config.timeouts.connect,
+                    source: e,
+                },
+                _ => failed(e),
+            })?;
+
+        socket
+            .set_write_timeout(Some(config.timeouts.write))
+            .map_err(failed)?;
+        socket
+            .set_read_timeout(Some(config.timeouts.read))
+            .map_err(failed)?;
+        Ok(socket)
+    }
+}
+
+impl Printer for BluetoothPrinter {
+    fn send_raw(&mut self, data: &[u8]) -> Result<(), PrintError> {
+        trace::emit(
+            &self.config,
+            "bluetooth",
+            self.trace_session_id,
+            TraceDirection::Sent,
+            data,
+        );
+        self.socket
+            .write_all(data)
+            .map_err(PrintError::WriteFailed)?;
+        self.socket.flush().map_err(PrintError::WriteFailed)?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), PrintError> {
+        self.socket.flush().map_err(PrintError::WriteFailed)
+    }
+}
+
+impl StatusQuery for BluetoothPrinter {
+    fn query_raw(&mut self, cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
+        self.send_raw(cmd)?;
+
+        let expected_frames = expected_frame_count(cmd);
+
+        let frames = read_frames(
+            &mut self.socket,
+            expected_frames,
+            self.config.timeouts.read,
+            max_frame_size(cmd),
+        )?;
+        for frame in &frames {
+            trace::emit(
+                &self.config,
+                "bluetooth",
+                self.trace_session_id,
+                TraceDirection::Received,
+                frame,
+            );
+        }
+        Ok(frames)
+    }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        Some(self.config.timeouts.read)
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+        self.socket
+            .set_read_timeout(Some(timeout))
+            .map_err(|e| PrintError::ConnectionFailed {
+                addr: self.addr.to_string(),
+                source: e,
+            })?;
+        self.config.timeouts.read = timeout;
+        Ok(())
+    }
+}
+
+impl Drop for BluetoothPrinter {
+    fn drop(&mut self) {
+        let _ = self.socket.shutdown(Shutdown::Both);
+    }
+}
+
+impl crate::Reconnectable for BluetoothPrinter {
+    fn reconnect(&mut self) -> Result<(), PrintError> {
+        BluetoothPrinter::reconnect(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mac_with_channel() {
+        let addr = BluetoothAddr::parse("AA:BB:CC:DD:EE:FF:3").unwrap();
+        assert_eq!(addr.mac, [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+        assert_eq!(addr.channel, 3);
+        assert_eq!(addr.to_string(), "AA:BB:CC:DD:EE:FF:3");
+    }
+
+    #[test]
+    fn parse_mac_defaults_channel_and_accepts_dashes() {
+        let addr = BluetoothAddr::parse(" 00-11-22-aa-bb-cc ").unwrap();
+        assert_eq!(addr.mac, [0x00, 0x11, 0x22, 0xAA, 0xBB, 0xCC]);
+        assert_eq!(addr.channel, 1);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_addresses() {
+        for bad in [
+            "",
+            "AA:BB:CC:DD:EE",
+            "AA:BB:CC:DD:EE:GG",
+            "AAA:BB:CC:DD:EE:FF",
+            "AA:BB:CC:DD:EE:FF:0",
+            "AA:BB:CC:DD:EE:FF:31",
+            "AA:BB:CC:DD:EE:FF:x",
+            "AA:BB:CC:DD:EE:FF:1:2",
+        ] {
+            assert!(
+                matches!(
+                    BluetoothAddr::parse(bad),
+                    Err(PrintError::InvalidAddress(_))
+                ),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn sock_addr_stores_bdaddr_little_endian() {
+        let addr = BluetoothAddr::parse("AA:BB:CC:DD:EE:FF:5").unwrap();
+        let sa = addr.to_sock_addr().unwrap();
+        assert_eq!(sa.family() as i32, AF_BLUETOOTH);
+        assert_eq!(sa.len() as usize, std::mem::size_of::<SockaddrRc>());
+        // SAFETY: `as_ptr` points to a valid `sockaddr_rc` written above.
+        let raw = unsafe { &*sa.as_ptr().cast::<SockaddrRc>() };
+        assert_eq!(raw.rc_bdaddr, [0xFF, 0xEE, 0xDD, 0xCC, 0xBB, 0xAA]);
+        assert_eq!(raw.rc_channel, 5);
+    }
+}
@@ -2,9 +2,11 @@
 
 use std::time::Duration;
 
+use crate::trace::TraceSink;
+
 /// Complete printer configuration: timeouts + retry settings.
 #[non_exhaustive]
-#[derive(Debug, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct PrinterConfig {
     /// Network/transport timeout settings.
     pub timeouts: PrinterTimeouts,
@@ -12,8 +14,23 @@ pub struct PrinterConfig {
     pub retry: RetryConfig,
     /// Enable transport-level byte tracing for diagnostics.
     ///
-    /// When enabled, transports may emit hex/ASCII byte dumps to stderr.
+    /// When enabled without a [`trace_sink`](Self::trace_sink), transports
+    /// emit hex/ASCII byte dumps to stderr.
     pub trace_io: bool,
+    /// Receives every byte transfer on the transport, e.g. to route traces
+    /// to an application logger. Takes precedence over `trace_io`.
+    pub trace_sink: Option<TraceSink>,
+}
+
+impl std::fmt::Debug for PrinterConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrinterConfig")
+            .field("timeouts", &self.timeouts)
+            .field("retry", &self.retry)
+            .field("trace_io", &self.trace_io)
+            .field("trace_sink", &self.trace_sink.as_ref().map(|_| "<fn>"))
+            .finish()
+    }
 }
 
 /// Timeout settings for printer connections.
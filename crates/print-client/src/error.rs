@@ -141,6 +141,34 @@ pub enum PrintError {
 }
 
 impl PrintError {
+    /// Stable kebab-case code for this error, e.g. `"connection-refused"`.
+    ///
+    /// Unlike the [`Display`](fmt::Display) message, codes do not change
+    /// between releases, so callers (and bindings) can branch on them.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PrintError::ConnectionRefused { .. } => "connection-refused",
+            PrintError::ConnectionTimeout { .. } => "connection-timeout",
+            PrintError::ConnectionFailed { .. } => "connection-failed",
+            PrintError::ConnectionClosed => "connection-closed",
+            PrintError::InvalidAddress(_) => "invalid-address",
+            PrintError::NoAddressFound(_) => "no-address-found",
+            PrintError::WriteFailed(_) => "write-failed",
+            PrintError::ReadFailed(_) => "read-failed",
+            PrintError::ReadTimeout => "read-timeout",
+            PrintError::MalformedFrame { .. } => "malformed-frame",
+            PrintError::FrameTooLarge { .. } => "frame-too-large",
+            PrintError::PrinterError(_) => "printer-error",
+            PrintError::RetriesExhausted { .. } => "retries-exhausted",
+            PrintError::PreflightFailed => "preflight-failed",
+            PrintError::InvalidConfig(_) => "invalid-config",
+            PrintError::UsbDeviceNotFound => "usb-device-not-found",
+            PrintError::UsbError(_) => "usb-error",
+            PrintError::SerialError(_) => "serial-error",
+            PrintError::CompletionTimeout { .. } => "completion-timeout",
+        }
+    }
+
     /// Returns `true` if this error is transient and worth retrying.
     pub fn is_retryable(&self) -> bool {
         matches!(
@@ -193,6 +221,77 @@ impl fmt::Display for PrinterErrorKind {
 mod tests {
     use super::*;
 
+    #[test]
+    fn every_variant_has_a_stable_code() {
+        let io = || io::Error::other("x");
+        let cases = [
+            (
+                PrintError::ConnectionRefused {
+                    addr: "x".into(),
+                    source: io(),
+                },
+                "connection-refused",
+            ),
+            (
+                PrintError::ConnectionTimeout {
+                    addr: "x".into(),
+                    timeout: Duration::from_secs(1),
+                    source: io(),
+                },
+                "connection-timeout",
+            ),
+            (
+                PrintError::ConnectionFailed {
+                    addr: "x".into(),
+                    source: io(),
+                },
+                "connection-failed",
+            ),
+            (PrintError::ConnectionClosed, "connection-closed"),
+            (PrintError::InvalidAddress("x".into()), "invalid-address"),
+            (PrintError::NoAddressFound("x".into()), "no-address-found"),
+            (PrintError::WriteFailed(io()), "write-failed"),
+            (PrintError::ReadFailed(io()), "read-failed"),
+            (PrintError::ReadTimeout, "read-timeout"),
+            (
+                PrintError::MalformedFrame {
+                    details: "x".into(),
+                },
+                "malformed-frame",
+            ),
+            (
+                PrintError::FrameTooLarge { size: 2, max: 1 },
+                "frame-too-large",
+            ),
+            (
+                PrintError::PrinterError(PrinterErrorKind::PaperOut),
+                "printer-error",
+            ),
+            (
+                PrintError::RetriesExhausted {
+                    attempts: 3,
+                    last_error: Box::new(PrintError::ReadTimeout),
+                },
+                "retries-exhausted",
+            ),
+            (PrintError::PreflightFailed, "preflight-failed"),
+            (PrintError::InvalidConfig("x".into()), "invalid-config"),
+            (PrintError::UsbDeviceNotFound, "usb-device-not-found"),
+            (PrintError::UsbError("x".into()), "usb-error"),
+            (PrintError::SerialError("x".into()), "serial-error"),
+            (
+                PrintError::CompletionTimeout {
+                    formats_in_buffer: 1,
+                    labels_remaining: 1,
+                },
+                "completion-timeout",
+            ),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.code(), code, "{err}");
+        }
+    }
+
     #[test]
     fn retryable_errors() {
         assert!(
@@ -21,12 +21,127 @@ const ETX: u8 = 0x03;
 /// per frame; this guard prevents runaway reads from a misbehaving printer.
 pub(crate) const DEFAULT_MAX_FRAME_SIZE: usize = 1024;
 
+/// Maximum frame size for `^HW` directory listings (64 KB), which return one
+/// line per stored object in a single frame.
+const LISTING_MAX_FRAME_SIZE: usize = 64 * 1024;
+
 /// Internal state of the frame parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum FrameState {
     /// Waiting for a STX byte; skip any garbage (CR, LF, etc.) between frames.
     WaitingForStx,
     /// Inside a frame -- collecting bytes until ETX.
     ReadingFrame,
+    /// Inside a frame that outgrew the size limit -- dropping bytes until
+    /// ETX, then resynchronizing on the next STX.
+    DiscardingFrame,
+}
+
+/// Incremental STX/ETX frame assembler for continuously read streams.
+///
+/// Feed it whatever each read returns; a frame split across reads is
+/// buffered until its ETX arrives. Bytes between frames are ignored.
+///
+/// ```
+/// use zpl_toolchain_print_client::FrameReader;
+///
+/// let mut reader = FrameReader::new(1024);
+/// assert!(reader.push(b"\x02030,0,0").is_empty());
+/// assert_eq!(reader.push(b",1245\x03\r\n"), vec![b"030,0,0,1245".to_vec()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameReader {
+    state: FrameState,
+    current: Vec<u8>,
+    max_frame_size: usize,
+}
+
+impl Default for FrameReader {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_SIZE)
+    }
+}
+
+impl FrameReader {
+    /// Create a reader that accepts frames of up to `max_frame_size` bytes.
+    pub fn new(max_frame_size: usize) -> Self {
+        Self {
+            state: FrameState::WaitingForStx,
+            current: Vec::with_capacity(256),
+            max_frame_size,
+        }
+    }
+
+    /// Whether a frame has started (STX seen) but not yet ended.
+    pub fn has_partial_frame(&self) -> bool {
+        self.state != FrameState::WaitingForStx
+    }
+
+    /// Buffer `bytes` and return every frame they complete, in order.
+    ///
+    /// A frame larger than the size limit is dropped, and the reader picks
+    /// up again at the next STX, which suits long-lived sessions. Use
+    /// [`try_push`](Self::try_push) to treat it as an error instead.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<Vec<u8>> {
+        let mut frames = Vec::new();
+        for &byte in bytes {
+            if let Some(frame) = self.step(byte, true).unwrap_or(None) {
+                frames.push(frame);
+            }
+        }
+        frames
+    }
+
+    /// Like [`push`](Self::push), but fails with
+    /// [`PrintError::FrameTooLarge`] as soon as a frame outgrows the limit.
+    /// The partial frame is discarded, so the reader can keep going.
+    pub fn try_push(&mut self, bytes: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
+        let mut frames = Vec::new();
+        for &byte in bytes {
+            if let Some(frame) = self.step(byte, false)? {
+                frames.push(frame);
+            }
+        }
+        Ok(frames)
+    }
+
+    fn step(&mut self, byte: u8, discard_oversized: bool) -> Result<Option<Vec<u8>>, PrintError> {
+        match (self.state, byte) {
+            (FrameState::WaitingForStx, STX) => {
+                self.current.clear();
+                self.state = FrameState::ReadingFrame;
+            }
+            (FrameState::WaitingForStx, _) => {
+                // Skip CR, LF, and any garbage between frames
+            }
+            (FrameState::ReadingFrame, ETX) => {
+                self.state = FrameState::WaitingForStx;
+                return Ok(Some(std::mem::take(&mut self.current)));
+            }
+            (FrameState::ReadingFrame, _) => {
+                if self.current.len() >= self.max_frame_size {
+                    let size = self.current.len() + 1;
+                    self.current.clear();
+                    if discard_oversized {
+                        self.state = FrameState::DiscardingFrame;
+                    } else {
+                        self.state = FrameState::WaitingForStx;
+                        return Err(PrintError::FrameTooLarge {
+                            size,
+                            max: self.max_frame_size,
+                        });
+                    }
+                } else {
+                    self.current.push(byte);
+                }
+            }
+            (FrameState::DiscardingFrame, ETX) => {
+                self.state = FrameState::WaitingForStx;
+            }
+            (FrameState::DiscardingFrame, _) => {}
+        }
+        Ok(None)
+    }
 }
 
 /// Read exactly `expected_count` STX/ETX framed responses from a stream.
@@ -42,7 +157,8 @@ enum FrameState {
 ///
 /// A `Vec` of frame payloads (bytes between STX and ETX, exclusive).
 /// Each frame is the raw comma-separated data -- the caller is responsible
-/// for parsing the fields.
+/// for parsing the fields. Frames arriving after the first `expected_count`
+/// in the same read are discarded.
 pub fn read_frames(
     stream: &mut impl Read,
     expected_count: usize,
@@ -54,8 +170,7 @@ pub fn read_frames(
         .checked_add(timeout)
         .unwrap_or_else(|| now + Duration::from_secs(86400));
     let mut frames: Vec<Vec<u8>> = Vec::with_capacity(expected_count);
-    let mut current_frame: Vec<u8> = Vec::with_capacity(256);
-    let mut state = FrameState::WaitingForStx;
+    let mut reader = FrameReader::new(max_frame_size);
     let mut buf = [0u8; 512];
 
     while frames.len() < expected_count {
@@ -86,35 +201,10 @@ pub fn read_frames(
             }
         };
 
-        for &byte in &buf[..n] {
-            match (&state, byte) {
-                (FrameState::WaitingForStx, STX) => {
-                    current_frame.clear();
-                    state = FrameState::ReadingFrame;
-                }
-                (FrameState::WaitingForStx, _) => {
-                    // Skip CR, LF, and any garbage between frames
-                }
-                (FrameState::ReadingFrame, ETX) => {
-                    frames.push(std::mem::take(&mut current_frame));
-                    state = FrameState::WaitingForStx;
-                    if frames.len() >= expected_count {
-                        return Ok(frames);
-                    }
-                }
-                (FrameState::ReadingFrame, _) => {
-                    if current_frame.len() >= max_frame_size {
-                        return Err(PrintError::FrameTooLarge {
-                            size: current_frame.len() + 1,
-                            max: max_frame_size,
-                        });
-                    }
-                    current_frame.push(byte);
-                }
-            }
-        }
+        frames.extend(reader.try_push(&buf[..n])?);
     }
 
+    frames.truncate(expected_count);
     Ok(frames)
 }
 
@@ -123,11 +213,58 @@ pub fn expected_frame_count(cmd: &[u8]) -> usize {
     if cmd.starts_with(b"~HS") { 3 } else { 1 }
 }
 
+/// Determine the maximum frame size to accept for a given command.
+pub(crate) fn max_frame_size(cmd: &[u8]) -> usize {
+    if cmd.windows(3).any(|w| w == b"^HW") {
+        LISTING_MAX_FRAME_SIZE
+    } else {
+        DEFAULT_MAX_FRAME_SIZE
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::Cursor;
 
+    #[test]
+    fn directory_listing_allows_larger_frames() {
+        assert_eq!(max_frame_size(b"~HI"), DEFAULT_MAX_FRAME_SIZE);
+        assert_eq!(max_frame_size(b"^XA^HWR:*.*^XZ"), LISTING_MAX_FRAME_SIZE);
+    }
+
+    #[test]
+    fn frame_reader_reassembles_frame_split_across_pushes() {
+        let mut reader = FrameReader::default();
+        assert!(reader.push(b"\r\n\x02030,0,0,12").is_empty());
+        assert!(reader.has_partial_frame());
+        let frames = reader.push(b"45,000\x03\r\n\x02next");
+        assert_eq!(frames, vec![b"030,0,0,1245,000".to_vec()]);
+        assert_eq!(reader.push(b"\x03"), vec![b"next".to_vec()]);
+        assert!(!reader.has_partial_frame());
+    }
+
+    #[test]
+    fn frame_reader_yields_several_frames_from_one_push() {
+        let mut reader = FrameReader::default();
+        let frames = reader.push(b"\x02a\x03\r\n\x02\x03\x02bc\x03");
+        assert_eq!(frames, vec![b"a".to_vec(), Vec::new(), b"bc".to_vec()]);
+    }
+
+    #[test]
+    fn frame_reader_drops_oversized_frame_and_resyncs() {
+        let mut reader = FrameReader::new(4);
+        assert!(reader.push(b"\x02toolong").is_empty());
+        assert_eq!(reader.push(b"\x03\x02ok\x03"), vec![b"ok".to_vec()]);
+
+        let mut strict = FrameReader::new(4);
+        match strict.try_push(b"\x02toolong\x03") {
+            Err(PrintError::FrameTooLarge { max, .. }) => assert_eq!(max, 4),
+            other => panic!("expected FrameTooLarge, got {:?}", other),
+        }
+        assert!(!strict.has_partial_frame());
+    }
+
     #[test]
     fn reads_single_framed_payload() {
         let data = [0x02, b'H', b'e', b'l', b'l', b'o', 0x03];
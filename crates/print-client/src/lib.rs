@@ -1,8 +1,11 @@
 //! ZPL Print Client — send ZPL to Zebra and ZPL-compatible printers.
 //!
-//! Supports TCP (port 9100), USB, and serial/Bluetooth SPP transports.
+//! Supports TCP (port 9100), USB, serial/Bluetooth SPP, and (on Linux) direct
+//! Bluetooth RFCOMM transports.
 //! The core API is synchronous (`std::net`), with no async runtime required.
 mod addr;
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+mod bluetooth;
 mod config;
 mod error;
 mod frame;
@@ -11,25 +14,34 @@ mod retry;
 #[cfg(feature = "serial")]
 mod serial;
 mod status;
+mod target;
 #[cfg(feature = "tcp")]
 mod tcp;
+mod trace;
 #[cfg(feature = "usb")]
 mod usb;
 
 #[cfg(feature = "tcp")]
 pub use addr::resolve_printer_addr;
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+pub use bluetooth::{BluetoothAddr, BluetoothPrinter};
 pub use config::{BatchOptions, PrinterConfig, PrinterTimeouts, RetryConfig};
 pub use error::{PrintError, PrinterErrorKind};
-pub use frame::{expected_frame_count, read_frames};
+pub use frame::{FrameReader, expected_frame_count, read_frames};
 pub use job::{JobId, JobPhase, create_job_id};
-pub use retry::{ReconnectRetryPrinter, RetryPrinter};
+pub use retry::{BackoffIter, ReconnectRetryPrinter, RetryPrinter};
 #[cfg(feature = "serial")]
 pub use serial::{
     SerialDataBits, SerialFlowControl, SerialParity, SerialPrinter, SerialSettings, SerialStopBits,
 };
-pub use status::{HostStatus, PrintMode, PrinterInfo};
+pub use status::{
+    AlertSeverity, HeadDiagnostics, HostStatus, MemoryStatus, PrintMode, PrinterAlert, PrinterInfo,
+    PrinterSnapshot, StoredObject,
+};
+pub use target::{PrinterTarget, connect_any};
 #[cfg(feature = "tcp")]
 pub use tcp::TcpPrinter;
+pub use trace::{TraceDirection, TraceEvent, TraceSink, stderr_trace_sink};
 #[cfg(feature = "usb")]
 pub use usb::UsbPrinter;
 
@@ -47,6 +59,27 @@ pub trait Printer: Send {
     fn send_zpl(&mut self, zpl: &str) -> Result<(), PrintError> {
         self.send_raw(zpl.as_bytes())
     }
+
+    /// Send several chunks back to back, in order.
+    ///
+    /// The default concatenates them and calls [`send_raw`](Self::send_raw)
+    /// once, so a multi-file job costs one write instead of one per file.
+    fn send_all(&mut self, chunks: &[&[u8]]) -> Result<(), PrintError> {
+        match chunks {
+            [] => Ok(()),
+            [chunk] => self.send_raw(chunk),
+            _ => self.send_raw(&chunks.concat()),
+        }
+    }
+
+    /// Push any bytes still buffered by the transport onto the wire.
+    ///
+    /// Call between sending labels and querying status so the query cannot
+    /// overtake the data. The default does nothing, for transports whose
+    /// writes are unbuffered.
+    fn flush(&mut self) -> Result<(), PrintError> {
+        Ok(())
+    }
 }
 
 /// Query printer status. Only bidirectional transports implement this.
@@ -54,6 +87,50 @@ pub trait StatusQuery: Printer {
     /// Send a command and read the raw STX/ETX framed response.
     fn query_raw(&mut self, cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError>;
 
+    /// Read timeout currently used by [`query_raw`](Self::query_raw), or
+    /// `None` if this transport does not support overriding it.
+    fn read_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Set the read timeout used by subsequent queries.
+    ///
+    /// The default implementation reports the override as unsupported.
+    fn set_read_timeout(&mut self, _timeout: Duration) -> Result<(), PrintError> {
+        Err(PrintError::InvalidConfig(
+            "this transport does not support read timeout overrides".into(),
+        ))
+    }
+
+    /// Send a command with a one-off read timeout, restoring the previous
+    /// timeout afterwards (also when the query fails).
+    ///
+    /// Useful for slow queries such as `~HQJT` without raising the timeout
+    /// for every other query. Transports that cannot override the timeout
+    /// (see [`read_timeout`](Self::read_timeout)) fall back to
+    /// [`query_raw`](Self::query_raw) with their configured timeout.
+    fn query_raw_with_timeout(
+        &mut self,
+        cmd: &[u8],
+        read_timeout: Duration,
+    ) -> Result<Vec<Vec<u8>>, PrintError> {
+        if read_timeout.is_zero() {
+            return Err(PrintError::InvalidConfig(
+                "read timeout must be greater than zero".into(),
+            ));
+        }
+        let Some(previous) = self.read_timeout() else {
+            return self.query_raw(cmd);
+        };
+        self.set_read_timeout(read_timeout)?;
+        let result = self.query_raw(cmd);
+        let restored = self.set_read_timeout(previous);
+        match (result, restored) {
+            (Ok(_), Err(e)) => Err(e),
+            (result, _) => result,
+        }
+    }
+
     /// Query printer status via `~HS` and parse the response.
     fn query_status(&mut self) -> Result<HostStatus, PrintError> {
         let frames = self.query_raw(b"~HS")?;
@@ -65,6 +142,116 @@ pub trait StatusQuery: Printer {
         let frames = self.query_raw(b"~HI")?;
         PrinterInfo::parse(&frames)
     }
+
+    /// Query printhead diagnostics via `~HD` and parse the response.
+    fn query_head_diagnostics(&mut self) -> Result<HeadDiagnostics, PrintError> {
+        let frames = self.query_raw(b"~HD")?;
+        HeadDiagnostics::parse(&frames)
+    }
+
+    /// Query RAM status via `~HM` and parse the response.
+    fn query_memory(&mut self) -> Result<MemoryStatus, PrintError> {
+        let frames = self.query_raw(b"~HM")?;
+        MemoryStatus::parse(&frames)
+    }
+
+    /// List the objects stored on `drive` (e.g. `'R'`, `'E'`) via `^HW`.
+    ///
+    /// Useful to check whether a graphic or font is already on the printer
+    /// before downloading it again.
+    fn list_objects(&mut self, drive: char) -> Result<Vec<StoredObject>, PrintError> {
+        if !drive.is_ascii_alphabetic() {
+            return Err(PrintError::InvalidConfig(format!(
+                "invalid drive letter {drive:?}"
+            )));
+        }
+        let cmd = format!("^XA^HW{}:*.*^XZ", drive.to_ascii_uppercase());
+        let frames = self.query_raw(cmd.as_bytes())?;
+        StoredObject::parse_listing(&frames)
+    }
+
+    /// Query status (`~HS`), info (`~HI`), and memory (`~HM`) in turn.
+    ///
+    /// A failed query leaves its part of the snapshot `None` and does not
+    /// stop the remaining queries. When every query fails, the `~HS` error is
+    /// returned.
+    fn query_snapshot(&mut self) -> Result<PrinterSnapshot, PrintError> {
+        match (self.query_status(), self.query_info(), self.query_memory()) {
+            (Err(err), Err(_), Err(_)) => Err(err),
+            (status, info, memory) => Ok(PrinterSnapshot {
+                status: status.ok(),
+                info: info.ok(),
+                memory: memory.ok(),
+            }),
+        }
+    }
+}
+
+// Forwarding impls so boxed printers (e.g. from [`connect_any`]) work with
+// the generic helpers below.
+
+impl<P: Printer + ?Sized> Printer for Box<P> {
+    fn send_raw(&mut self, data: &[u8]) -> Result<(), PrintError> {
+        (**self).send_raw(data)
+    }
+
+    fn send_zpl(&mut self, zpl: &str) -> Result<(), PrintError> {
+        (**self).send_zpl(zpl)
+    }
+
+    fn send_all(&mut self, chunks: &[&[u8]]) -> Result<(), PrintError> {
+        (**self).send_all(chunks)
+    }
+
+    fn flush(&mut self) -> Result<(), PrintError> {
+        (**self).flush()
+    }
+}
+
+impl<P: StatusQuery + ?Sized> StatusQuery for Box<P> {
+    fn query_raw(&mut self, cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
+        (**self).query_raw(cmd)
+    }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        (**self).read_timeout()
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+        (**self).set_read_timeout(timeout)
+    }
+
+    fn query_raw_with_timeout(
+        &mut self,
+        cmd: &[u8],
+        read_timeout: Duration,
+    ) -> Result<Vec<Vec<u8>>, PrintError> {
+        (**self).query_raw_with_timeout(cmd, read_timeout)
+    }
+
+    fn query_status(&mut self) -> Result<HostStatus, PrintError> {
+        (**self).query_status()
+    }
+
+    fn query_info(&mut self) -> Result<PrinterInfo, PrintError> {
+        (**self).query_info()
+    }
+
+    fn query_head_diagnostics(&mut self) -> Result<HeadDiagnostics, PrintError> {
+        (**self).query_head_diagnostics()
+    }
+
+    fn query_memory(&mut self) -> Result<MemoryStatus, PrintError> {
+        (**self).query_memory()
+    }
+
+    fn list_objects(&mut self, drive: char) -> Result<Vec<StoredObject>, PrintError> {
+        (**self).list_objects(drive)
+    }
+
+    fn query_snapshot(&mut self) -> Result<PrinterSnapshot, PrintError> {
+        (**self).query_snapshot()
+    }
 }
 
 /// A printer that can re-establish its connection after a failure.
@@ -258,7 +445,7 @@ where
 
         let status = if let Some(interval) = opts.status_interval {
             if (i + 1) % interval.get() == 0 {
-                printer.query_status().ok()
+                printer.flush().and_then(|()| printer.query_status()).ok()
             } else {
                 None
             }
@@ -312,6 +499,8 @@ pub fn wait_for_completion<S: StatusQuery>(
     poll_interval: Duration,
     timeout: Duration,
 ) -> Result<(), PrintError> {
+    printer.flush()?;
+
     let now = Instant::now();
     let deadline = now
         .checked_add(timeout)
@@ -358,6 +547,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn send_all_coalesces_chunks_into_one_write() {
+        let mut printer = MockBatchPrinter {
+            sent: Vec::new(),
+            fail_on: None,
+        };
+        let chunks: [&[u8]; 3] = [b"^XA^FDOne^FS^XZ", b"", b"^XA^FDTwo^FS^XZ"];
+        printer.send_all(&chunks).unwrap();
+        assert_eq!(
+            printer.sent,
+            vec![b"^XA^FDOne^FS^XZ^XA^FDTwo^FS^XZ".to_vec()]
+        );
+
+        printer.send_all(&[]).unwrap();
+        assert_eq!(printer.sent.len(), 1, "no chunks means no write");
+    }
+
     #[test]
     fn batch_happy_path() {
         let mut printer = MockBatchPrinter {
@@ -696,6 +902,65 @@ mod tests {
         }
     }
 
+    // ── MockFlushPrinter (records send/flush/query order) ─────────────
+
+    #[derive(Default)]
+    struct MockFlushPrinter {
+        ops: Vec<&'static str>,
+    }
+
+    impl Printer for MockFlushPrinter {
+        fn send_raw(&mut self, _data: &[u8]) -> Result<(), PrintError> {
+            self.ops.push("send");
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), PrintError> {
+            self.ops.push("flush");
+            Ok(())
+        }
+    }
+
+    impl StatusQuery for MockFlushPrinter {
+        fn query_raw(&mut self, _cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
+            self.ops.push("query");
+            Ok(vec![
+                b"030,0,0,1245,000,0,0,0,000,0,0,0".to_vec(),
+                b"000,0,0,0,0,2,0,0,00000000,0,000".to_vec(),
+                b"1234,0".to_vec(),
+            ])
+        }
+    }
+
+    #[test]
+    fn wait_for_completion_flushes_before_first_query() {
+        let mut printer = MockFlushPrinter::default();
+        printer.send_zpl("^XA^XZ").unwrap();
+        wait_for_completion(
+            &mut printer,
+            Duration::from_millis(1),
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert_eq!(printer.ops, ["send", "flush", "query"]);
+    }
+
+    #[test]
+    fn batch_status_query_flushes_after_send() {
+        use std::num::NonZeroUsize;
+
+        let mut printer = MockFlushPrinter::default();
+        let opts = BatchOptions {
+            status_interval: Some(NonZeroUsize::new(2).unwrap()),
+            ..BatchOptions::default()
+        };
+        send_batch_with_status(&mut printer, &["L1", "L2"], &opts, |_| {
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(printer.ops, ["send", "send", "flush", "query"]);
+    }
+
     // ── MockFormatsInBufferPrinter (formats_in_buffer blocks completion) ──
 
     struct MockFormatsInBufferPrinter {
@@ -735,4 +1000,155 @@ mod tests {
             "should have polled until formats cleared"
         );
     }
+
+    // ── MockSnapshotPrinter (for query_snapshot tests) ───────────────
+
+    struct MockSnapshotPrinter {
+        queries: Vec<Vec<u8>>,
+        silent: bool,
+    }
+
+    impl Printer for MockSnapshotPrinter {
+        fn send_raw(&mut self, _data: &[u8]) -> Result<(), PrintError> {
+            Ok(())
+        }
+    }
+
+    impl StatusQuery for MockSnapshotPrinter {
+        fn query_raw(&mut self, cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
+            self.queries.push(cmd.to_vec());
+            match cmd {
+                _ if self.silent => Err(PrintError::ReadTimeout),
+                b"~HS" => Ok(vec![
+                    b"030,0,0,1245,000,0,0,0,000,0,0,0".to_vec(),
+                    b"000,0,0,0,0,2,0,0,00000000,0,000".to_vec(),
+                    b"1234,0".to_vec(),
+                ]),
+                b"~HI" => Ok(vec![b"ZD421-300dpi,V84.20.18Z,12,8192KB".to_vec()]),
+                _ => Err(PrintError::ReadTimeout),
+            }
+        }
+    }
+
+    #[test]
+    fn query_snapshot_keeps_successful_parts_when_one_query_fails() {
+        let mut printer = MockSnapshotPrinter {
+            queries: Vec::new(),
+            silent: false,
+        };
+        let snapshot = printer.query_snapshot().expect("snapshot");
+
+        assert_eq!(
+            printer.queries,
+            vec![b"~HS".to_vec(), b"~HI".to_vec(), b"~HM".to_vec()]
+        );
+        assert!(snapshot.status.is_some());
+        assert_eq!(snapshot.info.as_ref().map(|i| i.memory_kb), Some(8192));
+        assert!(snapshot.memory.is_none());
+        assert!(!snapshot.is_complete());
+    }
+
+    #[test]
+    fn query_snapshot_fails_when_every_query_fails() {
+        let mut printer = MockSnapshotPrinter {
+            queries: Vec::new(),
+            silent: true,
+        };
+        let err = printer.query_snapshot().unwrap_err();
+
+        assert!(matches!(err, PrintError::ReadTimeout), "{err:?}");
+        assert_eq!(printer.queries.len(), 3);
+    }
+
+    // ── MockTimeoutPrinter (for query_raw_with_timeout tests) ────────
+
+    struct MockTimeoutPrinter {
+        read_timeout: Duration,
+        /// Read timeout observed by each `query_raw` call.
+        seen: Vec<Duration>,
+        fail_query: bool,
+    }
+
+    impl Printer for MockTimeoutPrinter {
+        fn send_raw(&mut self, _data: &[u8]) -> Result<(), PrintError> {
+            Ok(())
+        }
+    }
+
+    impl StatusQuery for MockTimeoutPrinter {
+        fn query_raw(&mut self, _cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
+            self.seen.push(self.read_timeout);
+            if self.fail_query {
+                return Err(PrintError::ReadTimeout);
+            }
+            Ok(vec![b"PASS".to_vec()])
+        }
+
+        fn read_timeout(&self) -> Option<Duration> {
+            Some(self.read_timeout)
+        }
+
+        fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+            self.read_timeout = timeout;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn query_raw_with_timeout_applies_and_restores_override() {
+        let mut printer = MockTimeoutPrinter {
+            read_timeout: Duration::from_secs(10),
+            seen: Vec::new(),
+            fail_query: false,
+        };
+        let frames = printer
+            .query_raw_with_timeout(b"~HQJT", Duration::from_secs(90))
+            .unwrap();
+        assert_eq!(frames, vec![b"PASS".to_vec()]);
+        assert_eq!(printer.seen, vec![Duration::from_secs(90)]);
+        assert_eq!(printer.read_timeout, Duration::from_secs(10));
+
+        // Subsequent queries use the original timeout again.
+        printer.query_raw(b"~HS").unwrap();
+        assert_eq!(printer.seen[1], Duration::from_secs(10));
+    }
+
+    #[test]
+    fn query_raw_with_timeout_restores_after_error() {
+        let mut printer = MockTimeoutPrinter {
+            read_timeout: Duration::from_secs(10),
+            seen: Vec::new(),
+            fail_query: true,
+        };
+        let result = printer.query_raw_with_timeout(b"~HQJT", Duration::from_secs(90));
+        assert!(matches!(result, Err(PrintError::ReadTimeout)));
+        assert_eq!(printer.seen, vec![Duration::from_secs(90)]);
+        assert_eq!(printer.read_timeout, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn query_raw_with_timeout_rejects_zero() {
+        let mut printer = MockTimeoutPrinter {
+            read_timeout: Duration::from_secs(10),
+            seen: Vec::new(),
+            fail_query: false,
+        };
+        let result = printer.query_raw_with_timeout(b"~HS", Duration::ZERO);
+        assert!(matches!(result, Err(PrintError::InvalidConfig(_))));
+        assert!(printer.seen.is_empty());
+    }
+
+    #[test]
+    fn query_raw_with_timeout_falls_back_without_override_support() {
+        let mut printer = MockStatusPrinter {
+            sent: Vec::new(),
+            fail_on: None,
+            status_queries: 0,
+        };
+        let frames = printer
+            .query_raw_with_timeout(b"~HS", Duration::from_secs(90))
+            .unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(printer.status_queries, 1);
+    }
 }
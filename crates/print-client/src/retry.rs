@@ -52,16 +52,94 @@ impl<P: Printer> Printer for RetryPrinter<P> {
     fn send_raw(&mut self, data: &[u8]) -> Result<(), PrintError> {
         retry_op(&self.retry_config, || self.inner.send_raw(data))
     }
+
+    fn flush(&mut self) -> Result<(), PrintError> {
+        self.inner.flush()
+    }
 }
 
 impl<P: StatusQuery> StatusQuery for RetryPrinter<P> {
     fn query_raw(&mut self, cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
         retry_op(&self.retry_config, || self.inner.query_raw(cmd))
     }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        self.inner.read_timeout()
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+        self.inner.set_read_timeout(timeout)
+    }
+}
+
+// ── Backoff ────────────────────────────────────────────────────────────
+
+/// Exponential-backoff delays for a [`RetryConfig`].
+///
+/// Yields the delay to wait before each retry: `max_attempts - 1` values,
+/// doubling from `initial_delay` and capped at `max_delay`. With `jitter`,
+/// each delay is drawn from `[capped / 2, capped]`.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use zpl_toolchain_print_client::{BackoffIter, RetryConfig};
+///
+/// let mut config = RetryConfig::default();
+/// config.max_attempts = 5;
+/// config.initial_delay = Duration::from_millis(100);
+/// config.max_delay = Duration::from_millis(300);
+/// config.jitter = false;
+/// let delays: Vec<u64> = BackoffIter::new(&config)
+///     .map(|d| d.as_millis() as u64)
+///     .collect();
+/// assert_eq!(delays, [100, 200, 300, 300]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BackoffIter {
+    config: RetryConfig,
+    attempt: u32,
+}
+
+impl BackoffIter {
+    /// Create the delay sequence for `config`.
+    pub fn new(config: &RetryConfig) -> Self {
+        Self {
+            config: config.clone(),
+            attempt: 0,
+        }
+    }
 }
 
+impl Iterator for BackoffIter {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        if self.attempt + 1 >= self.config.max_attempts {
+            return None;
+        }
+        let delay = compute_delay(&self.config, self.attempt);
+        self.attempt += 1;
+        Some(delay)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.config.max_attempts.saturating_sub(self.attempt + 1) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for BackoffIter {}
+
 // ── Retry helper ───────────────────────────────────────────────────────
 
+/// Wrap the last retryable error once every attempt has failed.
+fn exhausted(config: &RetryConfig, last_error: PrintError) -> PrintError {
+    PrintError::RetriesExhausted {
+        attempts: config.max_attempts,
+        last_error: Box::new(last_error),
+    }
+}
+
 /// Execute `op`, retrying on retryable errors with exponential backoff.
 ///
 /// Non-retryable errors are returned immediately. On exhausting all attempts
@@ -76,35 +154,23 @@ where
         ));
     }
 
-    let mut last_error: Option<PrintError> = None;
+    let mut delays = BackoffIter::new(config);
 
-    for attempt in 0..config.max_attempts {
+    loop {
         match op() {
             Ok(val) => return Ok(val),
             Err(e) => {
                 if !e.is_retryable() {
                     return Err(e);
                 }
-                last_error = Some(e);
-
-                // Don't sleep after the last attempt.
-                if attempt + 1 < config.max_attempts {
-                    let delay = compute_delay(config, attempt);
-                    std::thread::sleep(delay);
-                }
+                // The delays run out after the last attempt.
+                let Some(delay) = delays.next() else {
+                    return Err(exhausted(config, e));
+                };
+                std::thread::sleep(delay);
             }
         }
     }
-
-    // We only reach here when every attempt failed with a retryable error.
-    Err(PrintError::RetriesExhausted {
-        attempts: config.max_attempts,
-        last_error: Box::new(
-            last_error.unwrap_or_else(|| {
-                unreachable!("at least one attempt was made (max_attempts >= 1)")
-            }),
-        ),
-    })
 }
 
 /// Compute the backoff delay for the given `attempt` (0-indexed).
@@ -158,37 +224,26 @@ where
         ));
     }
 
-    let mut last_error: Option<PrintError> = None;
+    let mut delays = BackoffIter::new(config);
 
-    for attempt in 0..config.max_attempts {
+    loop {
         match op(inner) {
             Ok(val) => return Ok(val),
             Err(e) => {
                 if !e.is_retryable() {
                     return Err(e);
                 }
-                last_error = Some(e);
-
-                // Don't sleep or reconnect after the last attempt.
-                if attempt + 1 < config.max_attempts {
-                    let delay = compute_delay(config, attempt);
-                    std::thread::sleep(delay);
-                    // Best-effort reconnection before the next retry.
-                    let _ = inner.reconnect();
-                }
+                // The delays run out after the last attempt: no sleep or
+                // reconnect then.
+                let Some(delay) = delays.next() else {
+                    return Err(exhausted(config, e));
+                };
+                std::thread::sleep(delay);
+                // Best-effort reconnection before the next retry.
+                let _ = inner.reconnect();
             }
         }
     }
-
-    // We only reach here when every attempt failed with a retryable error.
-    Err(PrintError::RetriesExhausted {
-        attempts: config.max_attempts,
-        last_error: Box::new(
-            last_error.unwrap_or_else(|| {
-                unreachable!("at least one attempt was made (max_attempts >= 1)")
-            }),
-        ),
-    })
 }
 
 /// A retry wrapper that **reconnects** between attempts.
@@ -239,12 +294,24 @@ impl<P: Printer + Reconnectable> Printer for ReconnectRetryPrinter<P> {
     fn send_raw(&mut self, data: &[u8]) -> Result<(), PrintError> {
         retry_op_with_reconnect(&self.retry_config, &mut self.inner, |p| p.send_raw(data))
     }
+
+    fn flush(&mut self) -> Result<(), PrintError> {
+        self.inner.flush()
+    }
 }
 
 impl<P: StatusQuery + Reconnectable> StatusQuery for ReconnectRetryPrinter<P> {
     fn query_raw(&mut self, cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
         retry_op_with_reconnect(&self.retry_config, &mut self.inner, |p| p.query_raw(cmd))
     }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        self.inner.read_timeout()
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+        self.inner.set_read_timeout(timeout)
+    }
 }
 
 impl<P: Reconnectable> Reconnectable for ReconnectRetryPrinter<P> {
@@ -469,6 +536,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn backoff_iter_doubles_and_caps_at_max_delay() {
+        let config = RetryConfig {
+            max_attempts: 6,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+            jitter: false,
+            ..RetryConfig::default()
+        };
+
+        let delays: Vec<Duration> = BackoffIter::new(&config).collect();
+        assert_eq!(
+            delays,
+            [1, 2, 4, 5, 5].map(Duration::from_secs),
+            "one delay between each pair of attempts, capped at max_delay"
+        );
+        assert_eq!(BackoffIter::new(&config).len(), 5);
+    }
+
+    #[test]
+    fn backoff_iter_yields_nothing_for_single_attempt() {
+        for max_attempts in [0, 1] {
+            let config = RetryConfig {
+                max_attempts,
+                ..RetryConfig::default()
+            };
+            assert_eq!(BackoffIter::new(&config).count(), 0);
+        }
+    }
+
+    #[test]
+    fn backoff_iter_with_jitter_stays_in_range() {
+        let config = RetryConfig {
+            max_attempts: 8,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            jitter: true,
+            ..RetryConfig::default()
+        };
+
+        for (attempt, d) in BackoffIter::new(&config).enumerate() {
+            let base = config
+                .initial_delay
+                .saturating_mul(2u32.saturating_pow(attempt as u32))
+                .min(config.max_delay);
+            let half = base / 2;
+            assert!(
+                d >= half && d <= base,
+                "attempt {attempt}: delay {d:?} not in [{half:?}, {base:?}]",
+            );
+        }
+    }
+
     // -- ReconnectRetryPrinter tests ------------------------------------
 
     /// A mock printer that tracks reconnect and send calls.
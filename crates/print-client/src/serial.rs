@@ -6,15 +6,14 @@
 //! both `Printer` and `StatusQuery` traits.
 
 use std::io::Write;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 
-use crate::frame::{DEFAULT_MAX_FRAME_SIZE, expected_frame_count, read_frames};
+use crate::frame::{expected_frame_count, max_frame_size, read_frames};
+use crate::trace::{self, TraceDirection};
 use crate::{PrintError, Printer, PrinterConfig, StatusQuery};
 
 /// Default baud rate for Zebra label printers (9600 8N1).
 const DEFAULT_BAUD: u32 = 9600;
-static NEXT_TRACE_SESSION_ID: AtomicU64 = AtomicU64::new(1);
 
 /// Serial line settings used to open a serial port.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -129,7 +128,7 @@ impl SerialPrinter {
             .open()
             .map_err(|e| PrintError::SerialError(e.to_string()))?;
 
-        let trace_session_id = NEXT_TRACE_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        let trace_session_id = trace::next_session_id();
         Ok(Self {
             port,
             config,
@@ -167,15 +166,23 @@ impl SerialPrinter {
 
 impl Printer for SerialPrinter {
     fn send_raw(&mut self, data: &[u8]) -> Result<(), PrintError> {
-        if self.config.trace_io {
-            trace_bytes("serial tx", data, self.trace_session_id);
-        }
+        trace::emit(
+            &self.config,
+            "serial",
+            self.trace_session_id,
+            TraceDirection::Sent,
+            data,
+        );
         self.port.write_all(data).map_err(PrintError::WriteFailed)?;
 
         self.port.flush().map_err(PrintError::WriteFailed)?;
 
         Ok(())
     }
+
+    fn flush(&mut self) -> Result<(), PrintError> {
+        self.port.flush().map_err(PrintError::WriteFailed)
+    }
 }
 
 impl StatusQuery for SerialPrinter {
@@ -193,16 +200,31 @@ impl StatusQuery for SerialPrinter {
             &mut self.port,
             expected_frames,
             timeout,
-            DEFAULT_MAX_FRAME_SIZE,
+            max_frame_size(cmd),
         )?;
 
-        if self.config.trace_io {
-            for frame in &frames {
-                trace_bytes("serial rx", frame, self.trace_session_id);
-            }
+        for frame in &frames {
+            trace::emit(
+                &self.config,
+                "serial",
+                self.trace_session_id,
+                TraceDirection::Received,
+                frame,
+            );
         }
         Ok(frames)
     }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        Some(self.config.timeouts.read)
+    }
+
+    /// `read_frames` enforces its own deadline and keeps reading through
+    /// port-level timeouts, so only the configured value needs to change.
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+        self.config.timeouts.read = timeout;
+        Ok(())
+    }
 }
 
 fn map_data_bits(bits: SerialDataBits) -> serialport::DataBits {
@@ -234,31 +256,3 @@ fn map_flow_control(flow: SerialFlowControl) -> serialport::FlowControl {
         SerialFlowControl::Hardware => serialport::FlowControl::Hardware,
     }
 }
-
-fn trace_bytes(label: &str, bytes: &[u8], session_id: u64) {
-    let hex = bytes
-        .iter()
-        .map(|b| format!("{:02X}", b))
-        .collect::<Vec<_>>()
-        .join(" ");
-    let ascii = bytes
-        .iter()
-        .map(|b| {
-            if b.is_ascii_graphic() || *b == b' ' {
-                char::from(*b)
-            } else {
-                '.'
-            }
-        })
-        .collect::<String>();
-    let ts_ms = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_millis())
-        .unwrap_or(0);
-    eprintln!(
-        "[trace-io t={ts_ms} session={session_id}] {label} len={} hex=[{}] ascii='{}'",
-        bytes.len(),
-        hex,
-        ascii
-    );
-}
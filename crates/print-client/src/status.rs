@@ -1,7 +1,8 @@
 //! Zebra printer status response parser.
 //!
-//! Parses `~HS` (Host Status) and `~HI` (Host Identification) responses
-//! from Zebra printers into typed Rust structs.
+//! Parses `~HS` (Host Status), `~HI` (Host Identification), `~HD` (Head
+//! Diagnostics), and `^HW` (Host Directory List) responses from Zebra
+//! printers into typed Rust structs.
 
 use crate::PrintError;
 
@@ -106,6 +107,82 @@ impl PrintMode {
     }
 }
 
+// ── PrinterAlert ────────────────────────────────────────────────────────
+
+/// How serious an active [`PrinterAlert`] is.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AlertSeverity {
+    /// The printer cannot print until the condition is cleared.
+    Fault,
+    /// The printer may still print, but needs attention.
+    Warning,
+}
+
+/// An active fault or warning flag reported by `~HS`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PrinterAlert {
+    /// Media is out ([`HostStatus::paper_out`]).
+    PaperOut,
+    /// Ribbon is out ([`HostStatus::ribbon_out`]).
+    RibbonOut,
+    /// Print head is open ([`HostStatus::head_up`]).
+    HeadUp,
+    /// Printer is paused ([`HostStatus::paused`]).
+    Paused,
+    /// Print head is over temperature ([`HostStatus::over_temperature`]).
+    #[cfg_attr(feature = "serde", serde(rename = "over_temp"))]
+    OverTemperature,
+    /// Print head is under temperature ([`HostStatus::under_temperature`]).
+    #[cfg_attr(feature = "serde", serde(rename = "under_temp"))]
+    UnderTemperature,
+    /// RAM is corrupt ([`HostStatus::corrupt_ram`]).
+    CorruptRam,
+    /// Receive buffer is full ([`HostStatus::buffer_full`]).
+    BufferFull,
+}
+
+impl PrinterAlert {
+    /// Stable machine-readable name (e.g. `"paper_out"`, `"over_temp"`).
+    pub fn name(self) -> &'static str {
+        match self {
+            PrinterAlert::PaperOut => "paper_out",
+            PrinterAlert::RibbonOut => "ribbon_out",
+            PrinterAlert::HeadUp => "head_up",
+            PrinterAlert::Paused => "paused",
+            PrinterAlert::OverTemperature => "over_temp",
+            PrinterAlert::UnderTemperature => "under_temp",
+            PrinterAlert::CorruptRam => "corrupt_ram",
+            PrinterAlert::BufferFull => "buffer_full",
+        }
+    }
+
+    /// Severity of this alert.
+    pub fn severity(self) -> AlertSeverity {
+        match self {
+            PrinterAlert::PaperOut
+            | PrinterAlert::RibbonOut
+            | PrinterAlert::HeadUp
+            | PrinterAlert::OverTemperature
+            | PrinterAlert::CorruptRam => AlertSeverity::Fault,
+            PrinterAlert::Paused | PrinterAlert::UnderTemperature | PrinterAlert::BufferFull => {
+                AlertSeverity::Warning
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PrinterAlert {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 // ── HostStatus ──────────────────────────────────────────────────────────
 
 /// Parsed `~HS` (Host Status) response.
@@ -258,6 +335,41 @@ impl HostStatus {
             static_ram_installed,
         })
     }
+
+    /// Active fault and warning flags, in a stable order.
+    pub fn alerts(&self) -> Vec<PrinterAlert> {
+        [
+            (self.paper_out, PrinterAlert::PaperOut),
+            (self.ribbon_out, PrinterAlert::RibbonOut),
+            (self.head_up, PrinterAlert::HeadUp),
+            (self.paused, PrinterAlert::Paused),
+            (self.over_temperature, PrinterAlert::OverTemperature),
+            (self.under_temperature, PrinterAlert::UnderTemperature),
+            (self.corrupt_ram, PrinterAlert::CorruptRam),
+            (self.buffer_full, PrinterAlert::BufferFull),
+        ]
+        .into_iter()
+        .filter_map(|(active, alert)| active.then_some(alert))
+        .collect()
+    }
+
+    /// Returns `true` when no fault or warning flag is set.
+    pub fn is_healthy(&self) -> bool {
+        self.alerts().is_empty()
+    }
+
+    /// Alerts active now that were not active in `previous`, in the same
+    /// order as [`alerts`](Self::alerts).
+    ///
+    /// Compare consecutive polls to react when a printer *enters* a state
+    /// (e.g. paper just ran out) rather than on every poll while it stays there.
+    pub fn newly_raised(&self, previous: &HostStatus) -> Vec<PrinterAlert> {
+        let before = previous.alerts();
+        self.alerts()
+            .into_iter()
+            .filter(|alert| !before.contains(alert))
+            .collect()
+    }
 }
 
 // ── PrinterInfo ─────────────────────────────────────────────────────────
@@ -271,7 +383,8 @@ pub struct PrinterInfo {
     pub model: String,
     /// Firmware version (e.g. `"V85.20.19"`).
     pub firmware: String,
-    /// Print resolution in DPI.
+    /// Print resolution in DPI. `~HI` reports dots per millimetre, which
+    /// [`PrinterInfo::parse`] converts to the nominal DPI (12 → 300).
     pub dpi: u32,
     /// Installed memory in kilobytes.
     pub memory_kb: u32,
@@ -281,7 +394,7 @@ impl PrinterInfo {
     /// Parse a `~HI` response from STX/ETX frames.
     ///
     /// Expects exactly **1** frame containing comma-separated fields:
-    /// `model,firmware,dpi,memory_kb`.
+    /// `model,firmware,dots_per_mm,memory_kb`.
     pub fn parse(frames: &[Vec<u8>]) -> Result<PrinterInfo, PrintError> {
         if frames.len() != 1 {
             return Err(PrintError::MalformedFrame {
@@ -303,12 +416,11 @@ impl PrinterInfo {
         let model = fields[0].trim().to_string();
         let firmware = fields[1].trim().to_string();
 
-        let dpi: u32 = fields[2]
-            .trim()
-            .parse()
-            .map_err(|_| PrintError::MalformedFrame {
+        let dpi = fields[2].trim().parse().map(resolution_dpi).map_err(|_| {
+            PrintError::MalformedFrame {
                 details: format!("~HI: cannot parse DPI ({:?})", fields[2].trim()),
-            })?;
+            }
+        })?;
 
         let memory_kb = parse_memory_kb_field(fields[3])?;
 
@@ -321,6 +433,288 @@ impl PrinterInfo {
     }
 }
 
+/// Map a `~HI` resolution in dots per millimetre (6, 8, 12, 24) to the
+/// nominal DPI; other values are passed through unchanged.
+fn resolution_dpi(reported: u32) -> u32 {
+    match reported {
+        6 => 152,
+        8 => 203,
+        12 => 300,
+        24 => 600,
+        other => other,
+    }
+}
+
+// ── MemoryStatus ────────────────────────────────────────────────────────
+
+/// Parsed `~HM` (Host RAM Status) response.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryStatus {
+    /// Total RAM installed, in kilobytes.
+    pub total_kb: u32,
+    /// Maximum RAM available to the user, in kilobytes.
+    pub max_available_kb: u32,
+    /// RAM currently available to the user, in kilobytes.
+    pub available_kb: u32,
+}
+
+impl MemoryStatus {
+    /// Parse a `~HM` response from STX/ETX frames.
+    ///
+    /// Expects exactly **1** frame containing comma-separated fields:
+    /// `total_kb,max_available_kb,available_kb` (e.g. `1024,0780,0780`).
+    pub fn parse(frames: &[Vec<u8>]) -> Result<MemoryStatus, PrintError> {
+        if frames.len() != 1 {
+            return Err(PrintError::MalformedFrame {
+                details: format!("~HM requires 1 frame, got {}", frames.len()),
+            });
+        }
+
+        let text = std::str::from_utf8(&frames[0]).map_err(|e| PrintError::MalformedFrame {
+            details: format!("~HM: invalid UTF-8: {e}"),
+        })?;
+        let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+        if fields.len() < 3 {
+            return Err(PrintError::MalformedFrame {
+                details: format!("~HM: expected 3 fields, got {}", fields.len()),
+            });
+        }
+
+        let parse_kb = |name: &str, raw: &str| {
+            raw.parse::<u32>().map_err(|_| PrintError::MalformedFrame {
+                details: format!("~HM: cannot parse {name} ({raw:?})"),
+            })
+        };
+        Ok(MemoryStatus {
+            total_kb: parse_kb("total_kb", fields[0])?,
+            max_available_kb: parse_kb("max_available_kb", fields[1])?,
+            available_kb: parse_kb("available_kb", fields[2])?,
+        })
+    }
+}
+
+// ── PrinterSnapshot ─────────────────────────────────────────────────────
+
+/// Combined `~HS` + `~HI` + `~HM` snapshot from
+/// [`StatusQuery::query_snapshot`](crate::StatusQuery::query_snapshot).
+///
+/// Each part is `None` when its query failed, so one unsupported or
+/// timed-out query does not hide the others.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrinterSnapshot {
+    /// Host status (`~HS`).
+    pub status: Option<HostStatus>,
+    /// Host identification (`~HI`).
+    pub info: Option<PrinterInfo>,
+    /// RAM status (`~HM`).
+    pub memory: Option<MemoryStatus>,
+}
+
+impl PrinterSnapshot {
+    /// Returns `true` when every query succeeded.
+    pub fn is_complete(&self) -> bool {
+        self.status.is_some() && self.info.is_some() && self.memory.is_some()
+    }
+}
+
+// ── HeadDiagnostics ─────────────────────────────────────────────────────
+
+/// Parsed `~HD` (Head Diagnostics) response.
+///
+/// The firmware reports one `Name = value` pair per line (e.g.
+/// `Head Temp = 29`). Well-known stats are decoded into typed fields; every
+/// pair is kept in [`entries`](Self::entries) since the set varies by model
+/// and firmware.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeadDiagnostics {
+    /// Printhead temperature in °C (`Head Temp`).
+    pub head_temp_c: Option<i32>,
+    /// Ambient temperature in °C (`Ambient Temp`).
+    pub ambient_temp_c: Option<i32>,
+    /// Printhead self-test result as reported (e.g. `"Passed"`).
+    pub head_test: Option<String>,
+    /// Darkness adjustment (`Darkness Adjust`).
+    pub darkness_adjust: Option<i32>,
+    /// Print speed in inches per second (`Print Speed`).
+    pub print_speed: Option<u32>,
+    /// Slew speed in inches per second (`Slew Speed`).
+    pub slew_speed: Option<u32>,
+    /// Backfeed speed in inches per second (`Backfeed Speed`).
+    pub backfeed_speed: Option<u32>,
+    /// All `Name = value` pairs in response order, trimmed but otherwise as
+    /// reported.
+    pub entries: Vec<(String, String)>,
+    /// The raw response text.
+    pub raw: String,
+}
+
+impl HeadDiagnostics {
+    /// Parse a `~HD` response from STX/ETX frames.
+    ///
+    /// Frames are joined and split into lines; lines without `=` are
+    /// ignored. Fails if no `Name = value` pair is found.
+    pub fn parse(frames: &[Vec<u8>]) -> Result<HeadDiagnostics, PrintError> {
+        let mut raw = String::new();
+        for (i, frame) in frames.iter().enumerate() {
+            let text = std::str::from_utf8(frame).map_err(|e| PrintError::MalformedFrame {
+                details: format!("~HD frame {}: invalid UTF-8: {e}", i + 1),
+            })?;
+            if !raw.is_empty() {
+                raw.push('\n');
+            }
+            raw.push_str(text);
+        }
+
+        let entries: Vec<(String, String)> = raw
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        if entries.is_empty() {
+            return Err(PrintError::MalformedFrame {
+                details: "~HD: no 'name = value' lines in response".into(),
+            });
+        }
+
+        let mut diagnostics = HeadDiagnostics {
+            head_temp_c: None,
+            ambient_temp_c: None,
+            head_test: None,
+            darkness_adjust: None,
+            print_speed: None,
+            slew_speed: None,
+            backfeed_speed: None,
+            entries,
+            raw,
+        };
+        diagnostics.head_temp_c = diagnostics.number("Head Temp");
+        diagnostics.ambient_temp_c = diagnostics.number("Ambient Temp");
+        diagnostics.head_test = diagnostics.get("Head Test").map(str::to_string);
+        diagnostics.darkness_adjust = diagnostics.number("Darkness Adjust");
+        diagnostics.print_speed = diagnostics.number("Print Speed");
+        diagnostics.slew_speed = diagnostics.number("Slew Speed");
+        diagnostics.backfeed_speed = diagnostics.number("Backfeed Speed");
+        Ok(diagnostics)
+    }
+
+    /// Value reported for `name`, matched case-insensitively with `_` and
+    /// space treated alike (`"static pitch length"` finds
+    /// `Static_pitch_length`).
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let wanted = normalize_hd_name(name);
+        self.entries
+            .iter()
+            .find(|(key, _)| normalize_hd_name(key) == wanted)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Leading integer of the value reported for `name` (see
+    /// [`get`](Self::get)), ignoring any unit suffix.
+    pub fn number<T: std::str::FromStr>(&self, name: &str) -> Option<T> {
+        let value = self.get(name)?;
+        let sign_len = usize::from(value.starts_with(['-', '+']));
+        let digits_len = value[sign_len..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits_len == 0 {
+            return None;
+        }
+        value[..sign_len + digits_len].parse().ok()
+    }
+}
+
+fn normalize_hd_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c == '_' {
+                ' '
+            } else {
+                c.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+// ── StoredObject ────────────────────────────────────────────────────────
+
+/// One object from a `^HW` (Host Directory List) response.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StoredObject {
+    /// Object name with extension, without the drive (e.g. `LOGO.GRF`).
+    pub name: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Drive letter the object is stored on (e.g. `R`).
+    pub drive: char,
+}
+
+impl StoredObject {
+    /// Parse a `^HW` directory listing from STX/ETX frames.
+    ///
+    /// The listing has one `*R:NAME.EXT size` line per object between a
+    /// `- DIR` header and a `-N bytes free` trailer. An empty drive yields an
+    /// empty list; a response with neither objects nor header lines fails.
+    pub fn parse_listing(frames: &[Vec<u8>]) -> Result<Vec<StoredObject>, PrintError> {
+        let mut objects = Vec::new();
+        let mut saw_header = false;
+        for (i, frame) in frames.iter().enumerate() {
+            let text = std::str::from_utf8(frame).map_err(|e| PrintError::MalformedFrame {
+                details: format!("^HW frame {}: invalid UTF-8: {e}", i + 1),
+            })?;
+            for line in text.lines().map(str::trim) {
+                if line.starts_with('-') {
+                    saw_header = true;
+                } else if let Some(entry) = line.strip_prefix('*') {
+                    objects.push(parse_listing_entry(entry)?);
+                }
+            }
+        }
+        if objects.is_empty() && !saw_header {
+            return Err(PrintError::MalformedFrame {
+                details: "^HW: no directory listing in response".into(),
+            });
+        }
+        Ok(objects)
+    }
+}
+
+/// Parse one `R:NAME.EXT size` listing entry (the leading `*` removed).
+fn parse_listing_entry(entry: &str) -> Result<StoredObject, PrintError> {
+    let malformed = || PrintError::MalformedFrame {
+        details: format!("^HW: malformed listing entry {entry:?}"),
+    };
+    let mut parts = entry.split_whitespace();
+    let path = parts.next().ok_or_else(malformed)?;
+    let size = parts
+        .next_back()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .ok_or_else(malformed)?;
+    let (drive, name) = path.split_once(':').ok_or_else(malformed)?;
+    let mut drive_chars = drive.chars();
+    let (Some(drive), None) = (drive_chars.next(), drive_chars.next()) else {
+        return Err(malformed());
+    };
+    if name.is_empty() {
+        return Err(malformed());
+    }
+    Ok(StoredObject {
+        name: name.to_string(),
+        size,
+        drive: drive.to_ascii_uppercase(),
+    })
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -332,6 +726,22 @@ mod tests {
         strings.iter().map(|s| s.as_bytes().to_vec()).collect()
     }
 
+    // ── MemoryStatus ────────────────────────────────────────────────
+
+    #[test]
+    fn parse_memory_status() {
+        let mem = MemoryStatus::parse(&frames(&["1024,0780,0780"])).unwrap();
+        assert_eq!(mem.total_kb, 1024);
+        assert_eq!(mem.max_available_kb, 780);
+        assert_eq!(mem.available_kb, 780);
+    }
+
+    #[test]
+    fn parse_memory_status_rejects_short_response() {
+        let err = MemoryStatus::parse(&frames(&["1024,0780"])).unwrap_err();
+        assert!(matches!(err, PrintError::MalformedFrame { .. }));
+    }
+
     // ── HostStatus ──────────────────────────────────────────────────
 
     #[test]
@@ -406,6 +816,101 @@ mod tests {
         assert!(hs.static_ram_installed);
     }
 
+    #[test]
+    fn host_status_alerts_empty_when_healthy() {
+        let input = frames(&[
+            "030,0,0,1245,000,0,0,0,000,0,0,0",
+            "000,0,0,0,0,2,4,0,00000000,1,000",
+            "1234,0",
+        ]);
+        let hs = HostStatus::parse(&input).expect("should parse");
+        assert!(hs.alerts().is_empty());
+        assert!(hs.is_healthy());
+    }
+
+    #[test]
+    fn host_status_alerts_lists_all_flags_in_order() {
+        let input = frames(&[
+            "030,1,1,1245,002,1,0,0,000,1,1,1",
+            "000,1,1,1,4,2,0,5,00000000,0,000",
+            "0000,1",
+        ]);
+        let hs = HostStatus::parse(&input).expect("should parse");
+        let names: Vec<&str> = hs.alerts().iter().map(|a| a.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "paper_out",
+                "ribbon_out",
+                "head_up",
+                "paused",
+                "over_temp",
+                "under_temp",
+                "corrupt_ram",
+                "buffer_full",
+            ]
+        );
+        assert!(!hs.is_healthy());
+    }
+
+    #[test]
+    fn host_status_alerts_carry_severity() {
+        // Paused with a full buffer: warnings only.
+        let paused = HostStatus::parse(&frames(&[
+            "030,0,1,1245,000,1,0,0,000,0,0,0",
+            "000,0,0,0,0,2,4,0,00000000,1,000",
+            "1234,0",
+        ]))
+        .expect("should parse");
+        assert_eq!(
+            paused.alerts(),
+            [PrinterAlert::Paused, PrinterAlert::BufferFull]
+        );
+        assert!(
+            paused
+                .alerts()
+                .iter()
+                .all(|a| a.severity() == AlertSeverity::Warning)
+        );
+        assert!(!paused.is_healthy());
+
+        // Head open: a fault.
+        let head_up = HostStatus::parse(&frames(&[
+            "030,0,0,1245,000,0,0,0,000,0,0,0",
+            "000,1,0,0,0,2,4,0,00000000,1,000",
+            "1234,0",
+        ]))
+        .expect("should parse");
+        assert_eq!(head_up.alerts(), [PrinterAlert::HeadUp]);
+        assert_eq!(PrinterAlert::HeadUp.severity(), AlertSeverity::Fault);
+        assert_eq!(PrinterAlert::OverTemperature.to_string(), "over_temp");
+    }
+
+    #[test]
+    fn host_status_newly_raised_reports_only_transitions() {
+        let healthy = HostStatus::parse(&frames(&[
+            "030,0,0,1245,000,0,0,0,000,0,0,0",
+            "000,0,0,0,0,2,4,0,00000000,1,000",
+            "1234,0",
+        ]))
+        .expect("should parse");
+        let paper_out = HostStatus::parse(&frames(&[
+            "030,1,1,1245,000,0,0,0,000,0,0,0",
+            "000,0,0,0,0,2,4,0,00000000,1,000",
+            "1234,0",
+        ]))
+        .expect("should parse");
+
+        assert_eq!(
+            paper_out.newly_raised(&healthy),
+            [PrinterAlert::PaperOut, PrinterAlert::Paused]
+        );
+        // Still out of paper on the next poll: nothing new.
+        assert!(paper_out.newly_raised(&paper_out).is_empty());
+        // Recovering raises nothing.
+        assert!(healthy.newly_raised(&paper_out).is_empty());
+    }
+
     #[test]
     fn parse_host_status_wrong_frame_count() {
         // Too few
@@ -491,6 +996,98 @@ mod tests {
         );
     }
 
+    // ── HeadDiagnostics ─────────────────────────────────────────────
+
+    const HD_RESPONSE: &str = "Head Temp = 29\r\nAmbient Temp = 155\r\nHead Test = Passed\r\nDarkness Adjust = 23\r\nPrint Speed = 2\r\nSlew Speed = 6\r\nBackfeed Speed = 2\r\nStatic_pitch_length = 0521\r\nDynamic_pitch_length = 0540\r\nMax_dynamic_pitch_length = 0537\r\nMin_dynamic_pitch_length = 0537\r\nCOMMAND PFX = ~ : 7E\r\nFORMAT PFX = ^ : 5E\r\nDELIMITER CHAR = , : 2C\r\n";
+
+    #[test]
+    fn parse_head_diagnostics_representative_response() {
+        let hd = HeadDiagnostics::parse(&frames(&[HD_RESPONSE])).expect("should parse");
+        assert_eq!(hd.head_temp_c, Some(29));
+        assert_eq!(hd.ambient_temp_c, Some(155));
+        assert_eq!(hd.head_test.as_deref(), Some("Passed"));
+        assert_eq!(hd.darkness_adjust, Some(23));
+        assert_eq!(hd.print_speed, Some(2));
+        assert_eq!(hd.slew_speed, Some(6));
+        assert_eq!(hd.backfeed_speed, Some(2));
+        assert_eq!(hd.entries.len(), 14);
+        assert_eq!(hd.raw, HD_RESPONSE);
+
+        assert_eq!(hd.number::<u32>("static pitch length"), Some(521));
+        assert_eq!(hd.number::<u32>("DYNAMIC_PITCH_LENGTH"), Some(540));
+        assert_eq!(hd.get("Command PFX"), Some("~ : 7E"));
+        assert_eq!(hd.get("Delimiter Char"), Some(", : 2C"));
+        assert_eq!(hd.number::<u32>("Head Test"), None);
+        assert_eq!(hd.get("Missing"), None);
+    }
+
+    #[test]
+    fn parse_head_diagnostics_negative_and_missing_stats() {
+        let hd = HeadDiagnostics::parse(&frames(&["Head Temp = -3 C", "Darkness Adjust = -5"]))
+            .expect("should parse");
+        assert_eq!(hd.head_temp_c, Some(-3));
+        assert_eq!(hd.darkness_adjust, Some(-5));
+        assert_eq!(hd.ambient_temp_c, None);
+        assert_eq!(hd.print_speed, None);
+        assert_eq!(hd.raw, "Head Temp = -3 C\nDarkness Adjust = -5");
+    }
+
+    #[test]
+    fn parse_head_diagnostics_without_pairs_fails() {
+        let err = HeadDiagnostics::parse(&[]).unwrap_err();
+        assert!(format!("{err}").contains("~HD"), "unexpected error: {err}");
+
+        let err = HeadDiagnostics::parse(&frames(&["garbage"])).unwrap_err();
+        assert!(
+            format!("{err}").contains("name = value"),
+            "unexpected error: {err}"
+        );
+    }
+
+    // ── StoredObject ────────────────────────────────────────────────
+
+    #[test]
+    fn parse_directory_listing() {
+        let listing = "\r\n- DIR R:*.*\r\n*R:ARIALN1.FNT    49140\r\n\
+                       * R:LOGO.GRF 8420\r\n-794292 bytes free R:RAM\r\n";
+        let objects = StoredObject::parse_listing(&frames(&[listing])).expect("should parse");
+        assert_eq!(
+            objects,
+            vec![
+                StoredObject {
+                    name: "ARIALN1.FNT".into(),
+                    size: 49140,
+                    drive: 'R',
+                },
+                StoredObject {
+                    name: "LOGO.GRF".into(),
+                    size: 8420,
+                    drive: 'R',
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_empty_directory_listing() {
+        let listing = "- DIR E:*.*\r\n-1536000 bytes free E:ONBOARD FLASH\r\n";
+        let objects = StoredObject::parse_listing(&frames(&[listing])).expect("should parse");
+        assert!(objects.is_empty());
+    }
+
+    #[test]
+    fn parse_directory_listing_rejects_garbage() {
+        let err = StoredObject::parse_listing(&frames(&["garbage"])).unwrap_err();
+        assert!(format!("{err}").contains("^HW"), "unexpected error: {err}");
+
+        let err =
+            StoredObject::parse_listing(&frames(&["- DIR R:*.*\n*R:LOGO.GRF lots"])).unwrap_err();
+        assert!(
+            format!("{err}").contains("malformed listing entry"),
+            "unexpected error: {err}"
+        );
+    }
+
     // ── PrinterInfo ─────────────────────────────────────────────────
 
     #[test]
@@ -549,6 +1146,21 @@ mod tests {
         assert_eq!(info.memory_kb, 8176);
     }
 
+    #[test]
+    fn parse_printer_info_converts_dots_per_mm_to_dpi() {
+        for (reported, dpi) in [
+            ("6", 152),
+            ("8", 203),
+            ("12", 300),
+            ("24", 600),
+            ("300", 300),
+        ] {
+            let input = frames(&[&format!("ZD621,V93.21.26Z,{reported},8176KB")]);
+            let info = PrinterInfo::parse(&input).expect("should parse");
+            assert_eq!(info.dpi, dpi, "reported {reported}");
+        }
+    }
+
     // ── Serialization (serde feature only) ────────────────────────────
 
     #[cfg(feature = "serde")]
@@ -567,6 +1179,22 @@ mod tests {
         assert!(json.contains("\"print_mode\":\"TearOff\""));
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn printer_alert_serializes_to_stable_name() {
+        for alert in [
+            PrinterAlert::PaperOut,
+            PrinterAlert::OverTemperature,
+            PrinterAlert::UnderTemperature,
+            PrinterAlert::BufferFull,
+        ] {
+            let json = serde_json::to_value(alert).expect("serialize");
+            assert_eq!(json, alert.name());
+        }
+        let severity = serde_json::to_value(AlertSeverity::Warning).expect("serialize");
+        assert_eq!(severity, "warning");
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn printer_info_serializes_to_json() {
@@ -575,7 +1203,7 @@ mod tests {
         let info = PrinterInfo::parse(&input).unwrap();
         let json = serde_json::to_string(&info).expect("should serialize");
         assert!(json.contains("\"model\":\"ZTC ZD421-300dpi ZPL\""));
-        assert!(json.contains("\"dpi\":12"));
+        assert!(json.contains("\"dpi\":300"));
     }
 
     /// Proves HostStatus and PrinterInfo parse successfully without serde feature.
@@ -0,0 +1,449 @@
+//! Transport-agnostic printer addressing and connection.
+//!
+//! [`PrinterTarget`] describes *where* a printer is (TCP address, USB device,
+//! serial port, or Bluetooth RFCOMM channel) and [`connect_any`] opens the
+//! matching transport, returning it as a boxed [`StatusQuery`] so callers can
+//! dispatch without branching on the transport type.
+
+use std::fmt;
+
+use crate::{PrintError, PrinterConfig, StatusQuery};
+
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+use crate::BluetoothAddr;
+#[cfg(feature = "serial")]
+use crate::{SerialDataBits, SerialFlowControl, SerialParity, SerialSettings, SerialStopBits};
+
+/// Default baud rate for serial targets without an explicit baud.
+#[cfg(feature = "serial")]
+const DEFAULT_SERIAL_BAUD: u32 = 9600;
+
+/// Where to reach a printer.
+///
+/// Parse one from a user-supplied string with [`PrinterTarget::parse`]:
+///
+/// | Input                                  | Target                          |
+/// |----------------------------------------|---------------------------------|
+/// | `192.168.1.55`, `printer.local:9100`   | [`Tcp`](Self::Tcp)              |
+/// | `usb`                                  | [`UsbAuto`](Self::UsbAuto)      |
+/// | `usb:0A5F:0100`                        | [`Usb`](Self::Usb) (hex VID:PID)|
+/// | `serial:/dev/ttyUSB0`                  | [`Serial`](Self::Serial) (9600 8N1, XON/XOFF) |
+/// | `serial:COM5,115200,8,N,1,R`           | [`Serial`](Self::Serial) with explicit settings |
+/// | `bt:AA:BB:CC:DD:EE:FF:1`               | `Bluetooth` (MAC + RFCOMM channel, Linux) |
+///
+/// Serial settings follow the `^SC` order: baud, data bits (`7`/`8`), parity
+/// (`N`/`E`/`O`), stop bits (`1`/`2`), flow control (`N` none, `X` XON/XOFF,
+/// `R` RTS/CTS). Trailing settings may be omitted.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrinterTarget {
+    /// TCP address (`host`, `host:port`, or IPv6 `[addr]:port`).
+    Tcp(String),
+    /// First Zebra USB printer found.
+    UsbAuto,
+    /// USB printer with a specific vendor and product ID.
+    Usb {
+        /// USB vendor ID.
+        vendor_id: u16,
+        /// USB product ID.
+        product_id: u16,
+    },
+    /// Serial port (RS-232, USB-serial, or Bluetooth SPP).
+    #[cfg(feature = "serial")]
+    Serial {
+        /// OS serial port path, e.g. `/dev/ttyUSB0` or `COM5`.
+        path: String,
+        /// Baud rate.
+        baud: u32,
+        /// Serial line settings.
+        settings: SerialSettings,
+    },
+    /// Bluetooth RFCOMM channel, opened directly via BlueZ sockets.
+    #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+    Bluetooth(BluetoothAddr),
+}
+
+impl PrinterTarget {
+    /// Parse a printer target string (see the type-level table).
+    ///
+    /// Anything that is not `usb`, `usb:…`, `serial:…`, or `bt:…` is treated
+    /// as a TCP address; it is resolved when connecting, not here.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PrintError::InvalidAddress` for an empty input, a malformed
+    /// USB VID:PID, malformed serial settings or Bluetooth address, or a
+    /// `serial:`/`bt:` target whose transport is not compiled in.
+    pub fn parse(s: &str) -> Result<Self, PrintError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(PrintError::InvalidAddress("empty printer address".into()));
+        }
+        if s.eq_ignore_ascii_case("usb") {
+            return Ok(Self::UsbAuto);
+        }
+        if let Some(vidpid) = strip_prefix_ignore_case(s, "usb:") {
+            let (vendor_id, product_id) = parse_usb_vidpid(vidpid)?;
+            return Ok(Self::Usb {
+                vendor_id,
+                product_id,
+            });
+        }
+        if let Some(spec) = strip_prefix_ignore_case(s, "serial:") {
+            return parse_serial(spec);
+        }
+        if let Some(spec) = strip_prefix_ignore_case(s, "bt:") {
+            return parse_bluetooth(spec);
+        }
+        Ok(Self::Tcp(s.to_string()))
+    }
+
+    /// Short transport name: `"tcp"`, `"usb"`, `"serial"`, or `"bluetooth"`.
+    pub fn transport(&self) -> &'static str {
+        match self {
+            Self::Tcp(_) => "tcp",
+            Self::UsbAuto | Self::Usb { .. } => "usb",
+            #[cfg(feature = "serial")]
+            Self::Serial { .. } => "serial",
+            #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+            Self::Bluetooth(_) => "bluetooth",
+        }
+    }
+}
+
+impl std::str::FromStr for PrinterTarget {
+    type Err = PrintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for PrinterTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => f.write_str(addr),
+            Self::UsbAuto => f.write_str("usb"),
+            Self::Usb {
+                vendor_id,
+                product_id,
+            } => write!(f, "usb:{:04X}:{:04X}", vendor_id, product_id),
+            #[cfg(feature = "serial")]
+            Self::Serial { path, .. } => write!(f, "serial:{}", path),
+            #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+            Self::Bluetooth(addr) => write!(f, "bt:{}", addr),
+        }
+    }
+}
+
+/// Open a connection to `target` using whichever transport it names.
+///
+/// The returned printer supports both sending and status queries; it can be
+/// passed to the generic helpers (e.g. [`wait_for_completion`](crate::wait_for_completion))
+/// directly, or used as `&mut dyn Printer` where only sending is needed.
+///
+/// # Errors
+///
+/// Returns the transport's connection error, or `PrintError::InvalidConfig`
+/// if the target's transport was not compiled in (`tcp` / `usb` features).
+pub fn connect_any(
+    target: &PrinterTarget,
+    config: PrinterConfig,
+) -> Result<Box<dyn StatusQuery>, PrintError> {
+    match target {
+        #[cfg(feature = "tcp")]
+        PrinterTarget::Tcp(addr) => Ok(Box::new(crate::TcpPrinter::connect(addr, config)?)),
+        #[cfg(feature = "usb")]
+        PrinterTarget::UsbAuto => Ok(Box::new(crate::UsbPrinter::find_zebra(config)?)),
+        #[cfg(feature = "usb")]
+        PrinterTarget::Usb {
+            vendor_id,
+            product_id,
+        } => Ok(Box::new(crate::UsbPrinter::find(
+            *vendor_id,
+            *product_id,
+            config,
+        )?)),
+        #[cfg(feature = "serial")]
+        PrinterTarget::Serial {
+            path,
+            baud,
+            settings,
+        } => Ok(Box::new(crate::SerialPrinter::open_with_settings(
+            path, *baud, *settings, config,
+        )?)),
+        #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+        PrinterTarget::Bluetooth(addr) => Ok(Box::new(crate::BluetoothPrinter::connect_addr(
+            *addr, config,
+        )?)),
+        #[allow(unreachable_patterns)]
+        other => {
+            let _ = config;
+            Err(PrintError::InvalidConfig(format!(
+                "{} transport not available (compiled without the '{}' feature)",
+                other.transport(),
+                other.transport()
+            )))
+        }
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix)
+        .then(|| &s[prefix.len()..])
+}
+
+/// Parse a hex `VID:PID` pair like `0A5F:0100`.
+fn parse_usb_vidpid(s: &str) -> Result<(u16, u16), PrintError> {
+    let (v, p) = s.split_once(':').ok_or_else(|| {
+        PrintError::InvalidAddress(format!(
+            "invalid USB address 'usb:{}': expected usb:VID:PID",
+            s
+        ))
+    })?;
+    let vid = u16::from_str_radix(v, 16)
+        .map_err(|_| PrintError::InvalidAddress(format!("invalid USB vendor ID '{}'", v)))?;
+    let pid = u16::from_str_radix(p, 16)
+        .map_err(|_| PrintError::InvalidAddress(format!("invalid USB product ID '{}'", p)))?;
+    Ok((vid, pid))
+}
+
+#[cfg(feature = "serial")]
+fn parse_serial(spec: &str) -> Result<PrinterTarget, PrintError> {
+    let mut parts = spec.split(',').map(str::trim);
+    let path = parts.next().unwrap_or_default();
+    if path.is_empty() {
+        return Err(PrintError::InvalidAddress(
+            "serial target is missing a port path".into(),
+        ));
+    }
+    let invalid = |what: &str, value: &str| {
+        PrintError::InvalidAddress(format!("invalid serial {} '{}' in '{}'", what, value, spec))
+    };
+
+    let mut baud = DEFAULT_SERIAL_BAUD;
+    let mut settings = SerialSettings::default();
+    if let Some(v) = parts.next() {
+        baud = v
+            .parse::<u32>()
+            .ok()
+            .filter(|b| *b > 0)
+            .ok_or_else(|| invalid("baud rate", v))?;
+    }
+    if let Some(v) = parts.next() {
+        settings.data_bits = match v {
+            "7" => SerialDataBits::Seven,
+            "8" => SerialDataBits::Eight,
+            _ => return Err(invalid("data bits", v)),
+        };
+    }
+    if let Some(v) = parts.next() {
+        settings.parity = match v.to_ascii_uppercase().as_str() {
+            "N" => SerialParity::None,
+            "E" => SerialParity::Even,
+            "O" => SerialParity::Odd,
+            _ => return Err(invalid("parity", v)),
+        };
+    }
+    if let Some(v) = parts.next() {
+        settings.stop_bits = match v {
+            "1" => SerialStopBits::One,
+            "2" => SerialStopBits::Two,
+            _ => return Err(invalid("stop bits", v)),
+        };
+    }
+    if let Some(v) = parts.next() {
+        settings.flow_control = match v.to_ascii_uppercase().as_str() {
+            "N" => SerialFlowControl::None,
+            "X" => SerialFlowControl::Software,
+            "R" => SerialFlowControl::Hardware,
+            _ => return Err(invalid("flow control", v)),
+        };
+    }
+    if let Some(extra) = parts.next() {
+        return Err(invalid("setting", extra));
+    }
+
+    Ok(PrinterTarget::Serial {
+        path: path.to_string(),
+        baud,
+        settings,
+    })
+}
+
+#[cfg(not(feature = "serial"))]
+fn parse_serial(_spec: &str) -> Result<PrinterTarget, PrintError> {
+    Err(PrintError::InvalidAddress(
+        "serial transport not available (compiled without the 'serial' feature)".into(),
+    ))
+}
+
+#[cfg(all(feature = "bluetooth", target_os = "linux"))]
+fn parse_bluetooth(spec: &str) -> Result<PrinterTarget, PrintError> {
+    Ok(PrinterTarget::Bluetooth(BluetoothAddr::parse(spec)?))
+}
+
+#[cfg(not(all(feature = "bluetooth", target_os = "linux")))]
+fn parse_bluetooth(_spec: &str) -> Result<PrinterTarget, PrintError> {
+    Err(PrintError::InvalidAddress(
+        "bluetooth transport not available (requires Linux and the 'bluetooth' feature)".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tcp_addresses() {
+        assert_eq!(
+            PrinterTarget::parse("192.168.1.55").unwrap(),
+            PrinterTarget::Tcp("192.168.1.55".into())
+        );
+        assert_eq!(
+            PrinterTarget::parse(" printer.local:9100 ").unwrap(),
+            PrinterTarget::Tcp("printer.local:9100".into())
+        );
+        assert_eq!(
+            PrinterTarget::parse("[::1]:9100").unwrap().transport(),
+            "tcp"
+        );
+    }
+
+    #[test]
+    fn parse_usb_targets() {
+        assert_eq!(PrinterTarget::parse("usb").unwrap(), PrinterTarget::UsbAuto);
+        assert_eq!(PrinterTarget::parse("USB").unwrap(), PrinterTarget::UsbAuto);
+        assert_eq!(
+            PrinterTarget::parse("usb:0A5F:0100").unwrap(),
+            PrinterTarget::Usb {
+                vendor_id: 0x0A5F,
+                product_id: 0x0100,
+            }
+        );
+        assert_eq!(
+            PrinterTarget::parse("usb:0a5f:0100").unwrap().to_string(),
+            "usb:0A5F:0100"
+        );
+    }
+
+    #[test]
+    fn parse_usb_rejects_malformed_vidpid() {
+        for bad in ["usb:", "usb:0A5F", "usb:ZZZZ:0100", "usb:0A5F:10000"] {
+            assert!(
+                matches!(
+                    PrinterTarget::parse(bad),
+                    Err(PrintError::InvalidAddress(_))
+                ),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_empty() {
+        assert!(matches!(
+            PrinterTarget::parse("  "),
+            Err(PrintError::InvalidAddress(_))
+        ));
+    }
+
+    #[cfg(feature = "serial")]
+    #[test]
+    fn parse_serial_defaults() {
+        assert_eq!(
+            PrinterTarget::parse("serial:/dev/ttyUSB0").unwrap(),
+            PrinterTarget::Serial {
+                path: "/dev/ttyUSB0".into(),
+                baud: 9600,
+                settings: SerialSettings::default(),
+            }
+        );
+    }
+
+    #[cfg(feature = "serial")]
+    #[test]
+    fn parse_serial_with_settings() {
+        assert_eq!(
+            PrinterTarget::parse("serial:COM5,115200,7,e,2,R").unwrap(),
+            PrinterTarget::Serial {
+                path: "COM5".into(),
+                baud: 115_200,
+                settings: SerialSettings {
+                    data_bits: SerialDataBits::Seven,
+                    parity: SerialParity::Even,
+                    stop_bits: SerialStopBits::Two,
+                    flow_control: SerialFlowControl::Hardware,
+                },
+            }
+        );
+        let PrinterTarget::Serial { baud, settings, .. } =
+            PrinterTarget::parse("serial:/dev/rfcomm0,19200").unwrap()
+        else {
+            panic!("expected serial target");
+        };
+        assert_eq!(baud, 19_200);
+        assert_eq!(settings, SerialSettings::default());
+    }
+
+    #[cfg(feature = "serial")]
+    #[test]
+    fn parse_serial_rejects_bad_settings() {
+        for bad in [
+            "serial:",
+            "serial:/dev/ttyUSB0,fast",
+            "serial:/dev/ttyUSB0,0",
+            "serial:COM5,9600,9",
+            "serial:COM5,9600,8,X",
+            "serial:COM5,9600,8,N,3",
+            "serial:COM5,9600,8,N,1,Q",
+            "serial:COM5,9600,8,N,1,X,extra",
+        ] {
+            assert!(
+                matches!(
+                    PrinterTarget::parse(bad),
+                    Err(PrintError::InvalidAddress(_))
+                ),
+                "{bad} should be rejected"
+            );
+        }
+    }
+
+    #[cfg(not(feature = "serial"))]
+    #[test]
+    fn parse_serial_without_feature_errors() {
+        assert!(matches!(
+            PrinterTarget::parse("serial:/dev/ttyUSB0"),
+            Err(PrintError::InvalidAddress(_))
+        ));
+    }
+
+    #[cfg(all(feature = "bluetooth", target_os = "linux"))]
+    #[test]
+    fn parse_bluetooth_target() {
+        let target = PrinterTarget::parse("bt:AA:BB:CC:DD:EE:FF:2").unwrap();
+        assert_eq!(
+            target,
+            PrinterTarget::Bluetooth(BluetoothAddr {
+                mac: [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF],
+                channel: 2,
+            })
+        );
+        assert_eq!(target.transport(), "bluetooth");
+        assert_eq!(target.to_string(), "bt:AA:BB:CC:DD:EE:FF:2");
+        assert!(matches!(
+            PrinterTarget::parse("BT:AA:BB:CC:DD:EE"),
+            Err(PrintError::InvalidAddress(_))
+        ));
+    }
+
+    #[cfg(not(all(feature = "bluetooth", target_os = "linux")))]
+    #[test]
+    fn parse_bluetooth_without_feature_errors() {
+        assert!(matches!(
+            PrinterTarget::parse("bt:AA:BB:CC:DD:EE:FF:1"),
+            Err(PrintError::InvalidAddress(_))
+        ));
+    }
+}
@@ -10,7 +10,8 @@ use std::time::Duration;
 use socket2::{SockRef, TcpKeepalive};
 
 use crate::addr::resolve_printer_addr;
-use crate::frame::{DEFAULT_MAX_FRAME_SIZE, expected_frame_count, read_frames};
+use crate::frame::{expected_frame_count, max_frame_size, read_frames};
+use crate::trace::{self, TraceDirection};
 use crate::{PrintError, Printer, PrinterConfig, StatusQuery};
 
 /// A synchronous TCP connection to a ZPL printer.
@@ -22,6 +23,8 @@ pub struct TcpPrinter {
     stream: TcpStream,
     config: PrinterConfig,
     addr: SocketAddr,
+    /// Unique id for trace output correlation; renewed on reconnect.
+    trace_session_id: u64,
 }
 
 impl TcpPrinter {
@@ -42,6 +45,7 @@ impl TcpPrinter {
             stream,
             config,
             addr: socket_addr,
+            trace_session_id: trace::next_session_id(),
         })
     }
 
@@ -54,6 +58,7 @@ impl TcpPrinter {
         let _ = self.stream.shutdown(Shutdown::Both);
 
         self.stream = Self::open_stream(&self.addr, &self.config)?;
+        self.trace_session_id = trace::next_session_id();
         Ok(())
     }
 
@@ -101,30 +106,63 @@ impl TcpPrinter {
 
 impl Printer for TcpPrinter {
     fn send_raw(&mut self, data: &[u8]) -> Result<(), PrintError> {
+        trace::emit(
+            &self.config,
+            "tcp",
+            self.trace_session_id,
+            TraceDirection::Sent,
+            data,
+        );
         self.stream
             .write_all(data)
             .map_err(PrintError::WriteFailed)?;
         self.stream.flush().map_err(PrintError::WriteFailed)?;
         Ok(())
     }
+
+    fn flush(&mut self) -> Result<(), PrintError> {
+        self.stream.flush().map_err(PrintError::WriteFailed)
+    }
 }
 
 impl StatusQuery for TcpPrinter {
     fn query_raw(&mut self, cmd: &[u8]) -> Result<Vec<Vec<u8>>, PrintError> {
         // Send the query command
-        self.stream
-            .write_all(cmd)
-            .map_err(PrintError::WriteFailed)?;
-        self.stream.flush().map_err(PrintError::WriteFailed)?;
+        self.send_raw(cmd)?;
 
         let expected_frames = expected_frame_count(cmd);
 
-        read_frames(
+        let frames = read_frames(
             &mut self.stream,
             expected_frames,
             self.config.timeouts.read,
-            DEFAULT_MAX_FRAME_SIZE,
-        )
+            max_frame_size(cmd),
+        )?;
+        for frame in &frames {
+            trace::emit(
+                &self.config,
+                "tcp",
+                self.trace_session_id,
+                TraceDirection::Received,
+                frame,
+            );
+        }
+        Ok(frames)
+    }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        Some(self.config.timeouts.read)
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+        self.stream
+            .set_read_timeout(Some(timeout))
+            .map_err(|e| PrintError::ConnectionFailed {
+                addr: self.addr.to_string(),
+                source: e,
+            })?;
+        self.config.timeouts.read = timeout;
+        Ok(())
     }
 }
 
@@ -0,0 +1,108 @@
+//! Transport byte tracing: trace events, caller-supplied sinks, and the
+//! default stderr sink.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::PrinterConfig;
+
+static NEXT_TRACE_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Direction of a traced transfer, relative to the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceDirection {
+    /// Bytes written to the printer.
+    Sent,
+    /// Bytes read from the printer (one status frame per event).
+    Received,
+}
+
+/// Raw bytes moved over a printer transport.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEvent {
+    /// Transport name (`"tcp"`, `"serial"`, `"usb"`, `"bluetooth"`).
+    pub transport: &'static str,
+    /// Connection id, unique within the process, for correlating events.
+    pub session_id: u64,
+    /// Whether the bytes were sent or received.
+    pub direction: TraceDirection,
+    /// The bytes transferred.
+    pub bytes: Vec<u8>,
+}
+
+/// Callback receiving [`TraceEvent`]s; set via [`PrinterConfig::trace_sink`].
+pub type TraceSink = Arc<dyn Fn(TraceEvent) + Send + Sync>;
+
+/// A sink that writes each event to stderr as a timestamped hex/ASCII dump.
+pub fn stderr_trace_sink() -> TraceSink {
+    Arc::new(|event| write_stderr(&event))
+}
+
+/// Allocate a session id for a new transport connection.
+pub(crate) fn next_session_id() -> u64 {
+    NEXT_TRACE_SESSION_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Deliver a trace event to the configured sink.
+///
+/// Falls back to the stderr dump when only [`PrinterConfig::trace_io`] is
+/// set. Does nothing (and copies nothing) when tracing is disabled.
+pub(crate) fn emit(
+    config: &PrinterConfig,
+    transport: &'static str,
+    session_id: u64,
+    direction: TraceDirection,
+    bytes: &[u8],
+) {
+    if config.trace_sink.is_none() && !config.trace_io {
+        return;
+    }
+    let event = TraceEvent {
+        transport,
+        session_id,
+        direction,
+        bytes: bytes.to_vec(),
+    };
+    match &config.trace_sink {
+        Some(sink) => sink(event),
+        None => write_stderr(&event),
+    }
+}
+
+fn write_stderr(event: &TraceEvent) {
+    let hex = event
+        .bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let ascii = event
+        .bytes
+        .iter()
+        .map(|b| {
+            if b.is_ascii_graphic() || *b == b' ' {
+                char::from(*b)
+            } else {
+                '.'
+            }
+        })
+        .collect::<String>();
+    let ts_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    let direction = match event.direction {
+        TraceDirection::Sent => "tx",
+        TraceDirection::Received => "rx",
+    };
+    eprintln!(
+        "[trace-io t={ts_ms} session={}] {} {direction} len={} hex=[{}] ascii='{}'",
+        event.session_id,
+        event.transport,
+        event.bytes.len(),
+        hex,
+        ascii
+    );
+}
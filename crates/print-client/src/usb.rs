@@ -6,10 +6,13 @@
 //! with a bulk OUT endpoint for sending ZPL and an optional bulk IN endpoint
 //! for reading status responses.
 
+use std::time::Duration;
+
 use futures_lite::future::block_on;
 use nusb::transfer::{Direction, EndpointType, RequestBuffer};
 
-use crate::frame::{DEFAULT_MAX_FRAME_SIZE, expected_frame_count, read_frames};
+use crate::frame::{expected_frame_count, max_frame_size, read_frames};
+use crate::trace::{self, TraceDirection};
 use crate::{PrintError, Printer, PrinterConfig, StatusQuery};
 
 /// Zebra Technologies USB Vendor ID.
@@ -31,6 +34,8 @@ pub struct UsbPrinter {
     ep_in: Option<u8>,
     /// Printer configuration (timeouts, retry settings).
     config: PrinterConfig,
+    /// Unique id for trace output correlation.
+    trace_session_id: u64,
 }
 
 impl UsbPrinter {
@@ -161,6 +166,7 @@ impl UsbPrinter {
             ep_out,
             ep_in,
             config,
+            trace_session_id: trace::next_session_id(),
         })
     }
 
@@ -229,6 +235,13 @@ impl UsbPrinter {
 
 impl Printer for UsbPrinter {
     fn send_raw(&mut self, data: &[u8]) -> Result<(), PrintError> {
+        trace::emit(
+            &self.config,
+            "usb",
+            self.trace_session_id,
+            TraceDirection::Sent,
+            data,
+        );
         self.bulk_write(data)
     }
 }
@@ -242,7 +255,7 @@ impl StatusQuery for UsbPrinter {
         };
 
         // Send the query command
-        self.bulk_write(cmd)?;
+        self.send_raw(cmd)?;
 
         // Read the response. Zebra printers respond with STX/ETX framed data.
         let expected_frames = expected_frame_count(cmd);
@@ -256,12 +269,26 @@ impl StatusQuery for UsbPrinter {
             pos: 0,
         };
 
-        read_frames(
-            &mut reader,
-            expected_frames,
-            timeout,
-            DEFAULT_MAX_FRAME_SIZE,
-        )
+        let frames = read_frames(&mut reader, expected_frames, timeout, max_frame_size(cmd))?;
+        for frame in &frames {
+            trace::emit(
+                &self.config,
+                "usb",
+                self.trace_session_id,
+                TraceDirection::Received,
+                frame,
+            );
+        }
+        Ok(frames)
+    }
+
+    fn read_timeout(&self) -> Option<Duration> {
+        Some(self.config.timeouts.read)
+    }
+
+    fn set_read_timeout(&mut self, timeout: Duration) -> Result<(), PrintError> {
+        self.config.timeouts.read = timeout;
+        Ok(())
     }
 }
 
@@ -2,10 +2,14 @@
 
 use std::io::{Read, Write};
 use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-use zpl_toolchain_print_client::{PrintError, Printer, PrinterConfig, StatusQuery, TcpPrinter};
+use zpl_toolchain_print_client::{
+    PrintError, Printer, PrinterConfig, PrinterTarget, StatusQuery, TcpPrinter, TraceDirection,
+    TraceEvent, connect_any,
+};
 
 // ── Mock printer server ─────────────────────────────────────────────────
 
@@ -152,6 +156,30 @@ fn send_raw_bytes() {
     assert_eq!(received, raw);
 }
 
+#[test]
+fn trace_sink_receives_sent_bytes() {
+    let server = MockPrinterServer::start(None);
+    let addr = format!("127.0.0.1:{}", server.addr.port());
+
+    let events: Arc<Mutex<Vec<TraceEvent>>> = Arc::default();
+    let sink_events = Arc::clone(&events);
+    let mut config = fast_config();
+    config.trace_sink = Some(Arc::new(move |event| {
+        sink_events.lock().unwrap().push(event);
+    }));
+
+    let mut printer = TcpPrinter::connect(&addr, config).unwrap();
+    printer.send_raw(b"^XA^XZ").unwrap();
+    drop(printer);
+    server.received_data();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].transport, "tcp");
+    assert_eq!(events[0].direction, TraceDirection::Sent);
+    assert_eq!(events[0].bytes, b"^XA^XZ");
+}
+
 #[test]
 fn query_status_parses_hs_response() {
     let server = MockPrinterServer::start(Some(mock_hs_response()));
@@ -179,7 +207,7 @@ fn query_info_parses_hi_response() {
 
     assert_eq!(info.model, "ZD421-300dpi");
     assert_eq!(info.firmware, "V84.20.18");
-    assert_eq!(info.dpi, 8);
+    assert_eq!(info.dpi, 203);
     assert_eq!(info.memory_kb, 8192);
 }
 
@@ -283,3 +311,46 @@ fn send_large_payload() {
     let received = server.received_data();
     assert_eq!(received.len(), zpl.len());
 }
+
+// ── Boxed transport factory ─────────────────────────────────────────────
+
+#[test]
+fn connect_any_tcp_sends_zpl() {
+    let server = MockPrinterServer::start(None);
+    let target = PrinterTarget::parse(&format!("127.0.0.1:{}", server.addr.port())).unwrap();
+
+    let mut printer = connect_any(&target, fast_config()).unwrap();
+    let sender: &mut dyn Printer = printer.as_mut();
+    sender.send_zpl("^XA^FDBoxed^FS^XZ").unwrap();
+    drop(printer);
+
+    assert_eq!(server.received_data(), b"^XA^FDBoxed^FS^XZ");
+}
+
+#[test]
+fn connect_any_tcp_queries_status_through_box() {
+    let server = MockPrinterServer::start(Some(mock_hs_response()));
+    let target = PrinterTarget::parse(&format!("127.0.0.1:{}", server.addr.port())).unwrap();
+
+    let mut printer = connect_any(&target, fast_config()).unwrap();
+    let status = printer.query_status().unwrap();
+    assert_eq!(status.label_length_dots, 1245);
+}
+
+#[test]
+fn connect_any_reports_tcp_connection_errors() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let target = PrinterTarget::Tcp(format!("127.0.0.1:{}", port));
+    let err = connect_any(&target, fast_config())
+        .err()
+        .expect("connect to closed port should fail");
+    assert!(matches!(
+        err,
+        PrintError::ConnectionRefused { .. }
+            | PrintError::ConnectionFailed { .. }
+            | PrintError::ConnectionTimeout { .. }
+    ));
+}
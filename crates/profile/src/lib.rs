@@ -71,6 +71,131 @@ pub struct Profile {
     pub memory: Option<Memory>,
 }
 
+impl Profile {
+    /// Usable print area from [`page`](Self::page) and [`dpi`](Self::dpi).
+    ///
+    /// Returns `None` when `page` or either of its dimensions is absent, or
+    /// when `dpi` is zero.
+    pub fn printable_area(&self) -> Option<PrintArea> {
+        let page = self.page.as_ref()?;
+        let width_dots = page.width_dots?;
+        let height_dots = page.height_dots?;
+        if self.dpi == 0 {
+            return None;
+        }
+        let dpi = f64::from(self.dpi);
+        Some(PrintArea {
+            width_dots,
+            height_dots,
+            width_in: f64::from(width_dots) / dpi,
+            height_in: f64::from(height_dots) / dpi,
+        })
+    }
+
+    /// Capability differences between this profile and `other`.
+    ///
+    /// Compares resolution, page size, ranges, feature flags, media, and
+    /// memory field by field; `id` and `schema_version` are identity, not
+    /// capability, and are ignored. A missing section compares as all of its
+    /// fields unset, so `features: None` and `features: {}` are equal.
+    pub fn diff(&self, other: &Profile) -> ProfileDiff {
+        let mut changes = Vec::new();
+        for ((field, left), (_, right)) in self
+            .capability_fields()
+            .into_iter()
+            .zip(other.capability_fields())
+        {
+            if left != right {
+                changes.push(ProfileChange { field, left, right });
+            }
+        }
+        ProfileDiff { changes }
+    }
+
+    /// Capability fields as `(path, rendered value)`, in a fixed order.
+    fn capability_fields(&self) -> Vec<(&'static str, Option<String>)> {
+        let page = self.page.clone().unwrap_or_default();
+        let features = self.features.clone().unwrap_or_default();
+        let media = self.media.clone().unwrap_or_default();
+        let memory = self.memory.clone().unwrap_or_default();
+        let range = |r: &Option<Range>| r.as_ref().map(|r| format!("{}-{}", r.min, r.max));
+        let flag = |f: Option<bool>| f.map(|f| f.to_string());
+        let list = |l: &Option<Vec<String>>| l.as_ref().map(|l| l.join(","));
+        vec![
+            ("dpi", Some(self.dpi.to_string())),
+            ("page.width_dots", page.width_dots.map(|v| v.to_string())),
+            ("page.height_dots", page.height_dots.map(|v| v.to_string())),
+            ("speed_range", range(&self.speed_range)),
+            ("darkness_range", range(&self.darkness_range)),
+            ("features.cutter", flag(features.cutter)),
+            ("features.peel", flag(features.peel)),
+            ("features.rewinder", flag(features.rewinder)),
+            ("features.applicator", flag(features.applicator)),
+            ("features.rfid", flag(features.rfid)),
+            ("features.rtc", flag(features.rtc)),
+            ("features.battery", flag(features.battery)),
+            ("features.zbi", flag(features.zbi)),
+            ("features.lcd", flag(features.lcd)),
+            ("features.kiosk", flag(features.kiosk)),
+            (
+                "media.print_method",
+                media.print_method.map(|m| m.as_str().to_string()),
+            ),
+            ("media.supported_modes", list(&media.supported_modes)),
+            ("media.supported_tracking", list(&media.supported_tracking)),
+            ("memory.ram_kb", memory.ram_kb.map(|v| v.to_string())),
+            ("memory.flash_kb", memory.flash_kb.map(|v| v.to_string())),
+            ("memory.firmware_version", memory.firmware_version),
+        ]
+    }
+}
+
+/// Capability differences between two profiles, from [`Profile::diff`].
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct ProfileDiff {
+    /// Differing fields, in profile field order.
+    pub changes: Vec<ProfileChange>,
+}
+
+impl ProfileDiff {
+    /// Whether the two profiles have identical capabilities.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The change for a dotted field path (e.g. `"features.cutter"`), if it differs.
+    pub fn get(&self, field: &str) -> Option<&ProfileChange> {
+        self.changes.iter().find(|c| c.field == field)
+    }
+}
+
+/// One differing capability field.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ProfileChange {
+    /// Dotted path using the profile JSON keys (e.g. `"page.width_dots"`).
+    pub field: &'static str,
+    /// Value in the profile `diff` was called on; `None` when unset.
+    /// Ranges render as `min-max` and lists as comma-separated values.
+    pub left: Option<String>,
+    /// Value in the other profile; `None` when unset.
+    pub right: Option<String>,
+}
+
+/// Usable print area of a profile, in dots and inches.
+///
+/// See [`Profile::printable_area`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintArea {
+    /// Width in dots.
+    pub width_dots: u32,
+    /// Height in dots.
+    pub height_dots: u32,
+    /// Width in inches.
+    pub width_in: f64,
+    /// Height in inches.
+    pub height_in: f64,
+}
+
 /// Page/label dimension constraints for a printer profile.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct Page {
@@ -114,6 +239,27 @@ impl Range {
         }
         Ok(Self { min, max })
     }
+
+    /// Whether `v` lies within the range, bounds included.
+    ///
+    /// Accepts fractional values (e.g. a darkness after a `^MD` adjustment);
+    /// NaN is never contained.
+    pub fn contains(&self, v: impl Into<f64>) -> bool {
+        let (lo, hi) = self.bounds();
+        (lo as f64..=hi as f64).contains(&v.into())
+    }
+
+    /// Clamp `v` to the nearest bound when it falls outside the range.
+    pub fn clamp(&self, v: u32) -> u32 {
+        let (lo, hi) = self.bounds();
+        v.clamp(lo, hi)
+    }
+
+    /// `(min, max)` with the bounds swapped if a struct literal inverted
+    /// them, so the helpers above never panic on an unchecked `Range`.
+    fn bounds(&self) -> (u32, u32) {
+        (self.min.min(self.max), self.min.max(self.max))
+    }
 }
 
 /// Hardware feature flags for a printer profile.
@@ -151,11 +297,25 @@ pub struct Features {
 
 /// Resolve a gate string (e.g., `"cutter"`, `"rfid"`) against a [`Features`] struct.
 ///
+/// A pipe-separated gate (e.g., `"cutter|peel"`) is satisfied by any of its
+/// alternatives and fails only when every alternative is explicitly absent.
+///
 /// Returns:
 /// - `Some(true)` if the feature is present
 /// - `Some(false)` if the feature is explicitly absent
 /// - `None` if the feature is unknown (gate should be skipped)
 pub fn resolve_gate(features: &Features, gate: &str) -> Option<bool> {
+    if gate.contains('|') {
+        let mut all_absent = true;
+        for alternative in gate.split('|') {
+            match resolve_gate(features, alternative.trim()) {
+                Some(true) => return Some(true),
+                Some(false) => {}
+                None => all_absent = false,
+            }
+        }
+        return if all_absent { Some(false) } else { None };
+    }
     match gate {
         "cutter" => features.cutter,
         "peel" => features.peel,
@@ -183,6 +343,17 @@ pub enum PrintMethod {
     Both,
 }
 
+impl PrintMethod {
+    /// The profile JSON spelling (e.g. `"direct_thermal"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrintMethod::DirectThermal => "direct_thermal",
+            PrintMethod::ThermalTransfer => "thermal_transfer",
+            PrintMethod::Both => "both",
+        }
+    }
+}
+
 /// Media capability descriptors for a printer profile.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct Media {
@@ -475,6 +646,34 @@ mod tests {
         assert_ne!(a, c);
     }
 
+    #[test]
+    fn printable_area_from_page_and_dpi() {
+        let mut p = Profile {
+            id: "test".into(),
+            schema_version: "1.0.0".into(),
+            dpi: 203,
+            page: Some(Page {
+                width_dots: Some(812),
+                height_dots: Some(1218),
+            }),
+            speed_range: None,
+            darkness_range: None,
+            features: None,
+            media: None,
+            memory: None,
+        };
+        let area = p.printable_area().expect("page is present");
+        assert_eq!(area.width_dots, 812);
+        assert_eq!(area.height_dots, 1218);
+        assert!((area.width_in - 4.0).abs() < 0.01, "{}", area.width_in);
+        assert!((area.height_in - 6.0).abs() < 0.01, "{}", area.height_in);
+
+        p.page.as_mut().unwrap().height_dots = None;
+        assert_eq!(p.printable_area(), None);
+        p.page = None;
+        assert_eq!(p.printable_area(), None);
+    }
+
     #[test]
     fn resolve_gate_known() {
         let f = Features {
@@ -557,6 +756,20 @@ mod tests {
         assert_eq!(resolve_gate(&features, "unknown_feature"), None);
     }
 
+    #[test]
+    fn resolve_gate_any_of_alternatives() {
+        let f = Features {
+            cutter: Some(false),
+            peel: Some(true),
+            rewinder: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(resolve_gate(&f, "cutter|peel"), Some(true));
+        assert_eq!(resolve_gate(&f, "cutter|rewinder"), Some(false));
+        // An unknown alternative might be present, so the gate is skipped.
+        assert_eq!(resolve_gate(&f, "cutter|applicator"), None);
+    }
+
     #[test]
     fn load_profile_malformed_json() {
         let err = load_profile_from_str("not json at all");
@@ -678,4 +891,100 @@ mod tests {
         assert_eq!(p.darkness_range.as_ref().unwrap().min, 0);
         assert_eq!(p.darkness_range.as_ref().unwrap().max, 30);
     }
+
+    #[test]
+    fn diff_reports_only_differing_capabilities() {
+        let a = load_profile_from_str(
+            r#"{
+                "id": "a", "schema_version": "1.0.0", "dpi": 203,
+                "page": { "width_dots": 812 },
+                "features": { "cutter": true, "rfid": false }
+            }"#,
+        )
+        .unwrap();
+        let b = load_profile_from_str(
+            r#"{
+                "id": "b", "schema_version": "1.0.0", "dpi": 300,
+                "page": { "width_dots": 812 },
+                "features": { "cutter": false, "rfid": false }
+            }"#,
+        )
+        .unwrap();
+
+        let diff = a.diff(&b);
+        assert_eq!(
+            diff.changes,
+            vec![
+                ProfileChange {
+                    field: "dpi",
+                    left: Some("203".into()),
+                    right: Some("300".into()),
+                },
+                ProfileChange {
+                    field: "features.cutter",
+                    left: Some("true".into()),
+                    right: Some("false".into()),
+                },
+            ]
+        );
+        assert!(diff.get("features.rfid").is_none());
+        assert!(a.diff(&a).is_empty());
+    }
+
+    #[test]
+    fn diff_treats_missing_sections_as_unset_fields() {
+        let a = load_profile_from_str(r#"{ "id": "a", "schema_version": "1.0.0", "dpi": 203 }"#)
+            .unwrap();
+        let b = load_profile_from_str(
+            r#"{
+                "id": "b", "schema_version": "1.0.0", "dpi": 203,
+                "features": {},
+                "darkness_range": { "min": 0, "max": 30 }
+            }"#,
+        )
+        .unwrap();
+        let diff = a.diff(&b);
+        assert_eq!(diff.changes.len(), 1, "{diff:?}");
+        let change = diff.get("darkness_range").unwrap();
+        assert_eq!(change.left, None);
+        assert_eq!(change.right.as_deref(), Some("0-30"));
+    }
+
+    #[test]
+    fn range_contains_is_inclusive() {
+        let r = Range::new(2, 12);
+        assert!(r.contains(7));
+        assert!(r.contains(2));
+        assert!(r.contains(12));
+        assert!(!r.contains(1));
+        assert!(!r.contains(13));
+    }
+
+    #[test]
+    fn range_clamp_pins_to_nearest_bound() {
+        let r = Range::new(2, 12);
+        assert_eq!(r.clamp(7), 7);
+        assert_eq!(r.clamp(2), 2);
+        assert_eq!(r.clamp(12), 12);
+        assert_eq!(r.clamp(0), 2);
+        assert_eq!(r.clamp(40), 12);
+    }
+
+    #[test]
+    fn range_accepts_fractional_values() {
+        let r = Range::new(0, 30);
+        assert!(r.contains(29.5));
+        assert!(!r.contains(30.5));
+        assert!(!r.contains(-0.5));
+        assert!(!r.contains(f64::NAN));
+    }
+
+    #[test]
+    fn inverted_range_does_not_panic() {
+        let r = Range { min: 12, max: 2 };
+        assert!(r.contains(7));
+        assert!(!r.contains(13));
+        assert_eq!(r.clamp(0), 2);
+        assert_eq!(r.clamp(40), 12);
+    }
 }
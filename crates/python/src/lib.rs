@@ -14,6 +14,32 @@ fn to_python_value(py: Python<'_>, json_text: String) -> PyResult<Py<PyAny>> {
     Ok(obj.unbind())
 }
 
+/// Raised when printing or a printer query fails.
+///
+/// A `RuntimeError` subclass, so existing `except RuntimeError` handlers
+/// still catch it.
+#[cfg(not(target_arch = "wasm32"))]
+mod exceptions {
+    pyo3::create_exception!(
+        zpl_toolchain,
+        PrintError,
+        pyo3::exceptions::PyRuntimeError,
+        "A print or printer query failed. `code` is the stable print-client error code (e.g. \"connection-refused\"), or None for configuration errors."
+    );
+}
+
+/// Convert a bindings print failure into a `PrintError` carrying its `code`.
+#[cfg(not(target_arch = "wasm32"))]
+fn print_error(failure: common::PrintFailure) -> PyErr {
+    Python::with_gil(|py| {
+        let err = exceptions::PrintError::new_err(failure.message);
+        if let Err(setattr_err) = err.value(py).setattr("code", failure.code) {
+            return setattr_err;
+        }
+        err
+    })
+}
+
 fn json_result_to_python(
     py: Python<'_>,
     json: Result<String, serde_json::Error>,
@@ -26,21 +52,39 @@ fn json_result_to_python(
 
 /// Parse a ZPL string and return `{ ast, diagnostics }` as a Python dict by default.
 ///
-/// Uses embedded parser tables and raises when unavailable.
+/// Uses embedded parser tables and raises when unavailable. Pass
+/// `with_spans=False` to omit source spans from the result, and
+/// `with_stats=True` to add `stats.commands` (occurrences per command code).
 #[pyfunction]
-fn parse(py: Python<'_>, input: &str) -> PyResult<Py<PyAny>> {
+#[pyo3(signature = (input, with_spans=true, with_stats=false))]
+fn parse(py: Python<'_>, input: &str, with_spans: bool, with_stats: bool) -> PyResult<Py<PyAny>> {
     let result = common::parse_zpl(input).map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
-    json_result_to_python(py, serde_json::to_string(&result))
+    to_python_value(
+        py,
+        common::parse_result_to_json_with_stats(&result, with_spans.into(), with_stats).to_string(),
+    )
 }
 
 /// Parse a ZPL string with explicitly provided parser tables (JSON string).
 ///
-/// Returns `{ ast, diagnostics }` as a Python dict by default.
+/// Returns `{ ast, diagnostics }` as a Python dict by default. Pass
+/// `with_spans=False` to omit source spans from the result, and
+/// `with_stats=True` to add `stats.commands`.
 #[pyfunction]
-fn parse_with_tables(py: Python<'_>, input: &str, tables_json: &str) -> PyResult<Py<PyAny>> {
+#[pyo3(signature = (input, tables_json, with_spans=true, with_stats=false))]
+fn parse_with_tables(
+    py: Python<'_>,
+    input: &str,
+    tables_json: &str,
+    with_spans: bool,
+    with_stats: bool,
+) -> PyResult<Py<PyAny>> {
     let result = common::parse_zpl_with_tables_json(input, tables_json)
         .map_err(pyo3::exceptions::PyValueError::new_err)?;
-    json_result_to_python(py, serde_json::to_string(&result))
+    to_python_value(
+        py,
+        common::parse_result_to_json_with_stats(&result, with_spans.into(), with_stats).to_string(),
+    )
 }
 
 /// Parse and validate a ZPL string.
@@ -134,7 +178,7 @@ fn print_zpl_with_options(
         timeout_ms,
         config_json,
     )
-    .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+    .map_err(print_error)?;
     to_python_value(py, json)
 }
 
@@ -159,7 +203,7 @@ fn query_printer_status_with_options(
     config_json: Option<&str>,
 ) -> PyResult<Py<PyAny>> {
     let json = common::query_printer_status_with_options(printer_addr, timeout_ms, config_json)
-        .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        .map_err(print_error)?;
     to_python_value(py, json)
 }
 
@@ -181,10 +225,110 @@ fn query_printer_info_with_options(
     config_json: Option<&str>,
 ) -> PyResult<Py<PyAny>> {
     let json = common::query_printer_info_with_options(printer_addr, timeout_ms, config_json)
-        .map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        .map_err(print_error)?;
     to_python_value(py, json)
 }
 
+/// A persistent connection to a network printer.
+///
+/// Exposed to Python as `Printer`. Use it as a context manager so the
+/// socket is closed when the block exits:
+///
+/// ```python
+/// with zpl_toolchain.Printer("192.168.1.55") as printer:
+///     printer.send("^XA^FDHello^FS^XZ")
+///     status = printer.query_status()
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+#[pyclass(name = "Printer")]
+struct PyPrinter {
+    session: Option<common::PrinterSession>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PyPrinter {
+    fn session(&mut self) -> PyResult<&mut common::PrinterSession> {
+        self.session
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("printer is closed"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[pymethods]
+impl PyPrinter {
+    /// Connect to the printer at `printer_addr` with optional timeout/config overrides.
+    #[new]
+    #[pyo3(signature = (printer_addr, timeout_ms=None, config_json=None))]
+    fn new(
+        printer_addr: &str,
+        timeout_ms: Option<u64>,
+        config_json: Option<&str>,
+    ) -> PyResult<Self> {
+        let session = common::PrinterSession::connect(printer_addr, timeout_ms, config_json)
+            .map_err(print_error)?;
+        Ok(Self {
+            session: Some(session),
+        })
+    }
+
+    /// Send ZPL over the open connection and return a Python dict.
+    ///
+    /// Validation behaves like `print_zpl`.
+    #[pyo3(signature = (zpl, profile_json=None, validate=true))]
+    fn send(
+        &mut self,
+        py: Python<'_>,
+        zpl: &str,
+        profile_json: Option<&str>,
+        validate: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let json = self
+            .session()?
+            .send_zpl(zpl, profile_json, validate)
+            .map_err(print_error)?;
+        to_python_value(py, json)
+    }
+
+    /// Query printer status via `~HS` and return a Python dict.
+    fn query_status(&mut self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let json = self.session()?.query_status().map_err(print_error)?;
+        to_python_value(py, json)
+    }
+
+    /// Query printer info via `~HI` and return a Python dict.
+    fn query_info(&mut self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let json = self.session()?.query_info().map_err(print_error)?;
+        to_python_value(py, json)
+    }
+
+    /// Close the connection. Closing twice is a no-op.
+    fn close(&mut self) {
+        self.session = None;
+    }
+
+    /// Whether the connection has been closed.
+    #[getter]
+    fn closed(&self) -> bool {
+        self.session.is_none()
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_value=None, _traceback=None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> bool {
+        self.close();
+        false
+    }
+}
+
 // ── Module ──────────────────────────────────────────────────────────────
 
 /// ZPL toolchain — parse, validate, and format Zebra Programming Language files.
@@ -204,17 +348,23 @@ fn zpl_toolchain(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m.add_function(wrap_pyfunction!(query_printer_status_with_options, m)?)?;
         m.add_function(wrap_pyfunction!(query_printer_info, m)?)?;
         m.add_function(wrap_pyfunction!(query_printer_info_with_options, m)?)?;
+        m.add_class::<PyPrinter>()?;
+        m.add("PrintError", m.py().get_type::<exceptions::PrintError>())?;
     }
     Ok(())
 }
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
+    use super::exceptions::PrintError;
     use super::{
-        print_zpl_with_options, query_printer_info_with_options, query_printer_status_with_options,
-        validate_with_tables,
+        PyPrinter, print_zpl_with_options, query_printer_info_with_options,
+        query_printer_status_with_options, validate_with_tables,
     };
     use pyo3::Python;
+    use pyo3::types::PyAnyMethods;
+    use std::io::Read;
+    use std::net::TcpListener;
 
     #[test]
     fn print_with_options_rejects_zero_timeout() {
@@ -244,6 +394,31 @@ mod tests {
         });
     }
 
+    #[test]
+    fn print_failures_raise_print_error_with_code() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let addr = listener.local_addr().expect("local addr").to_string();
+        drop(listener);
+
+        Python::with_gil(|py| {
+            let err = query_printer_status_with_options(py, &addr, Some(1_000), None)
+                .expect_err("closed port should refuse");
+            assert!(err.is_instance_of::<PrintError>(py));
+            assert!(err.is_instance_of::<pyo3::exceptions::PyRuntimeError>(py));
+            let code: Option<String> = err
+                .value(py)
+                .getattr("code")
+                .and_then(|c| c.extract())
+                .expect("code attribute");
+            assert_eq!(code.as_deref(), Some("connection-refused"));
+
+            let err = query_printer_status_with_options(py, "127.0.0.1:9100", Some(0), None)
+                .expect_err("timeout=0 should fail before I/O");
+            assert!(err.is_instance_of::<PrintError>(py));
+            assert!(err.value(py).getattr("code").expect("code").is_none());
+        });
+    }
+
     #[test]
     fn validate_with_tables_rejects_invalid_tables_json() {
         Python::with_gil(|py| {
@@ -252,4 +427,47 @@ mod tests {
             assert!(err.to_string().contains("invalid"));
         });
     }
+
+    #[test]
+    fn printer_rejects_zero_timeout() {
+        let err = PyPrinter::new("127.0.0.1:9100", Some(0), None)
+            .err()
+            .expect("timeout=0 should fail before I/O");
+        assert!(err.to_string().contains("timeout_ms must be > 0"));
+    }
+
+    #[test]
+    fn printer_sends_bytes_and_closes() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock listener");
+        let addr = listener.local_addr().expect("local addr").to_string();
+        let server = std::thread::spawn(move || {
+            let (mut conn, _) = listener.accept().expect("accept");
+            let mut received = Vec::new();
+            conn.read_to_end(&mut received).expect("read");
+            received
+        });
+
+        Python::with_gil(|py| {
+            let mut printer = PyPrinter::new(&addr, Some(1_000), None).expect("connect");
+            let result = printer
+                .send(py, "^XA^FDHello^FS^XZ", None, false)
+                .expect("send");
+            let result = result.bind(py);
+            assert!(
+                result
+                    .get_item("success")
+                    .and_then(|v| v.extract::<bool>())
+                    .expect("success key")
+            );
+            printer.__exit__(None, None, None);
+            assert!(printer.closed());
+            let err = printer
+                .query_status(py)
+                .expect_err("closed printer should reject queries");
+            assert!(err.to_string().contains("printer is closed"));
+        });
+
+        let received = server.join().expect("server thread");
+        assert_eq!(received, b"^XA^FDHello^FS^XZ");
+    }
 }
@@ -62,6 +62,19 @@ class PythonBindingApiTests(unittest.TestCase):
         self.assertIn("ast", result)
         self.assertGreater(len(result["ast"]["labels"]), 0)
 
+    def test_parse_without_spans_omits_span_fields(self) -> None:
+        with_spans = zpl_toolchain.parse("^XA^FO50,50^FDHELLO^FS^XZ")
+        without_spans = zpl_toolchain.parse("^XA^FO50,50^FDHELLO^FS^XZ", with_spans=False)
+        self.assertIn("span", with_spans["ast"]["labels"][0]["nodes"][0])
+        self.assertNotIn("span", without_spans["ast"]["labels"][0]["nodes"][0])
+
+    def test_parse_with_stats_counts_commands(self) -> None:
+        zpl = "^XA^FO1,1^FDa^FS^FO2,2^FDb^FS^XZ"
+        result = zpl_toolchain.parse(zpl, with_stats=True)
+        self.assertEqual(result["stats"]["commands"]["^FO"], 2)
+        self.assertEqual(result["stats"]["commands"]["^FD"], 2)
+        self.assertNotIn("stats", zpl_toolchain.parse(zpl))
+
     def test_format_returns_string(self) -> None:
         formatted = zpl_toolchain.format("^XA^FD Hello ^FS^XZ", "label")
         self.assertIsInstance(formatted, str)
@@ -110,6 +123,19 @@ class PythonBindingApiTests(unittest.TestCase):
             zpl_toolchain.query_printer_info_with_options("127.0.0.1:9100", 0, None)
         self.assertIn("timeout_ms must be > 0", str(ctx.exception))
 
+    def test_print_failure_raises_print_error_with_code(self) -> None:
+        with socket.socket(socket.AF_INET, socket.SOCK_STREAM) as sock:
+            sock.bind(("127.0.0.1", 0))
+            port = sock.getsockname()[1]
+        with self.assertRaises(zpl_toolchain.PrintError) as ctx:
+            zpl_toolchain.print_zpl_with_options("^XA^XZ", f"127.0.0.1:{port}", None, False, 1000, None)
+        self.assertIsInstance(ctx.exception, RuntimeError)
+        self.assertEqual(ctx.exception.code, "connection-refused")
+
+        with self.assertRaises(zpl_toolchain.PrintError) as ctx:
+            zpl_toolchain.query_printer_status_with_options("127.0.0.1:9100", 0, None)
+        self.assertIsNone(ctx.exception.code)
+
     def test_print_zpl_sends_payload_to_mock_printer(self) -> None:
         server = MockPrinterServer()
         try:
@@ -124,5 +150,28 @@ class PythonBindingApiTests(unittest.TestCase):
         self.assertGreater(result["bytes_sent"], 0)
         self.assertTrue(any("^XA" in payload for payload in server.received_payloads))
 
+    def test_printer_rejects_zero_timeout(self) -> None:
+        with self.assertRaises(RuntimeError) as ctx:
+            zpl_toolchain.Printer("127.0.0.1:9100", timeout_ms=0)
+        self.assertIn("timeout_ms must be > 0", str(ctx.exception))
+
+    def test_printer_context_manager_sends_and_closes(self) -> None:
+        server = MockPrinterServer()
+        try:
+            with zpl_toolchain.Printer(f"{server.host}:{server.port}", timeout_ms=1000) as printer:
+                result = printer.send("^XA^FO20,20^FDCTX^FS^XZ", validate=False)
+                self.assertFalse(printer.closed)
+            self.assertTrue(printer.closed)
+            for _ in range(20):
+                if server.received_payloads:
+                    break
+                time.sleep(0.01)
+        finally:
+            server.close()
+        self.assertTrue(result["success"])
+        self.assertTrue(any("^FDCTX" in payload for payload in server.received_payloads))
+        with self.assertRaises(RuntimeError):
+            printer.query_status()
+
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,206 @@
+//! Spec coverage computed directly from compiled parser tables.
+//!
+//! [`compute_coverage`] is the library counterpart of `coverage.json`: it
+//! works on a deserialized [`ParserTables`] plus a master opcode list, so
+//! callers can measure coverage without running the full build pipeline.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use zpl_toolchain_spec_tables::{CommandEntry, ParserTables};
+
+/// Per-command field presence for a command found in the tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CommandCoverage {
+    /// Whether the entry declares a signature.
+    pub has_signature: bool,
+    /// Whether the entry declares argument definitions.
+    pub has_args: bool,
+    /// Whether the entry declares command-level constraints.
+    pub has_constraints: bool,
+    /// Whether the entry carries documentation metadata (a command name).
+    pub has_docs: bool,
+    /// Standard fields that are missing, after structural exemptions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub missing_fields: Vec<&'static str>,
+}
+
+/// Coverage summary of a set of parser tables against a master opcode list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CoverageReport {
+    /// Number of distinct opcodes in the master list.
+    pub master_total: usize,
+    /// Master opcodes that are recognized by the tables.
+    pub present_count: usize,
+    /// Master opcodes that are not recognized by the tables.
+    pub missing_count: usize,
+    /// Sorted list of master opcodes missing from the tables.
+    pub missing_opcodes: Vec<String>,
+    /// Number of command entries in the tables.
+    pub total: usize,
+    /// Command entries that declare a signature.
+    pub with_signature: usize,
+    /// Command entries that declare argument definitions.
+    pub with_args: usize,
+    /// Command entries that declare command-level constraints.
+    pub with_constraints: usize,
+    /// Command entries that carry documentation metadata.
+    pub with_docs: usize,
+    /// Field presence keyed by canonical (first) opcode of each entry.
+    pub per_code: BTreeMap<String, CommandCoverage>,
+}
+
+impl CoverageReport {
+    /// Percentage of master opcodes present in the tables (0.0 when the
+    /// master list is empty).
+    pub fn percent_present(&self) -> f64 {
+        if self.master_total == 0 {
+            0.0
+        } else {
+            (self.present_count as f64) * 100.0 / (self.master_total as f64)
+        }
+    }
+}
+
+/// Compute spec coverage for `tables` relative to the `master` opcode list.
+///
+/// Presence is checked against every opcode of every entry, so aliases such
+/// as `~CC` for `^CC` count as present. Duplicate master entries are counted
+/// once. Parser tables do not embed prose docs, so `has_docs` reflects the
+/// command `name` metadata carried into the tables.
+pub fn compute_coverage(tables: &ParserTables, master: &[String]) -> CoverageReport {
+    let master: BTreeSet<&str> = master.iter().map(String::as_str).collect();
+    let known = tables.code_set();
+
+    let missing_opcodes: Vec<String> = master
+        .iter()
+        .filter(|code| !known.contains(**code))
+        .map(|code| code.to_string())
+        .collect();
+
+    let mut report = CoverageReport {
+        master_total: master.len(),
+        present_count: master.len() - missing_opcodes.len(),
+        missing_count: missing_opcodes.len(),
+        missing_opcodes,
+        total: tables.commands.len(),
+        ..CoverageReport::default()
+    };
+
+    for cmd in &tables.commands {
+        let entry = command_coverage(cmd);
+        report.with_signature += usize::from(entry.has_signature);
+        report.with_args += usize::from(entry.has_args);
+        report.with_constraints += usize::from(entry.has_constraints);
+        report.with_docs += usize::from(entry.has_docs);
+        if let Some(code) = cmd.codes.first() {
+            report.per_code.insert(code.clone(), entry);
+        }
+    }
+
+    report
+}
+
+fn command_coverage(cmd: &CommandEntry) -> CommandCoverage {
+    let has_signature = cmd.signature.is_some();
+    let has_args = cmd.args.is_some();
+    let has_constraints = cmd.constraints.is_some();
+    let has_docs = cmd.name.is_some();
+
+    // Mirrors the structural exemption used for `coverage.json`: commands
+    // with no arguments or that only collect field data need no signature,
+    // args, or constraints.
+    let structural = cmd.arity == 0 || cmd.field_data;
+    let mut missing_fields = Vec::new();
+    for (name, present, structural_exempt) in [
+        ("signature", has_signature, true),
+        ("args", has_args, true),
+        ("constraints", has_constraints, true),
+        ("docs", has_docs, false),
+    ] {
+        let exempt = structural_exempt && structural;
+        if !exempt && !present {
+            missing_fields.push(name);
+        }
+    }
+
+    CommandCoverage {
+        has_signature,
+        has_args,
+        has_constraints,
+        has_docs,
+        missing_fields,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compute_coverage;
+    use zpl_toolchain_spec_tables::ParserTables;
+
+    fn sample_tables() -> ParserTables {
+        serde_json::from_value(serde_json::json!({
+            "schemaVersion": "1.1.1",
+            "commands": [
+                {
+                    "codes": ["^FO"],
+                    "arity": 3,
+                    "name": "Field Origin",
+                    "signature": {"params": ["x", "y", "z"]},
+                    "args": [],
+                    "constraints": []
+                },
+                {
+                    "codes": ["^CC", "~CC"],
+                    "arity": 1,
+                    "signature": {"params": ["x"]}
+                },
+                {
+                    "codes": ["^XA"],
+                    "arity": 0,
+                    "name": "Start Format"
+                }
+            ]
+        }))
+        .expect("valid tables")
+    }
+
+    fn codes(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn counts_present_and_missing_master_codes() {
+        let tables = sample_tables();
+        let master = codes(&["^FO", "~CC", "^XA", "^XZ", "^GB", "^XZ"]);
+        let report = compute_coverage(&tables, &master);
+
+        assert_eq!(report.master_total, 5);
+        assert_eq!(report.present_count, 3);
+        assert_eq!(report.missing_count, 2);
+        assert_eq!(report.missing_opcodes, codes(&["^GB", "^XZ"]));
+        assert!((report.percent_present() - 60.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn counts_field_presence_per_entry() {
+        let report = compute_coverage(&sample_tables(), &[]);
+
+        assert_eq!(report.total, 3);
+        assert_eq!(report.with_signature, 2);
+        assert_eq!(report.with_args, 1);
+        assert_eq!(report.with_constraints, 1);
+        assert_eq!(report.with_docs, 2);
+        assert_eq!(report.percent_present(), 0.0);
+
+        let fo = &report.per_code["^FO"];
+        assert!(fo.has_signature && fo.has_args && fo.has_constraints && fo.has_docs);
+        assert!(fo.missing_fields.is_empty());
+
+        let cc = &report.per_code["^CC"];
+        assert_eq!(cc.missing_fields, vec!["args", "constraints", "docs"]);
+
+        // Structural commands are exempt from signature/args/constraints.
+        assert!(report.per_code["^XA"].missing_fields.is_empty());
+        assert!(!report.per_code.contains_key("~CC"));
+    }
+}
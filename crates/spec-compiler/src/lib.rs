@@ -2,6 +2,7 @@
 //! compiles them into parser tables, documentation bundles, and coverage
 //! reports. This is an internal build-time tool, not a runtime dependency.
 
+pub mod coverage;
 pub mod pipeline;
 pub mod source;
 
@@ -103,7 +103,14 @@ fn check(spec_dir: PathBuf) -> Result<()> {
         }
     }
 
-    // 4. Report summary
+    // 4. Verify example PNG hashes against fresh renders
+    #[cfg(feature = "render")]
+    {
+        let tables = pipeline::generate_tables(&loaded.commands, &loaded.schema_versions)?;
+        verify_example_png_hashes(&loaded.commands, &tables, &workspace_root())?;
+    }
+
+    // 5. Report summary
     let ok = validation_errors.is_empty();
     println!(
         "{}",
@@ -146,11 +153,7 @@ fn build(spec_dir: PathBuf, out_dir: PathBuf, strict: bool) -> Result<()> {
     }
 
     // 4. Load master code list (resolve from workspace root, not process CWD)
-    let workspace_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .parent()
-        .and_then(|p| p.parent())
-        .expect("spec-compiler manifest should be nested under workspace/crates/spec-compiler")
-        .to_path_buf();
+    let workspace_root = workspace_root();
     let master_codes_path = workspace_root.join("docs/public/schema/zpl-commands.jsonc");
     let master_codes = pipeline::load_master_codes(
         master_codes_path
@@ -161,6 +164,10 @@ fn build(spec_dir: PathBuf, out_dir: PathBuf, strict: bool) -> Result<()> {
     // 5. Generate parser tables (includes opcode trie inline)
     let tables = pipeline::generate_tables(&loaded.commands, &loaded.schema_versions)?;
 
+    // 5b. Verify example PNG hashes against fresh renders
+    #[cfg(feature = "render")]
+    verify_example_png_hashes(&loaded.commands, &tables, &workspace_root)?;
+
     // 6. Generate docs bundle (written as separate file, not embedded in parser_tables)
     let docs_bundle =
         pipeline::generate_docs_bundle(&loaded.commands, &loaded.schema_versions, &master_codes)?;
@@ -191,6 +198,36 @@ fn build(spec_dir: PathBuf, out_dir: PathBuf, strict: bool) -> Result<()> {
     Ok(())
 }
 
+/// Workspace root, resolved from this crate's manifest rather than the process CWD.
+fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .and_then(|p| p.parent())
+        .expect("spec-compiler manifest should be nested under workspace/crates/spec-compiler")
+        .to_path_buf()
+}
+
+/// Fail when any example `pngHash` differs from a fresh render with the
+/// generic 203 dpi profile.
+#[cfg(feature = "render")]
+fn verify_example_png_hashes(
+    commands: &[zpl_toolchain_spec_compiler::source::SourceCommand],
+    tables: &serde_json::Value,
+    workspace_root: &std::path::Path,
+) -> Result<()> {
+    let profile_path = workspace_root.join("profiles/zebra-generic-203.json");
+    let profile_json = std::fs::read_to_string(&profile_path)?;
+    let profile = zpl_toolchain_profile::load_profile_from_str(&profile_json)?;
+    let mismatches = pipeline::verify_example_png_hashes(commands, tables, &profile)?;
+    for mismatch in &mismatches {
+        eprintln!("error [pngHash]: {mismatch}");
+    }
+    if !mismatches.is_empty() {
+        bail!("{} example pngHash mismatch(es)", mismatches.len());
+    }
+    Ok(())
+}
+
 fn note_audit(spec_dir: PathBuf, format: &str, allow_findings: bool) -> Result<()> {
     let loaded = pipeline::load_spec_files(&spec_dir)?;
     let findings = pipeline::audit_notes(&loaded.commands);
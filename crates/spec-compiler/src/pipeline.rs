@@ -673,7 +673,11 @@ fn required_structural_bindings_for_code(code: &str) -> Option<&'static [Structu
             StructuralBindingKey::PositionAction(PA::TrackFieldOrigin),
             StructuralBindingKey::PositionAction(PA::ValidateFieldOrigin),
         ]),
-        "^A" => Some(&[StructuralBindingKey::FontAction(FA::Validate)]),
+        "^A" => Some(&[
+            StructuralBindingKey::FontAction(FA::Validate),
+            StructuralBindingKey::Kind(K::FontSize),
+        ]),
+        "^A@" => Some(&[StructuralBindingKey::Kind(K::FontSize)]),
         "^CW" => Some(&[StructuralBindingKey::FontAction(FA::Register)]),
         "^MM" => Some(&[StructuralBindingKey::MediaTarget(MT::SupportedModes)]),
         "^MN" => Some(&[StructuralBindingKey::MediaTarget(MT::SupportedTracking)]),
@@ -682,6 +686,9 @@ fn required_structural_bindings_for_code(code: &str) -> Option<&'static [Structu
             StructuralBindingKey::Kind(K::GfDataLength),
             StructuralBindingKey::Kind(K::GfPreflightTracking),
         ]),
+        "^MD" => Some(&[StructuralBindingKey::Kind(K::DarknessAdjustment)]),
+        "^SF" => Some(&[StructuralBindingKey::Kind(K::SerializationMask)]),
+        "^GC" | "^GD" | "^GE" => Some(&[StructuralBindingKey::Kind(K::GraphicExtent)]),
         _ => None,
     }
 }
@@ -702,7 +709,8 @@ fn validate_structural_rules_binding(cmd: &SourceCommand, errors: &mut Vec<Strin
         match rule {
             zpl_toolchain_spec_tables::StructuralRule::DuplicateFieldNumber { arg_index }
             | zpl_toolchain_spec_tables::StructuralRule::FontReference { arg_index, .. }
-            | zpl_toolchain_spec_tables::StructuralRule::MediaModes { arg_index, .. } => {
+            | zpl_toolchain_spec_tables::StructuralRule::MediaModes { arg_index, .. }
+            | zpl_toolchain_spec_tables::StructuralRule::DarknessAdjustment { arg_index } => {
                 if *arg_index >= arity {
                     errors.push(format!(
                         "structuralRules argIndex {} is out of range for command '{}' (arity {})",
@@ -741,6 +749,45 @@ fn validate_structural_rules_binding(cmd: &SourceCommand, errors: &mut Vec<Strin
                     }
                 }
             }
+            zpl_toolchain_spec_tables::StructuralRule::FontSize {
+                height_arg_index,
+                width_arg_index,
+            } => {
+                for idx in [height_arg_index, width_arg_index] {
+                    if *idx >= arity {
+                        errors.push(format!(
+                            "structuralRules fontSize arg index {} is out of range for command '{}' (arity {})",
+                            idx, code, arity
+                        ));
+                    }
+                }
+            }
+            zpl_toolchain_spec_tables::StructuralRule::SerializationMask {
+                mask_arg_index,
+                increment_arg_index,
+            } => {
+                for idx in [mask_arg_index, increment_arg_index] {
+                    if *idx >= arity {
+                        errors.push(format!(
+                            "structuralRules serializationMask arg index {} is out of range for command '{}' (arity {})",
+                            idx, code, arity
+                        ));
+                    }
+                }
+            }
+            zpl_toolchain_spec_tables::StructuralRule::GraphicExtent {
+                width_arg_index,
+                height_arg_index,
+            } => {
+                for idx in [width_arg_index, height_arg_index] {
+                    if *idx >= arity {
+                        errors.push(format!(
+                            "structuralRules graphicExtent arg index {} is out of range for command '{}' (arity {})",
+                            idx, code, arity
+                        ));
+                    }
+                }
+            }
             zpl_toolchain_spec_tables::StructuralRule::PositionBounds { .. } => {}
         }
     }
@@ -772,6 +819,24 @@ fn validate_structural_rules_binding(cmd: &SourceCommand, errors: &mut Vec<Strin
                     zpl_toolchain_spec_tables::StructuralRuleKind::GfPreflightTracking,
                 )
             }
+            zpl_toolchain_spec_tables::StructuralRule::DarknessAdjustment { .. } => {
+                StructuralBindingKey::Kind(
+                    zpl_toolchain_spec_tables::StructuralRuleKind::DarknessAdjustment,
+                )
+            }
+            zpl_toolchain_spec_tables::StructuralRule::FontSize { .. } => {
+                StructuralBindingKey::Kind(zpl_toolchain_spec_tables::StructuralRuleKind::FontSize)
+            }
+            zpl_toolchain_spec_tables::StructuralRule::SerializationMask { .. } => {
+                StructuralBindingKey::Kind(
+                    zpl_toolchain_spec_tables::StructuralRuleKind::SerializationMask,
+                )
+            }
+            zpl_toolchain_spec_tables::StructuralRule::GraphicExtent { .. } => {
+                StructuralBindingKey::Kind(
+                    zpl_toolchain_spec_tables::StructuralRuleKind::GraphicExtent,
+                )
+            }
         })
         .collect::<Vec<_>>();
     let configured_set: HashSet<StructuralBindingKey> = configured.into_iter().collect();
@@ -805,14 +870,23 @@ fn validate_profile_constraints_spec(
         && let Some(args) = &cmd.args
     {
         visit_args(args, |idx, arg| {
-            if let Some(pc) = &arg.profile_constraint
-                && !profile_fields.contains(&pc.field)
-            {
-                errors.push(format!(
-                    "arg[{}] profileConstraint references unknown profile field '{}' \
-                     (valid: {:?})",
-                    idx, pc.field, profile_fields
-                ));
+            let Some(pc) = &arg.profile_constraint else {
+                return;
+            };
+            // `within` names a range object; its `.min`/`.max` leaves must exist.
+            let fields = if pc.op == zpl_toolchain_spec_tables::ComparisonOp::Within {
+                vec![format!("{}.min", pc.field), format!("{}.max", pc.field)]
+            } else {
+                vec![pc.field.clone()]
+            };
+            for field in fields {
+                if !profile_fields.contains(&field) {
+                    errors.push(format!(
+                        "arg[{}] profileConstraint references unknown profile field '{}' \
+                         (valid: {:?})",
+                        idx, field, profile_fields
+                    ));
+                }
             }
         });
     }
@@ -904,6 +978,7 @@ const WHEN_PREDICATE_PREFIXES: &[&str] = &[
     "profile:featureMissing:",
     "profile:firmware:",
     "profile:firmwareGte:",
+    "profile:firmwareLt:",
     "profile:model:",
 ];
 
@@ -1087,10 +1162,12 @@ pub fn generate_tables(
         BTreeMap::new();
     let mut by_effect: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
 
-    for cmd in &out_cmds {
-        let Some(code) = cmd.codes.first().cloned() else {
-            continue;
-        };
+    // Index every code of a multi-code command (e.g. ^FD/^FV) so lookups by
+    // the code seen in the input match the command entry flags.
+    for (cmd, code) in out_cmds
+        .iter()
+        .flat_map(|cmd| cmd.codes.iter().map(move |code| (cmd, code.clone())))
+    {
         if let Some(rules) = cmd.structural_rules.as_ref() {
             for rule in rules {
                 by_kind.entry(rule.kind()).or_default().insert(code.clone());
@@ -1591,6 +1668,38 @@ pub fn generate_state_keys(
     })
 }
 
+/// Re-render examples that carry a `pngHash` and report hash mismatches.
+///
+/// Each mismatch is returned as `"<code> example <n>: expected <hash>, got <hash>"`.
+/// Examples are rendered with `profile` against the freshly generated `tables`.
+#[cfg(feature = "render")]
+pub fn verify_example_png_hashes(
+    commands: &[SourceCommand],
+    tables: &serde_json::Value,
+    profile: &zpl_toolchain_profile::Profile,
+) -> Result<Vec<String>> {
+    let tables: zpl_toolchain_spec_tables::ParserTables = serde_json::from_value(tables.clone())?;
+    let mut mismatches = Vec::new();
+    for cmd in commands {
+        let code = cmd.all_codes().into_iter().next().unwrap_or_default();
+        for (idx, example) in cmd.examples.iter().flatten().enumerate() {
+            let Some(expected) = example.png_hash.as_deref() else {
+                continue;
+            };
+            let actual =
+                zpl_toolchain_core::render::example_png_hash(&example.zpl, &tables, profile)
+                    .map_err(|e| anyhow::anyhow!("{code} example {}: {e}", idx + 1))?;
+            if actual != expected {
+                mismatches.push(format!(
+                    "{code} example {}: expected {expected}, got {actual}",
+                    idx + 1
+                ));
+            }
+        }
+    }
+    Ok(mismatches)
+}
+
 // ─── Helpers ────────────────────────────────────────────────────────────────
 
 fn anchor_from_code(code: &str) -> String {
@@ -2507,11 +2616,11 @@ mod tests {
             .as_ref()
             .expect("expected structural_rule_index in parser tables");
 
-        for cmd in &tables.commands {
-            let Some(code) = cmd.codes.first() else {
-                continue;
-            };
-
+        for (cmd, code) in tables
+            .commands
+            .iter()
+            .flat_map(|cmd| cmd.codes.iter().map(move |code| (cmd, code)))
+        {
             let checks = [
                 (
                     "opens_field",
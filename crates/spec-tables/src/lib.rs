@@ -7,7 +7,10 @@
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
+use std::str::FromStr;
 use std::sync::OnceLock;
+use thiserror::Error;
 
 // ─── Custom serde for HashMap<char, V> ──────────────────────────────────────
 // JSON object keys are always strings. The opcode trie uses single-character
@@ -56,6 +59,25 @@ where
 /// Current format version for the spec table JSON schema.
 pub const TABLE_FORMAT_VERSION: &str = "0.4.0";
 
+/// Errors that can occur when loading parser tables from JSON.
+#[derive(Debug, Error)]
+pub enum TableLoadError {
+    /// The input could not be read or is not valid tables JSON.
+    #[error("invalid tables JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    /// The tables were generated for a different table format version.
+    #[error(
+        "incompatible tables format version {found} (expected {expected}); regenerate the tables with the matching spec compiler"
+    )]
+    IncompatibleVersion {
+        /// The `formatVersion` recorded in the tables.
+        found: String,
+        /// The format version this crate reads ([`TABLE_FORMAT_VERSION`]).
+        expected: String,
+    },
+}
+
 /// Command scope — determines the lifecycle boundary of the command's effect.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -177,6 +199,14 @@ pub enum StructuralRuleKind {
     GfDataLength,
     /// ^GF bounds + preflight memory tracking checks.
     GfPreflightTracking,
+    /// Relative darkness (^MD) checks against profile darkness range.
+    DarknessAdjustment,
+    /// Font size sanity checks (zero dimensions, oversize) for ^A/^A@.
+    FontSize,
+    /// ^SF mask placeholder and increment alignment checks.
+    SerializationMask,
+    /// Graphic primitive extent checks (^GC/^GD/^GE) against label bounds.
+    GraphicExtent,
 }
 
 impl StructuralRuleKind {
@@ -188,6 +218,10 @@ impl StructuralRuleKind {
         Self::MediaModes,
         Self::GfDataLength,
         Self::GfPreflightTracking,
+        Self::DarknessAdjustment,
+        Self::FontSize,
+        Self::SerializationMask,
+        Self::GraphicExtent,
     ];
 }
 
@@ -279,6 +313,37 @@ pub enum StructuralRule {
         #[serde(default = "default_gf_bpr_arg_index")]
         bytes_per_row_arg_index: usize,
     },
+    /// Check a relative darkness adjustment against the profile darkness range.
+    DarknessAdjustment {
+        /// Argument index carrying the adjustment.
+        #[serde(default)]
+        arg_index: usize,
+    },
+    /// Check font height/width for ambiguous zero values and oversize cells.
+    #[serde(rename_all = "camelCase")]
+    FontSize {
+        /// Argument index carrying the character height.
+        height_arg_index: usize,
+        /// Argument index carrying the character width.
+        width_arg_index: usize,
+    },
+    /// Check ^SF mask placeholders and the increment string laid over them.
+    #[serde(rename_all = "camelCase")]
+    SerializationMask {
+        /// Argument index carrying the mask string.
+        mask_arg_index: usize,
+        /// Argument index carrying the increment string.
+        increment_arg_index: usize,
+    },
+    /// Check that a graphic primitive drawn at the field origin fits the label.
+    #[serde(rename_all = "camelCase")]
+    GraphicExtent {
+        /// Argument index carrying the horizontal extent in dots.
+        width_arg_index: usize,
+        /// Argument index carrying the vertical extent (same as the width for
+        /// a circle's diameter).
+        height_arg_index: usize,
+    },
 }
 
 const fn default_gf_declared_arg_index() -> usize {
@@ -307,6 +372,10 @@ impl StructuralRule {
             Self::MediaModes { .. } => StructuralRuleKind::MediaModes,
             Self::GfDataLength { .. } => StructuralRuleKind::GfDataLength,
             Self::GfPreflightTracking { .. } => StructuralRuleKind::GfPreflightTracking,
+            Self::DarknessAdjustment { .. } => StructuralRuleKind::DarknessAdjustment,
+            Self::FontSize { .. } => StructuralRuleKind::FontSize,
+            Self::SerializationMask { .. } => StructuralRuleKind::SerializationMask,
+            Self::GraphicExtent { .. } => StructuralRuleKind::GraphicExtent,
         }
     }
 }
@@ -398,6 +467,28 @@ impl ParserTables {
         }
     }
 
+    /// Load tables from a JSON reader, rejecting tables whose
+    /// `formatVersion` differs from [`TABLE_FORMAT_VERSION`].
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, TableLoadError> {
+        let tables: Self = serde_json::from_reader(reader)?;
+        tables.check_format_version()?;
+        Ok(tables)
+    }
+
+    /// Check that these tables use [`TABLE_FORMAT_VERSION`].
+    ///
+    /// Tables without a `formatVersion` field are assumed current.
+    pub fn check_format_version(&self) -> Result<(), TableLoadError> {
+        if self.format_version == TABLE_FORMAT_VERSION {
+            Ok(())
+        } else {
+            Err(TableLoadError::IncompatibleVersion {
+                found: self.format_version.clone(),
+                expected: TABLE_FORMAT_VERSION.to_string(),
+            })
+        }
+    }
+
     /// Returns a cached set of all known command codes.
     /// The set is built lazily on first access and reused thereafter.
     pub fn code_set(&self) -> &HashSet<String> {
@@ -429,6 +520,18 @@ impl ParserTables {
     }
 }
 
+impl FromStr for ParserTables {
+    type Err = TableLoadError;
+
+    /// Parse tables from a JSON string with the same version check as
+    /// [`ParserTables::from_reader`].
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        let tables: Self = serde_json::from_str(json)?;
+        tables.check_format_version()?;
+        Ok(tables)
+    }
+}
+
 /// Metadata for a single ZPL command (or group of aliased commands).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -571,6 +674,10 @@ pub struct Placement {
     /// Whether this command is allowed outside ^XA/^XZ label bounds.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allowed_outside_label: Option<bool>,
+    /// Whether this command belongs in the label header, before the first
+    /// field-opening command (e.g. `^FO`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_precede_fields: Option<bool>,
 }
 
 /// Validation rules for field data content associated with a barcode command.
@@ -601,11 +708,26 @@ pub struct FieldDataRules {
     /// Severity for length/parity violations (defaults to Warn).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub length_severity: Option<ConstraintSeverity>,
+    /// Structured payload format whose capacity is checked (e.g., QR switch fields).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_format: Option<PayloadFormat>,
     /// Human-readable notes about the data format.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
 }
 
+/// Structured field data payload formats with format-specific validation.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PayloadFormat {
+    /// QR Code `^FD` switch fields (`<errCorr><inputMode>,<data>`), checked
+    /// against the symbol's data capacity for the error correction level.
+    Qr,
+    /// Data Matrix data, checked against the capacity implied by the `^BX`
+    /// quality level, format ID, and requested symbol size.
+    DataMatrix,
+}
+
 /// Rule for splitting a parameter into multiple parts by character count.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -639,7 +761,9 @@ pub struct Signature {
     pub joiner: String,
     /// Spacing policy between opcode and first argument.
     pub spacing_policy: SpacingPolicy,
-    /// Whether to pad the argument list with empty trailing slots.
+    /// Whether to pad the argument list with empty trailing slots. When
+    /// `true` the emitter may drop trailing empties; when `false` it writes
+    /// back every slot the source carried.
     #[serde(default = "default_allow_empty_trailing")]
     pub allow_empty_trailing: bool,
     /// Optional rule for splitting a single raw parameter into multiple args.
@@ -810,6 +934,11 @@ pub struct Arg {
     /// Profile-driven constraint on this argument's value.
     #[serde(default)]
     pub profile_constraint: Option<ProfileConstraint>,
+    /// Symbolic values that stand for a number (e.g., `^PR` speed letters
+    /// `"A"` → `2`). Used when a numeric check such as `profileConstraint`
+    /// meets a non-numeric value.
+    #[serde(default)]
+    pub numeric_aliases: Option<std::collections::HashMap<String, f64>>,
 
     /// Conditional range overrides based on other argument values.
     #[serde(default)]
@@ -843,6 +972,9 @@ pub enum ComparisonOp {
     Gt,
     /// Equal (with tolerance for integer-cast floats).
     Eq,
+    /// Within an inclusive profile range. `field` names the range object
+    /// (e.g., `"speed_range"`); its `.min` and `.max` are the bounds.
+    Within,
 }
 
 /// Data-driven profile constraint on an arg value.
@@ -1095,10 +1227,43 @@ pub struct Constraint {
 #[cfg(test)]
 mod tests {
     use super::{
-        Arg, ArgPresence, ConstraintDefaults, ConstraintSeverity, ResourceKind, RoundingPolicy,
-        Signature, SpacingPolicy,
+        Arg, ArgPresence, ConstraintDefaults, ConstraintSeverity, ParserTables, ResourceKind,
+        RoundingPolicy, Signature, SpacingPolicy, TABLE_FORMAT_VERSION, TableLoadError,
     };
 
+    fn tables_json(format_version: &str) -> String {
+        format!(r#"{{"schemaVersion":"1.1.1","formatVersion":"{format_version}","commands":[]}}"#)
+    }
+
+    #[test]
+    fn tables_with_current_format_version_load() {
+        let json = tables_json(TABLE_FORMAT_VERSION);
+        let tables: ParserTables = json.parse().expect("current tables");
+        assert_eq!(tables.format_version, TABLE_FORMAT_VERSION);
+        assert!(ParserTables::from_reader(json.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn tables_with_older_format_version_are_rejected() {
+        let err =
+            ParserTables::from_reader(tables_json("0.1.0").as_bytes()).expect_err("stale tables");
+        match err {
+            TableLoadError::IncompatibleVersion { found, expected } => {
+                assert_eq!(found, "0.1.0");
+                assert_eq!(expected, TABLE_FORMAT_VERSION);
+            }
+            other => panic!("expected version error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn malformed_tables_json_is_invalid_json() {
+        let err = "{\"commands\": ["
+            .parse::<ParserTables>()
+            .expect_err("malformed");
+        assert!(matches!(err, TableLoadError::InvalidJson(_)), "{err:?}");
+    }
+
     #[test]
     fn signature_allow_empty_trailing_defaults_true() {
         let sig: Signature =
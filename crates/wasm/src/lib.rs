@@ -1,6 +1,6 @@
 //! WASM bindings for the ZPL toolchain.
 //!
-//! Exposes parse, validate, format, and explain functions to JavaScript
+//! Exposes parse, validate, format, explain, and reference-listing functions to JavaScript
 //! via `wasm-bindgen`. Results are returned as native JS objects using
 //! `serde-wasm-bindgen` for zero-copy interop.
 
@@ -13,20 +13,32 @@ use zpl_toolchain_bindings_common as common;
 /// Parse a ZPL string and return `{ ast, diagnostics }`.
 ///
 /// Uses embedded parser tables and returns an error when unavailable.
+/// Pass `withSpans = false` to omit source spans from the result, and
+/// `withStats = true` to add `stats.commands` (occurrences per command code).
 #[wasm_bindgen]
-pub fn parse(input: &str) -> Result<JsValue, JsError> {
+pub fn parse(
+    input: &str,
+    with_spans: Option<bool>,
+    with_stats: Option<bool>,
+) -> Result<JsValue, JsError> {
     let result = common::parse_zpl(input).map_err(|e| JsError::new(&e))?;
-    to_js(&result)
+    parse_result_to_js(&result, with_spans, with_stats)
 }
 
 /// Parse a ZPL string with explicitly provided parser tables (JSON string).
 ///
-/// Returns `{ ast, diagnostics }`.
+/// Returns `{ ast, diagnostics }`. Pass `withSpans = false` to omit source
+/// spans from the result, and `withStats = true` to add `stats.commands`.
 #[wasm_bindgen(js_name = "parseWithTables")]
-pub fn parse_with_tables_js(input: &str, tables_json: &str) -> Result<JsValue, JsError> {
+pub fn parse_with_tables_js(
+    input: &str,
+    tables_json: &str,
+    with_spans: Option<bool>,
+    with_stats: Option<bool>,
+) -> Result<JsValue, JsError> {
     let result =
         common::parse_zpl_with_tables_json(input, tables_json).map_err(|e| JsError::new(&e))?;
-    to_js(&result)
+    parse_result_to_js(&result, with_spans, with_stats)
 }
 
 /// Parse and validate a ZPL string.
@@ -76,8 +88,41 @@ pub fn explain(id: &str) -> Option<String> {
     common::explain_diagnostic(id).map(|s| s.to_string())
 }
 
+/// Return the full diagnostic catalog as an array of
+/// `{ id, severity, category, summary, description }`.
+#[wasm_bindgen(js_name = "explainAll")]
+pub fn explain_all() -> Result<JsValue, JsError> {
+    to_js(common::diagnostic_catalog())
+}
+
+/// List every command in the embedded tables as an array of
+/// `{ codes, name, category, plane, scope, arity, params, deprecated }`,
+/// sorted by opcode.
+#[wasm_bindgen(js_name = "listCommands")]
+pub fn list_commands() -> Result<JsValue, JsError> {
+    let commands = common::list_commands().map_err(|e| JsError::new(&e))?;
+    to_js(&commands)
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────────
 
 fn to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, JsError> {
     serde_wasm_bindgen::to_value(value).map_err(|e| JsError::new(&e.to_string()))
 }
+
+fn parse_result_to_js(
+    result: &common::ParseResult,
+    with_spans: Option<bool>,
+    with_stats: Option<bool>,
+) -> Result<JsValue, JsError> {
+    let with_spans = with_spans.unwrap_or(true);
+    let with_stats = with_stats.unwrap_or(false);
+    if with_spans && !with_stats {
+        return to_js(result);
+    }
+    // Spanless or stats output goes through a JSON value; serialize maps as
+    // plain objects so the shape matches the default path.
+    let json = common::parse_result_to_json_with_stats(result, with_spans.into(), with_stats);
+    serde::Serialize::serialize(&json, &serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
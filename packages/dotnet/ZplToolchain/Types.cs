@@ -18,19 +18,34 @@ public record ArgSlot(
     [property: JsonPropertyName("value")] string? Value = null
 );
 
+/// <summary>A secondary location a diagnostic refers to.</summary>
+public record RelatedSpan(
+    [property: JsonPropertyName("span")] Span Span,
+    [property: JsonPropertyName("message")] string Message
+);
+
 /// <summary>A diagnostic message from the parser or validator.</summary>
 public record Diagnostic(
     [property: JsonPropertyName("id")] string Id,
     [property: JsonPropertyName("severity")] string Severity,
     [property: JsonPropertyName("message")] string Message,
     [property: JsonPropertyName("span")] Span? Span = null,
-    [property: JsonPropertyName("context")] Dictionary<string, string>? Context = null
+    [property: JsonPropertyName("context")] Dictionary<string, string>? Context = null,
+    [property: JsonPropertyName("related")] List<RelatedSpan>? Related = null
+);
+
+/// <summary>Per-severity diagnostic counts.</summary>
+public record DiagnosticSummary(
+    [property: JsonPropertyName("errors")] int Errors,
+    [property: JsonPropertyName("warns")] int Warns,
+    [property: JsonPropertyName("infos")] int Infos
 );
 
 /// <summary>Result of parsing a ZPL string.</summary>
 public record ParseResult(
     [property: JsonPropertyName("ast")] Ast Ast,
-    [property: JsonPropertyName("diagnostics")] List<Diagnostic> Diagnostics
+    [property: JsonPropertyName("diagnostics")] List<Diagnostic> Diagnostics,
+    [property: JsonPropertyName("summary")] DiagnosticSummary? Summary = null
 );
 
 /// <summary>Typed defaults from ^BY.</summary>
@@ -79,11 +94,23 @@ public record LabelValueState(
     [property: JsonPropertyName("layout")] LayoutDefaults Layout
 );
 
+/// <summary>A ^FN field with its optional prompt name.</summary>
+public record NamedField(
+    [property: JsonPropertyName("number")] uint Number,
+    [property: JsonPropertyName("span")] Span Span,
+    [property: JsonPropertyName("name")] string? Name = null
+);
+
 /// <summary>Renderer-ready per-label resolved state.</summary>
 public record ResolvedLabelState(
     [property: JsonPropertyName("values")] LabelValueState Values,
     [property: JsonPropertyName("effective_width")] double? EffectiveWidth = null,
-    [property: JsonPropertyName("effective_height")] double? EffectiveHeight = null
+    [property: JsonPropertyName("effective_height")] double? EffectiveHeight = null,
+    [property: JsonPropertyName("index")] int Index = 0,
+    [property: JsonPropertyName("span")] Span? Span = null,
+    [property: JsonPropertyName("command_count")] int CommandCount = 0,
+    [property: JsonPropertyName("error_count")] int ErrorCount = 0,
+    [property: JsonPropertyName("fields")] List<NamedField>? Fields = null
 );
 
 /// <summary>Result of validating a ZPL string.</summary>
@@ -127,6 +127,12 @@ type Span struct {
 	End   int `json:"end"`
 }
 
+// RelatedSpan is a secondary location a diagnostic refers to.
+type RelatedSpan struct {
+	Span    Span   `json:"span"`
+	Message string `json:"message"`
+}
+
 // Diagnostic represents a single diagnostic message.
 type Diagnostic struct {
 	ID       string            `json:"id"`
@@ -134,12 +140,21 @@ type Diagnostic struct {
 	Message  string            `json:"message"`
 	Span     *Span             `json:"span,omitempty"`
 	Context  map[string]string `json:"context,omitempty"`
+	Related  []RelatedSpan     `json:"related,omitempty"`
+}
+
+// DiagnosticSummary counts diagnostics by severity.
+type DiagnosticSummary struct {
+	Errors int `json:"errors"`
+	Warns  int `json:"warns"`
+	Infos  int `json:"infos"`
 }
 
 // ParseResult is the result of parsing ZPL input.
 type ParseResult struct {
-	Ast         Ast          `json:"ast"`
-	Diagnostics []Diagnostic `json:"diagnostics"`
+	Ast         Ast               `json:"ast"`
+	Diagnostics []Diagnostic      `json:"diagnostics"`
+	Summary     DiagnosticSummary `json:"summary"`
 }
 
 // BarcodeDefaults contains typed defaults from ^BY.
@@ -188,11 +203,23 @@ type LabelValueState struct {
 	Layout    LayoutDefaults           `json:"layout"`
 }
 
+// NamedField is a ^FN field with its optional prompt name.
+type NamedField struct {
+	Number uint32  `json:"number"`
+	Name   *string `json:"name,omitempty"`
+	Span   Span    `json:"span"`
+}
+
 // ResolvedLabelState is renderer-ready per-label state from validation output.
 type ResolvedLabelState struct {
+	Index           int             `json:"index"`
+	Span            *Span           `json:"span,omitempty"`
+	CommandCount    int             `json:"command_count"`
+	ErrorCount      int             `json:"error_count"`
 	Values          LabelValueState `json:"values"`
 	EffectiveWidth  *float64        `json:"effective_width,omitempty"`
 	EffectiveHeight *float64        `json:"effective_height,omitempty"`
+	Fields          []NamedField    `json:"fields,omitempty"`
 }
 
 // ValidationResult is the result of validating ZPL input.
@@ -53,6 +53,10 @@ export interface CommandNode {
   code: string;
   args: ArgSlot[];
   span: Span;
+  /** Blank lines preceding this command in the source (omitted when zero). */
+  blank_lines_before?: number;
+  /** Source argument separator, present only when it differs from the signature joiner. */
+  joiner?: string;
 }
 
 export interface FieldDataNode {
@@ -86,11 +90,19 @@ export interface Label {
 /** Top-level AST for a ZPL document. */
 export interface Ast {
   labels: Label[];
+  /** Set when non-UTF-8 input was decoded one byte per character (omitted otherwise). */
+  byte_decoded?: boolean;
 }
 
 /** Diagnostic severity level. Serialized as lowercase by Rust. */
 export type Severity = "error" | "warn" | "info";
 
+/** A secondary location a diagnostic refers to (e.g. a first definition). */
+export interface RelatedSpan {
+  span: Span;
+  message: string;
+}
+
 /** A diagnostic message from the parser or validator. */
 export interface Diagnostic {
   id: string;
@@ -98,12 +110,31 @@ export interface Diagnostic {
   message: string;
   span?: Span;
   context?: Record<string, string>;
+  /** Secondary locations to mark alongside `span` (omitted when empty). */
+  related?: RelatedSpan[];
 }
 
 /** Result of parsing a ZPL string. */
 export interface ParseResult {
   ast: Ast;
   diagnostics: Diagnostic[];
+  /** Counts of `diagnostics` by severity. */
+  summary: DiagnosticSummary;
+  /** Usage statistics, present only when requested with `withStats`. */
+  stats?: ParseStats;
+}
+
+/** Per-severity diagnostic counts. */
+export interface DiagnosticSummary {
+  errors: number;
+  warns: number;
+  infos: number;
+}
+
+/** Command usage statistics for a parse result. */
+export interface ParseStats {
+  /** Occurrences of each command code (e.g. `"^FO"`) across all labels. */
+  commands: Record<string, number>;
 }
 
 /** Typed defaults from `^BY`. */
@@ -152,11 +183,24 @@ export interface LabelValueState {
   layout: LayoutDefaults;
 }
 
+/** A `^FN` field with its optional prompt name. */
+export interface NamedField {
+  number: number;
+  name?: string | null;
+  span: Span;
+}
+
 /** Renderer-ready resolved label state from validator output. */
 export interface ResolvedLabelState {
+  index: number;
+  span?: Span | null;
+  command_count: number;
+  error_count: number;
   values: LabelValueState;
   effective_width?: number | null;
   effective_height?: number | null;
+  /** `^FN` fields in source order. */
+  fields: NamedField[];
 }
 
 /** Result of validating a ZPL string. */
@@ -166,6 +210,27 @@ export interface ValidationResult {
   resolved_labels?: ResolvedLabelState[];
 }
 
+/** One entry of the diagnostic catalog returned by `explainAll()`. */
+export interface DiagnosticInfo {
+  id: string;
+  severity: Severity;
+  category: string;
+  summary: string;
+  description: string;
+}
+
+/** Reference metadata for one command, as returned by `listCommands()`. */
+export interface CommandSummary {
+  codes: string[];
+  name?: string;
+  category?: string;
+  plane?: string;
+  scope?: string;
+  arity: number;
+  params: string[];
+  deprecated: boolean;
+}
+
 /** Indentation style for the formatter. */
 export type IndentStyle = "none" | "label" | "field";
 /** Optional compaction mode for the formatter. */
@@ -226,24 +291,44 @@ function invokeWasm<T>(op: string, fn: () => T): T {
 /**
  * Parse a ZPL string and return the AST with diagnostics.
  *
- * Uses embedded parser tables for spec-driven parsing.
+ * Uses embedded parser tables for spec-driven parsing. Pass
+ * `withSpans = false` to omit `span` fields (smaller output for logging),
+ * and `withStats = true` to add `stats.commands` (per-opcode usage counts).
  */
-export function parse(input: string): ParseResult {
+export function parse(
+  input: string,
+  withSpans = true,
+  withStats = false
+): ParseResult {
   const wasm = ensureInit();
-  return invokeWasm("parse", () => wasm.parse(input) as ParseResult);
+  return invokeWasm(
+    "parse",
+    () => wasm.parse(input, withSpans, withStats) as ParseResult
+  );
 }
 
 /**
  * Parse a ZPL string with explicitly provided parser tables (JSON string).
+ *
+ * Pass `withSpans = false` to omit `span` fields, and `withStats = true` to
+ * add `stats.commands`.
  */
 export function parseWithTables(
   input: string,
-  tablesJson: string
+  tablesJson: string,
+  withSpans = true,
+  withStats = false
 ): ParseResult {
   const wasm = ensureInit();
   return invokeWasm(
     "parseWithTables",
-    () => wasm.parseWithTables(input, tablesJson) as ParseResult
+    () =>
+      wasm.parseWithTables(
+        input,
+        tablesJson,
+        withSpans,
+        withStats
+      ) as ParseResult
   );
 }
 
@@ -308,3 +393,15 @@ export function explain(id: string): string | null {
   const wasm = ensureInit();
   return invokeWasm("explain", () => wasm.explain(id) ?? null);
 }
+
+/** Return every diagnostic code with its severity, category, and explanation. */
+export function explainAll(): DiagnosticInfo[] {
+  const wasm = ensureInit();
+  return invokeWasm("explainAll", () => wasm.explainAll() as DiagnosticInfo[]);
+}
+
+/** List every command in the embedded parser tables, sorted by opcode. */
+export function listCommands(): CommandSummary[] {
+  const wasm = ensureInit();
+  return invokeWasm("listCommands", () => wasm.listCommands() as CommandSummary[]);
+}
@@ -6,13 +6,19 @@
  * `wasm/pkg/`, TypeScript uses the generated types from that directory instead.
  */
 declare module "../wasm/pkg/zpl_toolchain_wasm.js" {
-  /** Parse ZPL input and return { ast, diagnostics }. */
-  export function parse(input: string): unknown;
+  /** Parse ZPL input and return { ast, diagnostics, stats? }. */
+  export function parse(
+    input: string,
+    withSpans?: boolean,
+    withStats?: boolean
+  ): unknown;
 
   /** Parse ZPL input with explicit parser tables (JSON string). */
   export function parseWithTables(
     input: string,
-    tablesJson: string
+    tablesJson: string,
+    withSpans?: boolean,
+    withStats?: boolean
   ): unknown;
 
   /** Validate ZPL input with optional printer profile. Returns { ok, issues }. */
@@ -30,4 +36,10 @@ declare module "../wasm/pkg/zpl_toolchain_wasm.js" {
 
   /** Explain a diagnostic code. Returns the explanation or undefined. */
   export function explain(id: string): string | undefined;
+
+  /** Return the full diagnostic catalog. */
+  export function explainAll(): unknown;
+
+  /** List every command in the embedded parser tables. */
+  export function listCommands(): unknown;
 }